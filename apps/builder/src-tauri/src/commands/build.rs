@@ -1,13 +1,10 @@
-use forvanced_build::{BuildOptions, BuildTarget, Builder};
+use forvanced_build::{
+    BuildError, BuildEvent, BuildOptions, BuildProgress, BuildTarget, Builder, LogLevel,
+};
 use serde::{Deserialize, Serialize};
-use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
-use std::process::{Command, Stdio};
-use std::sync::atomic::Ordering;
-use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager, State};
-use tokio::sync::RwLock;
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
 
 use crate::AppState;
 
@@ -117,6 +114,9 @@ pub struct BuildConfig {
     pub target: String,
     pub release: bool,
     pub bundle_frida: bool,
+    /// Seal the embedded config and target script
+    #[serde(default)]
+    pub protect: bool,
     /// Optional project file path for relative path resolution
     pub project_path: Option<String>,
 }
@@ -126,6 +126,7 @@ pub struct BuildResult {
     pub project_dir: String,
     pub executables: Vec<String>,
     pub target: String,
+    pub manifest: String,
 }
 
 /// Build log event emitted during build process
@@ -209,10 +210,7 @@ pub async fn build_project(
     }
 
     // Set building state
-    {
-        *state.is_building.write().await = true;
-        *state.build_cancelled.write().await = false;
-    }
+    *state.is_building.write().await = true;
 
     emit_build_state(&app, true, "initializing", 0);
     emit_build_log(&app, "Starting build process...", "info");
@@ -223,7 +221,7 @@ pub async fn build_project(
     // Cleanup
     {
         *state.is_building.write().await = false;
-        state.build_child_pid.store(0, Ordering::SeqCst);
+        *state.build_progress.write().await = None;
     }
 
     if result.is_err() {
@@ -264,6 +262,8 @@ async fn do_build(
         release: config.release,
         bundle_frida: config.bundle_frida,
         runtime_path: runtime_path.clone(),
+        clean: false,
+        protect: config.protect,
     };
 
     // Try to find runtime path from app context first
@@ -273,261 +273,63 @@ async fn do_build(
         Builder::new().map_err(|e| e.to_string())?
     };
 
-    // Check for cancellation
-    if *state.build_cancelled.read().await {
-        return Err("Build cancelled".to_string());
-    }
-
-    // Generate project first
-    emit_build_state(app, true, "generating", 10);
-    emit_build_log(app, "Generating project files...", "info");
-    emit_build_log(app, &format!("Runtime template: {}", runtime_path.as_ref().map(|p| p.display().to_string()).unwrap_or_else(|| "auto-detect".to_string())), "debug");
-    emit_build_log(app, &format!("Output directory: {}", resolved_output_dir.display()), "debug");
-
     // Drop the project guard before the long async operation
     let project_clone = project.clone();
     drop(project_guard);
 
-    emit_build_log(app, "Starting project generation (this may take a moment)...", "info");
-    let project_dir = builder
-        .generate_project(&project_clone, &resolved_output_dir)
+    // Forward library build events to the frontend; cancel_build cancels this
+    let events_app = app.clone();
+    let progress = BuildProgress::new(move |event| forward_build_event(&events_app, event));
+    *state.build_progress.write().await = Some(progress.clone());
+
+    let output = builder
+        .build_with(&project_clone, &options, &progress)
         .await
         .map_err(|e| {
-            emit_build_log(app, &format!("Generation failed: {}", e), "error");
+            if !matches!(e, BuildError::Cancelled) {
+                emit_build_log(app, &format!("Build failed: {}", e), "error");
+            }
             e.to_string()
         })?;
 
-    emit_build_log(app, &format!("Project generated at: {}", project_dir.display()), "info");
-
-    // Check for cancellation
-    if *state.build_cancelled.read().await {
-        return Err("Build cancelled".to_string());
-    }
-
-    emit_build_state(app, true, "installing_deps", 20);
-
-    // Install dependencies with bun (preferred) or fall back to npm
-    emit_build_log(app, "Installing frontend dependencies...", "info");
-
-    // Try bun first, fall back to npm if not available
-    let (install_cmd, install_args): (&str, Vec<&str>) = if which::which("bun").is_ok() {
-        ("bun", vec!["install"])
-    } else if which::which("npm").is_ok() {
-        ("npm", vec!["install"])
-    } else {
-        emit_build_log(app, "Warning: Neither bun nor npm found, skipping frontend dependencies", "warn");
-        ("", vec![])
-    };
-
-    if !install_cmd.is_empty() {
-        run_command_with_cancellation(
-            app,
-            &state.build_cancelled,
-            &state.build_child_pid,
-            install_cmd,
-            &install_args,
-            &project_dir,
-        ).await.map_err(|e| {
-            emit_build_log(app, &format!("Failed to install dependencies: {}", e), "error");
-            format!("{} install failed: {}", install_cmd, e)
-        })?;
-    }
-
-    // Check for cancellation
-    if *state.build_cancelled.read().await {
-        return Err("Build cancelled".to_string());
-    }
-
-    // Build frontend with bun/vite
-    emit_build_state(app, true, "building_frontend", 30);
-    emit_build_log(app, "Building frontend...", "info");
-
-    let (build_cmd, build_args): (&str, Vec<&str>) = if which::which("bun").is_ok() {
-        ("bun", vec!["run", "build"])
-    } else if which::which("npm").is_ok() {
-        ("npm", vec!["run", "build"])
-    } else {
-        return Err("Neither bun nor npm found. Please install bun.".to_string());
-    };
-
-    run_command_with_cancellation(
-        app,
-        &state.build_cancelled,
-        &state.build_child_pid,
-        build_cmd,
-        &build_args,
-        &project_dir,
-    ).await.map_err(|e| {
-        emit_build_log(app, &format!("Failed to build frontend: {}", e), "error");
-        format!("Frontend build failed: {}", e)
-    })?;
-
-    // Check for cancellation
-    if *state.build_cancelled.read().await {
-        return Err("Build cancelled".to_string());
-    }
-
-    // Build Tauri application with bun tauri build
-    emit_build_state(app, true, "building", 50);
-    emit_build_log(app, "Building Tauri application...", "info");
-
-    // Use bun tauri build to create bundled executables
-    let mut tauri_args = vec!["tauri", "build"];
-    
-    if !options.release {
-        tauri_args.push("--debug");
-    }
-
-    let target_str = options.target.tauri_target();
-    if !target_str.is_empty() {
-        tauri_args.push("--target");
-        tauri_args.push(target_str);
-    }
-
-    if options.bundle_frida {
-        tauri_args.push("--");
-        tauri_args.push("--features");
-        tauri_args.push("real");
-    }
-
-    run_command_with_cancellation(
-        app,
-        &state.build_cancelled,
-        &state.build_child_pid,
-        "bun",
-        &tauri_args,
-        &project_dir,
-    ).await.map_err(|e| {
-        emit_build_log(app, &format!("Build failed: {}", e), "error");
-        format!("Tauri build failed: {}", e)
-    })?;
-
-    // Find built executables
-    emit_build_state(app, true, "finalizing", 90);
-    emit_build_log(app, "Finding built executables...", "info");
-
-    let executables = find_executables(&project_dir, options.release);
-
+    emit_build_log(app, &format!("Build manifest: {}", output.manifest.display()), "info");
+    let total: u128 = output.timings.iter().map(|t| t.duration.as_millis()).sum();
+    emit_build_log(app, &format!("Build completed successfully in {:.1}s", total as f64 / 1000.0), "info");
     emit_build_state(app, false, "complete", 100);
-    emit_build_log(app, "Build completed successfully!", "info");
 
     Ok(BuildResult {
-        project_dir: project_dir.to_string_lossy().to_string(),
-        executables: executables
+        project_dir: output.project_dir.to_string_lossy().to_string(),
+        executables: output
+            .executables
             .iter()
             .map(|p| p.to_string_lossy().to_string())
             .collect(),
-        target: format!("{:?}", options.target),
+        target: format!("{:?}", output.target),
+        manifest: output.manifest.to_string_lossy().to_string(),
     })
 }
 
-/// Run a command with streaming output and cancellation support
-async fn run_command_with_cancellation(
-    app: &AppHandle,
-    cancelled: &Arc<RwLock<bool>>,
-    child_pid: &std::sync::atomic::AtomicU32,
-    cmd: &str,
-    args: &[&str],
-    cwd: &std::path::Path,
-) -> Result<(), String> {
-    let mut command = Command::new(cmd);
-    command
-        .args(args)
-        .current_dir(cwd)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    let mut child = command.spawn().map_err(|e| e.to_string())?;
-
-    // Store the child PID for cancellation
-    let pid = child.id();
-    child_pid.store(pid, Ordering::SeqCst);
-    info!("Started process {} with PID {}", cmd, pid);
-
-    // Stream stdout in background thread
-    if let Some(stdout) = child.stdout.take() {
-        let app_clone = app.clone();
-        std::thread::spawn(move || {
-            let reader = BufReader::new(stdout);
-            for line in reader.lines().map_while(Result::ok) {
-                emit_build_log(&app_clone, &line, "info");
-            }
-        });
-    }
-
-    // Stream stderr in background thread
-    if let Some(stderr) = child.stderr.take() {
-        let app_clone = app.clone();
-        std::thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines().map_while(Result::ok) {
-                // Determine if it's a warning or error
-                let level = if line.to_lowercase().contains("error") {
-                    "error"
-                } else if line.to_lowercase().contains("warn") {
-                    "warn"
-                } else {
-                    "info"
-                };
-                emit_build_log(&app_clone, &line, level);
-            }
-        });
-    }
-
-    // Poll for completion or cancellation
-    loop {
-        // Check if cancelled
-        if *cancelled.read().await {
-            warn!("Build cancelled, killing process {}", pid);
-            emit_build_log(app, "Build cancelled by user", "warn");
-            kill_process_tree(pid);
-            child_pid.store(0, Ordering::SeqCst);
-            return Err("Build cancelled".to_string());
+/// Map a library build event onto the build-state/build-log events
+fn forward_build_event(app: &AppHandle, event: &BuildEvent) {
+    match event {
+        BuildEvent::PhaseStarted { phase, progress } => {
+            emit_build_state(app, true, phase.as_str(), *progress);
         }
-
-        // Check if process finished
-        match child.try_wait() {
-            Ok(Some(status)) => {
-                child_pid.store(0, Ordering::SeqCst);
-                if !status.success() {
-                    return Err(format!("Command exited with status: {}", status));
-                }
-                return Ok(());
-            }
-            Ok(None) => {
-                // Still running, wait a bit
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            }
-            Err(e) => {
-                child_pid.store(0, Ordering::SeqCst);
-                return Err(format!("Failed to wait for process: {}", e));
-            }
+        BuildEvent::PhaseFinished { phase, duration_ms } => {
+            emit_build_log(app, &format!("{} finished in {} ms", phase, duration_ms), "debug");
+        }
+        BuildEvent::PhaseSkipped { phase, reason } => {
+            emit_build_log(app, &format!("Skipping {}: {}", phase, reason), "info");
+        }
+        BuildEvent::Log { level, message, .. } => {
+            let level = match level {
+                LogLevel::Debug => "debug",
+                LogLevel::Info => "info",
+                LogLevel::Warn => "warn",
+                LogLevel::Error => "error",
+            };
+            emit_build_log(app, message, level);
         }
-    }
-}
-
-/// Kill a process and its children
-fn kill_process_tree(pid: u32) {
-    #[cfg(unix)]
-    {
-        use std::process::Command;
-        // Kill process group (negative PID kills the group)
-        let _ = Command::new("kill")
-            .args(["-TERM", "--", &format!("-{}", pid)])
-            .status();
-        // Also try killing just the process
-        let _ = Command::new("kill")
-            .args(["-TERM", &pid.to_string()])
-            .status();
-    }
-
-    #[cfg(windows)]
-    {
-        use std::process::Command;
-        // Use taskkill to kill process tree
-        let _ = Command::new("taskkill")
-            .args(["/F", "/T", "/PID", &pid.to_string()])
-            .status();
     }
 }
 
@@ -554,68 +356,6 @@ fn emit_build_state(app: &AppHandle, is_building: bool, phase: &str, progress: u
     let _ = app.emit("build-state", state);
 }
 
-fn find_executables(project_dir: &std::path::Path, release: bool) -> Vec<PathBuf> {
-    let target_dir = project_dir.join("src-tauri/target");
-    let profile = if release { "release" } else { "debug" };
-
-    let mut executables = Vec::new();
-
-    #[cfg(target_os = "windows")]
-    {
-        let exe_dir = target_dir.join(profile);
-        if exe_dir.exists() {
-            if let Ok(entries) = std::fs::read_dir(&exe_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.extension().map(|e| e == "exe").unwrap_or(false) {
-                        executables.push(path);
-                    }
-                }
-            }
-        }
-    }
-
-    #[cfg(target_os = "macos")]
-    {
-        let app_path = target_dir.join(profile).join("bundle/macos");
-        if app_path.exists() {
-            if let Ok(entries) = std::fs::read_dir(&app_path) {
-                for entry in entries.flatten() {
-                    if entry
-                        .path()
-                        .extension()
-                        .map(|e| e == "app")
-                        .unwrap_or(false)
-                    {
-                        executables.push(entry.path());
-                    }
-                }
-            }
-        }
-    }
-
-    #[cfg(target_os = "linux")]
-    {
-        let deb_path = target_dir.join(profile).join("bundle/deb");
-        if deb_path.exists() {
-            if let Ok(entries) = std::fs::read_dir(&deb_path) {
-                for entry in entries.flatten() {
-                    if entry
-                        .path()
-                        .extension()
-                        .map(|e| e == "deb")
-                        .unwrap_or(false)
-                    {
-                        executables.push(entry.path());
-                    }
-                }
-            }
-        }
-    }
-
-    executables
-}
-
 #[tauri::command]
 pub fn get_build_targets() -> Vec<String> {
     vec![
@@ -660,14 +400,9 @@ pub async fn is_build_in_progress(state: State<'_, AppState>) -> Result<bool, St
 pub async fn cancel_build(state: State<'_, AppState>) -> Result<(), String> {
     info!("Cancel build requested");
 
-    // Set cancel flag
-    *state.build_cancelled.write().await = true;
-
-    // Also try to kill the current child process immediately
-    let pid = state.build_child_pid.load(Ordering::SeqCst);
-    if pid != 0 {
-        info!("Killing build process with PID {}", pid);
-        kill_process_tree(pid);
+    // Kills the running child process; the build then fails with Cancelled
+    if let Some(progress) = state.build_progress.read().await.as_ref() {
+        progress.cancel();
     }
 
     Ok(())
@@ -10,9 +10,12 @@ use tauri::State;
 use tokio::sync::RwLock;
 use tracing::info;
 
+use forvanced_executor::rpc::generate_target_script;
 use forvanced_executor::value::Value;
-use forvanced_executor::{RpcCaller, ScriptExecutor};
-use forvanced_frida::FridaManager;
+use forvanced_executor::{
+    DeviceProvider, FridaSessionHost, HostSession, RpcCaller, ScriptExecutor, SessionHost,
+};
+use forvanced_frida::{ApplicationInfo, AttachTarget, DeviceInfo, FridaManager, ProcessInfo};
 
 use crate::AppState;
 
@@ -33,6 +36,19 @@ impl ExecutorState {
             ui_values,
         }
     }
+
+    /// Executor whose process nodes attach through the app's Frida manager
+    pub fn with_frida(frida_manager: Arc<RwLock<Option<Arc<FridaManager>>>>) -> Self {
+        let ui_values = Arc::new(RwLock::new(HashMap::new()));
+        let mut executor = ScriptExecutor::new(Arc::clone(&ui_values));
+        let host = Arc::new(LazyFridaHost { frida_manager });
+        executor.set_device_provider(host.clone());
+        executor.set_session_host(host);
+        Self {
+            executor: Arc::new(RwLock::new(executor)),
+            ui_values,
+        }
+    }
 }
 
 impl Default for ExecutorState {
@@ -41,16 +57,28 @@ impl Default for ExecutorState {
     }
 }
 
+/// The app's FridaManager once it is created; the lock is released before
+/// the caller awaits anything on the manager
+async fn current_manager(
+    frida_manager: &RwLock<Option<Arc<FridaManager>>>,
+) -> Result<Arc<FridaManager>, String> {
+    frida_manager
+        .read()
+        .await
+        .clone()
+        .ok_or_else(|| "Frida not initialized".to_string())
+}
+
 /// RPC caller that uses FridaManager to call Frida RPC methods
 pub struct FridaRpcCaller {
-    frida_manager: Arc<RwLock<Option<FridaManager>>>,
+    frida_manager: Arc<RwLock<Option<Arc<FridaManager>>>>,
     session_id: String,
     script_id: String,
 }
 
 impl FridaRpcCaller {
     pub fn new(
-        frida_manager: Arc<RwLock<Option<FridaManager>>>,
+        frida_manager: Arc<RwLock<Option<Arc<FridaManager>>>>,
         session_id: String,
         script_id: String,
     ) -> Self {
@@ -69,16 +97,77 @@ impl RpcCaller for FridaRpcCaller {
         method: &str,
         args: Vec<serde_json::Value>,
     ) -> Result<serde_json::Value, String> {
-        let guard = self.frida_manager.read().await;
-        let manager = guard.as_ref().ok_or("Frida not initialized")?;
-
-        manager
+        current_manager(&self.frida_manager)
+            .await?
             .call_rpc(&self.session_id, &self.script_id, method, args)
             .await
             .map_err(|e| e.to_string())
     }
 }
 
+/// Device provider and session host for device and process nodes, over the
+/// lazily created FridaManager
+struct LazyFridaHost {
+    frida_manager: Arc<RwLock<Option<Arc<FridaManager>>>>,
+}
+
+impl LazyFridaHost {
+    async fn manager(&self) -> Result<Arc<FridaManager>, String> {
+        current_manager(&self.frida_manager).await
+    }
+
+    /// Session host over the current manager, loading the built-in agent
+    async fn sessions(&self) -> Result<FridaSessionHost, String> {
+        Ok(FridaSessionHost::new(self.manager().await?))
+    }
+}
+
+#[async_trait]
+impl SessionHost for LazyFridaHost {
+    async fn attach(&self, device_id: &str, target: AttachTarget) -> Result<HostSession, String> {
+        self.sessions().await?.attach(device_id, target).await
+    }
+
+    async fn spawn(
+        &self,
+        device_id: &str,
+        identifier: &str,
+        resume: bool,
+    ) -> Result<HostSession, String> {
+        self.sessions()
+            .await?
+            .spawn(device_id, identifier, resume)
+            .await
+    }
+
+    async fn detach(&self, session_id: &str) -> Result<(), String> {
+        self.sessions().await?.detach(session_id).await
+    }
+
+    async fn session_process(&self, session_id: &str) -> Option<ProcessInfo> {
+        self.sessions()
+            .await
+            .ok()?
+            .session_process(session_id)
+            .await
+    }
+}
+
+#[async_trait]
+impl DeviceProvider for LazyFridaHost {
+    async fn devices(&self) -> Result<Vec<DeviceInfo>, String> {
+        self.manager().await?.devices().await
+    }
+
+    async fn processes(&self, device_id: &str) -> Result<Vec<ProcessInfo>, String> {
+        self.manager().await?.processes(device_id).await
+    }
+
+    async fn applications(&self, device_id: &str) -> Result<Vec<ApplicationInfo>, String> {
+        self.manager().await?.applications(device_id).await
+    }
+}
+
 /// Script data from frontend (matching Script in script.ts)
 #[derive(Debug, Clone, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -305,6 +394,12 @@ pub async fn execute_script(
     })
 }
 
+/// Target agent the executor's target nodes talk to, for the frontend to inject
+#[tauri::command]
+pub fn get_target_agent() -> String {
+    generate_target_script()
+}
+
 /// Set session for target node execution
 #[tauri::command]
 pub async fn set_executor_session(
@@ -71,13 +71,13 @@ async fn get_manager(state: &AppState) -> Result<impl std::ops::Deref<Target = F
 }
 
 /// Wrapper to deref through Option in RwLockReadGuard
-struct ManagerGuard<'a>(tokio::sync::RwLockReadGuard<'a, Option<FridaManager>>);
+struct ManagerGuard<'a>(tokio::sync::RwLockReadGuard<'a, Option<std::sync::Arc<FridaManager>>>);
 
 impl<'a> std::ops::Deref for ManagerGuard<'a> {
     type Target = FridaManager;
     fn deref(&self) -> &Self::Target {
         // Safe: we checked is_none() before constructing
-        self.0.as_ref().unwrap()
+        self.0.as_deref().unwrap()
     }
 }
 
@@ -197,16 +197,43 @@ pub async fn attach_by_identifier(state: State<'_, AppState>, identifier: String
 pub async fn spawn_and_attach(
     state: State<'_, AppState>,
     identifier: String,
+    options: Option<SpawnOptions>,
 ) -> Result<String, CommandError> {
     info!("spawn_and_attach called with identifier: {}", identifier);
     let device_id = get_device_id(&state).await?;
     let manager = get_manager(&state).await?;
     manager
-        .spawn_and_attach(&device_id, &identifier, SpawnOptions::default())
+        .spawn_and_attach(&device_id, &identifier, options.unwrap_or_default())
         .await
         .map_err(CommandError::from)
 }
 
+/// Wait for a gated process (a launcher's child or any new spawn) and attach to it
+///
+/// The process is attached once it is seen running, so code it runs at
+/// startup happens before any script is injected.
+#[tauri::command]
+pub async fn attach_gated(state: State<'_, AppState>, target: AttachTarget) -> Result<String, CommandError> {
+    info!("attach_gated called with target: {}", target);
+    if !target.is_gated() {
+        return Err(CommandError::Other(format!("{} is not a gated target", target)));
+    }
+    let device_id = get_device_id(&state).await?;
+    let manager = get_manager(&state).await?;
+    manager
+        .attach_target(&device_id, target)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Let a suspended process run
+#[tauri::command]
+pub async fn resume_process(state: State<'_, AppState>, session_id: String) -> Result<(), CommandError> {
+    info!("resume_process called with session: {}", session_id);
+    let manager = get_manager(&state).await?;
+    manager.resume(&session_id).await.map_err(CommandError::from)
+}
+
 /// Detach from a session
 #[tauri::command]
 pub async fn detach_from_process(
@@ -218,6 +245,20 @@ pub async fn detach_from_process(
     manager.detach(&session_id).await.map_err(CommandError::from)
 }
 
+/// Change how long a session may sit idle before it is detached (`None` disables reaping)
+#[tauri::command]
+pub async fn set_session_idle_timeout(
+    state: State<'_, AppState>,
+    seconds: Option<u64>,
+) -> Result<(), CommandError> {
+    info!("set_session_idle_timeout called with: {:?}", seconds);
+    let manager = get_manager(&state).await?;
+    manager
+        .set_idle_timeout(seconds.map(std::time::Duration::from_secs))
+        .await;
+    Ok(())
+}
+
 /// Inject a script into a session
 #[tauri::command]
 pub async fn inject_script(
@@ -255,6 +296,27 @@ pub async fn unload_script(
         .map_err(CommandError::from)
 }
 
+/// Replace a loaded script without detaching; the script id stays valid
+///
+/// Returns whether hooks and other state were carried over to the new script.
+#[tauri::command]
+pub async fn reload_script(
+    state: State<'_, AppState>,
+    session_id: String,
+    script_id: String,
+    script: String,
+) -> Result<bool, CommandError> {
+    info!(
+        "reload_script called for session: {}, script: {}",
+        session_id, script_id
+    );
+    let manager = get_manager(&state).await?;
+    manager
+        .reload_script(&session_id, &script_id, &script)
+        .await
+        .map_err(CommandError::from)
+}
+
 /// Call an RPC method on a Frida script
 #[tauri::command]
 pub async fn call_rpc(
@@ -313,6 +375,45 @@ pub async fn subscribe_to_messages(
     Ok(())
 }
 
+/// Emit a "frida-detached" event whenever a session ends, whatever the cause
+pub async fn forward_detach_events(app: tauri::AppHandle, state: &AppState) {
+    let Ok(manager) = get_manager(state).await else {
+        return;
+    };
+
+    manager
+        .on_detached(std::sync::Arc::new(move |session_id, reason| {
+            use tauri::Emitter;
+
+            let payload = serde_json::json!({
+                "sessionId": session_id,
+                "reason": reason
+            });
+
+            if let Err(e) = app.emit("frida-detached", payload) {
+                tracing::error!("Failed to emit frida-detached event: {}", e);
+            }
+        }))
+        .await;
+}
+
+/// Emit a "frida-gated" event for every process a gate catches or lets go
+pub async fn forward_gate_events(app: tauri::AppHandle, state: &AppState) {
+    let Ok(manager) = get_manager(state).await else {
+        return;
+    };
+
+    manager
+        .on_gated(std::sync::Arc::new(move |event| {
+            use tauri::Emitter;
+
+            if let Err(e) = app.emit("frida-gated", event) {
+                tracing::error!("Failed to emit frida-gated event: {}", e);
+            }
+        }))
+        .await;
+}
+
 /// Simulate a Frida message for testing (mock mode only)
 #[cfg(feature = "mock")]
 #[tauri::command]
@@ -3,7 +3,8 @@ use tauri::State;
 use tokio::fs;
 use tracing::{debug, info};
 
-use forvanced_core::Project;
+use forvanced_core::structs::{parse_c_header, DataModel, StructDefinition};
+use forvanced_core::{MigrationReport, Project};
 
 use crate::state::RecentProjectEntry;
 use crate::AppState;
@@ -71,8 +72,19 @@ pub async fn save_project(
     Ok(path_str)
 }
 
+/// A project read from disk, with what was upgraded to bring it to the
+/// current schema so the UI can tell the user
+#[derive(Debug, serde::Serialize)]
+pub struct LoadedProject {
+    pub project: Project,
+    pub migration: MigrationReport,
+}
+
 #[tauri::command]
-pub async fn load_project(state: State<'_, AppState>, path: String) -> Result<Project, String> {
+pub async fn load_project(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<LoadedProject, String> {
     info!("Loading project from: {}", path);
 
     let path = PathBuf::from(&path);
@@ -84,8 +96,17 @@ pub async fn load_project(state: State<'_, AppState>, path: String) -> Result<Pr
         .await
         .map_err(|e| format!("Failed to read file: {}", e))?;
 
-    let project: Project = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse project file: {}", e))?;
+    let (project, migration) = forvanced_core::migration::load_project(&content)
+        .map_err(|e| format!("Failed to load project file: {}", e))?;
+    if migration.is_migrated() {
+        info!(
+            "Migrated project from schema v{} to v{}",
+            migration.from_version, migration.to_version
+        );
+        for change in &migration.changes {
+            info!("  {}", change);
+        }
+    }
 
     // Update state
     let project_name = project.name.clone();
@@ -104,7 +125,7 @@ pub async fn load_project(state: State<'_, AppState>, path: String) -> Result<Pr
         tracing::warn!("Failed to save recent projects list: {}", e);
     }
 
-    Ok(project)
+    Ok(LoadedProject { project, migration })
 }
 
 #[tauri::command]
@@ -149,6 +170,19 @@ pub async fn remove_recent_project(
     state.save_recent_projects().await
 }
 
+/// Parse struct layouts from C header source for the project's struct list.
+/// `data_model` is the target's (defaults to the host's).
+#[tauri::command]
+pub async fn import_c_header(
+    source: String,
+    data_model: Option<DataModel>,
+) -> Result<Vec<StructDefinition>, String> {
+    let structs = parse_c_header(&source, data_model.unwrap_or_else(DataModel::host))
+        .map_err(|e| format!("Failed to import header: {}", e))?;
+    info!("Imported {} struct(s) from C header", structs.len());
+    Ok(structs)
+}
+
 fn sanitize_filename(name: &str) -> String {
     name.chars()
         .map(|c| {
@@ -30,15 +30,19 @@ pub fn run() {
             app.manage(state.clone());
 
             // Initialize executor state
-            let executor_state = ExecutorState::new();
+            let executor_state = ExecutorState::with_frida(state.frida_manager.clone());
             app.manage(executor_state);
 
             // Initialize async tasks (connect default adapter)
             let state_clone = state.clone();
+            let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
                 if let Err(e) = state_clone.initialize().await {
                     tracing::error!("Failed to initialize app state: {}", e);
+                    return;
                 }
+                commands::forward_detach_events(app_handle.clone(), &state_clone).await;
+                commands::forward_gate_events(app_handle, &state_clone).await;
             });
 
             info!("Forvanced Builder initialized");
@@ -57,9 +61,13 @@ pub fn run() {
             commands::attach_by_name,
             commands::attach_by_identifier,
             commands::spawn_and_attach,
+            commands::attach_gated,
+            commands::resume_process,
             commands::detach_from_process,
+            commands::set_session_idle_timeout,
             commands::inject_script,
             commands::unload_script,
+            commands::reload_script,
             // Project commands
             commands::create_project,
             commands::save_project,
@@ -69,6 +77,7 @@ pub fn run() {
             commands::close_project,
             commands::get_recent_projects,
             commands::remove_recent_project,
+            commands::import_c_header,
             // Build commands
             commands::generate_project,
             commands::build_project,
@@ -78,6 +87,7 @@ pub fn run() {
             commands::cancel_build,
             // Executor commands
             commands::execute_script,
+            commands::get_target_agent,
             commands::set_executor_session,
             commands::clear_executor_session,
             commands::set_ui_value,
@@ -2,14 +2,17 @@ use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use std::sync::atomic::AtomicU32;
 
+use forvanced_build::BuildProgress;
 use forvanced_core::Project;
 use forvanced_frida::FridaManager;
 use serde::{Deserialize, Serialize};
 
 const MAX_RECENT_PROJECTS: usize = 10;
 
+/// Sessions with no RPC calls or script messages for this long are detached
+pub const SESSION_IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecentProjectEntry {
     pub name: String,
@@ -47,7 +50,7 @@ impl RecentProjectsStore {
 
 #[derive(Clone)]
 pub struct AppState {
-    pub frida_manager: Arc<RwLock<Option<FridaManager>>>,
+    pub frida_manager: Arc<RwLock<Option<Arc<FridaManager>>>>,
     pub current_device_id: Arc<RwLock<Option<String>>>,
     pub current_project: Arc<RwLock<Option<Project>>>,
     pub project_path: Arc<RwLock<Option<PathBuf>>>,
@@ -55,10 +58,8 @@ pub struct AppState {
     pub config_dir: PathBuf,
     /// Whether a build is currently in progress
     pub is_building: Arc<RwLock<bool>>,
-    /// Whether the current build should be cancelled
-    pub build_cancelled: Arc<RwLock<bool>>,
-    /// Current build child process ID (0 if none)
-    pub build_child_pid: Arc<AtomicU32>,
+    /// Progress handle of the running build, used to cancel it
+    pub build_progress: Arc<RwLock<Option<BuildProgress>>>,
 }
 
 impl AppState {
@@ -78,15 +79,15 @@ impl AppState {
             recent_projects: Arc::new(RwLock::new(recent_projects)),
             config_dir,
             is_building: Arc::new(RwLock::new(false)),
-            build_cancelled: Arc::new(RwLock::new(false)),
-            build_child_pid: Arc::new(AtomicU32::new(0)),
+            build_progress: Arc::new(RwLock::new(None)),
         }
     }
 
     /// Initialize the app state asynchronously (create FridaManager)
     pub async fn initialize(&self) -> Result<(), String> {
         let manager = FridaManager::new().map_err(|e| e.to_string())?;
-        *self.frida_manager.write().await = Some(manager);
+        manager.set_idle_timeout(Some(SESSION_IDLE_TIMEOUT)).await;
+        *self.frida_manager.write().await = Some(Arc::new(manager));
         tracing::info!("FridaManager initialized");
         Ok(())
     }
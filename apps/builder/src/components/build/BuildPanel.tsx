@@ -80,6 +80,7 @@ interface BuildConfig {
   target: string;
   release: boolean;
   bundle_frida: boolean;
+  protect: boolean;
   project_path?: string;
 }
 
@@ -87,6 +88,7 @@ interface BuildResult {
   project_dir: string;
   executables: string[];
   target: string;
+  manifest: string;
 }
 
 interface PreviewCode {
@@ -109,11 +111,11 @@ interface BuildState {
 // Phase display names
 const phaseNames: Record<string, string> = {
   initializing: "Initializing...",
-  generating: "Generating project files...",
-  installing_deps: "Installing dependencies...",
-  building_frontend: "Building frontend...",
-  building: "Building Tauri application...",
-  finalizing: "Finalizing...",
+  generate: "Generating project files...",
+  install: "Installing dependencies...",
+  compile: "Compiling application...",
+  bundle: "Bundling application...",
+  collect: "Finalizing...",
   complete: "Build complete!",
   error: "Build failed",
 };
@@ -122,6 +124,7 @@ export const BuildPanel: Component = () => {
   const [buildTarget, setBuildTarget] = createSignal("current");
   const [releaseMode, setReleaseMode] = createSignal(true);
   const [bundleFrida, setBundleFrida] = createSignal(true);
+  const [protect, setProtect] = createSignal(false);
   const [outputDir, setOutputDir] = createSignal("./dist");
   const [isBuilding, setIsBuilding] = createSignal(false);
   const [buildResult, setBuildResult] = createSignal<BuildResult | null>(null);
@@ -212,6 +215,7 @@ export const BuildPanel: Component = () => {
         target: buildTarget(),
         release: releaseMode(),
         bundle_frida: bundleFrida(),
+        protect: protect(),
         project_path: projectPath ?? undefined,
       };
 
@@ -479,6 +483,26 @@ export const BuildPanel: Component = () => {
                       />
                     </button>
                   </div>
+
+                  <div class="flex items-center justify-between">
+                    <label class="text-sm text-foreground-muted">
+                      Protect Scripts
+                    </label>
+                    <button
+                      class={`w-10 h-5 rounded-full transition-colors relative ${
+                        protect() ? "bg-accent" : "bg-background-secondary"
+                      } disabled:opacity-50`}
+                      onClick={() => setProtect(!protect())}
+                      disabled={isBuilding()}
+                      title="Encrypt the embedded config and target script and refuse to start if they were modified"
+                    >
+                      <div
+                        class={`absolute top-0.5 w-4 h-4 bg-white rounded-full transition-transform ${
+                          protect() ? "translate-x-5" : "translate-x-0.5"
+                        }`}
+                      />
+                    </button>
+                  </div>
                 </div>
               </div>
             </div>
@@ -14,6 +14,7 @@ import {
   FolderOpenIcon,
   SaveIcon,
   TrashIcon,
+  IconX,
 } from "@/components/common/Icons";
 
 export const ProjectPanel: Component = () => {
@@ -95,6 +96,32 @@ export const ProjectPanel: Component = () => {
               </div>
             </div>
 
+            {/* Schema upgrade applied on open */}
+            <Show when={projectStore.migration()}>
+              {(report) => (
+                <div class="mx-4 mt-4 p-3 text-xs text-warning bg-warning/10 rounded">
+                  <div class="flex items-center justify-between gap-2">
+                    <span class="font-medium">
+                      Upgraded from schema v{report().fromVersion} to v
+                      {report().toVersion}; save to keep the changes
+                    </span>
+                    <button
+                      class="p-1 hover:bg-surface-hover rounded transition-colors"
+                      onClick={() => projectStore.dismissMigration()}
+                      title="Dismiss"
+                    >
+                      <IconX class="w-3 h-3" />
+                    </button>
+                  </div>
+                  <ul class="mt-2 space-y-0.5 list-disc list-inside">
+                    <For each={report().changes}>
+                      {(change) => <li>{change}</li>}
+                    </For>
+                  </ul>
+                </div>
+              )}
+            </Show>
+
             {/* Project Info */}
             <div class="flex-1 overflow-y-auto p-4 space-y-6">
               {/* Basic Info */}
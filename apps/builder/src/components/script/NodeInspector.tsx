@@ -15,6 +15,7 @@ import {
   nodeTemplates,
   getPortTypeSchema,
   formatTypeSchema,
+  isTargetNode,
 } from "@/stores/script";
 import { designerStore } from "@/stores/designer";
 import {
@@ -204,6 +205,11 @@ const NodeProperties: Component<NodePropertiesProps> = (props) => {
             </div>
           </PropertyRow>
 
+          {/* Session a Target node runs in, unless a handle is connected */}
+          <Show when={isTargetNode(props.node.type)}>
+            <SessionNameRow node={props.node} />
+          </Show>
+
           {/* Constant Node Configs */}
           <Show when={props.node.type === "const_string"}>
             <PropertyRow label="Value">
@@ -1142,6 +1148,7 @@ const NodeProperties: Component<NodePropertiesProps> = (props) => {
 
           {/* Process Spawn */}
           <Show when={props.node.type === "process_spawn"}>
+            <SessionNameRow node={props.node} />
             <PropertyRow label="Resume After Spawn">
               <input
                 type="checkbox"
@@ -2124,6 +2131,27 @@ const DeviceSelectConfig: Component<DeviceSelectConfigProps> = (props) => {
   );
 };
 
+// ============================================
+// Session Name - For attach/spawn and Target nodes
+// ============================================
+interface SessionNameRowProps {
+  node: ScriptNode;
+}
+
+const SessionNameRow: Component<SessionNameRowProps> = (props) => (
+  <PropertyRow label="Session">
+    <InspectorTextInput
+      placeholder="default"
+      value={(props.node.config.session as string) ?? ""}
+      onChange={(val) =>
+        scriptStore.updateNode(props.node.id, {
+          config: { ...props.node.config, session: val },
+        })
+      }
+    />
+  </PropertyRow>
+);
+
 // ============================================
 // Process Attach Config - For process_attach node
 // ============================================
@@ -2136,6 +2164,7 @@ const ProcessAttachConfig: Component<ProcessAttachConfigProps> = (props) => {
 
   return (
     <>
+      <SessionNameRow node={props.node} />
       <PropertyRow label="Attach Mode">
         <select
           class="w-full px-2 py-1 text-xs bg-background border border-border rounded"
@@ -2152,9 +2181,24 @@ const ProcessAttachConfig: Component<ProcessAttachConfigProps> = (props) => {
           <option value="pid">By PID (number)</option>
           <option value="name">By Process Name</option>
           <option value="identifier">By Bundle Identifier</option>
+          <option value="launch">Wait for Launch (pattern)</option>
         </select>
       </PropertyRow>
 
+      <Show when={attachMode() === "launch"}>
+        <PropertyRow label="Timeout (ms)">
+          <InspectorNumberInput
+            value={(props.node.config.timeoutMs as number) ?? 60000}
+            min={0}
+            onChange={(val) =>
+              scriptStore.updateNode(props.node.id, {
+                config: { ...props.node.config, timeoutMs: val },
+              })
+            }
+          />
+        </PropertyRow>
+      </Show>
+
       <div class="text-[9px] text-foreground-muted p-2 bg-background/50 rounded space-y-1">
         <Show when={attachMode() === "pid"}>
           <p>
@@ -2174,6 +2218,13 @@ const ProcessAttachConfig: Component<ProcessAttachConfigProps> = (props) => {
             "com.example.app").
           </p>
         </Show>
+        <Show when={attachMode() === "launch"}>
+          <p>
+            <strong>Wait for Launch:</strong> Connect a name pattern (e.g.,
+            "game*.exe") to <code>target</code>. Attaches as soon as a matching
+            process starts. A timeout of 0 waits forever.
+          </p>
+        </Show>
         <p class="pt-1 border-t border-border/50">
           Use "Select Device" first to choose the target device.
         </p>
@@ -22,7 +22,6 @@ import {
   executeEventNode,
   resetScriptStates,
 } from "@/lib/script-executor";
-import { FRIDA_AGENT_SCRIPT } from "@/lib/frida-agent";
 
 // ============================================
 // Types
@@ -35,17 +34,15 @@ interface LogEntry {
   message: string;
 }
 
-// Forvanced event from Frida agent
-interface ForvancedEvent {
-  type: "forvanced_event";
-  eventType:
-    | "hook_enter"
-    | "hook_leave"
-    | "memory_access"
-    | "java_hook_enter"
-    | "java_hook_leave";
-  data: Record<string, unknown>;
-  timestamp: number;
+// Hook event sent by the target agent
+interface HookEvent {
+  type: "hook";
+  hookId: string;
+  phase: "enter" | "leave";
+  address: string;
+  args?: string[];
+  retval?: string | null;
+  threadId: number;
 }
 
 // ============================================
@@ -149,13 +146,26 @@ export const TestPanel: Component = () => {
   const handleAttach = async (pid: number, name: string) => {
     addLog("info", `Attaching to ${name} (PID: ${pid})...`);
     try {
-      await targetStore.attachAndSetupExecutor(pid, name, FRIDA_AGENT_SCRIPT);
+      await targetStore.attachAndSetupExecutor(pid, name);
       addLog("info", `Attached and executor ready`);
     } catch (error) {
       addLog("error", `Failed to attach: ${error}`);
     }
   };
 
+  const handleReloadAgent = async () => {
+    addLog("info", "Reloading agent...");
+    try {
+      const migrated = await targetStore.reloadAgent();
+      addLog(
+        "info",
+        migrated ? "Agent reloaded, state carried over" : "Agent reloaded",
+      );
+    } catch (error) {
+      addLog("error", `Failed to reload agent: ${error}`);
+    }
+  };
+
   const handleDetach = async () => {
     addLog("info", "Detaching...");
     try {
@@ -289,10 +299,10 @@ export const TestPanel: Component = () => {
         addLog("frida", `[${message.level}] ${message.text}`);
         break;
       case "send": {
-        // Check if it's a Forvanced event
+        // Check if it's a hook event from the target agent
         const payload = message.payload as Record<string, unknown>;
-        if (payload?.type === "forvanced_event") {
-          await handleForvancedEvent(payload as unknown as ForvancedEvent);
+        if (payload?.type === "hook") {
+          await handleHookEvent(payload as unknown as HookEvent);
         } else {
           addLog("frida", `[send] ${JSON.stringify(message.payload)}`);
         }
@@ -307,44 +317,23 @@ export const TestPanel: Component = () => {
     }
   };
 
-  const handleForvancedEvent = async (event: ForvancedEvent) => {
-    const { eventType, data } = event;
-
-    switch (eventType) {
-      case "hook_enter":
-        addLog("hook", `Hook Enter: ${data.hookId} @ ${data.address}`);
-        if (data.args) {
-          addLog("debug", `  Args: ${JSON.stringify(data.args)}`);
-        }
-        await triggerHookEvent(data.hookId as string, "enter", data);
-        break;
-
-      case "hook_leave":
-        addLog("hook", `Hook Leave: ${data.hookId}`);
-        if (data.retval !== undefined) {
-          addLog("debug", `  Return: ${data.retval}`);
-        }
-        await triggerHookEvent(data.hookId as string, "leave", data);
-        break;
-
-      case "memory_access":
-        addLog(
-          "hook",
-          `Memory ${data.operation}: ${data.address} from ${data.from}`,
-        );
-        await triggerMemoryWatchEvent(data.watchId as string, data);
-        break;
-
-      case "java_hook_enter":
-        addLog("hook", `Java Enter: ${data.className}.${data.methodName}`);
-        await triggerHookEvent(data.hookId as string, "enter", data);
-        break;
-
-      case "java_hook_leave":
-        addLog("hook", `Java Leave: ${data.className}.${data.methodName}`);
-        await triggerHookEvent(data.hookId as string, "leave", data);
-        break;
+  const handleHookEvent = async (event: HookEvent) => {
+    if (event.phase === "enter") {
+      addLog("hook", `Hook Enter: ${event.hookId} @ ${event.address}`);
+      if (event.args) {
+        addLog("debug", `  Args: ${JSON.stringify(event.args)}`);
+      }
+    } else {
+      addLog("hook", `Hook Leave: ${event.hookId}`);
+      if (event.retval !== undefined && event.retval !== null) {
+        addLog("debug", `  Return: ${event.retval}`);
+      }
     }
+    await triggerHookEvent(
+      event.hookId,
+      event.phase,
+      event as unknown as Record<string, unknown>,
+    );
   };
 
   const triggerHookEvent = async (
@@ -379,35 +368,6 @@ export const TestPanel: Component = () => {
     }
   };
 
-  const triggerMemoryWatchEvent = async (
-    watchId: string,
-    data: Record<string, unknown>,
-  ) => {
-    const script = selectedScript();
-    if (!script) return;
-
-    const watchEventNodes = script.nodes.filter(
-      (node) =>
-        node.type === "event_memory_watch" && node.config.watchId === watchId,
-    );
-
-    if (watchEventNodes.length === 0) return;
-
-    const scriptData = convertScript(script);
-
-    for (const eventNode of watchEventNodes) {
-      addLog("exec", `Memory watch triggered: ${eventNode.label}`);
-      try {
-        const result = await executeEventNode(scriptData, eventNode, data);
-        if (!result.success) {
-          addLog("error", `Watch handler failed: ${result.error}`);
-        }
-      } catch (error) {
-        addLog("error", `Watch execution error: ${error}`);
-      }
-    }
-  };
-
   // ============================================
   // Utility
   // ============================================
@@ -496,12 +456,22 @@ export const TestPanel: Component = () => {
                     {targetStore.attachedTarget()} ({targetStore.attachedPid()})
                   </span>
                 </div>
-                <button
-                  class="px-2 py-0.5 text-xs bg-error/20 text-error rounded hover:bg-error/30"
-                  onClick={handleDetach}
-                >
-                  Detach
-                </button>
+                <div class="flex items-center gap-1">
+                  <button
+                    class="px-2 py-0.5 text-xs bg-background border border-border rounded hover:bg-surface-hover"
+                    onClick={handleReloadAgent}
+                    disabled={!targetStore.isExecutorReady()}
+                    title="Reload the target agent, keeping its hooks"
+                  >
+                    Reload
+                  </button>
+                  <button
+                    class="px-2 py-0.5 text-xs bg-error/20 text-error rounded hover:bg-error/30"
+                    onClick={handleDetach}
+                  >
+                    Detach
+                  </button>
+                </div>
               </div>
             </Show>
 
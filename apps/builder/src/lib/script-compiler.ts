@@ -659,9 +659,11 @@ function compileFindSymbolNode(
   const symbolName = inputs.get("symbol") ?? '""';
   const varName = `_sym_${node.id.substring(0, 8)}`;
 
+  // Frida 17 looks exports up on a module, or globally without one
+  emit(ctx, `const ${varName}_mod = ${moduleName};`);
   emit(
     ctx,
-    `const ${varName} = Module.findExportByName(${moduleName}, ${symbolName});`,
+    `const ${varName} = ${varName}_mod ? Process.getModuleByName(${varName}_mod).findExportByName(${symbolName}) : Module.findGlobalExportByName(${symbolName});`,
   );
 
   const outputs = new Map<string, string>();
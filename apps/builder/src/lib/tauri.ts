@@ -18,6 +18,18 @@ export interface DeviceInfo {
 
 export type AttachMode = "pid" | "name" | "identifier";
 
+// Processes that have yet to start: a launcher's child or any new spawn
+export type GatedTarget =
+  | { type: "Child"; value: { parent: number; pattern: string } }
+  | { type: "Spawn"; value: { pattern: string } };
+
+export interface SpawnOptions {
+  argv?: string[];
+  envp?: string[]; // "KEY=VALUE"
+  cwd?: string;
+  stdio?: "inherit" | "pipe";
+}
+
 // Structured IPC error with command context
 export class IPCError extends Error {
   readonly command: string;
@@ -112,8 +124,10 @@ function getMockResponse<T>(cmd: string, args?: Record<string, unknown>): T {
     detach_from_process: undefined,
     inject_script: `mock-script-${Date.now()}`,
     unload_script: undefined,
+    reload_script: true,
     // Project commands
     create_project: {
+      schema_version: 2,
       id: `project-${Date.now()}`,
       name: args?.name || "New Project",
       version: "1.0.0",
@@ -149,38 +163,42 @@ function getMockResponse<T>(cmd: string, args?: Record<string, unknown>): T {
     },
     save_project: args?.path || `/tmp/project-${Date.now()}.forvanced`,
     load_project: {
-      id: `loaded-${Date.now()}`,
-      name: "Loaded Project",
-      version: "1.0.0",
-      description: null,
-      author: null,
-      config: {
-        target: {
-          process_name: null,
-          process_patterns: [],
-          adapter_type: "local_pc",
-          adapter_config: {},
-          auto_attach: false,
+      project: {
+        schema_version: 2,
+        id: `loaded-${Date.now()}`,
+        name: "Loaded Project",
+        version: "1.0.0",
+        description: null,
+        author: null,
+        config: {
+          target: {
+            process_name: null,
+            process_patterns: [],
+            adapter_type: "local_pc",
+            adapter_config: {},
+            auto_attach: false,
+          },
+          build: {
+            output_name: null,
+            targets: ["windows"],
+            icon: null,
+            bundle_frida: false,
+          },
+          hotkeys: { enabled: true, bindings: [] },
         },
-        build: {
-          output_name: null,
-          targets: ["windows"],
-          icon: null,
-          bundle_frida: false,
+        ui: {
+          components: [],
+          width: 400,
+          height: 500,
+          theme: "dark",
+          padding: 12,
+          gap: 8,
         },
-        hotkeys: { enabled: true, bindings: [] },
-      },
-      ui: {
-        components: [],
-        width: 400,
-        height: 500,
-        theme: "dark",
-        padding: 12,
-        gap: 8,
+        scripts: [],
+        created_at: Date.now(),
+        updated_at: Date.now(),
       },
-      scripts: [],
-      created_at: Date.now(),
-      updated_at: Date.now(),
+      migration: { fromVersion: 2, toVersion: 2, changes: [] },
     },
     close_project: undefined,
     update_project: undefined,
@@ -255,8 +273,28 @@ export async function attachByIdentifier(identifier: string): Promise<string> {
   return invoke<string>("attach_by_identifier", { identifier });
 }
 
-export async function spawnAndAttach(identifier: string): Promise<string> {
-  return invoke<string>("spawn_and_attach", { identifier });
+export async function spawnAndAttach(
+  identifier: string,
+  options?: SpawnOptions,
+): Promise<string> {
+  return invoke<string>("spawn_and_attach", { identifier, options });
+}
+
+// Resolves once a matching process is seen running and attached; Child
+// targets are refused by the current backends
+export async function attachGated(target: GatedTarget): Promise<string> {
+  return invoke<string>("attach_gated", { target });
+}
+
+export async function resumeProcess(sessionId: string): Promise<void> {
+  return invoke<void>("resume_process", { sessionId });
+}
+
+// Detach sessions idle for longer than `seconds`; null turns this off
+export async function setSessionIdleTimeout(
+  seconds: number | null,
+): Promise<void> {
+  return invoke<void>("set_session_idle_timeout", { seconds });
 }
 
 export async function detachFromProcess(sessionId: string): Promise<void> {
@@ -278,6 +316,16 @@ export async function unloadScript(
   return invoke<void>("unload_script", { sessionId, scriptId });
 }
 
+// Swap in new target-side JS without re-attaching; resolves to whether
+// hooks were carried over to the new script
+export async function reloadScript(
+  sessionId: string,
+  scriptId: string,
+  script: string,
+): Promise<boolean> {
+  return invoke<boolean>("reload_script", { sessionId, scriptId, script });
+}
+
 // RPC commands
 export async function callRpc(
   sessionId: string,
@@ -354,6 +402,55 @@ export async function onFridaMessage(
   return unlisten;
 }
 
+export type DetachReason =
+  | "application_requested"
+  | "process_terminated"
+  | "connection_lost"
+  | "device_lost"
+  | "idle_timeout";
+
+export interface FridaDetachedEvent {
+  sessionId: string;
+  reason: DetachReason;
+}
+
+// Listen for sessions ending (target exit, lost device, our own detach)
+export async function onFridaDetached(
+  callback: (event: FridaDetachedEvent) => void,
+): Promise<() => void> {
+  if (!isTauri()) {
+    return () => {};
+  }
+
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<FridaDetachedEvent>("frida-detached", (event) => {
+    callback(event.payload);
+  });
+}
+
+export type GateEvent =
+  | {
+      kind: "attached";
+      session_id: string;
+      process: ProcessInfo;
+      parent_pid: number | null;
+    }
+  | { kind: "released"; process: ProcessInfo; parent_pid: number | null };
+
+// Listen for processes caught or let go by child/spawn gates
+export async function onFridaGated(
+  callback: (event: GateEvent) => void,
+): Promise<() => void> {
+  if (!isTauri()) {
+    return () => {};
+  }
+
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<GateEvent>("frida-gated", (event) => {
+    callback(event.payload);
+  });
+}
+
 // Simulate a Frida message (mock mode only, for testing)
 export async function simulateFridaMessage(
   sessionId: string,
@@ -441,6 +538,14 @@ export async function executeScript(
   });
 }
 
+/**
+ * Get the target agent script the executor's target nodes call into.
+ * Inject it before handing the session to the executor.
+ */
+export async function getTargetAgent(): Promise<string> {
+  return invoke<string>("get_target_agent");
+}
+
 /**
  * Set the Frida session for target node execution.
  * Must be called after attaching to a process and injecting a Frida agent script.
@@ -527,8 +632,20 @@ export async function saveProject(
   return invoke<string>("save_project", { project, path });
 }
 
-export async function loadProject(path: string): Promise<unknown> {
-  return invoke<unknown>("load_project", { path });
+// What was upgraded to bring a project file to the current schema
+export interface MigrationReport {
+  fromVersion: number;
+  toVersion: number;
+  changes: string[];
+}
+
+export interface LoadedProject {
+  project: unknown;
+  migration: MigrationReport;
+}
+
+export async function loadProject(path: string): Promise<LoadedProject> {
+  return invoke<LoadedProject>("load_project", { path });
 }
 
 export async function closeProject(): Promise<void> {
@@ -546,3 +663,13 @@ export async function getRecentProjects(): Promise<RecentProjectEntry[]> {
 export async function removeRecentProject(path: string): Promise<void> {
   return invoke<void>("remove_recent_project", { path });
 }
+
+// C data model of the target: ILP32 (32-bit), LP64 (64-bit Unix) or LLP64 (64-bit Windows)
+export type DataModel = "ilp32" | "lp64" | "llp64";
+
+export async function importCHeader(
+  source: string,
+  dataModel?: DataModel,
+): Promise<unknown[]> {
+  return invoke<unknown[]>("import_c_header", { source, dataModel });
+}
@@ -8,6 +8,7 @@ import {
   updateProject as ipcUpdateProject,
   getRecentProjects as ipcGetRecentProjects,
   removeRecentProject as ipcRemoveRecentProject,
+  type MigrationReport,
   type RecentProjectEntry,
 } from "@/lib/tauri";
 
@@ -39,10 +40,12 @@ async function openDialogTauri(): Promise<string | null> {
 import type { Script } from "./script";
 import { scriptStore } from "./script";
 
-export type { RecentProjectEntry };
+export type { MigrationReport, RecentProjectEntry };
 
 // Project types (matching Rust schema)
 export interface Project {
+  /** File format version; older files are migrated on load */
+  schema_version: number;
   id: string;
   name: string;
   description: string | null;
@@ -51,10 +54,27 @@ export interface Project {
   config: ProjectConfig;
   ui: UILayout;
   scripts: Script[];
+  structs?: StructDefinition[];
   created_at: number;
   updated_at: number;
 }
 
+// Struct layouts for struct_read/struct_write nodes
+export interface StructDefinition {
+  name: string;
+  fields: StructField[];
+  size?: number;
+}
+
+export type StructField = {
+  name: string;
+  offset: number;
+  arrayLength?: number;
+} & (
+  | { kind: "value"; valueType: string }
+  | { kind: "struct"; structName: string }
+);
+
 export interface ProjectConfig {
   target: TargetConfig;
   build: BuildConfig;
@@ -74,6 +94,8 @@ export interface BuildConfig {
   targets: string[];
   icon: string | null;
   bundle_frida: boolean;
+  /** Public keys data-only packages may be signed with (hex Ed25519) */
+  trusted_keys?: string[];
 }
 
 export interface HotkeyConfig {
@@ -233,6 +255,8 @@ function createProjectStore() {
   const [isDirty, setIsDirty] = createSignal(false);
   const [isLoading, setIsLoading] = createSignal(false);
   const [error, setError] = createSignal<string | null>(null);
+  // Schema upgrade applied to the project last opened, if any
+  const [migration, setMigration] = createSignal<MigrationReport | null>(null);
   const [recentProjects, setRecentProjects] = createSignal<
     RecentProjectEntry[]
   >([]);
@@ -247,6 +271,7 @@ function createProjectStore() {
     setError(null);
     try {
       const project = (await ipcCreateProject(name)) as Project;
+      setMigration(null);
       // Initialize with empty scripts array if not present
       if (!project.scripts) {
         project.scripts = [];
@@ -316,7 +341,10 @@ function createProjectStore() {
     setIsLoading(true);
     setError(null);
     try {
-      const project = (await ipcLoadProject(path)) as Project;
+      const loaded = await ipcLoadProject(path);
+      const project = loaded.project as Project;
+      const report = loaded.migration;
+      setMigration(report.fromVersion !== report.toVersion ? report : null);
       // Ensure scripts array exists (for backward compatibility with old projects)
       if (!project.scripts) {
         project.scripts = [];
@@ -352,6 +380,7 @@ function createProjectStore() {
       await ipcCloseProject();
       setCurrentProject(null);
       setProjectPath(null);
+      setMigration(null);
       setIsDirty(false);
       // Clear scripts from script store
       scriptStore.clearScripts();
@@ -456,6 +485,8 @@ function createProjectStore() {
     isDirty,
     isLoading,
     error,
+    migration,
+    dismissMigration: () => setMigration(null),
     recentProjects,
     createNew,
     save,
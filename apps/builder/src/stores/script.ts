@@ -58,16 +58,21 @@ export type ScriptNodeType =
   | "memory_freeze" // Freeze value at address
   | "memory_alloc" // Allocate memory
   | "memory_protect" // Change memory protection
+  | "code_cave" // Redirect code into an assembled code cave
   | "memory_watch" // Watch memory for access (triggers event_memory_watch)
   | "memory_unwatch" // Remove memory watch
   // Pointer Operations
   | "pointer_add" // Add offset to pointer
   | "pointer_read" // Read from pointer (with type)
   | "pointer_write" // Write to pointer (with type)
+  // Struct Operations
+  | "struct_read" // Read a whole struct as an object
+  | "struct_write" // Write struct fields from an object
   // Module/Symbol
   | "get_module" // Get module by name
   | "find_symbol" // Find symbol/export in module
   | "get_base_address" // Get module base address
+  | "resolve_address" // Resolve module+offset / export / pointer chain syntax
   | "enumerate_exports" // List all exports of a module
   | "enumerate_modules" // List all loaded modules
   // Variables
@@ -471,16 +476,21 @@ export function getNodeContext(nodeType: ScriptNodeType): NodeContext {
     "memory_freeze",
     "memory_alloc",
     "memory_protect",
+    "code_cave",
     "memory_watch",
     "memory_unwatch",
     // Pointer operations (executed in target for memory access)
     "pointer_add",
     "pointer_read",
     "pointer_write",
+    // Struct operations
+    "struct_read",
+    "struct_write",
     // Module operations
     "get_module",
     "find_symbol",
     "get_base_address",
+    "resolve_address",
     "enumerate_modules",
     "enumerate_exports",
     // Native function calls
@@ -504,6 +514,23 @@ export function isTargetNode(nodeType: ScriptNodeType): boolean {
   return getNodeContext(nodeType) === "target";
 }
 
+// Target nodes take an optional session handle (from Attach/Spawn) selecting
+// which attached process they run in; the "session" config is the fallback
+function targetSessionInput(
+  type: ScriptNodeType,
+  template: NodeTemplate,
+): Omit<Port, "id">[] {
+  if (
+    !isTargetNode(type) ||
+    template.inputs.some((p) => p.name === "session")
+  ) {
+    return [];
+  }
+  return [
+    { name: "session", type: "value", valueType: "string", direction: "input" },
+  ];
+}
+
 // Check if a node type is a host node (executes locally)
 export function isHostNode(nodeType: ScriptNodeType): boolean {
   return getNodeContext(nodeType) === "host";
@@ -1163,6 +1190,38 @@ export const nodeTemplates: NodeTemplate[] = [
       },
     ],
   },
+  {
+    type: "code_cave",
+    label: "Code Cave",
+    category: "Memory",
+    description:
+      "Jump from address into assembled instructions (reverts when disabled)",
+    defaultConfig: { instructions: [], keepOriginal: true },
+    inputs: [
+      { name: "exec", type: "flow", direction: "input" },
+      {
+        name: "address",
+        type: "value",
+        valueType: "pointer",
+        direction: "input",
+      },
+      {
+        name: "enabled",
+        type: "value",
+        valueType: "boolean",
+        direction: "input",
+      },
+    ],
+    outputs: [
+      { name: "exec", type: "flow", direction: "output" },
+      {
+        name: "cave",
+        type: "value",
+        valueType: "pointer",
+        direction: "output",
+      },
+    ],
+  },
   {
     type: "memory_watch",
     label: "Watch Memory",
@@ -1277,6 +1336,46 @@ export const nodeTemplates: NodeTemplate[] = [
     outputs: [{ name: "exec", type: "flow", direction: "output" }],
   },
 
+  // Struct Operations
+  {
+    type: "struct_read",
+    label: "Read Struct",
+    category: "Pointer",
+    description: "Read all fields of a project struct in one call",
+    defaultConfig: { structName: "" },
+    inputs: [
+      { name: "exec", type: "flow", direction: "input" },
+      {
+        name: "address",
+        type: "value",
+        valueType: "pointer",
+        direction: "input",
+      },
+    ],
+    outputs: [
+      { name: "exec", type: "flow", direction: "output" },
+      { name: "value", type: "value", valueType: "any", direction: "output" },
+    ],
+  },
+  {
+    type: "struct_write",
+    label: "Write Struct",
+    category: "Pointer",
+    description: "Write the fields present in an object to a project struct",
+    defaultConfig: { structName: "" },
+    inputs: [
+      { name: "exec", type: "flow", direction: "input" },
+      {
+        name: "address",
+        type: "value",
+        valueType: "pointer",
+        direction: "input",
+      },
+      { name: "value", type: "value", valueType: "any", direction: "input" },
+    ],
+    outputs: [{ name: "exec", type: "flow", direction: "output" }],
+  },
+
   // Module/Symbol
   {
     type: "get_module",
@@ -1361,6 +1460,32 @@ export const nodeTemplates: NodeTemplate[] = [
       },
     ],
   },
+  {
+    type: "resolve_address",
+    label: "Resolve Address",
+    category: "Module",
+    description:
+      "Resolve game.exe+0x10, module!export, sym:name or [[base]+0x10]+0x8 at run time",
+    defaultConfig: { address: "" },
+    inputs: [
+      { name: "exec", type: "flow", direction: "input" },
+      {
+        name: "address",
+        type: "value",
+        valueType: "string",
+        direction: "input",
+      },
+    ],
+    outputs: [
+      { name: "exec", type: "flow", direction: "output" },
+      {
+        name: "address",
+        type: "value",
+        valueType: "pointer",
+        direction: "output",
+      },
+    ],
+  },
   {
     type: "enumerate_modules",
     label: "Enumerate Modules",
@@ -1890,15 +2015,31 @@ export const nodeTemplates: NodeTemplate[] = [
         direction: "input",
       },
     ],
-    outputs: [{ name: "exec", type: "flow", direction: "output" }],
+    outputs: [
+      { name: "exec", type: "flow", direction: "output" },
+      {
+        name: "hookId",
+        type: "value",
+        valueType: "string",
+        direction: "output",
+      },
+    ],
   },
   {
     type: "interceptor_detach",
     label: "Interceptor Detach",
     category: "Interceptor",
-    description: "Detach all hooks with Interceptor.detachAll()",
-    defaultConfig: {},
-    inputs: [{ name: "exec", type: "flow", direction: "input" }],
+    description: "Detach a hook by ID (all hooks when no ID is given)",
+    defaultConfig: { hookId: "" },
+    inputs: [
+      { name: "exec", type: "flow", direction: "input" },
+      {
+        name: "hookId",
+        type: "value",
+        valueType: "string",
+        direction: "input",
+      },
+    ],
     outputs: [{ name: "exec", type: "flow", direction: "output" }],
   },
   {
@@ -1918,12 +2059,19 @@ export const nodeTemplates: NodeTemplate[] = [
     type: "write_arg",
     label: "Write Argument",
     category: "Interceptor",
-    description: "Modify function argument in hook context",
-    defaultConfig: { index: 0 },
+    description:
+      "Rewrite an argument on every hook call (toggle with enabled)",
+    defaultConfig: { index: 0, hookId: "" },
     inputs: [
       { name: "exec", type: "flow", direction: "input" },
       { name: "context", type: "value", valueType: "any", direction: "input" },
       { name: "value", type: "value", valueType: "any", direction: "input" },
+      {
+        name: "enabled",
+        type: "value",
+        valueType: "boolean",
+        direction: "input",
+      },
     ],
     outputs: [{ name: "exec", type: "flow", direction: "output" }],
   },
@@ -1944,12 +2092,19 @@ export const nodeTemplates: NodeTemplate[] = [
     type: "replace_retval",
     label: "Replace Return Value",
     category: "Interceptor",
-    description: "Replace return value with retval.replace()",
-    defaultConfig: {},
+    description:
+      "Replace the return value on every hook call (toggle with enabled)",
+    defaultConfig: { hookId: "" },
     inputs: [
       { name: "exec", type: "flow", direction: "input" },
       { name: "context", type: "value", valueType: "any", direction: "input" },
       { name: "value", type: "value", valueType: "any", direction: "input" },
+      {
+        name: "enabled",
+        type: "value",
+        valueType: "boolean",
+        direction: "input",
+      },
     ],
     outputs: [{ name: "exec", type: "flow", direction: "output" }],
   },
@@ -1957,13 +2112,23 @@ export const nodeTemplates: NodeTemplate[] = [
     type: "native_callback",
     label: "Native Callback",
     category: "Native",
-    description: "Create NativeCallback for hooks or replacements",
+    description:
+      "Replace a function with script logic (onCall runs per call; Return sets the result)",
     defaultConfig: {
       returnType: "void",
       argCount: 0,
       argTypes: [] as string[],
+      timeoutMs: 1000, // Falls back to the original function after this
     },
-    inputs: [{ name: "exec", type: "flow", direction: "input" }],
+    inputs: [
+      { name: "exec", type: "flow", direction: "input" },
+      {
+        name: "target",
+        type: "value",
+        valueType: "pointer",
+        direction: "input",
+      },
+    ],
     outputs: [
       { name: "exec", type: "flow", direction: "output" },
       {
@@ -1973,6 +2138,7 @@ export const nodeTemplates: NodeTemplate[] = [
         direction: "output",
       },
       { name: "onCall", type: "flow", direction: "output" },
+      { name: "args", type: "value", valueType: "any", direction: "output" },
     ],
   },
 
@@ -2136,9 +2302,11 @@ export const nodeTemplates: NodeTemplate[] = [
     label: "Attach to Process",
     category: "Process",
     description:
-      "Attach to a process on the currently selected device. Use 'Select Device' first to choose the target device.",
+      "Attach to a process on the currently selected device under a session name. Target nodes pick the session by name or through the session output.",
     defaultConfig: {
-      attachMode: "pid", // "pid" | "name" | "identifier"
+      attachMode: "pid", // "pid" | "name" | "identifier" | "launch"
+      session: "default", // Session name Target nodes refer to
+      timeoutMs: 60000, // "launch" only: give up after this long (0 = default, 60s)
     },
     inputs: [
       { name: "exec", type: "flow", direction: "input" },
@@ -2147,6 +2315,12 @@ export const nodeTemplates: NodeTemplate[] = [
     outputs: [
       { name: "success", type: "flow", direction: "output" },
       { name: "failure", type: "flow", direction: "output" },
+      {
+        name: "session",
+        type: "value",
+        valueType: "string",
+        direction: "output",
+      }, // Session handle for Target nodes and Detach
       {
         name: "sessionId",
         type: "value",
@@ -2172,9 +2346,18 @@ export const nodeTemplates: NodeTemplate[] = [
     type: "process_detach",
     label: "Detach from Process",
     category: "Process",
-    description: "Detach from the currently attached process",
+    description:
+      "Detach a session. Without a session input the default session is detached.",
     defaultConfig: {},
-    inputs: [{ name: "exec", type: "flow", direction: "input" }],
+    inputs: [
+      { name: "exec", type: "flow", direction: "input" },
+      {
+        name: "session",
+        type: "value",
+        valueType: "string",
+        direction: "input",
+      },
+    ],
     outputs: [
       { name: "success", type: "flow", direction: "output" },
       { name: "failure", type: "flow", direction: "output" },
@@ -2194,6 +2377,7 @@ export const nodeTemplates: NodeTemplate[] = [
       "Spawn an application on the selected device and attach to it. Use 'Select Device' first. Ideal for apps not yet running.",
     defaultConfig: {
       resumeAfterSpawn: true, // Whether to resume the app after spawning
+      session: "default", // Session name Target nodes refer to
     },
     inputs: [
       { name: "exec", type: "flow", direction: "input" },
@@ -2207,6 +2391,12 @@ export const nodeTemplates: NodeTemplate[] = [
     outputs: [
       { name: "success", type: "flow", direction: "output" },
       { name: "failure", type: "flow", direction: "output" },
+      {
+        name: "session",
+        type: "value",
+        valueType: "string",
+        direction: "output",
+      },
       {
         name: "sessionId",
         type: "value",
@@ -2226,9 +2416,17 @@ export const nodeTemplates: NodeTemplate[] = [
     type: "process_is_attached",
     label: "Is Attached?",
     category: "Process",
-    description: "Check if currently attached to a process",
+    description:
+      "Check if a session is attached. Without a session input the default session is checked.",
     defaultConfig: {},
-    inputs: [],
+    inputs: [
+      {
+        name: "session",
+        type: "value",
+        valueType: "string",
+        direction: "input",
+      },
+    ],
     outputs: [
       {
         name: "attached",
@@ -2641,7 +2839,9 @@ function createScriptStore() {
       x,
       y,
       config: { ...template.defaultConfig },
-      inputs: template.inputs.map((p) => ({ ...p, id: crypto.randomUUID() })),
+      inputs: [...template.inputs, ...targetSessionInput(type, template)].map(
+        (p) => ({ ...p, id: crypto.randomUUID() }),
+      ),
       outputs: template.outputs.map((p) => ({ ...p, id: crypto.randomUUID() })),
     };
   }
@@ -10,9 +10,12 @@ import {
   spawnAndAttach,
   detachFromProcess,
   injectScript,
+  getTargetAgent,
+  reloadScript,
   unloadScript,
   setExecutorSession,
   clearExecutorSession,
+  onFridaDetached,
 } from "@/lib/tauri";
 import type { AttachMode } from "@/lib/tauri";
 import { errorStore } from "./error";
@@ -88,6 +91,19 @@ function resetSessionState() {
   setIsExecutorReady(false);
 }
 
+// Drop the session when the target dies or its device goes away
+void onFridaDetached(({ sessionId: detachedId, reason }) => {
+  if (reason === "application_requested" || detachedId !== sessionId()) return;
+  resetSessionState();
+  clearExecutorSession().catch(() => {
+    /* executor may already be idle */
+  });
+  errorStore.showError(
+    "Session Detached",
+    `Target session ended: ${reason.replace("_", " ")}`,
+  );
+});
+
 // Actions
 async function changeDevice(deviceId: string) {
   try {
@@ -205,15 +221,15 @@ async function detach() {
   }
 }
 
-// Attach to process and set up executor (inject agent + configure executor session)
-async function attachAndSetupExecutor(pid: number, name: string, agentScript: string): Promise<void> {
+// Attach to process and set up executor (inject the executor's agent + configure executor session)
+async function attachAndSetupExecutor(pid: number, name: string): Promise<void> {
   try {
     const session = await attachToProcess(pid);
     setSessionId(session);
     setAttachedPid(pid);
     setAttachedTarget(name);
 
-    const scriptId = await injectScript(session, agentScript);
+    const scriptId = await injectScript(session, await getTargetAgent());
     setInjectedScriptId(scriptId);
 
     await setExecutorSession(session, scriptId);
@@ -238,6 +254,33 @@ async function attachAndSetupExecutor(pid: number, name: string, agentScript: st
   }
 }
 
+// Swap the injected agent for a fresh copy without detaching; hooks, rules
+// and freezes carry over when the agent hands over its state
+async function reloadAgent(): Promise<boolean> {
+  const session = sessionId();
+  const scriptId = injectedScriptId();
+  if (!session || !scriptId) return false;
+
+  try {
+    const migrated = await reloadScript(
+      session,
+      scriptId,
+      await getTargetAgent(),
+    );
+    errorStore.showInfo(
+      "Agent Reloaded",
+      migrated ? "Hooks and freezes were carried over" : "Reloaded without previous state",
+    );
+    return migrated;
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    console.error("Failed to reload agent:", error);
+    // The old agent keeps running when the new one can't take over
+    errorStore.showError("Reload Failed", message);
+    throw error;
+  }
+}
+
 // Detach and clean up executor (clear session + unload script + detach)
 async function detachAndCleanup(): Promise<void> {
   const session = sessionId();
@@ -303,6 +346,7 @@ export const targetStore = {
   spawn,
   detach,
   attachAndSetupExecutor,
+  reloadAgent,
   detachAndCleanup,
   setAttachMode,
   refetchDevices,
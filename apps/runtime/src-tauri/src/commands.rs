@@ -12,7 +12,10 @@ use forvanced_executor::script::{
 };
 use forvanced_executor::value::Value;
 use forvanced_executor::{rpc::generate_target_script, RpcCaller};
-use forvanced_frida::FridaError;
+use forvanced_frida::{
+    glob_match, protocol, AttachTarget, DetachReason, FridaError, FridaManager, ProcessMatcher,
+    ProcessWatcher, ScriptMessage, SpawnOptions, WatchConfig, WatchEvent,
+};
 use serde::Serialize;
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, State};
@@ -68,53 +71,200 @@ pub async fn get_project_config(
 /// Attach to a process
 #[tauri::command]
 pub async fn attach_process(
+    app: AppHandle,
     state: State<'_, Arc<Mutex<AppState>>>,
     process_name: String,
 ) -> Result<String, String> {
+    let shared_state = Arc::clone(&state);
     let mut state = state.lock().await;
 
-    // List devices and find local
-    let devices = state
-        .frida_manager
-        .enumerate_devices()
-        .await
-        .map_err(|e: FridaError| e.to_string())?;
-
-    let local_device = devices
-        .iter()
-        .find(|d| d.device_type == forvanced_frida::FridaDeviceType::Local)
-        .ok_or("No local device found")?;
+    // Pick the device the trainer was configured for
+    let (adapter_type, adapter_config, patterns) = match &state.config {
+        Some(config) => (
+            config.adapter_type.clone(),
+            config.adapter_config.clone(),
+            config.process_patterns.clone(),
+        ),
+        None => ("local_pc".to_string(), serde_json::Value::Null, Vec::new()),
+    };
+    let device_id = select_device(&state.frida_manager, &adapter_type, &adapter_config).await?;
 
     // List processes
     let processes = state
         .frida_manager
-        .enumerate_processes_on_device(&local_device.id)
+        .enumerate_processes_on_device(&device_id)
         .await
         .map_err(|e: FridaError| e.to_string())?;
 
-    // Find target process
+    // Find target process by name, then by the configured patterns
+    let requested = process_name.to_lowercase();
     let target = processes
         .iter()
-        .find(|p| p.name.to_lowercase().contains(&process_name.to_lowercase()))
+        .find(|p| !requested.is_empty() && p.name.to_lowercase().contains(&requested))
+        .or_else(|| {
+            processes
+                .iter()
+                .find(|p| patterns.iter().any(|pattern| glob_match(pattern, &p.name)))
+        })
         .ok_or(format!("Process '{}' not found", process_name))?;
 
     // Attach
     let session_id = state
         .frida_manager
-        .attach_on_device(&local_device.id, target.pid)
+        .attach_on_device(&device_id, target.pid)
         .await
         .map_err(|e: FridaError| e.to_string())?;
 
-    state.session_id = Some(session_id.clone());
+    install_target_script(app.clone(), shared_state, &mut state, &session_id).await?;
+    run_session_handlers(&app, &state, "event_attach", &session_id).await;
+
+    Ok(session_id)
+}
+
+/// Spawn the target suspended, instrument it, then let it run
+///
+/// The target script and every event_attach handler are in place before the
+/// program executes its first instruction, so startup code can be hooked.
+#[tauri::command]
+pub async fn spawn_process(
+    app: AppHandle,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    program: String,
+    options: Option<SpawnOptions>,
+) -> Result<String, String> {
+    let shared_state = Arc::clone(&state);
+    let mut state = state.lock().await;
+
+    let (adapter_type, adapter_config) = match &state.config {
+        Some(config) => (config.adapter_type.clone(), config.adapter_config.clone()),
+        None => ("local_pc".to_string(), serde_json::Value::Null),
+    };
+    let device_id = select_device(&state.frida_manager, &adapter_type, &adapter_config).await?;
+
+    let session_id = state
+        .frida_manager
+        .spawn_suspended(&device_id, &program, options.unwrap_or_default())
+        .await
+        .map_err(|e: FridaError| e.to_string())?;
+
+    if let Err(e) = install_target_script(app.clone(), shared_state, &mut state, &session_id).await
+    {
+        state.executor.clear_session().await;
+        state.session_id = None;
+        state.script_id = None;
+        let _ = state.frida_manager.detach(&session_id).await;
+        return Err(e);
+    }
+    run_session_handlers(&app, &state, "event_attach", &session_id).await;
+
+    state
+        .frida_manager
+        .resume(&session_id)
+        .await
+        .map_err(|e: FridaError| e.to_string())?;
+
+    Ok(session_id)
+}
+
+/// Start a launcher and instrument the game it starts
+///
+/// The launcher runs untouched; the first process started on the device
+/// matching `pattern` gets the target script and event_attach handlers once
+/// it is seen running. Backends can't tell which process started which, so
+/// a matching process started by anything else is taken as well.
+#[tauri::command]
+pub async fn launch_process(
+    app: AppHandle,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    launcher: String,
+    pattern: String,
+    options: Option<SpawnOptions>,
+) -> Result<String, String> {
+    let shared_state = Arc::clone(&state);
+    let (manager, device_id) = {
+        let state = state.lock().await;
+        let (adapter_type, adapter_config) = match &state.config {
+            Some(config) => (config.adapter_type.clone(), config.adapter_config.clone()),
+            None => ("local_pc".to_string(), serde_json::Value::Null),
+        };
+        let device_id = select_device(&state.frida_manager, &adapter_type, &adapter_config).await?;
+        (Arc::clone(&state.frida_manager), device_id)
+    };
+
+    // The gate has to be armed before the launcher gets a chance to run
+    let mut gate = manager
+        .gate(&device_id, AttachTarget::Spawn { pattern })
+        .await
+        .map_err(|e: FridaError| e.to_string())?;
+    let launcher_session = manager
+        .spawn_suspended(&device_id, &launcher, options.unwrap_or_default())
+        .await
+        .map_err(|e: FridaError| e.to_string())?;
+    let resumed = manager.resume(&launcher_session).await;
+    let _ = manager.detach(&launcher_session).await;
+    resumed.map_err(|e: FridaError| e.to_string())?;
+
+    // Waiting on the launcher must not block the UI, so the state stays unlocked
+    let session_id = gate.next().await.map_err(|e: FridaError| e.to_string())?;
+    drop(gate);
+
+    let mut state = shared_state.lock().await;
+    if let Err(e) = install_target_script(
+        app.clone(),
+        Arc::clone(&shared_state),
+        &mut state,
+        &session_id,
+    )
+    .await
+    {
+        state.executor.clear_session().await;
+        state.session_id = None;
+        state.script_id = None;
+        let _ = manager.detach(&session_id).await;
+        return Err(e);
+    }
+    run_session_handlers(&app, &state, "event_attach", &session_id).await;
+
+    Ok(session_id)
+}
 
+/// Inject the target script into a fresh session and route its messages
+async fn install_target_script(
+    app: AppHandle,
+    shared_state: Arc<Mutex<AppState>>,
+    state: &mut AppState,
+    session_id: &str,
+) -> Result<(), String> {
     // Inject the target RPC handler script
-    let target_script = generate_target_script();
     let script_id = state
         .frida_manager
-        .inject_script(&session_id, &target_script)
+        .inject_script(session_id, &target_script_source(state))
         .await
         .map_err(|e: FridaError| e.to_string())?;
 
+    wire_session(app, shared_state, state, session_id, &script_id).await
+}
+
+/// The built-in target script unless the build shipped its own
+fn target_script_source(state: &AppState) -> String {
+    state
+        .target_script
+        .clone()
+        .unwrap_or_else(generate_target_script)
+}
+
+/// Make a session with the target script loaded the current one
+async fn wire_session(
+    app: AppHandle,
+    shared_state: Arc<Mutex<AppState>>,
+    state: &mut AppState,
+    session_id: &str,
+    script_id: &str,
+) -> Result<(), String> {
+    let session_id = session_id.to_string();
+    let script_id = script_id.to_string();
+
+    state.session_id = Some(session_id.clone());
     state.script_id = Some(script_id.clone());
 
     // Set up RPC caller for executor
@@ -125,8 +275,475 @@ pub async fn attach_process(
     });
     state.executor.set_rpc_caller(rpc_caller).await;
     state.executor.set_session(session_id.clone()).await;
+    if let Some(config) = &state.config {
+        state
+            .executor
+            .set_struct_definitions(config.structs.clone())
+            .await;
+    }
 
-    Ok(session_id)
+    // Stop driving the target once Frida reports the session gone
+    let detach_app = app.clone();
+    let detach_state = Arc::clone(&shared_state);
+    state
+        .frida_manager
+        .on_session_detached(
+            &session_id,
+            Arc::new(move |session_id, reason| {
+                tokio::spawn(handle_session_detached(
+                    detach_app.clone(),
+                    Arc::clone(&detach_state),
+                    session_id,
+                    reason,
+                ));
+            }),
+        )
+        .await
+        .map_err(|e: FridaError| e.to_string())?;
+
+    // Route hook events, native callback calls and compiled agent reports
+    // from the target to their handlers
+    let message_session_id = session_id.clone();
+    state
+        .frida_manager
+        .on_session_message(
+            &session_id,
+            Arc::new(move |script_id, message| {
+                if let ScriptMessage::Send { payload } = message {
+                    match payload.get("type").and_then(|t| t.as_str()) {
+                        Some("hook") => {
+                            tokio::spawn(dispatch_hook_event(Arc::clone(&shared_state), payload));
+                        }
+                        Some("native_callback") => {
+                            tokio::spawn(dispatch_native_callback(
+                                Arc::clone(&shared_state),
+                                message_session_id.clone(),
+                                script_id,
+                                payload,
+                            ));
+                        }
+                        Some("agent") => {
+                            tokio::spawn(dispatch_agent_message(
+                                app.clone(),
+                                Arc::clone(&shared_state),
+                                payload,
+                            ));
+                        }
+                        _ => {}
+                    }
+                }
+            }),
+        )
+        .await
+        .map_err(|e: FridaError| e.to_string())
+}
+
+/// Session end reported to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDetachedEvent {
+    pub session_id: String,
+    pub reason: DetachReason,
+}
+
+/// Forget a session that ended underneath us so no more RPCs go into it
+async fn handle_session_detached(
+    app: AppHandle,
+    state: Arc<Mutex<AppState>>,
+    session_id: String,
+    reason: DetachReason,
+) {
+    let mut state = state.lock().await;
+    if state.session_id.as_deref() != Some(session_id.as_str()) {
+        return;
+    }
+
+    tracing::warn!("Session {} detached: {}", session_id, reason);
+    state.executor.clear_session().await;
+    state.session_id = None;
+    state.script_id = None;
+    run_session_handlers(&app, &state, "event_detach", &session_id).await;
+    if reason != DetachReason::ApplicationRequested {
+        let _ = app.emit(
+            "notification",
+            NotificationEvent {
+                title: "Detached".to_string(),
+                message: format!("Target session ended: {}", reason),
+                level: "warning".to_string(),
+            },
+        );
+    }
+    let _ = app.emit(
+        "session_detached",
+        SessionDetachedEvent { session_id, reason },
+    );
+}
+
+/// How often the auto-attach watcher looks for the target while detached
+const AUTO_ATTACH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Keep the configured target attached for trainers with `auto_attach`
+///
+/// The watcher attaches as soon as the game starts and again after it
+/// restarts; each new session gets the target script, the hooks recorded by
+/// the executor and the event_attach handlers.
+pub async fn start_auto_attach(
+    app: AppHandle,
+    shared_state: Arc<Mutex<AppState>>,
+) -> Result<(), String> {
+    let mut state = shared_state.lock().await;
+    let Some(config) = state.config.clone().filter(|c| c.auto_attach) else {
+        return Ok(());
+    };
+
+    let matcher = ProcessMatcher::new(
+        config.target_process.clone(),
+        config.process_patterns.clone(),
+    );
+    if matcher.is_empty() {
+        return Err("Auto-attach needs a target process or process pattern".to_string());
+    }
+    let device_id = select_device(
+        &state.frida_manager,
+        &config.adapter_type,
+        &config.adapter_config,
+    )
+    .await?;
+
+    let watch_config = WatchConfig {
+        device_id,
+        matcher,
+        interval: AUTO_ATTACH_INTERVAL,
+        scripts: vec![target_script_source(&state)],
+    };
+    let watch_state = Arc::clone(&shared_state);
+    let watcher = ProcessWatcher::start(
+        Arc::clone(&state.frida_manager),
+        watch_config,
+        Arc::new(move |event| {
+            tokio::spawn(handle_watch_event(
+                app.clone(),
+                Arc::clone(&watch_state),
+                event,
+            ));
+        }),
+    );
+    state.watcher = Some(watcher);
+    Ok(())
+}
+
+/// Adopt sessions the auto-attach watcher opens
+async fn handle_watch_event(app: AppHandle, shared_state: Arc<Mutex<AppState>>, event: WatchEvent) {
+    let (session_id, process, script_ids, reattached) = match event {
+        WatchEvent::Attached {
+            session_id,
+            process,
+            script_ids,
+            reattached,
+        } => (session_id, process, script_ids, reattached),
+        WatchEvent::Failed { pid, error } => {
+            tracing::warn!("Auto-attach to pid {} failed: {}", pid, error);
+            return;
+        }
+        // handle_session_detached already cleans up after the session
+        WatchEvent::Detached { .. } => return,
+    };
+    let Some(script_id) = script_ids.first() else {
+        return;
+    };
+
+    let mut state = shared_state.lock().await;
+    if let Err(e) = wire_session(
+        app.clone(),
+        Arc::clone(&shared_state),
+        &mut state,
+        &session_id,
+        script_id,
+    )
+    .await
+    {
+        tracing::error!(
+            "Failed to set up auto-attached session {}: {}",
+            session_id,
+            e
+        );
+        return;
+    }
+
+    if reattached {
+        match state.executor.replay_target_state().await {
+            Ok(failures) => {
+                for failure in failures {
+                    tracing::warn!("Failed to restore target state: {}", failure);
+                }
+            }
+            Err(e) => tracing::error!("Failed to restore target state: {}", e),
+        }
+    }
+    run_session_handlers(&app, &state, "event_attach", &session_id).await;
+
+    let _ = app.emit(
+        "notification",
+        NotificationEvent {
+            title: if reattached { "Reattached" } else { "Attached" }.to_string(),
+            message: format!("{} ({})", process.name, process.pid),
+            level: "info".to_string(),
+        },
+    );
+    let _ = app.emit(
+        "session_attached",
+        SessionAttachedEvent {
+            session_id,
+            reattached,
+        },
+    );
+}
+
+/// Session opened by the auto-attach watcher, reported to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAttachedEvent {
+    pub session_id: String,
+    pub reattached: bool,
+}
+
+/// Run every event_attach or event_detach node with the session id
+async fn run_session_handlers(
+    app: &AppHandle,
+    state: &AppState,
+    event_type: &str,
+    session_id: &str,
+) {
+    let Some(config) = &state.config else {
+        return;
+    };
+
+    for script in &config.scripts {
+        for node in script.nodes.iter().filter(|n| n.node_type == event_type) {
+            let executor_script = convert_config_script_to_executor(script);
+            match state
+                .executor
+                .execute_from_event(
+                    executor_script,
+                    &node.id,
+                    Value::String(session_id.to_string()),
+                    None,
+                )
+                .await
+            {
+                Ok(result) if !result.success => {
+                    tracing::error!(
+                        "Session script '{}' failed: {:?}",
+                        script.name,
+                        result.error
+                    );
+                }
+                Ok(result) => {
+                    for log in &result.logs {
+                        tracing::info!("Script log: {}", log);
+                    }
+                    for notification in &result.notifications {
+                        let _ = app.emit(
+                            "notification",
+                            NotificationEvent {
+                                title: notification.title.clone(),
+                                message: notification.message.clone(),
+                                level: notification.level.clone(),
+                            },
+                        );
+                    }
+                }
+                Err(e) => tracing::error!("Session script execution error: {}", e),
+            }
+        }
+    }
+}
+
+/// Resolve the configured adapter to a Frida device id
+async fn select_device(
+    manager: &FridaManager,
+    adapter_type: &str,
+    adapter_config: &serde_json::Value,
+) -> Result<String, String> {
+    use forvanced_frida::FridaDeviceType;
+
+    if adapter_type == "remote" {
+        let host = adapter_config
+            .get("host")
+            .and_then(|v| v.as_str())
+            .unwrap_or("127.0.0.1");
+        let port = adapter_config
+            .get("port")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(27042);
+        let device = manager
+            .add_remote_device(&format!("{}:{}", host, port))
+            .await
+            .map_err(|e: FridaError| e.to_string())?;
+        return Ok(device.id);
+    }
+
+    let device_type = match adapter_type {
+        "usb" => FridaDeviceType::Usb,
+        _ => FridaDeviceType::Local,
+    };
+    let devices = manager
+        .enumerate_devices()
+        .await
+        .map_err(|e: FridaError| e.to_string())?;
+    devices
+        .into_iter()
+        .find(|d| d.device_type == device_type)
+        .map(|d| d.id)
+        .ok_or_else(|| format!("No {} device found", device_type))
+}
+
+/// Run every event_hook node whose hookId and phase match the payload
+async fn dispatch_hook_event(state: Arc<Mutex<AppState>>, payload: serde_json::Value) {
+    let hook_id = payload.get("hookId").and_then(|v| v.as_str());
+    let phase = payload.get("phase").and_then(|v| v.as_str());
+
+    // Handlers run without the state lock; they may call back into commands
+    let (executor, handlers) = {
+        let state = state.lock().await;
+        let Some(config) = &state.config else {
+            return;
+        };
+
+        let mut handlers = Vec::new();
+        for script in &config.scripts {
+            for node in &script.nodes {
+                if node.node_type != "event_hook" {
+                    continue;
+                }
+                let node_hook_id = node
+                    .config
+                    .get("hookId")
+                    .and_then(|v| v.as_str())
+                    .filter(|id| !id.is_empty());
+                let node_phase = node
+                    .config
+                    .get("phase")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("enter");
+                if (node_hook_id.is_some() && node_hook_id != hook_id) || Some(node_phase) != phase
+                {
+                    continue;
+                }
+                handlers.push((
+                    script.name.clone(),
+                    convert_config_script_to_executor(script),
+                    node.id.clone(),
+                ));
+            }
+        }
+        (state.executor.clone(), handlers)
+    };
+
+    for (name, executor_script, node_id) in handlers {
+        match executor
+            .execute_from_event(
+                executor_script,
+                &node_id,
+                Value::from(payload.clone()),
+                None,
+            )
+            .await
+        {
+            Ok(result) if !result.success => {
+                tracing::error!("Hook script '{}' failed: {:?}", name, result.error);
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Hook script execution error: {}", e),
+        }
+    }
+}
+
+/// UI update pushed to the frontend by a compiled agent
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentValueEvent {
+    pub component_id: String,
+    pub value: serde_json::Value,
+}
+
+/// Apply one tick reported by an interval chain compiled into the target
+async fn dispatch_agent_message(
+    app: AppHandle,
+    state: Arc<Mutex<AppState>>,
+    payload: serde_json::Value,
+) {
+    let script_id = payload
+        .get("scriptId")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let state = state.lock().await;
+
+    // UI writes land even when the tick fails, as with the host executor
+    for update in payload
+        .get("ui")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let (Some(component_id), Some(value)) =
+            (update.get(0).and_then(|v| v.as_str()), update.get(1))
+        else {
+            continue;
+        };
+        state
+            .sync_component_value(component_id, value.clone())
+            .await;
+        let _ = app.emit(
+            "component_value",
+            ComponentValueEvent {
+                component_id: component_id.to_string(),
+                value: value.clone(),
+            },
+        );
+    }
+
+    if payload.get("success").and_then(|v| v.as_bool()) != Some(true) {
+        tracing::error!(
+            "Compiled script '{}' failed: {}",
+            script_id,
+            payload
+                .get("error")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown error")
+        );
+        return;
+    }
+    for log in payload
+        .get("logs")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        tracing::info!("Script log: {}", log.as_str().unwrap_or_default());
+    }
+    for notification in payload
+        .get("notifications")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let field = |key: &str, default: &str| {
+            notification
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or(default)
+                .to_string()
+        };
+        let _ = app.emit(
+            "notification",
+            NotificationEvent {
+                title: field("title", "Notification"),
+                message: field("message", ""),
+                level: field("level", "info"),
+            },
+        );
+    }
 }
 
 /// Detach from current process
@@ -134,6 +751,12 @@ pub async fn attach_process(
 pub async fn detach_process(state: State<'_, Arc<Mutex<AppState>>>) -> Result<(), String> {
     let mut state = state.lock().await;
 
+    // A manual detach ends auto-attach and forgets the hooks to restore
+    if let Some(watcher) = state.watcher.take() {
+        watcher.stop();
+    }
+    state.executor.clear_target_journal().await;
+
     // Clear executor session
     state.executor.clear_session().await;
 
@@ -185,7 +808,12 @@ pub async fn execute_action(
 
     state
         .frida_manager
-        .call_rpc(session_id, script_id, "executeTargetNode", vec![request])
+        .call_rpc(
+            session_id,
+            script_id,
+            protocol::EXECUTE_TARGET_NODE,
+            vec![request],
+        )
         .await
         .map_err(|e: FridaError| e.to_string())
 }
@@ -207,34 +835,40 @@ pub async fn trigger_ui_event(
         value
     );
 
-    let state = state.lock().await;
-
-    // Sync component value
-    state
-        .sync_component_value(&component_id, value.clone())
-        .await;
+    // Scripts run without the state lock; they may wait on a process or call
+    // back into other commands
+    let (executor, handlers) = {
+        let state = state.lock().await;
 
-    // Find scripts that listen to this component's events
-    if let Some(config) = &state.config {
-        tracing::info!("Found {} scripts in config", config.scripts.len());
+        // Sync component value
+        state
+            .sync_component_value(&component_id, value.clone())
+            .await;
 
-        for script in &config.scripts {
-            tracing::debug!(
-                "Checking script '{}' with {} nodes",
-                script.name,
-                script.nodes.len()
-            );
+        let mut handlers = Vec::new();
+        // Find scripts that listen to this component's events
+        if let Some(config) = &state.config {
+            tracing::info!("Found {} scripts in config", config.scripts.len());
 
-            // Look for event_ui nodes that match this component
-            for node in &script.nodes {
+            for script in &config.scripts {
                 tracing::debug!(
-                    "  Node '{}' type='{}' config={:?}",
-                    node.id,
-                    node.node_type,
-                    node.config
+                    "Checking script '{}' with {} nodes",
+                    script.name,
+                    script.nodes.len()
                 );
 
-                if node.node_type == "event_ui" {
+                // Look for event_ui nodes that match this component
+                for node in &script.nodes {
+                    tracing::debug!(
+                        "  Node '{}' type='{}' config={:?}",
+                        node.id,
+                        node.node_type,
+                        node.config
+                    );
+
+                    if node.node_type != "event_ui" {
+                        continue;
+                    }
                     let node_component_id = node.config.get("componentId").and_then(|v| v.as_str());
                     let node_event_type = node.config.get("eventType").and_then(|v| v.as_str());
 
@@ -249,74 +883,146 @@ pub async fn trigger_ui_event(
                     if node_component_id == Some(&component_id)
                         && node_event_type == Some(&event_type)
                     {
-                        // Convert and execute the script
-                        let executor_script = convert_config_script_to_executor(script);
-                        let event_value = Value::from(value.clone());
+                        handlers.push((
+                            script.name.clone(),
+                            convert_config_script_to_executor(script),
+                            node.id.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+        (state.executor.clone(), handlers)
+    };
+
+    for (name, executor_script, node_id) in handlers {
+        tracing::info!(
+            "Executing script '{}' node '{}' for component '{}' event '{}'",
+            name,
+            node_id,
+            component_id,
+            event_type
+        );
 
+        match executor
+            .execute_from_event(
+                executor_script,
+                &node_id,
+                Value::from(value.clone()),
+                Some(component_id.clone()),
+            )
+            .await
+        {
+            Ok(result) => {
+                if result.success {
+                    tracing::info!("Script '{}' executed successfully", name);
+                    for log in &result.logs {
+                        tracing::info!("Script log: {}", log);
+                    }
+                    // Emit notifications to frontend
+                    for notification in &result.notifications {
                         tracing::info!(
-                            "Executing script '{}' node '{}' for component '{}' event '{}'",
-                            script.name,
-                            node.id,
-                            component_id,
-                            event_type
+                            "Emitting notification: {} - {}",
+                            notification.title,
+                            notification.message
+                        );
+                        let _ = app.emit(
+                            "notification",
+                            NotificationEvent {
+                                title: notification.title.clone(),
+                                message: notification.message.clone(),
+                                level: notification.level.clone(),
+                            },
                         );
-
-                        match state
-                            .executor
-                            .execute_from_event(
-                                executor_script,
-                                &node.id,
-                                event_value,
-                                Some(component_id.clone()),
-                            )
-                            .await
-                        {
-                            Ok(result) => {
-                                if result.success {
-                                    tracing::info!(
-                                        "Script '{}' executed successfully",
-                                        script.name
-                                    );
-                                    for log in &result.logs {
-                                        tracing::info!("Script log: {}", log);
-                                    }
-                                    // Emit notifications to frontend
-                                    for notification in &result.notifications {
-                                        tracing::info!(
-                                            "Emitting notification: {} - {}",
-                                            notification.title,
-                                            notification.message
-                                        );
-                                        let _ = app.emit(
-                                            "notification",
-                                            NotificationEvent {
-                                                title: notification.title.clone(),
-                                                message: notification.message.clone(),
-                                                level: notification.level.clone(),
-                                            },
-                                        );
-                                    }
-                                } else {
-                                    tracing::error!(
-                                        "Script '{}' failed: {:?}",
-                                        script.name,
-                                        result.error
-                                    );
-                                }
-                            }
-                            Err(e) => {
-                                tracing::error!("Script execution error: {}", e);
-                            }
-                        }
                     }
+                } else {
+                    tracing::error!("Script '{}' failed: {:?}", name, result.error);
                 }
             }
+            Err(e) => {
+                tracing::error!("Script execution error: {}", e);
+            }
         }
     }
 
     Ok(())
 }
 
+/// Run the matching native_callback node and post its return value back to
+/// the script that made the call
+///
+/// The calling target thread is blocked until the reply arrives, so the state
+/// lock is only held while looking the node up.
+async fn dispatch_native_callback(
+    state: Arc<Mutex<AppState>>,
+    session_id: String,
+    script_id: String,
+    payload: serde_json::Value,
+) {
+    let callback_id = payload.get("callbackId").and_then(|v| v.as_str());
+    let call_id = payload
+        .get("callId")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+    let args = Value::from(payload.get("args").cloned().unwrap_or_default());
+
+    let (executor, manager, executor_script, node_id) = {
+        let state = state.lock().await;
+        let Some(config) = &state.config else {
+            return;
+        };
+
+        let found = config.scripts.iter().find_map(|script| {
+            script
+                .nodes
+                .iter()
+                .find(|node| {
+                    let node_callback_id = node
+                        .config
+                        .get("callbackId")
+                        .and_then(|v| v.as_str())
+                        .filter(|id| !id.is_empty())
+                        .unwrap_or(&node.id);
+                    node.node_type == "native_callback" && Some(node_callback_id) == callback_id
+                })
+                .map(|node| (script, node.id.clone()))
+        });
+
+        // No reply on failure: the target times out and calls the original function
+        let Some((script, node_id)) = found else {
+            tracing::warn!("No native_callback node for callback {:?}", callback_id);
+            return;
+        };
+        (
+            state.executor.clone(),
+            Arc::clone(&state.frida_manager),
+            convert_config_script_to_executor(script),
+            node_id,
+        )
+    };
+
+    let value = match executor
+        .execute_native_callback(executor_script, &node_id, args)
+        .await
+    {
+        Ok(value) => value,
+        Err(e) => {
+            tracing::error!("Native callback '{}' failed: {}", node_id, e);
+            return;
+        }
+    };
+
+    // Only the script instance that is waiting on the call can take the reply
+    let reply = serde_json::json!({
+        "type": "native_callback_reply",
+        "callId": call_id,
+        "value": serde_json::Value::from(value),
+    });
+    if let Err(e) = manager.post_message(&session_id, &script_id, reply).await {
+        tracing::error!("Failed to reply to native callback: {}", e);
+    }
+}
+
 /// Convert a config Script to executor Script format
 fn convert_config_script_to_executor(script: &ConfigScript) -> ExecutorScript {
     fn map_value_type(value_type: CoreValueType) -> ValueType {
@@ -459,13 +1165,22 @@ fn get_demo_config() -> ProjectConfig {
     ProjectConfig {
         name: "Demo Project".to_string(),
         version: "0.1.0".to_string(),
+        config_version: crate::state::SUPPORTED_CONFIG_VERSION,
+        description: None,
+        author: None,
         target_process: None,
+        process_patterns: vec![],
+        adapter_type: "local_pc".to_string(),
+        adapter_config: serde_json::Value::Null,
         auto_attach: false,
+        hotkeys: Default::default(),
+        structs: vec![],
         canvas: crate::state::CanvasSettings {
             width: 400,
             height: 500,
             padding: 12,
             gap: 8,
+            theme: "dark".to_string(),
         },
         components: vec![
             ConfigUIComponent {
@@ -580,6 +1295,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn process_patterns_match_wildcards() {
+        assert!(glob_match("game*.exe", "Game-Win64.exe"));
+        assert!(glob_match("proc?", "proc1"));
+        assert!(!glob_match("proc?", "proc12"));
+        assert!(!glob_match("game*.exe", "launcher.exe"));
+    }
+
     #[tokio::test]
     async fn generated_project_event_execution_smoke() {
         // 1) Create a minimal sample project with a UI event script:
@@ -688,8 +1411,15 @@ mod tests {
         let config_json = tokio::fs::read_to_string(&config_path)
             .await
             .expect("read generated config");
-        let config: ProjectConfig =
-            serde_json::from_str(&config_json).expect("runtime config deserialization");
+        let config = ProjectConfig::parse(&config_json).expect("runtime config deserialization");
+        assert_eq!(
+            config.config_version,
+            forvanced_build::RUNTIME_CONFIG_VERSION
+        );
+        assert_eq!(
+            crate::state::SUPPORTED_CONFIG_VERSION,
+            forvanced_build::RUNTIME_CONFIG_VERSION
+        );
         let cfg_script = config
             .scripts
             .iter()
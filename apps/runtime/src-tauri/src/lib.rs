@@ -21,13 +21,41 @@ pub fn run() {
 
     tracing::info!("Starting Forvanced Runtime");
 
-    let app_state = Arc::new(Mutex::new(AppState::new()));
+    // A prebuilt runtime gets its trainer from a signed payload (data-only packaging)
+    let app_state = match state::load_payload_config() {
+        Ok(Some(config)) => {
+            tracing::info!(
+                "Loaded config payload: {} with {} scripts",
+                config.name,
+                config.scripts.len()
+            );
+            AppState::from_config(config)
+        }
+        Ok(None) => AppState::new(),
+        Err(e) => {
+            tracing::error!("Rejected config payload: {}", e);
+            AppState::new()
+        }
+    };
+    let app_state = Arc::new(Mutex::new(app_state));
 
     tauri::Builder::default()
-        .manage(app_state)
+        .manage(Arc::clone(&app_state))
+        .setup(move |app| {
+            // Watch for the target when the trainer asks to attach on its own
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = commands::start_auto_attach(app_handle, app_state).await {
+                    tracing::error!("Failed to start auto-attach: {}", e);
+                }
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::get_project_config,
             commands::attach_process,
+            commands::spawn_process,
+            commands::launch_process,
             commands::detach_process,
             commands::execute_action,
             commands::trigger_ui_event,
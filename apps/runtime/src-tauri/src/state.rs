@@ -1,8 +1,11 @@
 //! Runtime application state
 
-use forvanced_core::project::{ComponentType, UIComponent, VisualScript};
-use forvanced_executor::{ScriptExecutor, Value as ExecutorValue};
-use forvanced_frida::FridaManager;
+use forvanced_core::project::{ComponentType, HotkeyConfig, UIComponent, VisualScript};
+use forvanced_core::structs::StructDefinition;
+use forvanced_executor::{
+    DeviceProvider, FridaSessionHost, ScriptExecutor, Value as ExecutorValue,
+};
+use forvanced_frida::{FridaManager, ProcessWatcher};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -56,42 +59,160 @@ pub fn executor_to_json(value: &ExecutorValue) -> serde_json::Value {
     }
 }
 
+/// Newest embedded config version this runtime understands
+/// (`forvanced_build::RUNTIME_CONFIG_VERSION` at the time it was built)
+pub const SUPPORTED_CONFIG_VERSION: u32 = 2;
+
 /// Trainer configuration embedded at build time
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectConfig {
+    /// Config format version (configs from before versioning are 1)
+    #[serde(default = "default_config_version")]
+    pub config_version: u32,
     /// Trainer name
     pub name: String,
     /// Trainer version
     #[serde(default = "default_project_version")]
     pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
     /// Target process name (optional - user can select if not specified)
     #[serde(default)]
     pub target_process: Option<String>,
+    /// Additional process name patterns (`*` and `?` wildcards)
+    #[serde(default)]
+    pub process_patterns: Vec<String>,
+    /// Device to attach through: `local_pc`, `usb` or `remote`
+    #[serde(default = "default_adapter_type")]
+    pub adapter_type: String,
+    /// Adapter settings, e.g. `{ "host": "...", "port": 27042 }` for remote
+    #[serde(default)]
+    pub adapter_config: serde_json::Value,
     /// Auto-attach on startup
     #[serde(default)]
     pub auto_attach: bool,
+    /// Keyboard shortcuts that trigger components
+    #[serde(default)]
+    pub hotkeys: HotkeyConfig,
     /// UI components
     #[serde(default)]
     pub components: Vec<UIComponent>,
     /// Visual scripts
     #[serde(default)]
     pub scripts: Vec<VisualScript>,
+    /// Struct layouts for struct_read/struct_write nodes
+    #[serde(default)]
+    pub structs: Vec<StructDefinition>,
     /// Canvas settings
     #[serde(default)]
     pub canvas: CanvasSettings,
 }
 
+/// Publisher keys this runtime build accepts payloads from (comma-separated hex).
+/// They are always trusted; a package manifest can add more.
+const TRUSTED_PAYLOAD_KEYS: Option<&str> = option_env!("FORVANCED_TRUSTED_KEYS");
+
+/// Keys a payload next to `exe` may be signed with: the embedded ones plus the
+/// `trustedKeys` of the package manifest (`<exe stem>.manifest.json`), which
+/// carries the project's `config.build.trusted_keys`. With neither, every
+/// payload is refused.
+fn trusted_payload_keys(exe: &std::path::Path) -> Vec<String> {
+    let mut keys: Vec<String> = TRUSTED_PAYLOAD_KEYS
+        .map(|keys| {
+            keys.split(',')
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let manifest = exe.with_extension("manifest.json");
+    if let Ok(json) = std::fs::read_to_string(&manifest) {
+        match serde_json::from_str::<serde_json::Value>(&json) {
+            Ok(manifest) => keys.extend(
+                manifest["trustedKeys"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|k| k.as_str())
+                    .map(String::from),
+            ),
+            Err(e) => tracing::warn!("Ignoring unreadable {}: {}", manifest.display(), e),
+        }
+    }
+    keys
+}
+
+/// Load the trainer config from a payload appended to this executable or
+/// stored next to it (data-only packaging). Returns `Ok(None)` without one.
+pub fn load_payload_config() -> Result<Option<ProjectConfig>, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let Some(payload) = forvanced_core::payload::find_payload(&exe).map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+
+    let trusted = trusted_payload_keys(&exe);
+    let trusted: Vec<&str> = trusted.iter().map(String::as_str).collect();
+    let config = payload.verify(&trusted).map_err(|e| e.to_string())?;
+    ProjectConfig::parse(config).map(Some)
+}
+
+impl ProjectConfig {
+    /// Parse an embedded config, refusing formats newer than this runtime
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let config: Self = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse project config: {}", e))?;
+        if config.config_version > SUPPORTED_CONFIG_VERSION {
+            return Err(format!(
+                "Project config version {} is newer than this runtime supports ({})",
+                config.config_version, SUPPORTED_CONFIG_VERSION
+            ));
+        }
+        Ok(config)
+    }
+}
+
+fn default_config_version() -> u32 {
+    1
+}
+
 fn default_project_version() -> String {
     "0.1.0".to_string()
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
+fn default_adapter_type() -> String {
+    "local_pc".to_string()
+}
+
+fn default_theme() -> String {
+    "dark".to_string()
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CanvasSettings {
     pub width: u32,
     pub height: u32,
     pub padding: u32,
     pub gap: u32,
+    #[serde(default = "default_theme")]
+    pub theme: String,
+}
+
+impl Default for CanvasSettings {
+    fn default() -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            padding: 0,
+            gap: 0,
+            theme: default_theme(),
+        }
+    }
 }
 
 /// Runtime application state
@@ -110,45 +231,55 @@ pub struct AppState {
     pub executor_ui_state: ExecutorUIState,
     /// Trainer configuration (loaded at startup)
     pub config: Option<ProjectConfig>,
+    /// Target script to inject instead of the built-in one (protected builds)
+    pub target_script: Option<String>,
+    /// Keeps the target attached when the config asks for auto-attach
+    pub watcher: Option<ProcessWatcher>,
 }
 
 impl AppState {
     pub fn new() -> Self {
-        let frida_manager = FridaManager::new().expect("Failed to initialize FridaManager");
+        let frida_manager =
+            Arc::new(FridaManager::new().expect("Failed to initialize FridaManager"));
 
         let component_values: Arc<RwLock<HashMap<String, serde_json::Value>>> =
             Arc::new(RwLock::new(HashMap::new()));
         let executor_ui_state: ExecutorUIState = Arc::new(RwLock::new(HashMap::new()));
-        let executor = ScriptExecutor::new(Arc::clone(&executor_ui_state));
+        let executor = new_executor(&frida_manager, &executor_ui_state);
 
         Self {
-            frida_manager: Arc::new(frida_manager),
+            frida_manager,
             session_id: None,
             script_id: None,
             executor,
             component_values,
             executor_ui_state,
             config: None,
+            target_script: None,
+            watcher: None,
         }
     }
 
     /// Create AppState from embedded config (used by generated projects)
     pub fn from_config(config: ProjectConfig) -> Self {
-        let frida_manager = FridaManager::new().expect("Failed to initialize FridaManager");
+        let frida_manager =
+            Arc::new(FridaManager::new().expect("Failed to initialize FridaManager"));
 
         let component_values: Arc<RwLock<HashMap<String, serde_json::Value>>> =
             Arc::new(RwLock::new(HashMap::new()));
         let executor_ui_state: ExecutorUIState = Arc::new(RwLock::new(HashMap::new()));
-        let executor = ScriptExecutor::new(Arc::clone(&executor_ui_state));
+        let executor = new_executor(&frida_manager, &executor_ui_state);
 
         Self {
-            frida_manager: Arc::new(frida_manager),
+            frida_manager,
             session_id: None,
             script_id: None,
             executor,
             component_values,
             executor_ui_state,
             config: Some(config),
+            target_script: None,
+            watcher: None,
         }
     }
 
@@ -195,25 +326,36 @@ impl AppState {
 
 impl Default for AppState {
     fn default() -> Self {
-        let frida_manager = FridaManager::new().expect("Failed to initialize FridaManager");
+        let frida_manager =
+            Arc::new(FridaManager::new().expect("Failed to initialize FridaManager"));
 
         let component_values: Arc<RwLock<HashMap<String, serde_json::Value>>> =
             Arc::new(RwLock::new(HashMap::new()));
         let executor_ui_state: ExecutorUIState = Arc::new(RwLock::new(HashMap::new()));
-        let executor = ScriptExecutor::new(Arc::clone(&executor_ui_state));
+        let executor = new_executor(&frida_manager, &executor_ui_state);
 
         Self {
-            frida_manager: Arc::new(frida_manager),
+            frida_manager,
             session_id: None,
             script_id: None,
             executor,
             component_values,
             executor_ui_state,
             config: None,
+            target_script: None,
+            watcher: None,
         }
     }
 }
 
+/// Executor whose device and process nodes go through `frida_manager`
+fn new_executor(frida_manager: &Arc<FridaManager>, ui_state: &ExecutorUIState) -> ScriptExecutor {
+    let mut executor = ScriptExecutor::new(Arc::clone(ui_state));
+    executor.set_device_provider(Arc::clone(frida_manager) as Arc<dyn DeviceProvider>);
+    executor.set_session_host(Arc::new(FridaSessionHost::new(Arc::clone(frida_manager))));
+    executor
+}
+
 /// Get default value for a component based on its type
 fn get_default_value(component: &UIComponent) -> Option<serde_json::Value> {
     match component.component_type {
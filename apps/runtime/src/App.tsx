@@ -1,4 +1,4 @@
-import { Component, createResource, createSignal, For, onCleanup, onMount, Show } from "solid-js";
+import { Component, createEffect, createResource, createSignal, For, onCleanup, onMount, Show } from "solid-js";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { ProjectUI } from "./components/ProjectUI";
@@ -19,8 +19,16 @@ interface Toast {
 
 // Types matching backend
 interface ProjectConfig {
+  configVersion: number;
   name: string;
+  version: string;
+  description: string | null;
+  author: string | null;
   targetProcess: string | null;
+  processPatterns: string[];
+  adapterType: string;
+  autoAttach: boolean;
+  hotkeys: HotkeyConfig;
   components: UIComponent[];
   scripts: Script[];
   canvas: CanvasSettings;
@@ -31,6 +39,32 @@ interface CanvasSettings {
   height: number;
   padding: number;
   gap: number;
+  theme: string;
+}
+
+interface HotkeyConfig {
+  enabled: boolean;
+  bindings: HotkeyBinding[];
+}
+
+interface HotkeyBinding {
+  key: string;
+  modifiers: string[];
+  action_id: string;
+}
+
+const MODIFIERS = ["ctrl", "shift", "alt", "meta"] as const;
+
+function matchesHotkey(event: KeyboardEvent, binding: HotkeyBinding): boolean {
+  if (event.key.toLowerCase() !== binding.key.toLowerCase()) return false;
+  const wanted = binding.modifiers.map((m) => m.toLowerCase());
+  const pressed = {
+    ctrl: event.ctrlKey,
+    shift: event.shiftKey,
+    alt: event.altKey,
+    meta: event.metaKey,
+  };
+  return MODIFIERS.every((m) => pressed[m] === wanted.includes(m));
 }
 
 interface UIComponent {
@@ -109,6 +143,30 @@ const App: Component = () => {
     }
   });
 
+  // Hotkeys trigger the bound component as if it was clicked
+  onMount(() => {
+    const onKeyDown = (event: KeyboardEvent) => {
+      const hotkeys = config()?.hotkeys;
+      if (!hotkeys?.enabled) return;
+      const binding = hotkeys.bindings.find((b) => matchesHotkey(event, b));
+      if (!binding) return;
+      event.preventDefault();
+      invoke("trigger_ui_event", {
+        componentId: binding.action_id,
+        eventType: "click",
+        value: true,
+      }).catch((e) => console.error("Failed to trigger hotkey:", e));
+    };
+    window.addEventListener("keydown", onKeyDown);
+    onCleanup(() => window.removeEventListener("keydown", onKeyDown));
+  });
+
+  createEffect(() => {
+    const theme = config()?.canvas.theme ?? "dark";
+    document.documentElement.dataset.theme = theme;
+    document.documentElement.classList.toggle("dark", theme !== "light");
+  });
+
   const getToastColor = (level: string) => {
     switch (level) {
       case "error": return "bg-red-600";
@@ -1,5 +1,6 @@
-import { Component, For, Show, createSignal, createMemo } from "solid-js";
+import { Component, For, Show, createSignal, createMemo, onMount, onCleanup } from "solid-js";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 
 // Types
 interface ProjectConfig {
@@ -15,6 +16,7 @@ interface CanvasSettings {
   height: number;
   padding: number;
   gap: number;
+  theme: string;
 }
 
 interface UIComponent {
@@ -75,6 +77,20 @@ export const ProjectUI: Component<ProjectUIProps> = (props) => {
     return values;
   });
 
+  // Values pushed by scripts running inside the target (compiled agents)
+  onMount(async () => {
+    const unlisten = await listen<{ componentId: string; value: unknown }>(
+      "component_value",
+      (event) => {
+        const current = componentValues().size === 0 ? initValues() : componentValues();
+        const next = new Map(current);
+        next.set(event.payload.componentId, event.payload.value);
+        setComponentValues(next);
+      }
+    );
+    onCleanup(unlisten);
+  });
+
   // Get root components (no parent)
   const rootComponents = createMemo(() =>
     props.config.components
@@ -0,0 +1,1869 @@
+//! Compiled target agents
+//!
+//! Every Target node costs an RPC round-trip from the host executor. For hot
+//! paths, an `event_interval` chain made only of Target nodes and host nodes
+//! that need nothing from the host (constants, variables, math, branching,
+//! strings, output) is compiled here into JavaScript that is appended to
+//! [`generate_target_script`] and runs entirely inside the target, calling the
+//! same `targetNodes` handlers directly. The host only hears from it once per
+//! tick, with UI updates, logs and notifications.
+//!
+//! The generated code follows `ScriptExecutor`'s graph semantics: flow ports
+//! are followed in connection order, value inputs come from the tick's output
+//! cache or are evaluated on demand, variables persist per script and roll
+//! back when a tick fails. Values keep the executor's integer/float/pointer
+//! distinction so math and formatting give the same results; integers are
+//! exact up to 2^53.
+//!
+//! Chains that can't be compiled are listed in [`CompiledAgent::skipped`].
+
+use forvanced_core::project::VisualScript;
+use forvanced_core::structs::{resolve_layout, StructDefinition};
+use forvanced_core::Address;
+use forvanced_executor::nodes::is_event_node;
+use forvanced_executor::rpc::generate_target_script;
+use forvanced_executor::script::{PortType, Script, ScriptNode, ValueType};
+use forvanced_executor::value::Value;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// Target handlers a compiled agent may call. `native_callback` needs the
+/// host, and the hook nodes only make sense inside an interceptor.
+const TARGET_NODES: &[&str] = &[
+    "memory_read",
+    "memory_write",
+    "memory_scan",
+    "memory_alloc",
+    "memory_protect",
+    "pointer_add",
+    "pointer_read",
+    "pointer_write",
+    "struct_read",
+    "struct_write",
+    "get_module",
+    "find_symbol",
+    "resolve_address",
+    "get_base_address",
+    "enumerate_modules",
+    "enumerate_exports",
+    "call_native",
+];
+
+const MATH_OPS: &[&str] = &[
+    "add",
+    "subtract",
+    "sub",
+    "multiply",
+    "mul",
+    "divide",
+    "div",
+    "modulo",
+    "mod",
+    "power",
+    "pow",
+    "min",
+    "max",
+    "abs",
+    "floor",
+    "ceil",
+    "round",
+    "sqrt",
+    "bit_and",
+    "bit_or",
+    "bit_xor",
+    "bit_not",
+    "shift_left",
+    "shl",
+    "shift_right",
+    "shr",
+];
+
+const COMPARE_OPS: &[&str] = &[
+    "equals",
+    "eq",
+    "==",
+    "not_equals",
+    "neq",
+    "!=",
+    "less_than",
+    "lt",
+    "<",
+    "less_than_equals",
+    "lte",
+    "<=",
+    "greater_than",
+    "gt",
+    ">",
+    "greater_than_equals",
+    "gte",
+    ">=",
+];
+
+const LOGIC_OPS: &[&str] = &[
+    "and", "&&", "or", "||", "not", "!", "xor", "^", "nand", "nor",
+];
+
+/// An interval chain that runs inside the target
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompiledEntry {
+    pub script_id: String,
+    pub script_name: String,
+    pub event_node_id: String,
+    pub interval_ms: u64,
+}
+
+/// An interval chain that was not compiled
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedEntry {
+    pub script_id: String,
+    pub script_name: String,
+    pub event_node_id: String,
+    pub reason: String,
+}
+
+/// Result of [`compile_agent`]
+#[derive(Debug, Clone, Default)]
+pub struct CompiledAgent {
+    /// JavaScript to append to the target script; empty when nothing compiled
+    pub code: String,
+    pub compiled: Vec<CompiledEntry>,
+    pub skipped: Vec<SkippedEntry>,
+}
+
+impl CompiledAgent {
+    pub fn is_empty(&self) -> bool {
+        self.compiled.is_empty()
+    }
+
+    /// The target RPC handler script with the compiled agents appended
+    pub fn target_script(&self) -> String {
+        generate_target_script() + &self.code
+    }
+}
+
+/// Compile every eligible `event_interval` chain in `scripts`
+pub fn compile_agent(scripts: &[VisualScript], structs: &[StructDefinition]) -> CompiledAgent {
+    let mut agent = CompiledAgent::default();
+    let mut address_specs = BTreeMap::new();
+    let mut blocks = String::new();
+
+    for visual in scripts {
+        let skip = |event_node_id: &str, reason: String| SkippedEntry {
+            script_id: visual.id.clone(),
+            script_name: visual.name.clone(),
+            event_node_id: event_node_id.to_string(),
+            reason,
+        };
+        let script: Script = match serde_json::to_value(visual).and_then(serde_json::from_value) {
+            Ok(script) => script,
+            Err(e) => {
+                agent.skipped.extend(
+                    visual
+                        .nodes
+                        .iter()
+                        .filter(|n| n.node_type == "event_interval")
+                        .map(|n| skip(&n.id, format!("script could not be read: {}", e))),
+                );
+                continue;
+            }
+        };
+        let compiler = ScriptCompiler::new(&script, structs);
+
+        // Variables touched by flows that stay on the host can't live in the target
+        let host_variables: BTreeSet<String> = script
+            .nodes
+            .iter()
+            .filter_map(|n| match n.node_type.as_str() {
+                "event_interval" => None,
+                "native_callback" => Some((n, "onCall")),
+                t if is_event_node(t) => Some((n, "exec")),
+                _ => None,
+            })
+            .flat_map(|(n, port)| compiler.variables_used(&compiler.reach(n, port)))
+            .collect();
+
+        let mut entries = Vec::new();
+        for event in script
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == "event_interval")
+        {
+            let checked = compiler.check_entry(event).and_then(|nodes| {
+                match compiler
+                    .variables_used(&nodes)
+                    .into_iter()
+                    .find(|name| host_variables.contains(name))
+                {
+                    Some(name) => Err(format!("shares variable '{}' with host-side events", name)),
+                    None => Ok(nodes),
+                }
+            });
+            match checked {
+                Ok(nodes) => entries.push((event, nodes)),
+                Err(reason) => agent.skipped.push(skip(&event.id, reason)),
+            }
+        }
+        if entries.is_empty() {
+            continue;
+        }
+
+        blocks.push_str(&compiler.emit(&entries, &mut address_specs));
+        agent
+            .compiled
+            .extend(entries.iter().map(|(event, _)| CompiledEntry {
+                script_id: script.id.clone(),
+                script_name: script.name.clone(),
+                event_node_id: event.id.clone(),
+                interval_ms: interval_ms(event),
+            }));
+    }
+
+    if !blocks.is_empty() {
+        let specs = serde_json::Value::Object(address_specs.into_iter().collect());
+        agent.code = format!(
+            "{}\nconst agentAddressSpecs = {};\n\nconst compiledAgents = [];\n{}{}",
+            AGENT_RUNTIME, specs, blocks, AGENT_START
+        );
+    }
+    agent
+}
+
+fn interval_ms(event: &ScriptNode) -> u64 {
+    event
+        .config_f64("intervalMs")
+        .or_else(|| event.config_f64("interval"))
+        .unwrap_or(1000.0)
+        .max(1.0) as u64
+}
+
+/// Where a value input comes from
+enum Source<'s> {
+    /// Not connected; the input is absent
+    None,
+    /// An event or flow node; its outputs exist only once it has run
+    Cached(&'s ScriptNode, &'s str),
+    /// A pure value node, evaluated on demand
+    Value(&'s ScriptNode, &'s str),
+}
+
+struct ScriptCompiler<'s> {
+    script: &'s Script,
+    structs: &'s [StructDefinition],
+    index: HashMap<&'s str, usize>,
+}
+
+impl<'s> ScriptCompiler<'s> {
+    fn new(script: &'s Script, structs: &'s [StructDefinition]) -> Self {
+        let index = script
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        Self {
+            script,
+            structs,
+            index,
+        }
+    }
+
+    fn node(&self, id: &str) -> Option<&'s ScriptNode> {
+        self.index.get(id).map(|&i| &self.script.nodes[i])
+    }
+
+    /// Pure value nodes have no flow inputs and are evaluated when read
+    fn is_value_node(node: &ScriptNode) -> bool {
+        !is_event_node(&node.node_type) && node.inputs.iter().all(|p| p.port_type != PortType::Flow)
+    }
+
+    /// Nodes reached by following `port` of `node`, in connection order
+    fn flow_targets(&self, node: &ScriptNode, port: &str) -> Vec<&'s str> {
+        let Some(port) = node.output_by_name(port) else {
+            return Vec::new();
+        };
+        self.script
+            .connections_from_port(&node.id, &port.id)
+            .into_iter()
+            .map(|c| c.to_node_id.as_str())
+            .collect()
+    }
+
+    /// Distinct flow output port names of a node
+    fn flow_ports(node: &ScriptNode) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for port in node
+            .outputs
+            .iter()
+            .filter(|p| p.port_type == PortType::Flow)
+        {
+            if !names.contains(&port.name.as_str()) {
+                names.push(&port.name);
+            }
+        }
+        names
+    }
+
+    fn source(&self, node: &ScriptNode, port_id: &str) -> Source<'s> {
+        let Some(conn) = self.script.connection_to_port(&node.id, port_id) else {
+            return Source::None;
+        };
+        let Some(from) = self.node(&conn.from_node_id) else {
+            return Source::None;
+        };
+        match from.output_by_id(&conn.from_port_id) {
+            None => Source::None,
+            Some(port) if Self::is_value_node(from) => Source::Value(from, &port.name),
+            Some(port) => Source::Cached(from, &port.name),
+        }
+    }
+
+    fn value_inputs(
+        &self,
+        node: &'s ScriptNode,
+    ) -> impl Iterator<Item = (&'s str, Source<'s>)> + '_ {
+        node.inputs
+            .iter()
+            .filter(|p| p.port_type != PortType::Flow)
+            .map(move |p| (p.name.as_str(), self.source(node, &p.id)))
+    }
+
+    /// Every node an entry may execute: flow-reachable nodes and the pure
+    /// value nodes they read from
+    fn reach(&self, start: &ScriptNode, port: &str) -> BTreeSet<usize> {
+        let mut seen = BTreeSet::new();
+        let mut stack: Vec<&str> = self.flow_targets(start, port);
+        while let Some(id) = stack.pop() {
+            let Some(&i) = self.index.get(id) else {
+                continue;
+            };
+            if !seen.insert(i) {
+                continue;
+            }
+            let node = &self.script.nodes[i];
+            for port in Self::flow_ports(node) {
+                stack.extend(self.flow_targets(node, port));
+            }
+            for (_, source) in self.value_inputs(node) {
+                if let Source::Value(from, _) = source {
+                    stack.push(&from.id);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Names of the variables a set of nodes reads or writes
+    fn variables_used(&self, nodes: &BTreeSet<usize>) -> BTreeSet<String> {
+        nodes
+            .iter()
+            .map(|&i| &self.script.nodes[i])
+            .filter_map(|n| match n.node_type.as_str() {
+                "declare_variable" => n.config_str("variableName"),
+                "get_variable" | "set_variable" => {
+                    n.config_str("variableId").map(|id| self.variable_name(&id))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn variable_name(&self, id: &str) -> String {
+        self.script
+            .find_variable(id)
+            .map(|v| v.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    /// Check that an interval chain can run in the target, returning its nodes
+    fn check_entry(&self, event: &ScriptNode) -> Result<BTreeSet<usize>, String> {
+        if self.flow_targets(event, "exec").is_empty() {
+            return Err("nothing is connected to its exec output".to_string());
+        }
+        let nodes = self.reach(event, "exec");
+
+        for &i in &nodes {
+            let node = &self.script.nodes[i];
+            self.compute(node)?;
+            for port in Self::flow_ports(node) {
+                if let Some(id) = self
+                    .flow_targets(node, port)
+                    .iter()
+                    .find(|id| self.node(id).is_none())
+                {
+                    return Err(format!("'{}' connects to missing node {}", node.label, id));
+                }
+            }
+            for (_, source) in self.value_inputs(node) {
+                let Source::Cached(from, port) = source else {
+                    continue;
+                };
+                if is_event_node(&from.node_type)
+                    && (from.id != event.id || !matches!(port, "value" | "tick"))
+                {
+                    return Err(format!(
+                        "'{}' reads {} of another event node",
+                        node.label, port
+                    ));
+                }
+            }
+        }
+
+        let mut state = HashMap::new();
+        for id in self.flow_targets(event, "exec") {
+            self.check_flow_cycle(id, &mut state)?;
+        }
+        let mut state = HashMap::new();
+        for &i in &nodes {
+            self.check_value_cycle(&self.script.nodes[i], &mut state)?;
+        }
+        Ok(nodes)
+    }
+
+    /// Reject flow cycles; `ScriptExecutor` would recurse until it overflows
+    fn check_flow_cycle(&self, id: &str, state: &mut HashMap<String, bool>) -> Result<(), String> {
+        let Some(node) = self.node(id) else {
+            return Ok(());
+        };
+        match state.get(id) {
+            Some(false) => return Err(format!("flow cycle through '{}'", node.label)),
+            Some(true) => return Ok(()),
+            None => {}
+        }
+        state.insert(id.to_string(), false);
+        for port in Self::flow_ports(node) {
+            for next in self.flow_targets(node, port) {
+                self.check_flow_cycle(next, state)?;
+            }
+        }
+        state.insert(id.to_string(), true);
+        Ok(())
+    }
+
+    /// Reject value cycles; `ScriptExecutor` fails them with `CycleDetected`
+    fn check_value_cycle(
+        &self,
+        node: &'s ScriptNode,
+        state: &mut HashMap<String, bool>,
+    ) -> Result<(), String> {
+        match state.get(&node.id) {
+            Some(false) => return Err(format!("value cycle through '{}'", node.label)),
+            Some(true) => return Ok(()),
+            None => {}
+        }
+        state.insert(node.id.clone(), false);
+        for (_, source) in self.value_inputs(node) {
+            if let Source::Value(from, _) = source {
+                self.check_value_cycle(from, state)?;
+            }
+        }
+        state.insert(node.id.clone(), true);
+        Ok(())
+    }
+
+    /// Body of `function c<i>(i, t)`: computes `[outputs, flowPort]` from the
+    /// collected inputs `i` and the tick state `t`
+    fn compute(&self, node: &ScriptNode) -> Result<String, String> {
+        let required = |key: &str| {
+            node.config_str(key)
+                .ok_or_else(|| format!("{} node '{}' requires {}", node.node_type, node.label, key))
+        };
+        let operation = |default: &str, known: &[&str]| {
+            let op = node
+                .config_str("operation")
+                .unwrap_or_else(|| default.to_string());
+            if known.contains(&op.as_str()) {
+                Ok(js_str(&op))
+            } else {
+                Err(format!("unknown {} operation '{}'", node.node_type, op))
+            }
+        };
+
+        let code = match node.node_type.as_str() {
+            "const_string" | "const_number" | "const_boolean" | "const_pointer" => {
+                format!("return [{{ value: {} }}, null];", js_value(&constant_value(node)))
+            }
+            "declare_variable" => {
+                let name = js_str(&required("variableName")?);
+                let inline = node
+                    .config_str("inlineValue")
+                    .filter(|s| !s.is_empty())
+                    .map(Value::String)
+                    .unwrap_or(Value::Null);
+                format!(
+                    "const v = i.initialValue !== undefined ? i.initialValue : {}; t.vars[{}] = v; return [{{ value: v }}, 'exec'];",
+                    js_value(&inline),
+                    name
+                )
+            }
+            "set_variable" => {
+                let name = js_str(&self.variable_name(&required("variableId")?));
+                format!("const v = agentOr(i.value, null); t.vars[{}] = v; return [{{ value: v }}, 'exec'];", name)
+            }
+            "get_variable" => {
+                let name = js_str(&self.variable_name(&required("variableId")?));
+                format!(
+                    "if (!Object.prototype.hasOwnProperty.call(t.vars, {n})) throw new Error('Variable not found: ' + {n}); return [{{ value: t.vars[{n}] }}, null];",
+                    n = name
+                )
+            }
+            "if" => "return [{}, agentTruthy(i.condition) ? 'true' : 'false'];".to_string(),
+            "switch" => {
+                let cases: Vec<String> = node.config_get("caseValues").unwrap_or_default();
+                format!(
+                    "const k = {}.indexOf(agentString(agentOr(i.value, null))); return [{{}}, k < 0 ? 'default' : 'case' + k];",
+                    serde_json::Value::from(cases)
+                )
+            }
+            "math" => format!(
+                "return [{{ result: agentMath({}, i.a, i.b) }}, null];",
+                operation("add", MATH_OPS)?
+            ),
+            "compare" => format!(
+                "return [{{ result: agentCompare({}, i.a, i.b) }}, null];",
+                operation("equals", COMPARE_OPS)?
+            ),
+            "logic" => format!(
+                "return [{{ result: agentLogic({}, i.a, i.b) }}, null];",
+                operation("and", LOGIC_OPS)?
+            ),
+            "string_format" => format!(
+                "let r = {}; for (let k = 0; k < 4; k++) {{ const a = i['arg' + k]; if (a !== undefined) r = r.split('{{' + k + '}}').join(agentString(a)); }} return [{{ result: r }}, null];",
+                js_str(&node.config_str("template").unwrap_or_default())
+            ),
+            "string_concat" => "return [{ result: (i.a !== undefined ? agentString(i.a) : '') + (i.b !== undefined ? agentString(i.b) : '') }, null];".to_string(),
+            "to_string" => {
+                let format = node.config_str("format").unwrap_or_else(|| "auto".to_string());
+                if format == "json" {
+                    return Err(format!("to_string node '{}' uses the json format", node.label));
+                }
+                format!(
+                    "return [{{ result: agentFormat({}, agentOr(i.value, null)) }}, null];",
+                    js_str(&format)
+                )
+            }
+            "log" => "t.logs.push(i.message !== undefined ? agentString(i.message) : '(empty)'); return [{}, 'exec'];".to_string(),
+            "notify" => format!(
+                "t.notifications.push({{ title: i.title !== undefined ? agentString(i.title) : 'Notification', message: i.message !== undefined ? agentString(i.message) : '', level: {} }}); return [{{}}, 'exec'];",
+                js_str(&node.config_str("level").unwrap_or_else(|| "info".to_string()))
+            ),
+            "ui_set_value" => format!(
+                "t.ui.push([{}, agentToWire(agentOr(i.value, null))]); return [{{}}, 'exec'];",
+                js_str(&required("componentId")?)
+            ),
+            t if TARGET_NODES.contains(&t) => {
+                let mut config: serde_json::Map<String, serde_json::Value> =
+                    node.config.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+                if matches!(t, "struct_read" | "struct_write") && !config.contains_key("layout") {
+                    let layout = resolve_layout(&required("structName")?, self.structs)
+                        .map_err(|e| e.to_string())?;
+                    config.insert("layout".to_string(), layout);
+                }
+                let fallback = match node.config_str("address") {
+                    Some(address) if t == "resolve_address" => format!(
+                        "if (i.address === undefined) i.address = {}; ",
+                        js_str(&address)
+                    ),
+                    _ => String::new(),
+                };
+                format!(
+                    "{}return [agentCallTarget({}, {}, i, {}), 'exec'];",
+                    fallback,
+                    js_str(t),
+                    serde_json::Value::Object(config),
+                    js_str(&node.id)
+                )
+            }
+            t => return Err(format!("{} node '{}' cannot run in the target", t, node.label)),
+        };
+        Ok(code)
+    }
+
+    /// JavaScript for one script's compiled entries
+    fn emit(
+        &self,
+        entries: &[(&ScriptNode, BTreeSet<usize>)],
+        address_specs: &mut BTreeMap<String, serde_json::Value>,
+    ) -> String {
+        let nodes: BTreeSet<usize> = entries
+            .iter()
+            .flat_map(|(_, n)| n.iter().copied())
+            .collect();
+
+        let variables: Vec<String> = self
+            .script
+            .variables
+            .iter()
+            .map(|v| {
+                let value = v
+                    .default_value
+                    .clone()
+                    .map(Value::from)
+                    .unwrap_or_else(|| default_for_type(v.value_type));
+                collect_address(&value, address_specs);
+                format!("{}: {}", js_str(&v.name), js_value(&value))
+            })
+            .collect();
+
+        let mut out = String::new();
+        let _ = writeln!(out, "\n// Script: {}", self.script.name.replace('\n', " "));
+        let _ = writeln!(out, "compiledAgents.push((function() {{");
+        let _ = writeln!(
+            out,
+            "    const agent = {{ scriptId: {}, variables: {{ {} }}, entries: [] }};",
+            js_str(&self.script.id),
+            variables.join(", ")
+        );
+
+        for &i in &nodes {
+            let node = &self.script.nodes[i];
+            for key in ["value", "inlineValue", "address"] {
+                if let Some(text) = node.config_str(key) {
+                    collect_address(&Value::String(text), address_specs);
+                }
+            }
+
+            let compute = self.compute(node).expect("checked by check_entry");
+            let _ = writeln!(
+                out,
+                "\n    // {} ({})",
+                node.label.replace('\n', " "),
+                node.node_type
+            );
+            let _ = writeln!(out, "    function c{}(i, t) {{ {} }}", i, compute);
+
+            let inputs: Vec<String> = self
+                .value_inputs(node)
+                .filter_map(|(name, source)| {
+                    let expr = match source {
+                        Source::None => return None,
+                        Source::Cached(from, port) => {
+                            format!("agentCached(o, {}, {})", js_str(&from.id), js_str(port))
+                        }
+                        Source::Value(from, port) => format!(
+                            "agentPull(o, {}, {}, v{}, t)",
+                            js_str(&from.id),
+                            js_str(port),
+                            self.index[from.id.as_str()]
+                        ),
+                    };
+                    Some(format!("[{}, {}]", js_str(name), expr))
+                })
+                .collect();
+            let inputs = format!("agentInputs([{}])", inputs.join(", "));
+
+            if Self::is_value_node(node) {
+                let _ = writeln!(
+                    out,
+                    "    function v{}(o, t) {{ return c{}({}, t)[0]; }}",
+                    i, i, inputs
+                );
+                continue;
+            }
+            let _ = writeln!(out, "    function n{}(o, t) {{", i);
+            let _ = writeln!(out, "        const r = c{}({}, t);", i, inputs);
+            let _ = writeln!(out, "        o[{}] = r[0];", js_str(&node.id));
+            let branches: Vec<(&str, String)> = Self::flow_ports(node)
+                .into_iter()
+                .map(|port| (port, self.flow_calls(node, port)))
+                .filter(|(_, calls)| !calls.is_empty())
+                .collect();
+            if !branches.is_empty() {
+                let _ = writeln!(out, "        switch (r[1]) {{");
+                for (port, calls) in branches {
+                    let _ = writeln!(out, "            case {}: {} break;", js_str(port), calls);
+                }
+                let _ = writeln!(out, "        }}");
+            }
+            let _ = writeln!(out, "    }}");
+        }
+
+        for (event, _) in entries {
+            let _ = writeln!(out, "\n    agent.entries.push({{");
+            let _ = writeln!(out, "        eventNodeId: {},", js_str(&event.id));
+            let _ = writeln!(out, "        intervalMs: {},", interval_ms(event));
+            let _ = writeln!(out, "        body: function(o, t, tick) {{");
+            let _ = writeln!(
+                out,
+                "            o[{}] = {{ value: tick, tick: tick }};",
+                js_str(&event.id)
+            );
+            let _ = writeln!(out, "            {}", self.flow_calls(event, "exec"));
+            let _ = writeln!(out, "        }}");
+            let _ = writeln!(out, "    }});");
+        }
+        let _ = writeln!(out, "    return agent;");
+        let _ = writeln!(out, "}})());");
+        out
+    }
+
+    fn flow_calls(&self, node: &ScriptNode, port: &str) -> String {
+        self.flow_targets(node, port)
+            .into_iter()
+            .map(|id| format!("n{}(o, t);", self.index[id]))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Output of a constant node, as `constants.rs` computes it
+fn constant_value(node: &ScriptNode) -> Value {
+    match node.node_type.as_str() {
+        "const_string" => Value::String(node.config_str("value").unwrap_or_default()),
+        "const_number" if node.config_bool("isFloat").unwrap_or(false) => {
+            Value::Float(node.config_f64("value").unwrap_or(0.0))
+        }
+        "const_number" => Value::Integer(node.config_i64("value").unwrap_or(0)),
+        "const_boolean" => Value::Boolean(node.config_bool("value").unwrap_or(true)),
+        _ => {
+            let text = node
+                .config_str("value")
+                .unwrap_or_else(|| "0x0".to_string());
+            Value::from_hex(&text).unwrap_or(Value::Pointer(0))
+        }
+    }
+}
+
+/// Initial value of a variable without a default, as `ScriptExecutor` uses
+fn default_for_type(value_type: ValueType) -> Value {
+    match value_type {
+        ValueType::Float | ValueType::Double => Value::Float(0.0),
+        ValueType::Pointer => Value::Pointer(0),
+        ValueType::String => Value::String(String::new()),
+        ValueType::Boolean => Value::Boolean(false),
+        ValueType::Any => Value::Null,
+        _ => Value::Integer(0),
+    }
+}
+
+/// The bridge turns module-relative address strings into `resolveAddress`
+/// specs on the host; the agent can only do that for strings known here
+fn collect_address(value: &Value, specs: &mut BTreeMap<String, serde_json::Value>) {
+    if let Value::String(text) = value {
+        if let Ok(address) = text.parse::<Address>() {
+            if !address.is_absolute() {
+                specs.insert(text.clone(), address.to_spec());
+            }
+        }
+    }
+}
+
+fn js_str(s: &str) -> String {
+    serde_json::Value::from(s).to_string()
+}
+
+/// JavaScript literal for an agent value
+fn js_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Boolean(b) => b.to_string(),
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) if f.is_nan() => "new AgentFloat(NaN)".to_string(),
+        Value::Float(f) if f.is_infinite() => {
+            format!(
+                "new AgentFloat({}Infinity)",
+                if *f < 0.0 { "-" } else { "" }
+            )
+        }
+        Value::Float(f) => format!("new AgentFloat({:?})", f),
+        Value::String(s) => js_str(s),
+        Value::Pointer(p) => format!("new AgentPointer({}n)", p),
+        Value::Array(items) => {
+            format!(
+                "[{}]",
+                items.iter().map(js_value).collect::<Vec<_>>().join(", ")
+            )
+        }
+        Value::Object(fields) => {
+            let fields: BTreeMap<_, _> = fields.iter().collect();
+            let fields: Vec<String> = fields
+                .into_iter()
+                .map(|(k, v)| format!("{}: {}", js_str(k), js_value(v)))
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+    }
+}
+
+/// Helpers shared by all compiled agents. They mirror `Value` and the host
+/// node executors in forvanced-executor.
+const AGENT_RUNTIME: &str = r#"
+// ============================================
+// Compiled agents
+// ============================================
+// event_interval chains compiled by forvanced-build. They run entirely in the
+// target and report to the host once per tick. Values follow the host
+// executor: integers are numbers, floats and pointers are boxed.
+
+class AgentFloat {
+    constructor(v) { this.v = v; }
+}
+
+class AgentPointer {
+    constructor(v) { this.v = BigInt.asUintN(64, v); }
+}
+
+const AGENT_I64 = 9223372036854775808;
+const AGENT_F64_TEXT = /^[+-]?(inf|infinity|nan|(\d+\.?\d*|\.\d+)(e[+-]?\d+)?)$/i;
+
+// JSON as the host decodes it: whole numbers are integers
+function agentFromWire(x) {
+    if (x === null || x === undefined) return null;
+    if (typeof x === 'number') {
+        return Number.isInteger(x) && x >= -AGENT_I64 && x < AGENT_I64 ? x : new AgentFloat(x);
+    }
+    if (Array.isArray(x)) return x.map(agentFromWire);
+    if (typeof x === 'object') {
+        const out = {};
+        for (const k of Object.keys(x)) out[k] = agentFromWire(x[k]);
+        return out;
+    }
+    return x;
+}
+
+function agentToWire(v) {
+    if (v instanceof AgentFloat) return Number.isFinite(v.v) ? v.v : null;
+    if (v instanceof AgentPointer) return '0x' + v.v.toString(16);
+    if (Array.isArray(v)) return v.map(agentToWire);
+    if (v !== null && typeof v === 'object') {
+        const out = {};
+        for (const k of Object.keys(v)) out[k] = agentToWire(v[k]);
+        return out;
+    }
+    return v;
+}
+
+function agentOr(v, fallback) {
+    return v === undefined || v === null ? fallback : v;
+}
+
+function agentTypeName(v) {
+    if (v === null || v === undefined) return 'null';
+    if (v instanceof AgentFloat) return 'float';
+    if (v instanceof AgentPointer) return 'pointer';
+    if (Array.isArray(v)) return 'array';
+    if (typeof v === 'number') return 'integer';
+    return typeof v;
+}
+
+function agentTruthy(v) {
+    if (v === null || v === undefined) return false;
+    if (v instanceof AgentFloat) return v.v !== 0 && !Number.isNaN(v.v);
+    if (v instanceof AgentPointer) return v.v !== 0n;
+    if (Array.isArray(v)) return v.length > 0;
+    if (typeof v === 'object') return Object.keys(v).length > 0;
+    if (typeof v === 'string') return v.length > 0;
+    return typeof v === 'number' ? v !== 0 : v;
+}
+
+// Saturating float -> i64 cast
+function agentTrunc(f) {
+    if (Number.isNaN(f)) return 0;
+    return Math.max(-AGENT_I64, Math.min(AGENT_I64 - 1, Math.trunc(f)));
+}
+
+function agentWrap(b) {
+    return Number(BigInt.asIntN(64, b));
+}
+
+function agentI64(v) {
+    if (typeof v === 'number') return v;
+    if (typeof v === 'boolean') return v ? 1 : 0;
+    if (v instanceof AgentFloat) return agentTrunc(v.v);
+    if (v instanceof AgentPointer) return Number(BigInt.asIntN(64, v.v));
+    if (typeof v === 'string' && /^[+-]?\d+$/.test(v)) {
+        const n = Number(v);
+        return n >= -AGENT_I64 && n < AGENT_I64 ? n : null;
+    }
+    return null;
+}
+
+function agentF64(v) {
+    if (typeof v === 'number') return v;
+    if (v instanceof AgentFloat) return v.v;
+    if (typeof v === 'string' && AGENT_F64_TEXT.test(v)) {
+        const s = v.toLowerCase();
+        if (s.endsWith('nan')) return NaN;
+        return Number(s.replace(/inf(inity)?$/, 'Infinity'));
+    }
+    return null;
+}
+
+// f64 Display: shortest round-trip digits, never an exponent
+function agentFloatString(f) {
+    if (Number.isNaN(f)) return 'NaN';
+    if (!Number.isFinite(f)) return f > 0 ? 'inf' : '-inf';
+    if (Object.is(f, -0)) return '-0';
+    const s = String(f);
+    if (s.indexOf('e') < 0) return s;
+    const m = /^(-?)(\d)(?:\.(\d+))?e([+-]\d+)$/.exec(f.toExponential());
+    const digits = m[2] + (m[3] || '');
+    const exp = parseInt(m[4], 10);
+    if (exp >= 0) return m[1] + digits + '0'.repeat(exp + 1 - digits.length);
+    return m[1] + '0.' + '0'.repeat(-exp - 1) + digits;
+}
+
+function agentString(v, depth) {
+    depth = depth || 0;
+    if (v === null || v === undefined) return 'null';
+    if (v instanceof AgentFloat) return agentFloatString(v.v);
+    if (v instanceof AgentPointer) return '0x' + v.v.toString(16);
+    if (Array.isArray(v)) {
+        if (depth >= 3) return '[Array(' + v.length + ')]';
+        if (v.length === 0) return '[]';
+        const shown = v.length <= 5 ? v : v.slice(0, 3);
+        const items = shown.map(x => agentString(x, depth + 1)).join(', ');
+        return v.length <= 5 ? '[' + items + ']' : '[' + items + ', ... (' + (v.length - 3) + ' more)]';
+    }
+    if (typeof v === 'object') {
+        const keys = Object.keys(v);
+        if (depth >= 3) return '{Object(' + keys.length + ')}';
+        if (keys.length === 0) return '{}';
+        const pairs = keys.slice(0, 5).map(k => k + ': ' + agentString(v[k], depth + 1));
+        if (keys.length > 5) pairs.push('... (' + (keys.length - 5) + ' more)');
+        return '{' + pairs.join(', ') + '}';
+    }
+    return String(v);
+}
+
+function agentFormat(format, v) {
+    const bits = x => BigInt.asUintN(64, x instanceof AgentPointer ? x.v : BigInt(x));
+    const integral = typeof v === 'number' || v instanceof AgentPointer;
+    switch (format) {
+        case 'hex':
+            if (integral) return '0x' + bits(v).toString(16);
+            break;
+        case 'binary':
+            if (integral) return '0b' + bits(v).toString(2);
+            break;
+        case 'decimal':
+            if (v instanceof AgentPointer) return v.v.toString();
+            break;
+    }
+    return agentString(v);
+}
+
+function agentMath(op, a, b) {
+    if (a === undefined) a = 0;
+    if (b === undefined) b = 0;
+    if (a instanceof AgentFloat || b instanceof AgentFloat) {
+        const x = agentOr(agentF64(a), 0);
+        const y = agentOr(agentF64(b), 0);
+        switch (op) {
+            case 'add': return new AgentFloat(x + y);
+            case 'subtract': case 'sub': return new AgentFloat(x - y);
+            case 'multiply': case 'mul': return new AgentFloat(x * y);
+            case 'divide': case 'div':
+                if (y === 0) throw new Error('Division by zero');
+                return new AgentFloat(x / y);
+            case 'modulo': case 'mod':
+                if (y === 0) throw new Error('Division by zero');
+                return new AgentFloat(x % y);
+            case 'power': case 'pow': return new AgentFloat(Math.pow(x, y));
+            case 'min': return new AgentFloat(Number.isNaN(x) ? y : Number.isNaN(y) ? x : Math.min(x, y));
+            case 'max': return new AgentFloat(Number.isNaN(x) ? y : Number.isNaN(y) ? x : Math.max(x, y));
+            case 'abs': return new AgentFloat(Math.abs(x));
+            case 'floor': return new AgentFloat(Math.floor(x));
+            case 'ceil': return new AgentFloat(Math.ceil(x));
+            case 'round': return new AgentFloat(x < 0 ? -Math.round(-x) : Math.round(x));
+            case 'sqrt': return new AgentFloat(Math.sqrt(x));
+        }
+        throw new Error('Invalid operation: Unknown math operation: ' + op);
+    }
+    const x = BigInt(agentOr(agentI64(a), 0));
+    const y = BigInt(agentOr(agentI64(b), 0));
+    switch (op) {
+        case 'add': return agentWrap(x + y);
+        case 'subtract': case 'sub': return agentWrap(x - y);
+        case 'multiply': case 'mul': return agentWrap(x * y);
+        case 'divide': case 'div':
+            if (y === 0n) throw new Error('Division by zero');
+            return agentWrap(x / y);
+        case 'modulo': case 'mod':
+            if (y === 0n) throw new Error('Division by zero');
+            return agentWrap(x % y);
+        case 'power': case 'pow': {
+            let r = 1n;
+            let base = x;
+            for (let e = BigInt.asUintN(32, y); e > 0n; e >>= 1n) {
+                if (e & 1n) r = BigInt.asIntN(64, r * base);
+                base = BigInt.asIntN(64, base * base);
+            }
+            return agentWrap(r);
+        }
+        case 'min': return agentWrap(x < y ? x : y);
+        case 'max': return agentWrap(x > y ? x : y);
+        case 'abs': return agentWrap(x < 0n ? -x : x);
+        case 'floor': case 'ceil': case 'round': return agentWrap(x);
+        case 'sqrt': return agentTrunc(Math.sqrt(Number(x)));
+        case 'bit_and': return agentWrap(x & y);
+        case 'bit_or': return agentWrap(x | y);
+        case 'bit_xor': return agentWrap(x ^ y);
+        case 'bit_not': return agentWrap(~x);
+        case 'shift_left': case 'shl': return agentWrap(x << (y & 63n));
+        case 'shift_right': case 'shr': return agentWrap(x >> (y & 63n));
+    }
+    throw new Error('Invalid operation: Unknown math operation: ' + op);
+}
+
+function agentOrder(x, y) {
+    return x < y ? -1 : x > y ? 1 : 0;
+}
+
+function agentCompareValues(a, b) {
+    const numeric = v => typeof v === 'number' || v instanceof AgentFloat;
+    if (numeric(a) && numeric(b)) {
+        return agentOrder(a instanceof AgentFloat ? a.v : a, b instanceof AgentFloat ? b.v : b);
+    }
+    if (typeof a === 'string' && typeof b === 'string') return agentOrder(a, b);
+    if (typeof a === 'boolean' && typeof b === 'boolean') return agentOrder(a, b);
+    if (a instanceof AgentPointer && b instanceof AgentPointer) return agentOrder(a.v, b.v);
+    if (a === null && b === null) return 0;
+    if (a === null) return -1;
+    if (b === null) return 1;
+    return agentOrder(agentTypeName(a), agentTypeName(b));
+}
+
+function agentCompare(op, a, b) {
+    const c = agentCompareValues(agentOr(a, null), agentOr(b, null));
+    switch (op) {
+        case 'equals': case 'eq': case '==': return c === 0;
+        case 'not_equals': case 'neq': case '!=': return c !== 0;
+        case 'less_than': case 'lt': case '<': return c < 0;
+        case 'less_than_equals': case 'lte': case '<=': return c <= 0;
+        case 'greater_than': case 'gt': case '>': return c > 0;
+        case 'greater_than_equals': case 'gte': case '>=': return c >= 0;
+    }
+    throw new Error('Invalid operation: Unknown compare operation: ' + op);
+}
+
+function agentLogic(op, a, b) {
+    const x = agentTruthy(a);
+    const y = agentTruthy(b);
+    switch (op) {
+        case 'and': case '&&': return x && y;
+        case 'or': case '||': return x || y;
+        case 'not': case '!': return !x;
+        case 'xor': case '^': return x !== y;
+        case 'nand': return !(x && y);
+        case 'nor': return !(x || y);
+    }
+    throw new Error('Invalid operation: Unknown logic operation: ' + op);
+}
+
+// Module-relative addresses are resolved to specs at build time
+function agentAddress(text) {
+    if (Object.prototype.hasOwnProperty.call(agentAddressSpecs, text)) return agentAddressSpecs[text];
+    if (/^\s*(0x)?[0-9a-f]+\s*$/i.test(text)) return text;
+    throw new Error('Address is not absolute and was not known at build time: ' + text);
+}
+
+function agentCallTarget(nodeType, config, inputs, nodeId) {
+    const wire = {};
+    for (const k of Object.keys(inputs)) wire[k] = agentToWire(inputs[k]);
+    // The same inputs RpcBridge resolves as addresses
+    for (const k of ['address', 'target', 'pointer']) {
+        if (typeof wire[k] === 'string') wire[k] = agentAddress(wire[k]);
+    }
+    const request = { node_id: nodeId, node_type: nodeType, config: config, inputs: wire };
+    const outputs = targetNodes[nodeType](config, wire, request);
+    return agentFromWire(JSON.parse(JSON.stringify(outputs || {})));
+}
+
+function agentInputs(pairs) {
+    const inputs = {};
+    for (const [name, value] of pairs) {
+        if (value !== undefined) inputs[name] = value;
+    }
+    return inputs;
+}
+
+// Outputs of event and flow nodes that already ran this tick
+function agentCached(o, id, port) {
+    const cached = o[id];
+    return cached === undefined ? undefined : cached[port];
+}
+
+// Outputs of a value node, evaluated on first use in a tick
+function agentPull(o, id, port, evaluate, t) {
+    const cached = o[id];
+    if (cached !== undefined && cached[port] !== undefined) return cached[port];
+    const values = evaluate(o, t);
+    o[id] = values;
+    return values[port];
+}
+
+// One tick; variables are only kept when the whole tick succeeds
+function agentRun(agent, entry, tick) {
+    const t = { vars: Object.assign({}, agent.variables), ui: [], logs: [], notifications: [] };
+    let error = null;
+    try {
+        entry.body({}, t, tick);
+        agent.variables = t.vars;
+    } catch (e) {
+        error = e instanceof Error ? e.message : String(e);
+    }
+    if (error === null && t.ui.length === 0 && t.logs.length === 0 && t.notifications.length === 0) {
+        return;
+    }
+    send({
+        type: 'agent',
+        scriptId: agent.scriptId,
+        eventNodeId: entry.eventNodeId,
+        tick: tick,
+        success: error === null,
+        error: error,
+        ui: t.ui,
+        logs: error === null ? t.logs : [],
+        notifications: error === null ? t.notifications : []
+    });
+}
+"#;
+
+const AGENT_START: &str = r#"
+for (const agent of compiledAgents) {
+    for (const entry of agent.entries) {
+        let tick = 1;
+        agentRun(agent, entry, tick);
+        setInterval(function() { agentRun(agent, entry, ++tick); }, entry.intervalMs);
+    }
+}
+
+console.log('[Forvanced] Compiled agents running: ' + compiledAgents.length);
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use forvanced_executor::context::UIState;
+    use forvanced_executor::{RpcCaller, ScriptExecutor};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    fn port(node: &str, name: &str, kind: &str, direction: &str) -> serde_json::Value {
+        json!({ "id": format!("{}.{}", node, name), "name": name, "type": kind, "direction": direction })
+    }
+
+    /// Node with `flow`/`value` input and output port names
+    fn node(
+        id: &str,
+        node_type: &str,
+        config: serde_json::Value,
+        inputs: &[(&str, &str)],
+        outputs: &[(&str, &str)],
+    ) -> serde_json::Value {
+        json!({
+            "id": id, "type": node_type, "label": id, "x": 0.0, "y": 0.0, "config": config,
+            "inputs": inputs.iter().map(|(n, k)| port(id, n, k, "input")).collect::<Vec<_>>(),
+            "outputs": outputs.iter().map(|(n, k)| port(id, n, k, "output")).collect::<Vec<_>>(),
+        })
+    }
+
+    fn link(from: &str, to: &str) -> serde_json::Value {
+        let (from_node, _) = from.split_once('.').unwrap();
+        let (to_node, _) = to.split_once('.').unwrap();
+        json!({ "id": format!("{}->{}", from, to), "fromNodeId": from_node, "fromPortId": from, "toNodeId": to_node, "toPortId": to })
+    }
+
+    fn script(nodes: Vec<serde_json::Value>, links: Vec<serde_json::Value>) -> VisualScript {
+        serde_json::from_value(json!({
+            "id": "s1",
+            "name": "Regen",
+            "variables": [
+                { "id": "v-rate", "name": "rate", "type": "float", "defaultValue": 1.5 },
+                { "id": "v-boosts", "name": "boosts", "type": "int32" },
+                { "id": "v-ticks", "name": "ticks", "type": "int32" }
+            ],
+            "nodes": nodes,
+            "connections": links,
+        }))
+        .unwrap()
+    }
+
+    /// Reads a value, scales it by a float variable and writes it back, then
+    /// branches into UI/log or a notification. Ticks divisible by 4 divide by
+    /// zero after `ticks` was bumped, so those ticks fail and roll back.
+    fn regen_script() -> VisualScript {
+        let (f, v) = ("flow", "value");
+        script(
+            vec![
+                node(
+                    "ev",
+                    "event_interval",
+                    json!({ "intervalMs": 50 }),
+                    &[],
+                    &[("exec", f), ("tick", v)],
+                ),
+                node(
+                    "one",
+                    "const_number",
+                    json!({ "value": 1 }),
+                    &[],
+                    &[("value", v)],
+                ),
+                node(
+                    "four",
+                    "const_number",
+                    json!({ "value": 4 }),
+                    &[],
+                    &[("value", v)],
+                ),
+                node(
+                    "two",
+                    "const_number",
+                    json!({ "value": 2 }),
+                    &[],
+                    &[("value", v)],
+                ),
+                node(
+                    "addr",
+                    "const_pointer",
+                    json!({ "value": "0x1000" }),
+                    &[],
+                    &[("value", v)],
+                ),
+                node(
+                    "ticks",
+                    "get_variable",
+                    json!({ "variableId": "v-ticks" }),
+                    &[],
+                    &[("value", v)],
+                ),
+                node(
+                    "ticks1",
+                    "math",
+                    json!({ "operation": "add" }),
+                    &[("a", v), ("b", v)],
+                    &[("result", v)],
+                ),
+                node(
+                    "bump",
+                    "set_variable",
+                    json!({ "variableId": "v-ticks" }),
+                    &[("exec", f), ("value", v)],
+                    &[("exec", f)],
+                ),
+                node(
+                    "read",
+                    "memory_read",
+                    json!({ "valueType": "float" }),
+                    &[("exec", f), ("address", v)],
+                    &[("exec", f), ("value", v)],
+                ),
+                node(
+                    "phase",
+                    "math",
+                    json!({ "operation": "mod" }),
+                    &[("a", v), ("b", v)],
+                    &[("result", v)],
+                ),
+                node(
+                    "div",
+                    "math",
+                    json!({ "operation": "divide" }),
+                    &[("a", v), ("b", v)],
+                    &[("result", v)],
+                ),
+                node(
+                    "rate",
+                    "get_variable",
+                    json!({ "variableId": "v-rate" }),
+                    &[],
+                    &[("value", v)],
+                ),
+                node(
+                    "mul",
+                    "math",
+                    json!({ "operation": "multiply" }),
+                    &[("a", v), ("b", v)],
+                    &[("result", v)],
+                ),
+                node(
+                    "sum",
+                    "math",
+                    json!({ "operation": "add" }),
+                    &[("a", v), ("b", v)],
+                    &[("result", v)],
+                ),
+                node(
+                    "write",
+                    "memory_write",
+                    json!({ "valueType": "float" }),
+                    &[("exec", f), ("address", v), ("value", v)],
+                    &[("exec", f)],
+                ),
+                node(
+                    "early",
+                    "compare",
+                    json!({ "operation": "lt" }),
+                    &[("a", v), ("b", v)],
+                    &[("result", v)],
+                ),
+                node(
+                    "branch",
+                    "if",
+                    json!({}),
+                    &[("exec", f), ("condition", v)],
+                    &[("true", f), ("false", f)],
+                ),
+                node(
+                    "boosts",
+                    "get_variable",
+                    json!({ "variableId": "v-boosts" }),
+                    &[],
+                    &[("value", v)],
+                ),
+                node(
+                    "boosts1",
+                    "math",
+                    json!({ "operation": "add" }),
+                    &[("a", v), ("b", v)],
+                    &[("result", v)],
+                ),
+                node(
+                    "count",
+                    "set_variable",
+                    json!({ "variableId": "v-boosts" }),
+                    &[("exec", f), ("value", v)],
+                    &[("exec", f)],
+                ),
+                node(
+                    "fmt",
+                    "string_format",
+                    json!({ "template": "HP {0} after {1} boosts" }),
+                    &[("arg0", v), ("arg1", v)],
+                    &[("result", v)],
+                ),
+                node(
+                    "label",
+                    "ui_set_value",
+                    json!({ "componentId": "hp" }),
+                    &[("exec", f), ("value", v)],
+                    &[("exec", f)],
+                ),
+                node(
+                    "log",
+                    "log",
+                    json!({}),
+                    &[("exec", f), ("message", v)],
+                    &[("exec", f)],
+                ),
+                node(
+                    "prefix",
+                    "const_string",
+                    json!({ "value": "Tick " }),
+                    &[],
+                    &[("value", v)],
+                ),
+                node(
+                    "title",
+                    "string_concat",
+                    json!({}),
+                    &[("a", v), ("b", v)],
+                    &[("result", v)],
+                ),
+                node(
+                    "hex",
+                    "to_string",
+                    json!({ "format": "hex" }),
+                    &[("value", v)],
+                    &[("result", v)],
+                ),
+                node(
+                    "notify",
+                    "notify",
+                    json!({ "level": "warning" }),
+                    &[("exec", f), ("title", v), ("message", v)],
+                    &[("exec", f)],
+                ),
+            ],
+            vec![
+                link("ev.exec", "bump.exec"),
+                link("ticks.value", "ticks1.a"),
+                link("one.value", "ticks1.b"),
+                link("ticks1.result", "bump.value"),
+                link("bump.exec", "read.exec"),
+                link("addr.value", "read.address"),
+                link("ev.tick", "phase.a"),
+                link("four.value", "phase.b"),
+                link("read.value", "div.a"),
+                link("phase.result", "div.b"),
+                link("div.result", "mul.a"),
+                link("rate.value", "mul.b"),
+                link("read.value", "sum.a"),
+                link("mul.result", "sum.b"),
+                link("read.exec", "write.exec"),
+                link("addr.value", "write.address"),
+                link("sum.result", "write.value"),
+                link("write.exec", "branch.exec"),
+                link("phase.result", "early.a"),
+                link("two.value", "early.b"),
+                link("early.result", "branch.condition"),
+                link("branch.true", "count.exec"),
+                link("boosts.value", "boosts1.a"),
+                link("one.value", "boosts1.b"),
+                link("boosts1.result", "count.value"),
+                link("count.exec", "label.exec"),
+                link("sum.result", "fmt.arg0"),
+                link("boosts1.result", "fmt.arg1"),
+                link("fmt.result", "label.value"),
+                link("label.exec", "log.exec"),
+                link("fmt.result", "log.message"),
+                link("branch.false", "notify.exec"),
+                link("prefix.value", "title.a"),
+                link("ev.tick", "title.b"),
+                link("title.result", "notify.title"),
+                link("read.value", "hex.value"),
+                link("hex.result", "notify.message"),
+            ],
+        )
+    }
+
+    /// A number as the target's JSON encodes it
+    fn js_number(n: f64) -> serde_json::Value {
+        if n.fract() == 0.0 && n.abs() < 1e15 {
+            json!(n as i64)
+        } else {
+            json!(n)
+        }
+    }
+
+    /// memory_read/memory_write over a map, like the JS stub in the harness
+    struct MemoryCaller(Mutex<HashMap<String, f64>>);
+
+    #[async_trait]
+    impl RpcCaller for MemoryCaller {
+        async fn call(
+            &self,
+            _method: &str,
+            args: Vec<serde_json::Value>,
+        ) -> Result<serde_json::Value, String> {
+            let request = &args[0];
+            let address = request["inputs"]["address"].as_str().unwrap().to_string();
+            let mut memory = self.0.lock().unwrap();
+            let outputs = match request["node_type"].as_str() {
+                Some("memory_read") => json!({ "value": js_number(memory[&address]) }),
+                Some("memory_write") => {
+                    memory.insert(address, request["inputs"]["value"].as_f64().unwrap());
+                    json!({})
+                }
+                other => return Err(format!("unexpected node {:?}", other)),
+            };
+            Ok(json!({ "id": request["id"], "success": true, "outputs": outputs }))
+        }
+    }
+
+    /// Values with their executor type, so 2 and 2.0 compare differently
+    fn tagged(value: &Value) -> serde_json::Value {
+        match value {
+            Value::Float(f) => json!({ "float": f.to_string() }),
+            Value::Pointer(p) => json!({ "pointer": format!("0x{:x}", p) }),
+            Value::Array(items) => items.iter().map(tagged).collect(),
+            Value::Object(fields) => fields.iter().map(|(k, v)| (k.clone(), tagged(v))).collect(),
+            other => serde_json::Value::from(other.clone()),
+        }
+    }
+
+    const HARNESS_PRELUDE: &str = r#"
+const memory = { '0x1000': 100 };
+const targetNodes = {
+    memory_read: function(config, inputs) { return { value: memory[inputs.address] }; },
+    memory_write: function(config, inputs) { memory[inputs.address] = inputs.value; return {}; }
+};
+const sent = [];
+function send(message) { sent.push(message); }
+const timers = [];
+function setInterval(fn, ms) { timers.push(fn); }
+"#;
+
+    /// Agent values with their executor type, like [`tagged`]
+    const HARNESS_TAGGED: &str = r#"
+function tagged(v) {
+    if (v instanceof AgentFloat) return { float: agentFloatString(v.v) };
+    if (v instanceof AgentPointer) return { pointer: '0x' + v.v.toString(16) };
+    if (Array.isArray(v)) return v.map(tagged);
+    if (v !== null && typeof v === 'object') {
+        const out = {};
+        for (const k of Object.keys(v)) out[k] = tagged(v[k]);
+        return out;
+    }
+    return v;
+}
+"#;
+
+    const HARNESS_DRIVER: &str = r#"
+for (let k = 0; k < TICKS - 1; k++) timers[0]();
+console.log(JSON.stringify({ memory: memory, sent: sent, variables: tagged(compiledAgents[0].variables) }));
+"#;
+
+    /// Each agent has run its first tick; report `r` per script, or the failure
+    const OPERATOR_DRIVER: &str = r#"
+const results = {};
+for (const agent of compiledAgents) results[agent.scriptId] = { success: true, r: tagged(agent.variables.r) };
+for (const m of sent) if (!m.success) results[m.scriptId] = { success: false, r: null };
+console.log(JSON.stringify(results));
+"#;
+
+    /// Run an agent harness in node and parse the JSON it prints last
+    fn run_harness(source: &str) -> serde_json::Value {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("harness.js");
+        std::fs::write(&path, source).unwrap();
+        let output = std::process::Command::new("node")
+            .arg(&path)
+            .output()
+            .expect("the agent equivalence tests need node on PATH");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        serde_json::from_str(stdout.lines().last().unwrap()).unwrap()
+    }
+
+    /// Constant node producing `value`
+    fn operand_node(id: &str, value: &Value) -> serde_json::Value {
+        let (node_type, config) = match value {
+            Value::Integer(i) => ("const_number", json!({ "value": i })),
+            Value::Float(f) => ("const_number", json!({ "value": f, "isFloat": true })),
+            Value::Boolean(b) => ("const_boolean", json!({ "value": b })),
+            Value::String(s) => ("const_string", json!({ "value": s })),
+            Value::Pointer(p) => ("const_pointer", json!({ "value": format!("0x{:x}", p) })),
+            other => panic!("no constant node for {:?}", other),
+        };
+        node(id, node_type, config, &[], &[("value", "value")])
+    }
+
+    /// `r = a <operation> b` on each tick; `b` is left unconnected when `None`
+    fn operator_script(
+        id: &str,
+        node_type: &str,
+        operation: &str,
+        a: &Value,
+        b: Option<&Value>,
+    ) -> VisualScript {
+        let (f, v) = ("flow", "value");
+        let mut nodes = vec![
+            node("ev", "event_interval", json!({}), &[], &[("exec", f)]),
+            operand_node("a", a),
+            node(
+                "op",
+                node_type,
+                json!({ "operation": operation }),
+                &[("a", v), ("b", v)],
+                &[("result", v)],
+            ),
+            node(
+                "set",
+                "set_variable",
+                json!({ "variableId": "v-r" }),
+                &[("exec", f), ("value", v)],
+                &[("exec", f)],
+            ),
+        ];
+        let mut links = vec![
+            link("ev.exec", "set.exec"),
+            link("a.value", "op.a"),
+            link("op.result", "set.value"),
+        ];
+        if let Some(b) = b {
+            nodes.push(operand_node("b", b));
+            links.push(link("b.value", "op.b"));
+        }
+        serde_json::from_value(json!({
+            "id": id,
+            "name": format!("{} {}", node_type, operation),
+            "variables": [{ "id": "v-r", "name": "r", "type": "any" }],
+            "nodes": nodes,
+            "connections": links,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_compiles_interval_chain() {
+        let agent = compile_agent(&[regen_script()], &[]);
+        assert!(agent.skipped.is_empty(), "{:?}", agent.skipped);
+        assert_eq!(agent.compiled.len(), 1);
+        assert_eq!(agent.compiled[0].event_node_id, "ev");
+        assert_eq!(agent.compiled[0].interval_ms, 50);
+        assert!(agent.code.contains("agentCallTarget(\"memory_read\""));
+        assert!(agent.target_script().contains("executeTargetNode"));
+
+        assert!(compile_agent(&[], &[]).code.is_empty());
+    }
+
+    #[test]
+    fn test_ineligible_chains_are_skipped() {
+        let (f, v) = ("flow", "value");
+        let reads_ui = script(
+            vec![
+                node("ev", "event_interval", json!({}), &[], &[("exec", f)]),
+                node(
+                    "get",
+                    "ui_get_value",
+                    json!({ "componentId": "c" }),
+                    &[],
+                    &[("value", v)],
+                ),
+                node(
+                    "log",
+                    "log",
+                    json!({}),
+                    &[("exec", f), ("message", v)],
+                    &[("exec", f)],
+                ),
+            ],
+            vec![
+                link("ev.exec", "log.exec"),
+                link("get.value", "log.message"),
+            ],
+        );
+        let skipped = compile_agent(&[reads_ui], &[]).skipped;
+        assert!(
+            skipped[0].reason.contains("ui_get_value"),
+            "{}",
+            skipped[0].reason
+        );
+
+        let shared = script(
+            vec![
+                node("ev", "event_interval", json!({}), &[], &[("exec", f)]),
+                node(
+                    "set",
+                    "set_variable",
+                    json!({ "variableId": "v-rate" }),
+                    &[("exec", f)],
+                    &[("exec", f)],
+                ),
+                node("click", "event_ui", json!({}), &[], &[("exec", f)]),
+                node(
+                    "get",
+                    "get_variable",
+                    json!({ "variableId": "v-rate" }),
+                    &[],
+                    &[("value", v)],
+                ),
+                node(
+                    "log",
+                    "log",
+                    json!({}),
+                    &[("exec", f), ("message", v)],
+                    &[("exec", f)],
+                ),
+            ],
+            vec![
+                link("ev.exec", "set.exec"),
+                link("click.exec", "log.exec"),
+                link("get.value", "log.message"),
+            ],
+        );
+        let skipped = compile_agent(&[shared], &[]).skipped;
+        assert!(
+            skipped[0].reason.contains("shares variable 'rate'"),
+            "{}",
+            skipped[0].reason
+        );
+
+        let cycle = script(
+            vec![
+                node("ev", "event_interval", json!({}), &[], &[("exec", f)]),
+                node("a", "log", json!({}), &[("exec", f)], &[("exec", f)]),
+                node("b", "log", json!({}), &[("exec", f)], &[("exec", f)]),
+            ],
+            vec![
+                link("ev.exec", "a.exec"),
+                link("a.exec", "b.exec"),
+                link("b.exec", "a.exec"),
+            ],
+        );
+        let skipped = compile_agent(&[cycle], &[]).skipped;
+        assert!(
+            skipped[0].reason.contains("flow cycle"),
+            "{}",
+            skipped[0].reason
+        );
+    }
+
+    #[test]
+    fn test_compiled_target_nodes_have_handlers() {
+        let script = generate_target_script();
+        for name in TARGET_NODES {
+            assert!(
+                script.contains(&format!("    {}: function(", name)),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_agent_matches_script_executor() {
+        const TICKS: i64 = 8;
+
+        // Host executor, with target nodes served over RPC
+        let ui_state: UIState = Default::default();
+        let executor = ScriptExecutor::new(Arc::clone(&ui_state));
+        let memory = Arc::new(MemoryCaller(Mutex::new(HashMap::from([(
+            "0x1000".to_string(),
+            100.0,
+        )]))));
+        executor.set_rpc_caller(memory.clone()).await;
+        executor.set_session("session".to_string()).await;
+
+        let visual = regen_script();
+        let executor_script: Script =
+            serde_json::from_value(serde_json::to_value(&visual).unwrap()).unwrap();
+        let mut host_ticks = Vec::new();
+        let mut variables = HashMap::new();
+        for tick in 1..=TICKS {
+            let result = executor
+                .execute_from_event(executor_script.clone(), "ev", Value::Integer(tick), None)
+                .await
+                .unwrap();
+            if result.success {
+                variables = result.variables.clone();
+            }
+            let notifications: Vec<_> = result
+                .notifications
+                .iter()
+                .map(|n| json!({ "title": n.title, "message": n.message, "level": n.level }))
+                .collect();
+            host_ticks.push(json!({
+                "success": result.success,
+                "logs": result.logs,
+                "notifications": notifications,
+            }));
+        }
+
+        // The same script compiled and run in node against a stubbed target
+        let agent = compile_agent(&[visual], &[]);
+        let report = run_harness(&format!(
+            "{}{}{}{}",
+            HARNESS_PRELUDE,
+            agent.code,
+            HARNESS_TAGGED,
+            HARNESS_DRIVER.replace("TICKS", &TICKS.to_string())
+        ));
+
+        let sent = report["sent"].as_array().unwrap();
+        let mut agent_ticks = Vec::new();
+        let mut agent_ui = serde_json::Map::new();
+        for tick in 1..=TICKS {
+            match sent.iter().find(|m| m["tick"] == tick) {
+                Some(message) => {
+                    for update in message["ui"].as_array().unwrap() {
+                        agent_ui.insert(update[0].as_str().unwrap().to_string(), update[1].clone());
+                    }
+                    agent_ticks.push(json!({
+                        "success": message["success"],
+                        "logs": message["logs"],
+                        "notifications": message["notifications"],
+                    }));
+                }
+                None => {
+                    agent_ticks.push(json!({ "success": true, "logs": [], "notifications": [] }))
+                }
+            }
+        }
+
+        assert_eq!(agent_ticks, host_ticks);
+        assert_eq!(host_ticks[3]["success"], false);
+        assert_eq!(report["variables"], tagged(&Value::Object(variables)));
+
+        let host_ui: serde_json::Map<_, _> = ui_state
+            .read()
+            .await
+            .iter()
+            .map(|(k, v)| (k.clone(), serde_json::Value::from(v.clone())))
+            .collect();
+        assert_eq!(agent_ui, host_ui);
+
+        let host_memory = memory.0.lock().unwrap()["0x1000"];
+        assert_eq!(report["memory"]["0x1000"].as_f64().unwrap(), host_memory);
+    }
+
+    #[tokio::test]
+    async fn test_operators_match_script_executor() {
+        // Agent integers are JS numbers, so results stay below 2^53 unless
+        // they wrap to something that fits
+        let lhs = [
+            Value::Integer(7),
+            Value::Integer(-3),
+            Value::Float(2.5),
+            Value::Boolean(true),
+            Value::String("5".to_string()),
+            Value::String("abc".to_string()),
+            Value::Pointer(0x1000),
+        ];
+        let rhs = [
+            Some(Value::Integer(2)),
+            Some(Value::Integer(0)),
+            Some(Value::Float(-0.5)),
+            Some(Value::Boolean(false)),
+            Some(Value::String("3".to_string())),
+            Some(Value::Pointer(0x10)),
+            None,
+        ];
+        let operators = [
+            ("math", MATH_OPS),
+            ("compare", COMPARE_OPS),
+            ("logic", LOGIC_OPS),
+        ];
+
+        let mut cases = Vec::new();
+        for (node_type, ops) in operators {
+            for op in ops {
+                for a in &lhs {
+                    for b in &rhs {
+                        let id = format!("case{}", cases.len());
+                        let label = format!("{:?} {} {:?}", a, op, b);
+                        cases.push((operator_script(&id, node_type, op, a, b.as_ref()), label));
+                    }
+                }
+            }
+        }
+
+        let executor = ScriptExecutor::new(Default::default());
+        let mut host = serde_json::Map::new();
+        for (visual, _) in &cases {
+            let script: Script =
+                serde_json::from_value(serde_json::to_value(visual).unwrap()).unwrap();
+            let result = executor
+                .execute_from_event(script, "ev", Value::Integer(1), None)
+                .await;
+            let outcome = match result {
+                Ok(result) if result.success => json!({
+                    "success": true,
+                    "r": result.variables.get("r").map(tagged).unwrap_or_default(),
+                }),
+                _ => json!({ "success": false, "r": null }),
+            };
+            host.insert(visual.id.clone(), outcome);
+        }
+
+        let visuals: Vec<VisualScript> = cases.iter().map(|(v, _)| v.clone()).collect();
+        let agent = compile_agent(&visuals, &[]);
+        assert!(agent.skipped.is_empty(), "{:?}", agent.skipped);
+        let report = run_harness(&format!(
+            "{}{}{}{}",
+            HARNESS_PRELUDE, agent.code, HARNESS_TAGGED, OPERATOR_DRIVER
+        ));
+
+        let mismatches: Vec<String> = cases
+            .iter()
+            .filter(|(visual, _)| report[&visual.id] != host[&visual.id])
+            .map(|(visual, label)| {
+                format!(
+                    "{}: agent {} host {}",
+                    label, report[&visual.id], host[&visual.id]
+                )
+            })
+            .collect();
+        assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+    }
+}
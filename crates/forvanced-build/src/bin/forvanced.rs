@@ -0,0 +1,506 @@
+//! Headless Forvanced build tool
+//!
+//! Validates, generates, builds, packages and verifies trainer projects
+//! without the Builder app.
+//! Every command prints a single JSON object on stdout; logs and the output of
+//! child tools (npm, cargo) go to stderr. The exit code is non-zero on failure.
+
+use clap::{Parser, Subcommand};
+use forvanced_build::{
+    package_data_only, validate_project, verify_build, BuildError, BuildManifest, BuildOptions,
+    BuildTarget, Builder, PackageOptions, PayloadPlacement,
+};
+use forvanced_core::payload::SigningKey;
+use forvanced_core::Project;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use tracing::{info, warn};
+
+#[derive(Parser)]
+#[command(name = "forvanced", version, about = "Build Forvanced trainers from the command line")]
+struct Cli {
+    /// Log level for stderr output (error, warn, info, debug, trace)
+    #[arg(long, global = true, default_value = "warn")]
+    log_level: String,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Check a project file for errors without generating anything
+    Validate {
+        /// Path to the project file (.json)
+        project: PathBuf,
+    },
+    /// Generate the runtime project without compiling it
+    Generate {
+        project: PathBuf,
+        #[command(flatten)]
+        paths: PathArgs,
+    },
+    /// Generate and compile the trainer for a target platform
+    Build {
+        project: PathBuf,
+        #[command(flatten)]
+        paths: PathArgs,
+        /// Target platform (current, windows, macos-x64, macos-arm64, linux, android, ios)
+        #[arg(long, short, default_value = "current")]
+        target: String,
+        /// Build in debug mode
+        #[arg(long)]
+        debug: bool,
+        /// Build against the mock Frida backend instead of bundling Frida
+        #[arg(long)]
+        no_bundle_frida: bool,
+    },
+    /// Package the project onto a prebuilt runtime binary (no compilation)
+    Package {
+        project: PathBuf,
+        /// Prebuilt generic runtime executable
+        #[arg(long)]
+        runtime_binary: PathBuf,
+        /// PKCS#8 signing key created by `forvanced keygen`
+        #[arg(long)]
+        key: PathBuf,
+        /// Output directory
+        #[arg(long, short, default_value = "./dist")]
+        out: PathBuf,
+        /// Executable name without extension (defaults to the project name)
+        #[arg(long)]
+        name: Option<String>,
+        /// Write the payload next to the executable instead of appending it
+        #[arg(long)]
+        sidecar: bool,
+    },
+    /// Check a build against its manifest, or identify a distributed trainer
+    Verify {
+        /// Manifest written by `build` or `package`
+        manifest: PathBuf,
+        /// Directory output paths are relative to (defaults to the manifest's directory)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        /// Also check that the build came from this project file
+        #[arg(long)]
+        project: Option<PathBuf>,
+        /// Identify this trainer file instead of verifying the build directory
+        #[arg(long)]
+        artifact: Option<PathBuf>,
+    },
+    /// Create a signing key for data-only packages; runtimes only load
+    /// packages signed by keys in their FORVANCED_TRUSTED_KEYS or the
+    /// project's config.build.trusted_keys
+    Keygen {
+        /// Where to write the PKCS#8 key
+        output: PathBuf,
+    },
+}
+
+#[derive(clap::Args)]
+struct PathArgs {
+    /// Output directory
+    #[arg(long, short, default_value = "./dist")]
+    out: PathBuf,
+    /// Runtime template directory (defaults to apps/runtime in the workspace)
+    #[arg(long)]
+    runtime: Option<PathBuf>,
+    /// Discard the generated project and its build cache first
+    #[arg(long)]
+    clean: bool,
+    /// Embed the config and target script encrypted, with a tamper check at startup
+    #[arg(long)]
+    protect: bool,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_new(&cli.log_level)
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn")),
+        )
+        .init();
+
+    let (ok, output) = match run(cli.command).await {
+        Ok(output) => (true, output),
+        Err(output) => (false, output),
+    };
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&output).unwrap_or_else(|_| output.to_string())
+    );
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+async fn run(command: Commands) -> Result<serde_json::Value, serde_json::Value> {
+    match command {
+        Commands::Validate { project } => {
+            let project = load_project(&project)?;
+            let report = validate_project(&project);
+            let output = json!({
+                "ok": report.is_ok(),
+                "command": "validate",
+                "project": project.name,
+                "issues": report.issues,
+            });
+            if report.is_ok() {
+                Ok(output)
+            } else {
+                Err(output)
+            }
+        }
+
+        Commands::Generate { project, paths } => {
+            let project = load_validated(&project)?;
+            let builder = make_builder(paths.runtime)?;
+            if paths.clean {
+                builder
+                    .clean(&project, &paths.out)
+                    .await
+                    .map_err(|e| failure("generate", e))?;
+            }
+            let generated = if paths.protect {
+                builder.generate_protected_project(&project, &paths.out).await
+            } else {
+                builder.generate_project(&project, &paths.out).await
+            };
+            let project_dir = generated.map_err(|e| failure("generate", e))?;
+            Ok(json!({
+                "ok": true,
+                "command": "generate",
+                "project": project.name,
+                "projectDir": project_dir,
+                "protected": paths.protect,
+            }))
+        }
+
+        Commands::Build {
+            project,
+            paths,
+            target,
+            debug,
+            no_bundle_frida,
+        } => {
+            let target = BuildTarget::from_str(&target)
+                .ok_or_else(|| failure("build", format!("Invalid build target: {}", target)))?;
+            let project = load_validated(&project)?;
+            let builder = make_builder(paths.runtime.clone())?;
+            let options = BuildOptions {
+                output_dir: paths.out,
+                target,
+                release: !debug,
+                bundle_frida: !no_bundle_frida,
+                runtime_path: paths.runtime,
+                clean: paths.clean,
+                protect: paths.protect,
+            };
+            let output = builder
+                .build(&project, &options)
+                .await
+                .map_err(build_failure)?;
+            Ok(json!({
+                "ok": true,
+                "command": "build",
+                "project": project.name,
+                "target": output.target.as_str(),
+                "release": options.release,
+                "projectDir": output.project_dir,
+                "executables": output.executables,
+                "manifest": output.manifest,
+                "timings": output.timings,
+            }))
+        }
+
+        Commands::Verify {
+            manifest,
+            dir,
+            project,
+            artifact,
+        } => verify(&manifest, dir, project, artifact),
+
+        Commands::Package {
+            project,
+            runtime_binary,
+            key,
+            out,
+            name,
+            sidecar,
+        } => {
+            let options = PackageOptions {
+                runtime_binary,
+                output_dir: out,
+                output_name: name,
+                placement: if sidecar {
+                    PayloadPlacement::Sidecar
+                } else {
+                    PayloadPlacement::Appended
+                },
+            };
+            package(project, options, key).await
+        }
+
+        Commands::Keygen { output } => keygen(&output),
+    }
+}
+
+async fn package(
+    project: PathBuf,
+    options: PackageOptions,
+    key: PathBuf,
+) -> Result<serde_json::Value, serde_json::Value> {
+    let project = load_validated(&project)?;
+    let key = std::fs::read(&key)
+        .map_err(|e| failure("package", format!("Failed to read {}: {}", key.display(), e)))
+        .and_then(|bytes| SigningKey::from_pkcs8(&bytes).map_err(|e| failure("package", e)))?;
+    let output = package_data_only(&project, &options, &key)
+        .await
+        .map_err(|e| failure("package", e))?;
+    Ok(json!({
+        "ok": true,
+        "command": "package",
+        "project": project.name,
+        "executable": output.executable,
+        "payload": output.payload,
+        "publicKey": output.public_key,
+        "manifest": output.manifest,
+    }))
+}
+
+fn verify(
+    manifest_path: &Path,
+    dir: Option<PathBuf>,
+    project: Option<PathBuf>,
+    artifact: Option<PathBuf>,
+) -> Result<serde_json::Value, serde_json::Value> {
+    let manifest = BuildManifest::load(manifest_path).map_err(|e| failure("verify", e))?;
+
+    if let Some(artifact) = artifact {
+        let matched = manifest.identify(&artifact).map_err(|e| failure("verify", e))?;
+        let output = json!({
+            "ok": matched.is_some(),
+            "command": "verify",
+            "project": manifest.project,
+            "matchedOutput": matched,
+        });
+        return if matched.is_some() { Ok(output) } else { Err(output) };
+    }
+
+    let project = project.as_deref().map(load_project).transpose()?;
+    let base_dir = dir.unwrap_or_else(|| {
+        manifest_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    });
+    let report =
+        verify_build(&manifest, &base_dir, project.as_ref()).map_err(|e| failure("verify", e))?;
+    let output = json!({
+        "ok": report.is_ok(),
+        "command": "verify",
+        "project": manifest.project,
+        "mismatches": report.mismatches,
+    });
+    if report.is_ok() {
+        Ok(output)
+    } else {
+        Err(output)
+    }
+}
+
+fn keygen(output: &Path) -> Result<serde_json::Value, serde_json::Value> {
+    if output.exists() {
+        return Err(failure("keygen", format!("{} already exists", output.display())));
+    }
+    let key = SigningKey::generate().map_err(|e| failure("keygen", e))?;
+    std::fs::write(output, key.pkcs8())
+        .map_err(|e| failure("keygen", format!("Failed to write {}: {}", output.display(), e)))?;
+    Ok(json!({
+        "ok": true,
+        "command": "keygen",
+        "key": output,
+        "publicKey": key.public_key_hex(),
+    }))
+}
+
+fn failure(command: &str, error: impl std::fmt::Display) -> serde_json::Value {
+    json!({ "ok": false, "command": command, "error": error.to_string() })
+}
+
+/// Build failure; missing tools are listed so scripts can report them
+fn build_failure(error: BuildError) -> serde_json::Value {
+    let mut output = failure("build", &error);
+    if let BuildError::MissingTools(info) = &error {
+        output["missingTools"] = info
+            .missing
+            .iter()
+            .map(|tool| json!({ "name": tool.name, "description": tool.description }))
+            .collect();
+    }
+    output
+}
+
+fn load_project(path: &Path) -> Result<Project, serde_json::Value> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| failure("load", format!("Failed to read {}: {}", path.display(), e)))?;
+    let (project, migration) = forvanced_core::migration::load_project(&content)
+        .map_err(|e| failure("load", format!("Failed to load project file: {}", e)))?;
+    if migration.is_migrated() {
+        warn!(
+            "{} uses schema v{}; migrated to v{} in memory",
+            path.display(),
+            migration.from_version,
+            migration.to_version
+        );
+        for change in &migration.changes {
+            info!("{}", change);
+        }
+    }
+    Ok(project)
+}
+
+/// Load a project and refuse to continue if it has validation errors
+fn load_validated(path: &Path) -> Result<Project, serde_json::Value> {
+    let project = load_project(path)?;
+    let report = validate_project(&project);
+    if !report.is_ok() {
+        return Err(json!({
+            "ok": false,
+            "command": "validate",
+            "project": project.name,
+            "error": "Project has validation errors",
+            "issues": report.issues,
+        }));
+    }
+    Ok(project)
+}
+
+fn make_builder(runtime: Option<PathBuf>) -> Result<Builder, serde_json::Value> {
+    match runtime {
+        Some(path) => Builder::with_runtime_path(path),
+        None => Builder::new(),
+    }
+    .map_err(|e| failure("generate", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Commands, clap::Error> {
+        Cli::try_parse_from([&["forvanced"], args].concat()).map(|cli| cli.command)
+    }
+
+    #[test]
+    fn test_build_arguments() {
+        let Commands::Build {
+            project,
+            paths,
+            target,
+            debug,
+            no_bundle_frida,
+        } = parse(&["build", "game.json"]).unwrap()
+        else {
+            panic!("not a build command");
+        };
+        assert_eq!(project, PathBuf::from("game.json"));
+        assert_eq!(target, "current");
+        assert_eq!(paths.out, PathBuf::from("./dist"));
+        assert!(!debug && !no_bundle_frida && !paths.clean && !paths.protect);
+        assert!(paths.runtime.is_none());
+
+        let Commands::Build {
+            paths,
+            target,
+            debug,
+            no_bundle_frida,
+            ..
+        } = parse(&[
+            "build",
+            "game.json",
+            "-t",
+            "android",
+            "-o",
+            "out",
+            "--runtime",
+            "apps/runtime",
+            "--debug",
+            "--no-bundle-frida",
+            "--clean",
+            "--protect",
+        ])
+        .unwrap()
+        else {
+            panic!("not a build command");
+        };
+        assert_eq!(target, "android");
+        assert_eq!(paths.out, PathBuf::from("out"));
+        assert_eq!(paths.runtime, Some(PathBuf::from("apps/runtime")));
+        assert!(debug && no_bundle_frida && paths.clean && paths.protect);
+    }
+
+    #[test]
+    fn test_package_and_verify_arguments() {
+        let Commands::Package { sidecar, name, .. } = parse(&[
+            "package",
+            "game.json",
+            "--runtime-binary",
+            "runtime",
+            "--key",
+            "key.pk8",
+            "--sidecar",
+        ])
+        .unwrap() else {
+            panic!("not a package command");
+        };
+        assert!(sidecar);
+        assert!(name.is_none());
+
+        let Commands::Verify { dir, artifact, .. } =
+            parse(&["verify", "manifest.json", "--artifact", "Game.exe"]).unwrap()
+        else {
+            panic!("not a verify command");
+        };
+        assert!(dir.is_none());
+        assert_eq!(artifact, Some(PathBuf::from("Game.exe")));
+
+        let cli =
+            Cli::try_parse_from(["forvanced", "validate", "game.json", "--log-level", "debug"]);
+        assert_eq!(cli.unwrap().log_level, "debug");
+    }
+
+    #[test]
+    fn test_invalid_arguments_are_rejected() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["validate"]).is_err());
+        assert!(parse(&["deploy", "game.json"]).is_err());
+        assert!(parse(&["build", "game.json", "--release"]).is_err());
+        // A package needs both the runtime binary and the signing key
+        assert!(parse(&["package", "game.json", "--key", "key.pk8"]).is_err());
+        assert!(parse(&["package", "game.json", "--runtime-binary", "runtime"]).is_err());
+    }
+
+    #[test]
+    fn test_missing_tools_are_listed() {
+        let error = BuildError::MissingTools(forvanced_build::MissingToolsInfo {
+            missing: vec![forvanced_build::MissingTool {
+                name: "Android NDK".to_string(),
+                description: "linker".to_string(),
+                install_instructions: Vec::new(),
+            }],
+        });
+        let output = build_failure(error);
+        assert_eq!(output["ok"], false);
+        assert_eq!(output["command"], "build");
+        assert_eq!(output["missingTools"][0]["name"], "Android NDK");
+    }
+}
@@ -2,13 +2,36 @@
 //!
 //! Uses apps/runtime as the template and embeds project configuration.
 
+use crate::agent::compile_agent;
+use crate::cache::{install_required, record_install, sync_template, write_if_changed};
 use crate::error::{BuildError, InstallInstruction, MissingTool, MissingToolsInfo};
-use forvanced_core::project::{Project, UIComponent, VisualScript};
+use crate::preflight;
+use crate::progress::{BuildEvent, BuildPhase, BuildProgress, LogLevel, PhaseTiming};
+use crate::manifest::{BuildManifest, ManifestOptions, MANIFEST_FILE};
+use crate::protect::{byte_array, remove_sealed, seal_embedded, KeyShares, SEALED_CONFIG, SEALED_SCRIPT};
+use crate::validate::validate_project;
+use forvanced_core::project::{HotkeyConfig, Project, UIComponent, VisualScript};
+use forvanced_core::structs::StructDefinition;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use tokio::fs;
 use tracing::{debug, info};
 
+/// Files in the runtime template that the generator rewrites for each project
+const GENERATED_FILES: &[&str] = &[
+    "package.json",
+    PROJECT_CONFIG_FILE,
+    "src-tauri/tauri.conf.json",
+    "src-tauri/Cargo.toml",
+    "src-tauri/src/lib.rs",
+    "src-tauri/src/main.rs",
+];
+
+/// Runtime config embedded by plain builds (protected builds seal it instead)
+const PROJECT_CONFIG_FILE: &str = "src-tauri/project_config.json";
+
+/// Target script injected by plain builds (protected builds seal it instead)
+const TARGET_SCRIPT: &str = "src-tauri/target_script.js";
+
 /// Build target platform
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BuildTarget {
@@ -42,6 +65,19 @@ impl BuildTarget {
         }
     }
 
+    /// Canonical name accepted by [`BuildTarget::from_str`]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Current => "current",
+            Self::WindowsX64 => "windows-x64",
+            Self::MacOsX64 => "macos-x64",
+            Self::MacOsArm64 => "macos-arm64",
+            Self::LinuxX64 => "linux-x64",
+            Self::Android => "android",
+            Self::Ios => "ios",
+        }
+    }
+
     pub fn tauri_target(&self) -> &'static str {
         match self {
             Self::Current => "",
@@ -68,6 +104,10 @@ pub struct BuildOptions {
     pub bundle_frida: bool,
     /// Path to runtime template (apps/runtime)
     pub runtime_path: Option<PathBuf>,
+    /// Delete the generated project (including its build cache) before building
+    pub clean: bool,
+    /// Embed the config and target script sealed, with a tamper check at startup
+    pub protect: bool,
 }
 
 impl Default for BuildOptions {
@@ -78,6 +118,8 @@ impl Default for BuildOptions {
             release: true,
             bundle_frida: true,
             runtime_path: None,
+            clean: false,
+            protect: false,
         }
     }
 }
@@ -91,18 +133,38 @@ pub struct BuildOutput {
     pub executables: Vec<PathBuf>,
     /// Target platform
     pub target: BuildTarget,
+    /// Manifest describing this build (see [`crate::manifest`])
+    pub manifest: PathBuf,
+    /// Time spent in each phase, in order
+    pub timings: Vec<PhaseTiming>,
 }
 
+/// Version of the embedded runtime config. The runtime refuses configs newer
+/// than the version it was built with.
+pub const RUNTIME_CONFIG_VERSION: u32 = 2;
+
 /// Project configuration for runtime (serialized and embedded)
+///
+/// Everything the runtime needs from a [`Project`]. Build-only settings
+/// (`config.build`) and editor metadata (ids, timestamps) are left out.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectConfig {
+    pub config_version: u32,
     pub name: String,
     pub version: String,
+    pub description: Option<String>,
+    pub author: Option<String>,
     pub target_process: Option<String>,
+    pub process_patterns: Vec<String>,
+    pub adapter_type: String,
+    pub adapter_config: serde_json::Value,
     pub auto_attach: bool,
+    pub hotkeys: HotkeyConfig,
     pub components: Vec<UIComponent>,
     pub scripts: Vec<VisualScript>,
+    #[serde(default)]
+    pub structs: Vec<StructDefinition>,
     pub canvas: CanvasConfig,
 }
 
@@ -112,27 +174,44 @@ pub struct CanvasConfig {
     pub height: u32,
     pub padding: u32,
     pub gap: u32,
+    pub theme: String,
 }
 
 impl From<&Project> for ProjectConfig {
     fn from(project: &Project) -> Self {
+        let target = &project.config.target;
         Self {
+            config_version: RUNTIME_CONFIG_VERSION,
             name: project.name.clone(),
             version: project.version.clone(),
-            target_process: project.config.target.process_name.clone(),
-            auto_attach: project.config.target.auto_attach,
+            description: project.description.clone(),
+            author: project.author.clone(),
+            target_process: target.process_name.clone(),
+            process_patterns: target.process_patterns.clone(),
+            adapter_type: target.adapter_type.clone(),
+            adapter_config: target.adapter_config.clone(),
+            auto_attach: target.auto_attach,
+            hotkeys: project.config.hotkeys.clone(),
             components: project.ui.components.clone(),
             scripts: project.scripts.clone(),
+            structs: project.structs.clone(),
             canvas: CanvasConfig {
                 width: project.ui.width,
                 height: project.ui.height,
                 padding: project.ui.padding,
                 gap: project.ui.gap,
+                theme: project.ui.theme.clone(),
             },
         }
     }
 }
 
+/// Runtime config JSON embedded in the generated app, plain or sealed
+fn runtime_config_json(project: &Project) -> Result<String, BuildError> {
+    serde_json::to_string_pretty(&ProjectConfig::from(project))
+        .map_err(|e| BuildError::SerializationError(e.to_string()))
+}
+
 /// Builder for generating project applications
 pub struct Builder {
     /// Path to the runtime template
@@ -156,11 +235,31 @@ impl Builder {
     }
 
     /// Generate a project from a Forvanced project
-    /// This copies the runtime template and embeds the project config
+    /// This syncs the runtime template and embeds the project config. An existing
+    /// project directory is updated in place so build caches survive.
     pub async fn generate_project(
         &self,
         project: &Project,
         output_dir: &Path,
+    ) -> Result<PathBuf, BuildError> {
+        self.generate(project, output_dir, false).await
+    }
+
+    /// Like [`Builder::generate_project`], but embeds the config and target
+    /// script sealed (see [`forvanced_core::protect`])
+    pub async fn generate_protected_project(
+        &self,
+        project: &Project,
+        output_dir: &Path,
+    ) -> Result<PathBuf, BuildError> {
+        self.generate(project, output_dir, true).await
+    }
+
+    async fn generate(
+        &self,
+        project: &Project,
+        output_dir: &Path,
+        protect: bool,
     ) -> Result<PathBuf, BuildError> {
         info!("Generating project: {}", project.name);
         info!("Runtime path: {}", self.runtime_path.display());
@@ -170,38 +269,51 @@ impl Builder {
         if project.ui.components.is_empty() {
             return Err(BuildError::EmptyProject);
         }
+        let report = validate_project(project);
+        if !report.is_ok() {
+            let errors = report
+                .errors()
+                .map(|i| format!("{}: {}", i.path, i.message))
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(BuildError::InvalidConfig(errors));
+        }
 
         // Create output directory
-        let project_dir = output_dir.join(&sanitize_name(&project.name));
+        let project_dir = self.project_dir(project, output_dir);
         info!("Project dir: {}", project_dir.display());
-
-        if project_dir.exists() {
-            info!("Removing existing project directory...");
-            fs::remove_dir_all(&project_dir).await?;
-            info!("Existing directory removed");
-        }
         fs::create_dir_all(&project_dir).await?;
-        info!("Created project directory");
 
-        // Copy runtime template
+        // Sync runtime template
         info!(
-            "Copying runtime template from {} to {}...",
+            "Syncing runtime template from {} to {}...",
             self.runtime_path.display(),
             project_dir.display()
         );
-        copy_dir_recursive(&self.runtime_path, &project_dir).await?;
-        info!("Copied runtime template to: {}", project_dir.display());
+        let (src, dst) = (self.runtime_path.clone(), project_dir.clone());
+        let stats = tokio::task::spawn_blocking(move || sync_template(&src, &dst, GENERATED_FILES))
+            .await
+            .map_err(|e| BuildError::CommandFailed(format!("Template sync failed: {}", e)))??;
+        info!(
+            "Synced runtime template: {} copied, {} unchanged, {} removed",
+            stats.copied, stats.unchanged, stats.removed
+        );
 
         // Generate project config
         info!("Generating project config...");
-        let config = ProjectConfig::from(project);
-        let config_json = serde_json::to_string_pretty(&config)
-            .map_err(|e| BuildError::SerializationError(e.to_string()))?;
-
-        // Write config to be embedded at build time
-        let config_path = project_dir.join("src-tauri/project_config.json");
-        fs::write(&config_path, &config_json).await?;
-        info!("Written project config: {}", config_path.display());
+        let config_json = runtime_config_json(project)?;
+
+        // Write config to be embedded at build time; protected builds only
+        // embed it sealed
+        let config_path = project_dir.join(PROJECT_CONFIG_FILE);
+        if protect {
+            if config_path.exists() {
+                fs::remove_file(&config_path).await?;
+            }
+        } else {
+            write_if_changed(&config_path, &config_json).await?;
+            info!("Written project config: {}", config_path.display());
+        }
 
         // Update tauri.conf.json with project name
         info!("Updating tauri.conf.json...");
@@ -218,29 +330,95 @@ impl Builder {
         self.update_package_json(&project_dir, project).await?;
         info!("Updated package.json");
 
+        // Compile eligible interval chains into the target script
+        let agent = compile_agent(&project.scripts, &project.structs);
+        for entry in &agent.skipped {
+            info!(
+                "Interval {} in '{}' stays on the host: {}",
+                entry.event_node_id, entry.script_name, entry.reason
+            );
+        }
+        info!("Compiled {} interval chain(s) into the target agent", agent.compiled.len());
+        let script = agent.target_script();
+        let script_path = project_dir.join(TARGET_SCRIPT);
+
         // Generate lib.rs that loads the embedded config
         info!("Generating lib.rs...");
-        self.generate_lib_rs(&project_dir, project).await?;
+        if protect {
+            if script_path.exists() {
+                fs::remove_file(&script_path).await?;
+            }
+            match seal_embedded(&project_dir, &config_json, &script)? {
+                Some(sealed) => {
+                    self.generate_lib_rs(&project_dir, Some(&sealed.shares)).await?;
+                    sealed.commit(&project_dir)?;
+                }
+                None => debug!("Sealed config unchanged, keeping lib.rs"),
+            }
+        } else {
+            remove_sealed(&project_dir)?;
+            write_if_changed(&script_path, &script).await?;
+            self.generate_lib_rs(&project_dir, None).await?;
+        }
+        self.generate_main_rs(&project_dir, project).await?;
         info!("Generated lib.rs");
 
         info!("Project generated at: {}", project_dir.display());
         Ok(project_dir)
     }
 
+    /// Directory the project is generated into below `output_dir`
+    pub fn project_dir(&self, project: &Project, output_dir: &Path) -> PathBuf {
+        output_dir.join(sanitize_name(&project.name))
+    }
+
+    /// Remove the generated project and its build cache
+    pub async fn clean(&self, project: &Project, output_dir: &Path) -> Result<(), BuildError> {
+        let project_dir = self.project_dir(project, output_dir);
+        if project_dir.exists() {
+            info!("Removing generated project: {}", project_dir.display());
+            fs::remove_dir_all(&project_dir).await?;
+        }
+        Ok(())
+    }
+
     /// Build the generated project
     pub async fn build(
         &self,
         project: &Project,
         options: &BuildOptions,
+    ) -> Result<BuildOutput, BuildError> {
+        self.build_with(project, options, &BuildProgress::default()).await
+    }
+
+    /// Build the project phase by phase (see [`BuildPhase`]), reporting events
+    /// to `progress`. Cancelling `progress` kills the running child process and
+    /// fails the build with [`BuildError::Cancelled`].
+    pub async fn build_with(
+        &self,
+        project: &Project,
+        options: &BuildOptions,
+        progress: &BuildProgress,
     ) -> Result<BuildOutput, BuildError> {
         // Check for required tools before starting
-        self.check_required_tools()?;
+        self.preflight(project, options)?;
+        let mut timings = Vec::new();
 
-        // First generate the project
-        let project_dir = self.generate_project(project, &options.output_dir).await?;
+        let project_dir = progress
+            .phase(BuildPhase::Generate, &mut timings, async {
+                if options.clean {
+                    self.clean(project, &options.output_dir).await?;
+                }
+                let dir = self.generate(project, &options.output_dir, options.protect).await?;
+                progress.log(
+                    BuildPhase::Generate,
+                    LogLevel::Info,
+                    format!("Project generated at {}", dir.display()),
+                );
+                Ok(dir)
+            })
+            .await?;
 
-        // Install dependencies
-        info!("Installing dependencies...");
         let install_cmd = if which::which("pnpm").is_ok() {
             "pnpm"
         } else if which::which("bun").is_ok() {
@@ -248,58 +426,97 @@ impl Builder {
         } else {
             "npm"
         };
-
-        let install_status = Command::new(install_cmd)
-            .arg("install")
-            .current_dir(&project_dir)
-            .status()
-            .map_err(|e| {
-                BuildError::CommandFailed(format!("{} install failed: {}", install_cmd, e))
-            })?;
-
-        if !install_status.success() {
-            return Err(BuildError::CommandFailed(format!(
-                "{} install failed",
-                install_cmd
-            )));
-        }
-
-        // Build with Tauri
-        info!("Building project...");
-        let mut tauri_cmd = Command::new("cargo");
-        tauri_cmd.arg("tauri").arg("build");
-
+        progress
+            .phase(BuildPhase::Install, &mut timings, async {
+                if !install_required(&project_dir, install_cmd) {
+                    info!("Dependencies unchanged, skipping {} install", install_cmd);
+                    progress.emit(BuildEvent::PhaseSkipped {
+                        phase: BuildPhase::Install,
+                        reason: format!("dependencies unchanged since the last {} install", install_cmd),
+                    });
+                    return Ok(());
+                }
+                info!("Installing dependencies...");
+                progress
+                    .run_command(BuildPhase::Install, install_cmd, &["install"], &project_dir)
+                    .await?;
+                record_install(&project_dir, install_cmd)
+            })
+            .await?;
+
+        // `tauri build --no-bundle` runs the frontend build and compiles the
+        // runtime; `tauri bundle` then packages that binary
+        let mut tauri_args: Vec<&str> = Vec::new();
         if !options.release {
-            tauri_cmd.arg("--debug");
+            tauri_args.push("--debug");
         }
-
         let target_str = options.target.tauri_target();
         if !target_str.is_empty() {
-            tauri_cmd.arg("--target").arg(target_str);
+            tauri_args.extend(["--target", target_str]);
         }
-
-        // Set feature flags based on options
         if options.bundle_frida {
-            tauri_cmd.args(["--features", "real"]);
+            tauri_args.extend(["--features", "real"]);
         }
 
-        tauri_cmd.current_dir(&project_dir);
-
-        let build_status = tauri_cmd
-            .status()
-            .map_err(|e| BuildError::CommandFailed(format!("tauri build failed: {}", e)))?;
+        // MSVC binaries from a non-Windows host are linked through cargo-xwin
+        let xwin = preflight::host_triple()
+            .is_some_and(|host| preflight::needs_xwin(target_str, &host));
 
-        if !build_status.success() {
-            return Err(BuildError::CommandFailed("tauri build failed".to_string()));
-        }
-
-        // Find built executables
-        let executables = find_executables(&project_dir, options.release)?;
+        progress
+            .phase(BuildPhase::Compile, &mut timings, async {
+                info!("Building project...");
+                let mut args = [&["tauri", "build", "--no-bundle"], tauri_args.as_slice()].concat();
+                if xwin {
+                    args.extend(["--runner", "cargo-xwin"]);
+                }
+                progress
+                    .run_command(BuildPhase::Compile, "cargo", &args, &project_dir)
+                    .await
+            })
+            .await?;
+
+        progress
+            .phase(BuildPhase::Bundle, &mut timings, async {
+                info!("Bundling project...");
+                let args = [&["tauri", "bundle"], tauri_args.as_slice()].concat();
+                progress
+                    .run_command(BuildPhase::Bundle, "cargo", &args, &project_dir)
+                    .await
+            })
+            .await?;
+
+        let (executables, manifest_path) = progress
+            .phase(BuildPhase::Collect, &mut timings, async {
+                // Find built executables
+                let executables = find_executables(&project_dir, options.release)?;
+
+                // Record inputs and output hashes
+                let config_json = runtime_config_json(project)?;
+                let manifest = BuildManifest::new(
+                    project,
+                    ManifestOptions {
+                        target: options.target.as_str().to_string(),
+                        release: options.release,
+                        bundle_frida: options.bundle_frida,
+                        protected: options.protect,
+                    },
+                    &config_json,
+                    &executables,
+                    &project_dir,
+                )?;
+                let manifest_path = project_dir.join(MANIFEST_FILE);
+                manifest.save(&manifest_path)?;
+                info!("Wrote build manifest: {}", manifest_path.display());
+                Ok((executables, manifest_path))
+            })
+            .await?;
 
         Ok(BuildOutput {
             project_dir,
             executables,
             target: options.target,
+            manifest: manifest_path,
+            timings,
         })
     }
 
@@ -309,7 +526,7 @@ impl Builder {
         project: &Project,
     ) -> Result<(), BuildError> {
         let config_path = project_dir.join("src-tauri/tauri.conf.json");
-        let content = fs::read_to_string(&config_path).await?;
+        let content = fs::read_to_string(self.runtime_path.join("src-tauri/tauri.conf.json")).await?;
 
         let mut config: serde_json::Value = serde_json::from_str(&content)
             .map_err(|e| BuildError::SerializationError(e.to_string()))?;
@@ -339,7 +556,7 @@ impl Builder {
 
         let updated = serde_json::to_string_pretty(&config)
             .map_err(|e| BuildError::SerializationError(e.to_string()))?;
-        fs::write(&config_path, updated).await?;
+        write_if_changed(&config_path, &updated).await?;
 
         Ok(())
     }
@@ -354,47 +571,12 @@ impl Builder {
         let sanitized = sanitize_name(&project.name);
         let lib_name = sanitized.replace('-', "_");
 
-        // Generate a standalone Cargo.toml without workspace inheritance
-        let cargo_content = format!(
-            r#"[package]
-name = "{name}"
-version = "{version}"
-edition = "2021"
-authors = ["Forvanced Team"]
-license = "MIT"
-
-[features]
-default = ["mock"]
-mock = []
-real = []
+        // The runtime's internal crates live next to it in the workspace
+        let crates_dir = self.runtime_path.join("../../crates");
+        let crates_dir = crates_dir.canonicalize().unwrap_or(crates_dir);
+        let cargo_content = render_cargo_toml(&sanitized, &project.version, &lib_name, &crates_dir);
 
-[lib]
-name = "{lib_name}_lib"
-crate-type = ["lib", "cdylib", "staticlib"]
-
-[build-dependencies]
-tauri-build = {{ version = "2", features = [] }}
-
-[dependencies]
-tauri = {{ version = "2", features = ["devtools"] }}
-serde = {{ version = "1.0", features = ["derive"] }}
-serde_json = "1.0"
-tokio = {{ version = "1.35", features = ["full"] }}
-tracing = "0.1"
-tracing-subscriber = {{ version = "0.3", features = ["env-filter"] }}
-async-trait = "0.1"
-
-[profile.release]
-opt-level = 3
-lto = true
-codegen-units = 1
-"#,
-            name = sanitized,
-            version = project.version,
-            lib_name = lib_name,
-        );
-
-        fs::write(&cargo_path, cargo_content).await?;
+        write_if_changed(&cargo_path, &cargo_content).await?;
         Ok(())
     }
 
@@ -404,7 +586,7 @@ codegen-units = 1
         project: &Project,
     ) -> Result<(), BuildError> {
         let pkg_path = project_dir.join("package.json");
-        let content = fs::read_to_string(&pkg_path).await?;
+        let content = fs::read_to_string(self.runtime_path.join("package.json")).await?;
 
         let mut pkg: serde_json::Value = serde_json::from_str(&content)
             .map_err(|e| BuildError::SerializationError(e.to_string()))?;
@@ -416,7 +598,7 @@ codegen-units = 1
 
         let updated = serde_json::to_string_pretty(&pkg)
             .map_err(|e| BuildError::SerializationError(e.to_string()))?;
-        fs::write(&pkg_path, updated).await?;
+        write_if_changed(&pkg_path, &updated).await?;
 
         Ok(())
     }
@@ -424,66 +606,12 @@ codegen-units = 1
     async fn generate_lib_rs(
         &self,
         project_dir: &Path,
-        project: &Project,
+        key_shares: Option<&KeyShares>,
     ) -> Result<(), BuildError> {
         let lib_path = project_dir.join("src-tauri/src/lib.rs");
 
         // Overwrite lib.rs to load the embedded config
-        let content = r#"//! Forvanced Runtime - Generated project app
-//!
-//! This is the runtime that executes the project built by Forvanced Builder.
-//! It renders the UI components and executes Frida scripts.
-
-mod commands;
-mod state;
-
-use state::AppState;
-use std::sync::Arc;
-use tokio::sync::Mutex;
-use tracing_subscriber::{fmt, prelude::*, EnvFilter};
-
-/// Embedded project configuration
-const PROJECT_CONFIG: &str = include_str!("../project_config.json");
-
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    // Initialize tracing
-    tracing_subscriber::registry()
-        .with(fmt::layer())
-        .with(EnvFilter::from_default_env().add_directive("forvanced=debug".parse().unwrap()))
-        .init();
-
-    tracing::info!("Starting Forvanced Runtime");
-
-    // Load embedded config and create app state
-    let config: state::ProjectConfig = serde_json::from_str(PROJECT_CONFIG)
-        .expect("Failed to parse embedded project config");
-    
-    tracing::info!("Loaded project config: {} with {} scripts", config.name, config.scripts.len());
-    
-    let app_state = Arc::new(Mutex::new(AppState::from_config(config)));
-
-    tauri::Builder::default()
-        .manage(app_state)
-        .invoke_handler(tauri::generate_handler![
-            commands::get_project_config,
-            commands::attach_process,
-            commands::detach_process,
-            commands::execute_action,
-            commands::trigger_ui_event,
-            commands::get_component_value,
-            commands::set_component_value,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
-"#;
-
-        fs::write(&lib_path, content).await?;
-
-        // Generate main.rs
-        self.generate_main_rs(project_dir, project).await?;
-
+        write_if_changed(&lib_path, &render_lib_rs(key_shares)).await?;
         Ok(())
     }
 
@@ -506,7 +634,7 @@ fn main() {{
             lib_name = lib_name
         );
 
-        fs::write(&path, content).await?;
+        write_if_changed(&path, &content).await?;
         Ok(())
     }
 
@@ -605,8 +733,25 @@ impl AppState {
         Ok(())
     }
 
-    /// Check if all required build tools are installed
-    fn check_required_tools(&self) -> Result<(), BuildError> {
+    /// Check that everything needed to build `project` for `options.target`
+    /// is installed, including cross-compilation toolchains and the Frida
+    /// devkit (see [`crate::preflight`])
+    pub fn preflight(&self, project: &Project, options: &BuildOptions) -> Result<(), BuildError> {
+        let mut missing = self.missing_tools();
+        missing.extend(preflight::check_target(
+            options.target,
+            options.bundle_frida,
+            options.release,
+            &self.project_dir(project, &options.output_dir),
+        ));
+        if !missing.is_empty() {
+            return Err(BuildError::MissingTools(MissingToolsInfo { missing }));
+        }
+        Ok(())
+    }
+
+    /// Base tools every build needs, whatever the target
+    fn missing_tools(&self) -> Vec<MissingTool> {
         let mut missing = Vec::new();
 
         // Check for Node.js package manager (npm/pnpm/bun)
@@ -616,23 +761,23 @@ impl AppState {
 
         if !has_node_pm {
             missing.push(MissingTool {
-                name: "Node.js",
-                description: "JavaScript 런타임 및 패키지 매니저 (npm 포함)",
+                name: "Node.js".into(),
+                description: "JavaScript 런타임 및 패키지 매니저 (npm 포함)".into(),
                 install_instructions: vec![
                     InstallInstruction {
-                        platform: "Windows",
-                        command: "winget install OpenJS.NodeJS.LTS",
-                        url: Some("https://nodejs.org"),
+                        platform: "Windows".into(),
+                        command: "winget install OpenJS.NodeJS.LTS".into(),
+                        url: Some("https://nodejs.org".into()),
                     },
                     InstallInstruction {
-                        platform: "macOS",
-                        command: "brew install node",
-                        url: Some("https://nodejs.org"),
+                        platform: "macOS".into(),
+                        command: "brew install node".into(),
+                        url: Some("https://nodejs.org".into()),
                     },
                     InstallInstruction {
-                        platform: "Linux",
-                        command: "curl -fsSL https://deb.nodesource.com/setup_lts.x | sudo -E bash - && sudo apt-get install -y nodejs",
-                        url: Some("https://nodejs.org"),
+                        platform: "Linux".into(),
+                        command: "curl -fsSL https://deb.nodesource.com/setup_lts.x | sudo -E bash - && sudo apt-get install -y nodejs".into(),
+                        url: Some("https://nodejs.org".into()),
                     },
                 ],
             });
@@ -641,12 +786,12 @@ impl AppState {
         // Check for Rust/Cargo
         if which::which("cargo").is_err() {
             missing.push(MissingTool {
-                name: "Rust",
-                description: "Rust 프로그래밍 언어 및 Cargo 패키지 매니저",
+                name: "Rust".into(),
+                description: "Rust 프로그래밍 언어 및 Cargo 패키지 매니저".into(),
                 install_instructions: vec![InstallInstruction {
-                    platform: "Windows/macOS/Linux",
-                    command: "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh",
-                    url: Some("https://rustup.rs"),
+                    platform: "Windows/macOS/Linux".into(),
+                    command: "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh".into(),
+                    url: Some("https://rustup.rs".into()),
                 }],
             });
         }
@@ -655,21 +800,17 @@ impl AppState {
         let has_tauri = which::which("cargo-tauri").is_ok() || which::which("tauri").is_ok();
         if !has_tauri {
             missing.push(MissingTool {
-                name: "Tauri CLI",
-                description: "Tauri 앱 빌드 도구",
+                name: "Tauri CLI".into(),
+                description: "Tauri 앱 빌드 도구".into(),
                 install_instructions: vec![InstallInstruction {
-                    platform: "모든 플랫폼",
-                    command: "cargo install tauri-cli",
+                    platform: "모든 플랫폼".into(),
+                    command: "cargo install tauri-cli".into(),
                     url: None,
                 }],
             });
         }
 
-        if !missing.is_empty() {
-            return Err(BuildError::MissingTools(MissingToolsInfo { missing }));
-        }
-
-        Ok(())
+        missing
     }
 }
 
@@ -761,63 +902,7 @@ fn find_workspace_root(start: &Path) -> Option<PathBuf> {
     None
 }
 
-/// Recursively copy a directory (using blocking I/O for better performance)
-async fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), BuildError> {
-    let src = src.to_path_buf();
-    let dst = dst.to_path_buf();
-
-    tokio::task::spawn_blocking(move || copy_dir_recursive_sync(&src, &dst))
-        .await
-        .map_err(|e| BuildError::CommandFailed(format!("Copy task failed: {}", e)))??;
-
-    Ok(())
-}
-
-/// Synchronous directory copy (faster than async for many small files)
-fn copy_dir_recursive_sync(src: &Path, dst: &Path) -> Result<(), BuildError> {
-    debug!(
-        "copy_dir_recursive_sync: {} -> {}",
-        src.display(),
-        dst.display()
-    );
-    std::fs::create_dir_all(dst)?;
-
-    let entries = std::fs::read_dir(src)?;
-    let mut file_count = 0;
-    let mut dir_count = 0;
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-        let file_name = path.file_name().unwrap();
-        let dst_path = dst.join(file_name);
-
-        // Skip certain directories
-        let name = file_name.to_string_lossy();
-        if name == "node_modules" || name == "target" || name == "dist" || name == ".git" {
-            debug!("Skipping: {}", name);
-            continue;
-        }
-
-        if path.is_dir() {
-            dir_count += 1;
-            copy_dir_recursive_sync(&path, &dst_path)?;
-        } else {
-            file_count += 1;
-            std::fs::copy(&path, &dst_path)?;
-        }
-    }
-
-    debug!(
-        "Copied {} files and {} directories from {}",
-        file_count,
-        dir_count,
-        src.display()
-    );
-    Ok(())
-}
-
-fn sanitize_name(name: &str) -> String {
+pub(crate) fn sanitize_name(name: &str) -> String {
     name.chars()
         .map(|c| {
             if c.is_alphanumeric() || c == '-' || c == '_' {
@@ -971,6 +1056,183 @@ fn find_executables(project_dir: &Path, release: bool) -> Result<Vec<PathBuf>, B
     Ok(executables)
 }
 
+/// Render the generated project's `Cargo.toml`
+///
+/// It is standalone (no workspace inheritance) and depends on the Forvanced
+/// crates in `crates_dir` by path, with the same features as the runtime app.
+fn render_cargo_toml(name: &str, version: &str, lib_name: &str, crates_dir: &Path) -> String {
+    // A JSON string is a valid TOML basic string, escapes included
+    let crate_path = |krate: &str| {
+        serde_json::to_string(&crates_dir.join(krate).to_string_lossy()).unwrap_or_default()
+    };
+    format!(
+        r#"[package]
+name = "{name}"
+version = "{version}"
+edition = "2021"
+authors = ["Forvanced Team"]
+license = "MIT"
+
+[features]
+default = ["mock"]
+mock = ["forvanced-frida/mock"]
+real = ["forvanced-frida/real"]
+
+[lib]
+name = "{lib_name}_lib"
+crate-type = ["lib", "cdylib", "staticlib"]
+
+[build-dependencies]
+tauri-build = {{ version = "2", features = [] }}
+
+[dependencies]
+tauri = {{ version = "2", features = ["devtools"] }}
+serde = {{ version = "1.0", features = ["derive"] }}
+serde_json = "1.0"
+tokio = {{ version = "1.35", features = ["full"] }}
+tracing = "0.1"
+tracing-subscriber = {{ version = "0.3", features = ["env-filter"] }}
+async-trait = "0.1"
+
+forvanced-core = {{ path = {core} }}
+forvanced-frida = {{ path = {frida}, default-features = false }}
+forvanced-executor = {{ path = {executor} }}
+
+[profile.release]
+opt-level = 3
+lto = true
+codegen-units = 1
+"#,
+        core = crate_path("forvanced-core"),
+        frida = crate_path("forvanced-frida"),
+        executor = crate_path("forvanced-executor"),
+    )
+}
+
+/// Render the generated `lib.rs`, sealed when key shares are given
+fn render_lib_rs(key_shares: Option<&KeyShares>) -> String {
+    match key_shares {
+        None => LIB_RS_TEMPLATE
+            .replace("__EMBEDDED__", PLAIN_EMBEDDED)
+            .replace("__LOAD_CONFIG__", PLAIN_LOAD_CONFIG),
+        Some((masked, mask)) => LIB_RS_TEMPLATE
+            .replace(
+                "__EMBEDDED__",
+                &SEALED_EMBEDDED
+                    .replace("__SEALED_CONFIG__", SEALED_CONFIG.trim_start_matches("src-tauri/"))
+                    .replace("__SEALED_SCRIPT__", SEALED_SCRIPT.trim_start_matches("src-tauri/"))
+                    .replace("__KEY_MASKED__", &byte_array(masked))
+                    .replace("__KEY_MASK__", &byte_array(mask)),
+            )
+            .replace("__LOAD_CONFIG__", SEALED_LOAD_CONFIG),
+    }
+}
+
+/// Generated `lib.rs`; `__EMBEDDED__` and `__LOAD_CONFIG__` differ between
+/// plain and protected builds
+const LIB_RS_TEMPLATE: &str = r#"//! Forvanced Runtime - Generated project app
+//!
+//! This is the runtime that executes the project built by Forvanced Builder.
+//! It renders the UI components and executes Frida scripts.
+
+mod commands;
+mod state;
+
+use state::AppState;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+__EMBEDDED__
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    // Initialize tracing
+    tracing_subscriber::registry()
+        .with(fmt::layer())
+        .with(EnvFilter::from_default_env().add_directive("forvanced=debug".parse().unwrap()))
+        .init();
+
+    tracing::info!("Starting Forvanced Runtime");
+
+__LOAD_CONFIG__
+    tauri::Builder::default()
+        .manage(Arc::clone(&app_state))
+        .setup(move |app| {
+            // Watch for the target when the trainer asks to attach on its own
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = commands::start_auto_attach(app_handle, app_state).await {
+                    tracing::error!("Failed to start auto-attach: {}", e);
+                }
+            });
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            commands::get_project_config,
+            commands::attach_process,
+            commands::spawn_process,
+            commands::launch_process,
+            commands::detach_process,
+            commands::execute_action,
+            commands::trigger_ui_event,
+            commands::get_component_value,
+            commands::set_component_value,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
+"#;
+
+const PLAIN_EMBEDDED: &str = r#"/// Embedded project configuration
+const PROJECT_CONFIG: &str = include_str!("../project_config.json");
+/// Target script with the project's compiled agents
+const TARGET_SCRIPT: &str = include_str!("../target_script.js");
+"#;
+
+const PLAIN_LOAD_CONFIG: &str = r#"    // Load embedded config and create app state
+    let config = state::ProjectConfig::parse(PROJECT_CONFIG)
+        .expect("Failed to load embedded project config");
+    
+    tracing::info!("Loaded project config: {} with {} scripts", config.name, config.scripts.len());
+    
+    let mut app_state = AppState::from_config(config);
+    app_state.target_script = Some(TARGET_SCRIPT.to_string());
+    let app_state = Arc::new(Mutex::new(app_state));
+"#;
+
+const SEALED_EMBEDDED: &str = r#"/// Sealed project configuration and target script (protected build)
+const SEALED_CONFIG: &[u8] = include_bytes!("../__SEALED_CONFIG__");
+const SEALED_SCRIPT: &[u8] = include_bytes!("../__SEALED_SCRIPT__");
+const KEY_MASKED: [u8; 32] = __KEY_MASKED__;
+const KEY_MASK: [u8; 32] = __KEY_MASK__;
+
+fn open_embedded() -> Result<(state::ProjectConfig, String), String> {
+    use forvanced_core::protect::{open_str, SealKey};
+
+    let key = SealKey::from_shares(&KEY_MASKED, &KEY_MASK);
+    let config = open_str(&key, "config", SEALED_CONFIG).map_err(|e| e.to_string())?;
+    let script = open_str(&key, "script", SEALED_SCRIPT).map_err(|e| e.to_string())?;
+    Ok((state::ProjectConfig::parse(&config)?, script))
+}
+"#;
+
+const SEALED_LOAD_CONFIG: &str = r#"    // Open the sealed config and script; a modified trainer refuses to start
+    let (config, target_script) = match open_embedded() {
+        Ok(embedded) => embedded,
+        Err(e) => {
+            tracing::error!("Integrity check failed: {}", e);
+            eprintln!("This trainer has been modified and will not start ({})", e);
+            std::process::exit(1);
+        }
+    };
+
+    tracing::info!("Loaded project config: {} with {} scripts", config.name, config.scripts.len());
+
+    let mut app_state = AppState::from_config(config);
+    app_state.target_script = Some(target_script);
+    let app_state = Arc::new(Mutex::new(app_state));
+"#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -993,5 +1255,156 @@ mod tests {
             BuildTarget::from_str("macos"),
             Some(BuildTarget::MacOsArm64)
         );
+        for target in [BuildTarget::WindowsX64, BuildTarget::LinuxX64, BuildTarget::Ios] {
+            assert_eq!(BuildTarget::from_str(target.as_str()), Some(target));
+        }
+    }
+
+    /// The part of a `lib.rs` that starts with `start` and runs to the next
+    /// `end`
+    fn lib_rs_section<'a>(lib_rs: &'a str, start: &str, end: &str) -> &'a str {
+        let from = lib_rs.find(start).unwrap();
+        let len = lib_rs[from..].find(end).unwrap();
+        &lib_rs[from..from + len]
+    }
+
+    /// Generated trainers must set up the app (auto-attach included) and
+    /// expose the same commands as the runtime app they are built from
+    #[test]
+    fn test_lib_rs_template_matches_runtime_app() {
+        let runtime = include_str!("../../../apps/runtime/src-tauri/src/lib.rs");
+        let expected = lib_rs_section(runtime, "tauri::Builder::default()", ".run(");
+        assert!(expected.contains("commands::start_auto_attach"));
+        let shares = ([1u8; 32], [2u8; 32]);
+        for generated in [render_lib_rs(None), render_lib_rs(Some(&shares))] {
+            let app = lib_rs_section(&generated, "tauri::Builder::default()", ".run(");
+            assert_eq!(app, expected);
+        }
+    }
+
+    /// Generated trainers must build against the same internal crates and
+    /// backend features as the runtime app
+    #[test]
+    fn test_cargo_toml_matches_runtime_app() {
+        let runtime = include_str!("../../../apps/runtime/src-tauri/Cargo.toml");
+        let crates_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let generated = render_cargo_toml("my-trainer", "1.2.0", "my_trainer", &crates_dir);
+
+        for feature in ["mock = [\"forvanced-frida/mock\"]", "real = [\"forvanced-frida/real\"]"] {
+            assert!(runtime.contains(feature));
+            assert!(generated.contains(feature), "missing {}", feature);
+        }
+        for krate in ["forvanced-core", "forvanced-frida", "forvanced-executor"] {
+            assert!(runtime.contains(&format!("{} = {{ path", krate)));
+            let path = crates_dir.join(krate).to_string_lossy().into_owned();
+            let dep = format!("{} = {{ path = {}", krate, serde_json::to_string(&path).unwrap());
+            assert!(generated.contains(&dep), "missing {}", dep);
+            assert!(crates_dir.join(krate).join("Cargo.toml").exists());
+        }
+        assert!(generated.contains("default-features = false"));
+    }
+
+    fn assert_same_json<T: serde::Serialize>(a: &T, b: &T) {
+        assert_eq!(serde_json::to_value(a).unwrap(), serde_json::to_value(b).unwrap());
+    }
+
+    /// Every runtime-relevant project field must survive the trip into the
+    /// embedded config. The exhaustive destructuring below stops compiling when
+    /// a field is added to the project model, so it has to be mapped (or
+    /// explicitly skipped) here.
+    #[test]
+    fn test_project_config_round_trip_is_complete() {
+        use forvanced_core::project::{
+            HotkeyBinding, ProjectConfig as CoreConfig, TargetConfig, UILayout,
+        };
+        use forvanced_core::{ComponentType, StructDefinition};
+
+        let mut project = Project::new("Complete");
+        project.description = Some("All fields".to_string());
+        project.author = Some("Tester".to_string());
+        project.version = "1.2.3".to_string();
+        project.config.target = TargetConfig {
+            process_name: Some("game.exe".to_string()),
+            process_patterns: vec!["game*.exe".to_string()],
+            adapter_type: "remote".to_string(),
+            adapter_config: serde_json::json!({ "host": "10.0.0.2", "port": 27042 }),
+            auto_attach: true,
+        };
+        project.config.hotkeys.enabled = true;
+        project.config.hotkeys.bindings.push(HotkeyBinding {
+            key: "F1".to_string(),
+            modifiers: vec!["ctrl".to_string()],
+            action_id: "btn".to_string(),
+        });
+        project.ui.theme = "light".to_string();
+        project.ui.padding = 4;
+        project.ui.gap = 2;
+        project
+            .ui
+            .components
+            .push(UIComponent::new(ComponentType::Button, "Go", 0.0, 0.0));
+        project.scripts.push(VisualScript::new("Main"));
+        project.structs.push(StructDefinition {
+            name: "Player".to_string(),
+            fields: vec![],
+            size: Some(8),
+        });
+
+        let json = serde_json::to_string(&ProjectConfig::from(&project)).unwrap();
+        let config: ProjectConfig = serde_json::from_str(&json).unwrap();
+
+        let Project {
+            schema_version: _,
+            id: _,
+            name,
+            description,
+            version,
+            author,
+            config: CoreConfig { target, build: _, hotkeys },
+            ui,
+            scripts,
+            structs,
+            created_at: _,
+            updated_at: _,
+        } = &project;
+        let TargetConfig {
+            process_name,
+            process_patterns,
+            adapter_type,
+            adapter_config,
+            auto_attach,
+        } = target;
+        let UILayout {
+            components,
+            width,
+            height,
+            theme,
+            padding,
+            gap,
+        } = ui;
+
+        assert_eq!(config.config_version, RUNTIME_CONFIG_VERSION);
+        assert_eq!(&config.name, name);
+        assert_eq!(&config.description, description);
+        assert_eq!(&config.version, version);
+        assert_eq!(&config.author, author);
+        assert_eq!(&config.target_process, process_name);
+        assert_eq!(&config.process_patterns, process_patterns);
+        assert_eq!(&config.adapter_type, adapter_type);
+        assert_eq!(&config.adapter_config, adapter_config);
+        assert_eq!(&config.auto_attach, auto_attach);
+        assert_same_json(&config.hotkeys, hotkeys);
+        assert_same_json(&config.components, components);
+        assert_same_json(&config.scripts, scripts);
+        assert_same_json(&config.structs, structs);
+        assert_eq!(
+            (config.canvas.width, config.canvas.height),
+            (*width, *height)
+        );
+        assert_eq!(
+            (config.canvas.padding, config.canvas.gap),
+            (*padding, *gap)
+        );
+        assert_eq!(&config.canvas.theme, theme);
     }
 }
@@ -0,0 +1,247 @@
+//! Incremental build cache
+//!
+//! The generated project directory is kept between builds. Template files are
+//! only copied when their content changed, generated files are only written
+//! when their content changed (so cargo and vite keep their caches), and the
+//! dependency install is skipped while the manifests and lockfiles match the
+//! last successful install.
+
+use crate::error::BuildError;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Cache state lives inside the generated project
+const CACHE_FILE: &str = ".forvanced/build-cache.json";
+
+/// Directories never copied from the template
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", ".git", ".forvanced"];
+
+/// Files that determine whether dependencies must be reinstalled
+const INSTALL_INPUTS: &[&str] = &[
+    "package.json",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "bun.lock",
+    "bun.lockb",
+];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BuildCache {
+    /// Template files copied by the last sync (relative paths)
+    #[serde(default)]
+    template_files: BTreeSet<String>,
+    /// Fingerprint of the install inputs at the last successful install
+    #[serde(default)]
+    install_fingerprint: Option<String>,
+    /// Digest of the plaintext behind the current sealed files (protected builds)
+    #[serde(default)]
+    pub(crate) sealed_digest: Option<String>,
+}
+
+impl BuildCache {
+    pub(crate) fn load(project_dir: &Path) -> Self {
+        std::fs::read_to_string(project_dir.join(CACHE_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn save(&self, project_dir: &Path) -> Result<(), BuildError> {
+        let path = project_dir.join(CACHE_FILE);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Counts reported by [`sync_template`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct SyncStats {
+    pub copied: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+}
+
+/// Bring `dst` in line with the template at `src`, touching only files whose
+/// content differs. Paths in `generated` are owned by the generator and skipped.
+pub(crate) fn sync_template(
+    src: &Path,
+    dst: &Path,
+    generated: &[&str],
+) -> Result<SyncStats, BuildError> {
+    let mut cache = BuildCache::load(dst);
+    let mut stats = SyncStats::default();
+    let mut files = BTreeSet::new();
+
+    for (relative, path) in template_files(src)? {
+        if generated.contains(&relative.as_str()) {
+            continue;
+        }
+        let target = dst.join(&relative);
+        let content = std::fs::read(&path)?;
+        if std::fs::read(&target).ok().as_deref() == Some(content.as_slice()) {
+            stats.unchanged += 1;
+        } else {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&target, content)?;
+            stats.copied += 1;
+        }
+        files.insert(relative);
+    }
+
+    // Files that were removed from the template since the last sync
+    for stale in cache.template_files.difference(&files) {
+        let target = dst.join(stale);
+        if target.exists() {
+            std::fs::remove_file(&target)?;
+            stats.removed += 1;
+        }
+    }
+
+    debug!(
+        "Template sync: {} copied, {} unchanged, {} removed",
+        stats.copied, stats.unchanged, stats.removed
+    );
+    cache.template_files = files;
+    cache.save(dst)?;
+    Ok(stats)
+}
+
+/// All template files below `root` as (relative path with `/`, absolute path)
+fn template_files(root: &Path) -> Result<Vec<(String, PathBuf)>, BuildError> {
+    let mut files = Vec::new();
+    let walker = walkdir::WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0 || !SKIPPED_DIRS.contains(&e.file_name().to_string_lossy().as_ref())
+        });
+    for entry in walker {
+        let entry = entry.map_err(|e| BuildError::Io(e.into()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.push((relative, entry.into_path()));
+    }
+    Ok(files)
+}
+
+/// Write `content` only if it differs from what is on disk. Returns true if written.
+pub(crate) async fn write_if_changed(path: &Path, content: &str) -> Result<bool, BuildError> {
+    if tokio::fs::read(path).await.ok().as_deref() == Some(content.as_bytes()) {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, content).await?;
+    Ok(true)
+}
+
+fn install_fingerprint(project_dir: &Path, install_cmd: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(install_cmd.as_bytes());
+    for name in INSTALL_INPUTS {
+        hasher.update([0]);
+        hasher.update(name.as_bytes());
+        if let Ok(content) = std::fs::read(project_dir.join(name)) {
+            hasher.update([1]);
+            hasher.update(&content);
+        }
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// True unless the install inputs match the last recorded install
+pub fn install_required(project_dir: &Path, install_cmd: &str) -> bool {
+    if !project_dir.join("node_modules").exists() {
+        return true;
+    }
+    let cache = BuildCache::load(project_dir);
+    cache.install_fingerprint.as_deref() != Some(install_fingerprint(project_dir, install_cmd).as_str())
+}
+
+/// Remember the install inputs after a successful install. Lockfiles written by
+/// the install itself are included, so the next build sees a match.
+pub fn record_install(project_dir: &Path, install_cmd: &str) -> Result<(), BuildError> {
+    let mut cache = BuildCache::load(project_dir);
+    cache.install_fingerprint = Some(install_fingerprint(project_dir, install_cmd));
+    cache.save(project_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_sync_template_copies_only_changes() {
+        let src = tempfile::tempdir().unwrap();
+        let dst = tempfile::tempdir().unwrap();
+        write(&src.path().join("a.txt"), "a");
+        write(&src.path().join("src-tauri/b.rs"), "b");
+        write(&src.path().join("src-tauri/Cargo.toml"), "generated");
+        write(&src.path().join("node_modules/x/index.js"), "skip");
+
+        let generated = ["src-tauri/Cargo.toml"];
+        let stats = sync_template(src.path(), dst.path(), &generated).unwrap();
+        assert_eq!(stats.copied, 2);
+        assert!(!dst.path().join("src-tauri/Cargo.toml").exists());
+        assert!(!dst.path().join("node_modules").exists());
+
+        let stats = sync_template(src.path(), dst.path(), &generated).unwrap();
+        assert_eq!(stats, SyncStats { copied: 0, unchanged: 2, removed: 0 });
+
+        write(&src.path().join("a.txt"), "changed");
+        std::fs::remove_file(src.path().join("src-tauri/b.rs")).unwrap();
+        let stats = sync_template(src.path(), dst.path(), &generated).unwrap();
+        assert_eq!(stats, SyncStats { copied: 1, unchanged: 0, removed: 1 });
+        assert_eq!(std::fs::read_to_string(dst.path().join("a.txt")).unwrap(), "changed");
+        assert!(!dst.path().join("src-tauri/b.rs").exists());
+    }
+
+    #[test]
+    fn test_install_fingerprint_tracks_lockfiles() {
+        let dir = tempfile::tempdir().unwrap();
+        write(&dir.path().join("package.json"), "{}");
+        assert!(install_required(dir.path(), "npm"));
+
+        std::fs::create_dir(dir.path().join("node_modules")).unwrap();
+        assert!(install_required(dir.path(), "npm"));
+        record_install(dir.path(), "npm").unwrap();
+        assert!(!install_required(dir.path(), "npm"));
+        assert!(install_required(dir.path(), "bun"));
+
+        write(&dir.path().join("package-lock.json"), "{\"lockfileVersion\":3}");
+        assert!(install_required(dir.path(), "npm"));
+    }
+
+    #[tokio::test]
+    async fn test_write_if_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        assert!(write_if_changed(&path, "{}").await.unwrap());
+        assert!(!write_if_changed(&path, "{}").await.unwrap());
+        assert!(write_if_changed(&path, "{\"a\":1}").await.unwrap());
+    }
+}
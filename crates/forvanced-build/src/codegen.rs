@@ -5,8 +5,10 @@
 //! as this is the core functionality of a trainer/cheat application.
 
 use forvanced_core::{
-    ActionBinding, ComponentEvent, ComponentType, FridaAction, Project, UIComponent, ValueType,
+    ActionBinding, Address, ComponentEvent, ComponentType, FridaAction, Project, UIComponent, ValueType,
 };
+use forvanced_executor::rpc::generate_target_script;
+use forvanced_frida::protocol;
 
 /// Generate TypeScript code for the trainer UI
 pub fn generate_ui_code(project: &Project) -> String {
@@ -303,10 +305,12 @@ fn generate_binding_handler(component_id: &str, binding: &ActionBinding) -> Stri
 fn generate_action_code(action: &FridaAction) -> String {
     match action {
         FridaAction::MemoryRead { address, value_type } => format!(
-            r#"const value = await cheatEngine.memoryRead("{}", "{}");
+            r#"{}
+    const value = await cheatEngine.{method}(address, "{}");
     console.log("Read value:", value);"#,
-            address,
-            value_type_to_str(value_type)
+            resolve_address_code(address),
+            value_type_to_str(value_type),
+            method = protocol::MEMORY_READ,
         ),
 
         FridaAction::MemoryWrite {
@@ -314,8 +318,12 @@ fn generate_action_code(action: &FridaAction) -> String {
             value,
             value_type,
         } => format!(
-            r#"await cheatEngine.memoryWrite("{}", "{}", "{}");"#,
-            address, value, value_type_to_str(value_type)
+            r#"{}
+    await cheatEngine.{method}(address, "{}", "{}");"#,
+            resolve_address_code(address),
+            value,
+            value_type_to_str(value_type),
+            method = protocol::MEMORY_WRITE,
         ),
 
         FridaAction::MemoryFreeze {
@@ -324,25 +332,36 @@ fn generate_action_code(action: &FridaAction) -> String {
             value_type,
             interval_ms,
         } => format!(
-            r#"await cheatEngine.memoryFreeze("{}", "{}", "{}", {});"#,
-            address, value, value_type_to_str(value_type), interval_ms
+            r#"{}
+    await cheatEngine.{method}(address, "{}", "{}", {});"#,
+            resolve_address_code(address),
+            value,
+            value_type_to_str(value_type),
+            interval_ms,
+            method = protocol::MEMORY_FREEZE,
         ),
 
-        FridaAction::MemoryUnfreeze { address } => {
-            format!(r#"await cheatEngine.memoryUnfreeze("{}");"#, address)
-        }
+        FridaAction::MemoryUnfreeze { address } => format!(
+            r#"{}
+    await cheatEngine.{method}(address);"#,
+            resolve_address_code(address),
+            method = protocol::MEMORY_UNFREEZE,
+        ),
 
         FridaAction::PatternScan { pattern, protection } => format!(
-            r#"const results = await cheatEngine.patternScan("{}", "{}");
+            r#"const results = await cheatEngine.{method}("{}", "{}");
     console.log("Pattern scan results:", results);"#,
-            pattern, protection
+            pattern,
+            protection,
+            method = protocol::PATTERN_SCAN,
         ),
 
         FridaAction::ValueScan { value, value_type } => format!(
-            r#"const results = await cheatEngine.valueScan("{}", "{}");
+            r#"const results = await cheatEngine.{method}("{}", "{}");
     console.log("Value scan results:", results);"#,
             value,
-            value_type_to_str(value_type)
+            value_type_to_str(value_type),
+            method = protocol::VALUE_SCAN,
         ),
 
         FridaAction::HookFunction {
@@ -350,21 +369,31 @@ fn generate_action_code(action: &FridaAction) -> String {
             log_enter,
             log_leave,
         } => format!(
-            r#"await cheatEngine.hookFunction("{}", {{ logEnter: {}, logLeave: {} }});"#,
-            address, log_enter, log_leave
+            r#"{}
+    await cheatEngine.{method}(address, {{ logEnter: {}, logLeave: {} }});"#,
+            resolve_address_code(address),
+            log_enter,
+            log_leave,
+            method = protocol::HOOK_FUNCTION,
         ),
 
         FridaAction::ReplaceReturn {
             address,
             return_value,
         } => format!(
-            r#"await cheatEngine.replaceReturn("{}", {});"#,
-            address, return_value
+            r#"{}
+    await cheatEngine.{method}(address, {});"#,
+            resolve_address_code(address),
+            return_value,
+            method = protocol::REPLACE_RETURN,
         ),
 
-        FridaAction::NopFunction { address } => {
-            format!(r#"await cheatEngine.nopFunction("{}");"#, address)
-        }
+        FridaAction::NopFunction { address } => format!(
+            r#"{}
+    await cheatEngine.{method}(address);"#,
+            resolve_address_code(address),
+            method = protocol::NOP_FUNCTION,
+        ),
 
         FridaAction::JavaHookMethod {
             class_name,
@@ -376,8 +405,11 @@ fn generate_action_code(action: &FridaAction) -> String {
                 .map(|o| format!(r#", "{}""#, o))
                 .unwrap_or_default();
             format!(
-                r#"await cheatEngine.javaHookMethod("{}", "{}"{});"#,
-                class_name, method_name, overload_str
+                r#"await cheatEngine.{method}("{}", "{}"{});"#,
+                class_name,
+                method_name,
+                overload_str,
+                method = protocol::JAVA_HOOK_METHOD,
             )
         }
 
@@ -386,8 +418,11 @@ fn generate_action_code(action: &FridaAction) -> String {
             method_name,
             return_value,
         } => format!(
-            r#"await cheatEngine.javaModifyReturn("{}", "{}", {});"#,
-            class_name, method_name, return_value
+            r#"await cheatEngine.{method}("{}", "{}", {});"#,
+            class_name,
+            method_name,
+            return_value,
+            method = protocol::JAVA_MODIFY_RETURN,
         ),
 
         FridaAction::JavaCallMethod {
@@ -395,8 +430,11 @@ fn generate_action_code(action: &FridaAction) -> String {
             method_name,
             is_static,
         } => format!(
-            r#"await cheatEngine.javaCallMethod("{}", "{}", {});"#,
-            class_name, method_name, is_static
+            r#"await cheatEngine.{method}("{}", "{}", {});"#,
+            class_name,
+            method_name,
+            is_static,
+            method = protocol::JAVA_CALL_METHOD,
         ),
 
         FridaAction::ObjcHookMethod {
@@ -404,8 +442,11 @@ fn generate_action_code(action: &FridaAction) -> String {
             selector,
             is_class_method,
         } => format!(
-            r#"await cheatEngine.objcHookMethod("{}", "{}", {});"#,
-            class_name, selector, is_class_method
+            r#"await cheatEngine.{method}("{}", "{}", {});"#,
+            class_name,
+            selector,
+            is_class_method,
+            method = protocol::OBJC_HOOK_METHOD,
         ),
 
         FridaAction::ObjcModifyReturn {
@@ -413,27 +454,32 @@ fn generate_action_code(action: &FridaAction) -> String {
             selector,
             return_value,
         } => format!(
-            r#"await cheatEngine.objcModifyReturn("{}", "{}", {});"#,
-            class_name, selector, return_value
+            r#"await cheatEngine.{method}("{}", "{}", {});"#,
+            class_name,
+            selector,
+            return_value,
+            method = protocol::OBJC_MODIFY_RETURN,
         ),
 
         FridaAction::SwiftHookFunction { mangled_name } => {
             format!(r#"await cheatEngine.swiftHookFunction("{}");"#, mangled_name)
         }
 
-        FridaAction::ListModules => {
-            r#"const modules = await cheatEngine.listModules();
-    console.log("Modules:", modules);"#
-                .to_string()
-        }
+        FridaAction::ListModules => format!(
+            r#"const modules = await cheatEngine.{method}();
+    console.log("Modules:", modules);"#,
+            method = protocol::LIST_MODULES,
+        ),
 
         FridaAction::FindExport {
             module_name,
             export_name,
         } => format!(
-            r#"const address = await cheatEngine.findExport("{}", "{}");
+            r#"const address = await cheatEngine.{method}("{}", "{}");
     console.log("Export address:", address);"#,
-            module_name, export_name
+            module_name,
+            export_name,
+            method = protocol::FIND_EXPORT,
         ),
 
         FridaAction::Custom { script } => format!(
@@ -444,6 +490,19 @@ fn generate_action_code(action: &FridaAction) -> String {
     }
 }
 
+/// Resolve the address when the handler runs, so module bases and pointer
+/// chains reflect the current process rather than the one seen at build time
+fn resolve_address_code(address: &Address) -> String {
+    if address.is_absolute() {
+        format!(r#"const address = "{}";"#, address)
+    } else {
+        format!(
+            "const address = await cheatEngine.resolveAddress({});",
+            address.to_spec()
+        )
+    }
+}
+
 fn value_type_to_str(vt: &ValueType) -> &'static str {
     match vt {
         ValueType::Int8 => "int8",
@@ -477,208 +536,14 @@ fn capitalize(s: &str) -> String {
     }
 }
 
-/// Generate Frida script that bundles all actions
+/// Generate the Frida script for a trainer: the target agent, whose trainer
+/// API backs every `cheatEngine` call emitted above
 pub fn generate_frida_script(project: &Project) -> String {
-    // Generate script with all necessary Frida operations
-    // NOTE: This intentionally provides code execution capabilities as it's
-    // the core functionality of a Frida-based trainer application.
     format!(
-        r#"// Auto-generated Frida script for {name}
-// Version: {version}
-
-'use strict';
-
-const memory = {{
-  freeze_intervals: new Map(),
-
-  read(address, type) {{
-    const p = ptr(address);
-    switch(type) {{
-      case 'int8': return p.readS8();
-      case 'uint8': return p.readU8();
-      case 'int16': return p.readS16();
-      case 'uint16': return p.readU16();
-      case 'int32': return p.readS32();
-      case 'uint32': return p.readU32();
-      case 'int64': return p.readS64();
-      case 'uint64': return p.readU64();
-      case 'float': return p.readFloat();
-      case 'double': return p.readDouble();
-      case 'pointer': return p.readPointer();
-      case 'string': return p.readUtf8String();
-      default: throw new Error('Unknown type: ' + type);
-    }}
-  }},
-
-  write(address, value, type) {{
-    const p = ptr(address);
-    switch(type) {{
-      case 'int8': p.writeS8(value); break;
-      case 'uint8': p.writeU8(value); break;
-      case 'int16': p.writeS16(value); break;
-      case 'uint16': p.writeU16(value); break;
-      case 'int32': p.writeS32(value); break;
-      case 'uint32': p.writeU32(value); break;
-      case 'int64': p.writeS64(value); break;
-      case 'uint64': p.writeU64(value); break;
-      case 'float': p.writeFloat(value); break;
-      case 'double': p.writeDouble(value); break;
-      case 'pointer': p.writePointer(value); break;
-      case 'string': p.writeUtf8String(value); break;
-      default: throw new Error('Unknown type: ' + type);
-    }}
-  }},
-
-  freeze(address, value, type, intervalMs) {{
-    this.unfreeze(address);
-    const interval = setInterval(() => {{
-      this.write(address, value, type);
-    }}, intervalMs);
-    this.freeze_intervals.set(address, interval);
-  }},
-
-  unfreeze(address) {{
-    const interval = this.freeze_intervals.get(address);
-    if (interval) {{
-      clearInterval(interval);
-      this.freeze_intervals.delete(address);
-    }}
-  }}
-}};
-
-const scanner = {{
-  patternScan(pattern, protection) {{
-    const results = [];
-    Process.enumerateRanges(protection).forEach(range => {{
-      const matches = Memory.scanSync(range.base, range.size, pattern);
-      results.push(...matches.map(m => m.address));
-    }});
-    return results;
-  }},
-
-  valueScan(value, type) {{
-    // Implementation depends on specific requirements
-    return [];
-  }}
-}};
-
-const hooks = {{
-  interceptors: new Map(),
-
-  hookFunction(address, options) {{
-    const p = ptr(address);
-    const interceptor = Interceptor.attach(p, {{
-      onEnter: options.logEnter ? function(args) {{
-        console.log('[ENTER]', address);
-      }} : undefined,
-      onLeave: options.logLeave ? function(retval) {{
-        console.log('[LEAVE]', address, '=', retval);
-      }} : undefined
-    }});
-    this.interceptors.set(address, interceptor);
-  }},
-
-  replaceReturn(address, value) {{
-    const p = ptr(address);
-    Interceptor.attach(p, {{
-      onLeave(retval) {{
-        retval.replace(ptr(value));
-      }}
-    }});
-  }},
-
-  nop(address) {{
-    const p = ptr(address);
-    Memory.protect(p, Process.pointerSize, 'rwx');
-    if (Process.arch === 'x64' || Process.arch === 'ia32') {{
-      p.writeByteArray([0x90]); // x86 NOP
-    }} else if (Process.arch === 'arm64') {{
-      p.writeByteArray([0x1f, 0x20, 0x03, 0xd5]); // ARM64 NOP
-    }}
-  }}
-}};
-
-// Java bridge (Android)
-const java = {{
-  hookMethod(className, methodName, overload) {{
-    Java.perform(() => {{
-      const clazz = Java.use(className);
-      const method = overload ? clazz[methodName].overload(overload) : clazz[methodName];
-      method.implementation = function(...args) {{
-        console.log('[Java]', className + '.' + methodName, args);
-        return method.apply(this, args);
-      }};
-    }});
-  }},
-
-  modifyReturn(className, methodName, value) {{
-    Java.perform(() => {{
-      const clazz = Java.use(className);
-      clazz[methodName].implementation = function() {{
-        return value;
-      }};
-    }});
-  }},
-
-  callMethod(className, methodName, isStatic) {{
-    Java.perform(() => {{
-      const clazz = Java.use(className);
-      if (isStatic) {{
-        return clazz[methodName]();
-      }} else {{
-        console.warn('Instance method call requires instance');
-      }}
-    }});
-  }}
-}};
-
-// ObjC bridge (iOS/macOS)
-const objc = {{
-  hookMethod(className, selector, isClassMethod) {{
-    const clazz = ObjC.classes[className];
-    const method = isClassMethod ? clazz['+ ' + selector] : clazz['- ' + selector];
-    Interceptor.attach(method.implementation, {{
-      onEnter(args) {{
-        console.log('[ObjC]', className, selector);
-      }}
-    }});
-  }},
-
-  modifyReturn(className, selector, value) {{
-    const clazz = ObjC.classes[className];
-    const method = clazz['- ' + selector];
-    Interceptor.attach(method.implementation, {{
-      onLeave(retval) {{
-        retval.replace(ObjC.Object(ptr(value)));
-      }}
-    }});
-  }}
-}};
-
-// Export API
-rpc.exports = {{
-  memoryRead: memory.read.bind(memory),
-  memoryWrite: memory.write.bind(memory),
-  memoryFreeze: memory.freeze.bind(memory),
-  memoryUnfreeze: memory.unfreeze.bind(memory),
-  patternScan: scanner.patternScan.bind(scanner),
-  valueScan: scanner.valueScan.bind(scanner),
-  hookFunction: hooks.hookFunction.bind(hooks),
-  replaceReturn: hooks.replaceReturn.bind(hooks),
-  nopFunction: hooks.nop.bind(hooks),
-  javaHookMethod: java.hookMethod.bind(java),
-  javaModifyReturn: java.modifyReturn.bind(java),
-  javaCallMethod: java.callMethod.bind(java),
-  objcHookMethod: objc.hookMethod.bind(objc),
-  objcModifyReturn: objc.modifyReturn.bind(objc),
-  listModules: () => Process.enumerateModules(),
-  findExport: (moduleName, exportName) => Module.findExportByName(moduleName, exportName)
-}};
-
-console.log('[Forvanced] Trainer loaded: {name}');
-"#,
-        name = project.name,
-        version = project.version,
+        "// Auto-generated Frida script for {}\n// Version: {}\n\n{}",
+        project.name,
+        project.version,
+        generate_target_script()
     )
 }
 
@@ -694,7 +559,7 @@ mod tests {
         btn.bindings.push(ActionBinding::new(
             ComponentEvent::OnClick,
             FridaAction::MemoryFreeze {
-                address: "0x12345678".to_string(),
+                address: "game.exe+0x1234".parse().unwrap(),
                 value: "999".to_string(),
                 value_type: ValueType::Int32,
                 interval_ms: 100,
@@ -706,6 +571,18 @@ mod tests {
         assert!(code.contains("Test Trainer"));
         assert!(code.contains("Freeze HP"));
         assert!(code.contains("memoryFreeze"));
+        assert!(code.contains("cheatEngine.resolveAddress("));
+        assert!(code.contains(r#""module":"game.exe""#));
+    }
+
+    #[test]
+    fn test_absolute_address_skips_resolution() {
+        let code = generate_action_code(&FridaAction::MemoryRead {
+            address: Address::absolute(0x1000),
+            value_type: ValueType::Int32,
+        });
+        assert!(code.contains(r#"const address = "0x1000";"#));
+        assert!(!code.contains("resolveAddress"));
     }
 
     #[test]
@@ -714,5 +591,6 @@ mod tests {
         let script = generate_frida_script(&project);
         assert!(script.contains("rpc.exports"));
         assert!(script.contains("memoryRead"));
+        assert!(script.contains("resolveAddress: (spec)"));
     }
 }
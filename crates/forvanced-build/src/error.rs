@@ -33,6 +33,9 @@ pub enum BuildError {
     #[error("Runtime template not found at: {0}")]
     RuntimeNotFound(PathBuf),
 
+    #[error("Payload error: {0}")]
+    Payload(String),
+
     #[error("Build cancelled")]
     Cancelled,
 
@@ -48,16 +51,16 @@ pub struct MissingToolsInfo {
 
 #[derive(Debug, Clone)]
 pub struct MissingTool {
-    pub name: &'static str,
-    pub description: &'static str,
+    pub name: String,
+    pub description: String,
     pub install_instructions: Vec<InstallInstruction>,
 }
 
 #[derive(Debug, Clone)]
 pub struct InstallInstruction {
-    pub platform: &'static str,
-    pub command: &'static str,
-    pub url: Option<&'static str>,
+    pub platform: String,
+    pub command: String,
+    pub url: Option<String>,
 }
 
 impl std::fmt::Display for MissingToolsInfo {
@@ -70,7 +73,7 @@ impl std::fmt::Display for MissingToolsInfo {
             writeln!(f, "❌ {} - {}", tool.name, tool.description)?;
             writeln!(f, "   설치 방법:")?;
             for inst in &tool.install_instructions {
-                if let Some(url) = inst.url {
+                if let Some(url) = &inst.url {
                     writeln!(f, "   • {}: {} ({})", inst.platform, inst.command, url)?;
                 } else {
                     writeln!(f, "   • {}: {}", inst.platform, inst.command)?;
@@ -1,7 +1,21 @@
+pub mod agent;
 mod builder;
+mod cache;
 pub mod codegen;
 mod error;
+pub mod manifest;
+mod package;
+pub mod preflight;
+pub mod progress;
+mod protect;
 mod template;
+pub mod validate;
 
-pub use builder::{BuildOptions, BuildOutput, Builder, BuildTarget};
-pub use error::BuildError;
+pub use agent::{compile_agent, CompiledAgent};
+pub use builder::{BuildOptions, BuildOutput, Builder, BuildTarget, RUNTIME_CONFIG_VERSION};
+pub use cache::{install_required, record_install};
+pub use error::{BuildError, InstallInstruction, MissingTool, MissingToolsInfo};
+pub use manifest::{verify_build, BuildManifest, VerifyReport};
+pub use package::{package_data_only, PackageOptions, PackageOutput, PayloadPlacement};
+pub use progress::{BuildEvent, BuildPhase, BuildProgress, LogLevel, PhaseTiming};
+pub use validate::{validate_project, Severity, ValidationIssue, ValidationReport};
@@ -0,0 +1,378 @@
+//! Build manifests and output verification
+//!
+//! Every build writes a manifest next to the generated project that records
+//! what went in (project hash, options, toolchain, embedded config digest) and
+//! what came out (output file hashes). [`verify_build`] re-checks a build
+//! directory against it, and [`BuildManifest::identify`] tells which build a
+//! distributed trainer file came from.
+
+use crate::error::BuildError;
+use forvanced_core::Project;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// File name of the manifest inside the generated project directory
+pub const MANIFEST_FILE: &str = "forvanced-manifest.json";
+
+/// Current manifest format
+pub const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildManifest {
+    pub manifest_version: u32,
+    /// Version of forvanced-build that produced the build
+    pub forvanced_version: String,
+    pub project: ProjectFingerprint,
+    pub toolchain: ToolchainVersions,
+    pub options: ManifestOptions,
+    /// SHA-256 of the embedded runtime config
+    pub config_digest: String,
+    pub outputs: Vec<OutputFile>,
+    /// Payload signing keys the runtime accepts in addition to its embedded
+    /// ones, from the project's `config.build.trusted_keys`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectFingerprint {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    /// SHA-256 of the project in canonical JSON form
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolchainVersions {
+    pub rustc: Option<String>,
+    pub cargo: Option<String>,
+    pub node: Option<String>,
+    pub tauri_cli: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestOptions {
+    /// Build target name, or `data-only` for payload packages
+    pub target: String,
+    pub release: bool,
+    pub bundle_frida: bool,
+    /// Config and target script were embedded sealed
+    #[serde(default)]
+    pub protected: bool,
+}
+
+/// A build output and its content hash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputFile {
+    /// Path relative to the manifest's directory when possible
+    pub path: String,
+    pub sha256: String,
+    pub size: u64,
+}
+
+impl BuildManifest {
+    /// Describe a finished build. `base_dir` is where the manifest will live;
+    /// output paths below it are stored relative to it.
+    pub fn new(
+        project: &Project,
+        options: ManifestOptions,
+        config_json: &str,
+        outputs: &[PathBuf],
+        base_dir: &Path,
+    ) -> Result<Self, BuildError> {
+        let outputs = outputs
+            .iter()
+            .map(|path| {
+                let (sha256, size) = hash_path(path)?;
+                let relative = path.strip_prefix(base_dir).unwrap_or(path);
+                Ok(OutputFile {
+                    path: relative.to_string_lossy().replace('\\', "/"),
+                    sha256,
+                    size,
+                })
+            })
+            .collect::<Result<Vec<_>, BuildError>>()?;
+
+        Ok(Self {
+            manifest_version: MANIFEST_VERSION,
+            forvanced_version: env!("CARGO_PKG_VERSION").to_string(),
+            project: ProjectFingerprint {
+                id: project.id.clone(),
+                name: project.name.clone(),
+                version: project.version.clone(),
+                hash: project_hash(project)?,
+            },
+            toolchain: ToolchainVersions::detect(),
+            options,
+            config_digest: sha256_hex(config_json.as_bytes()),
+            outputs,
+            trusted_keys: project.config.build.trusted_keys.clone(),
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, BuildError> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BuildError> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Find the output a distributed file matches, by content hash
+    pub fn identify(&self, artifact: &Path) -> Result<Option<&OutputFile>, BuildError> {
+        let (sha256, _) = hash_path(artifact)?;
+        Ok(self.outputs.iter().find(|o| o.sha256 == sha256))
+    }
+}
+
+impl ToolchainVersions {
+    /// Query installed tool versions; missing tools are recorded as `None`
+    pub fn detect() -> Self {
+        Self {
+            rustc: tool_version("rustc", &["--version"]),
+            cargo: tool_version("cargo", &["--version"]),
+            node: tool_version("node", &["--version"]),
+            tauri_cli: tool_version("cargo", &["tauri", "--version"]),
+        }
+    }
+}
+
+fn tool_version(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+}
+
+/// One problem found by [`verify_build`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum VerifyMismatch {
+    MissingOutput { path: String },
+    OutputHash { path: String, expected: String, actual: String },
+    ConfigDigest { expected: String, actual: String },
+    ProjectHash { expected: String, actual: String },
+}
+
+/// Result of [`verify_build`]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerifyReport {
+    pub mismatches: Vec<VerifyMismatch>,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Check a build against its manifest: output hashes, the embedded config in
+/// `<base_dir>/src-tauri/project_config.json` when present, and optionally the
+/// project it should have been built from
+pub fn verify_build(
+    manifest: &BuildManifest,
+    base_dir: &Path,
+    project: Option<&Project>,
+) -> Result<VerifyReport, BuildError> {
+    let mut report = VerifyReport::default();
+
+    for output in &manifest.outputs {
+        let path = base_dir.join(&output.path);
+        if !path.exists() {
+            report.mismatches.push(VerifyMismatch::MissingOutput {
+                path: output.path.clone(),
+            });
+            continue;
+        }
+        let (actual, _) = hash_path(&path)?;
+        if actual != output.sha256 {
+            report.mismatches.push(VerifyMismatch::OutputHash {
+                path: output.path.clone(),
+                expected: output.sha256.clone(),
+                actual,
+            });
+        }
+    }
+
+    let config_path = base_dir.join("src-tauri/project_config.json");
+    if config_path.exists() {
+        let actual = sha256_hex(&std::fs::read(&config_path)?);
+        if actual != manifest.config_digest {
+            report.mismatches.push(VerifyMismatch::ConfigDigest {
+                expected: manifest.config_digest.clone(),
+                actual,
+            });
+        }
+    }
+
+    if let Some(project) = project {
+        let actual = project_hash(project)?;
+        if actual != manifest.project.hash {
+            report.mismatches.push(VerifyMismatch::ProjectHash {
+                expected: manifest.project.hash.clone(),
+                actual,
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+/// Project fields that never reach the build output: the id is recorded in
+/// the fingerprint on its own, and the timestamps change on every save
+const UNHASHED_FIELDS: &[&str] = &["id", "created_at", "updated_at"];
+
+/// SHA-256 of the project's build-relevant content, with object keys sorted
+/// so map ordering in the serializer never changes the hash
+pub fn project_hash(project: &Project) -> Result<String, BuildError> {
+    let mut value = serde_json::to_value(project)?;
+    if let Some(fields) = value.as_object_mut() {
+        for field in UNHASHED_FIELDS {
+            fields.remove(*field);
+        }
+    }
+    let mut canonical = String::new();
+    write_canonical(&value, &mut canonical);
+    Ok(sha256_hex(canonical.as_bytes()))
+}
+
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<_> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Hash a file, or every file in a directory bundle (e.g. a macOS `.app`) in
+/// path order. Returns the hex digest and total size.
+fn hash_path(path: &Path) -> Result<(String, u64), BuildError> {
+    if path.is_file() {
+        let bytes = std::fs::read(path)?;
+        return Ok((sha256_hex(&bytes), bytes.len() as u64));
+    }
+
+    let mut hasher = Sha256::new();
+    let mut size = 0;
+    for entry in walkdir::WalkDir::new(path).sort_by_file_name() {
+        let entry = entry.map_err(|e| BuildError::Io(e.into()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(path).unwrap_or(entry.path());
+        let bytes = std::fs::read(entry.path())?;
+        hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+        hasher.update([0]);
+        hasher.update(&bytes);
+        size += bytes.len() as u64;
+    }
+    Ok((hex::encode(hasher.finalize()), size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> ManifestOptions {
+        ManifestOptions {
+            target: "linux-x64".to_string(),
+            release: true,
+            bundle_frida: true,
+            protected: false,
+        }
+    }
+
+    #[test]
+    fn test_manifest_verify_and_identify() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("target/release/trainer");
+        std::fs::create_dir_all(exe.parent().unwrap()).unwrap();
+        std::fs::write(&exe, b"binary v1").unwrap();
+        std::fs::create_dir_all(dir.path().join("src-tauri")).unwrap();
+        std::fs::write(dir.path().join("src-tauri/project_config.json"), "{}").unwrap();
+
+        let project = Project::new("Trainer");
+        let outputs = std::slice::from_ref(&exe);
+        let manifest = BuildManifest::new(&project, options(), "{}", outputs, dir.path()).unwrap();
+        assert_eq!(manifest.outputs[0].path, "target/release/trainer");
+        assert_eq!(manifest.outputs[0].size, 9);
+
+        let path = dir.path().join(MANIFEST_FILE);
+        manifest.save(&path).unwrap();
+        let manifest = BuildManifest::load(&path).unwrap();
+        assert!(verify_build(&manifest, dir.path(), Some(&project)).unwrap().is_ok());
+
+        let copy = dir.path().join("distributed");
+        std::fs::copy(&exe, &copy).unwrap();
+        assert!(manifest.identify(&copy).unwrap().is_some());
+
+        std::fs::write(&exe, b"binary v2").unwrap();
+        let mut changed = project.clone();
+        changed.version = "0.2.0".to_string();
+        let report = verify_build(&manifest, dir.path(), Some(&changed)).unwrap();
+        assert_eq!(report.mismatches.len(), 2);
+        assert!(matches!(report.mismatches[0], VerifyMismatch::OutputHash { .. }));
+        assert!(matches!(report.mismatches[1], VerifyMismatch::ProjectHash { .. }));
+    }
+
+    #[test]
+    fn test_project_hash_is_stable() {
+        let project = Project::new("Stable");
+        assert_eq!(project_hash(&project).unwrap(), project_hash(&project.clone()).unwrap());
+        let mut other = project.clone();
+        other.name = "Other".to_string();
+        assert_ne!(project_hash(&project).unwrap(), project_hash(&other).unwrap());
+    }
+
+    #[test]
+    fn test_project_hash_ignores_timestamps() {
+        let project = Project::new("Saved");
+        let mut resaved = project.clone();
+        resaved.created_at += 1000;
+        resaved.updated_at += 5000;
+        assert_eq!(project_hash(&project).unwrap(), project_hash(&resaved).unwrap());
+
+        resaved.config.build.bundle_frida = !resaved.config.build.bundle_frida;
+        assert_ne!(project_hash(&project).unwrap(), project_hash(&resaved).unwrap());
+    }
+}
@@ -0,0 +1,217 @@
+//! Data-only packaging
+//!
+//! Produces a trainer from a prebuilt runtime binary and a signed config
+//! payload, without generating or compiling anything. Only the project data
+//! changes between trainers, so no cargo, node or Tauri is needed.
+
+use crate::builder::{sanitize_name, ProjectConfig};
+use crate::error::BuildError;
+use crate::manifest::{BuildManifest, ManifestOptions};
+use crate::validate::validate_project;
+use forvanced_core::payload::{sidecar_path, ConfigPayload, SigningKey};
+use forvanced_core::Project;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Where the payload is stored relative to the runtime binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadPlacement {
+    /// Appended to the executable (single-file trainer)
+    Appended,
+    /// Written next to the executable as `<name>.fvcfg`
+    Sidecar,
+}
+
+/// Options for [`package_data_only`]
+#[derive(Debug, Clone)]
+pub struct PackageOptions {
+    /// Prebuilt generic runtime executable
+    pub runtime_binary: PathBuf,
+    /// Directory the trainer is written to
+    pub output_dir: PathBuf,
+    /// Executable file name without extension (defaults to the project name)
+    pub output_name: Option<String>,
+    pub placement: PayloadPlacement,
+}
+
+/// Result of data-only packaging
+#[derive(Debug, Clone)]
+pub struct PackageOutput {
+    pub executable: PathBuf,
+    /// Sidecar payload file, when not appended
+    pub payload: Option<PathBuf>,
+    /// Public key the payload was signed with
+    pub public_key: String,
+    /// Manifest describing this package (`<name>.manifest.json`)
+    pub manifest: PathBuf,
+}
+
+/// Package a project onto a prebuilt runtime binary
+pub async fn package_data_only(
+    project: &Project,
+    options: &PackageOptions,
+    key: &SigningKey,
+) -> Result<PackageOutput, BuildError> {
+    if project.ui.components.is_empty() {
+        return Err(BuildError::EmptyProject);
+    }
+    let report = validate_project(project);
+    if let Some(issue) = report.errors().next() {
+        return Err(BuildError::InvalidConfig(format!("{}: {}", issue.path, issue.message)));
+    }
+    if !options.runtime_binary.is_file() {
+        return Err(BuildError::RuntimeNotFound(options.runtime_binary.clone()));
+    }
+
+    let trusted = &project.config.build.trusted_keys;
+    if !trusted.is_empty()
+        && !trusted
+            .iter()
+            .any(|k| k.eq_ignore_ascii_case(&key.public_key_hex()))
+    {
+        return Err(BuildError::Payload(format!(
+            "signing key {} is not in the project's trusted keys",
+            key.public_key_hex()
+        )));
+    }
+
+    let config = serde_json::to_string(&ProjectConfig::from(project))?;
+    let payload = ConfigPayload::sign(config, key);
+
+    let name = options
+        .output_name
+        .clone()
+        .unwrap_or_else(|| sanitize_name(&project.name));
+    let mut executable = options.output_dir.join(name);
+    if let Some(ext) = options.runtime_binary.extension() {
+        executable.set_extension(ext);
+    }
+    tokio::fs::create_dir_all(&options.output_dir).await?;
+
+    let runtime = tokio::fs::read(&options.runtime_binary).await?;
+    let sidecar = match options.placement {
+        PayloadPlacement::Appended => {
+            let image = payload
+                .append_to(&runtime)
+                .map_err(|e| BuildError::Payload(e.to_string()))?;
+            tokio::fs::write(&executable, image).await?;
+            None
+        }
+        PayloadPlacement::Sidecar => {
+            tokio::fs::write(&executable, forvanced_core::payload::strip_appended(&runtime)).await?;
+            let path = sidecar_path(&executable);
+            let bytes = payload
+                .to_bytes()
+                .map_err(|e| BuildError::Payload(e.to_string()))?;
+            tokio::fs::write(&path, bytes).await?;
+            Some(path)
+        }
+    };
+    copy_permissions(&options.runtime_binary, &executable).await?;
+
+    let mut outputs = vec![executable.clone()];
+    outputs.extend(sidecar.clone());
+    let manifest = BuildManifest::new(
+        project,
+        ManifestOptions {
+            target: "data-only".to_string(),
+            release: true,
+            bundle_frida: true,
+            protected: false,
+        },
+        &payload.config,
+        &outputs,
+        &options.output_dir,
+    )?;
+    let manifest_path = executable.with_extension("manifest.json");
+    manifest.save(&manifest_path)?;
+
+    info!("Packaged {} (data-only)", executable.display());
+    Ok(PackageOutput {
+        executable,
+        payload: sidecar,
+        public_key: payload.public_key,
+        manifest: manifest_path,
+    })
+}
+
+async fn copy_permissions(from: &Path, to: &Path) -> Result<(), BuildError> {
+    let permissions = tokio::fs::metadata(from).await?.permissions();
+    tokio::fs::set_permissions(to, permissions).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forvanced_core::payload::find_payload;
+    use forvanced_core::{ComponentType, UIComponent};
+
+    fn project() -> Project {
+        let mut project = Project::new("Data Only");
+        project
+            .ui
+            .components
+            .push(UIComponent::new(ComponentType::Button, "Go", 0.0, 0.0));
+        project
+    }
+
+    #[tokio::test]
+    async fn test_package_data_only_appended_and_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = dir.path().join("forvanced-runtime.exe");
+        std::fs::write(&runtime, b"MZ runtime").unwrap();
+        let key = SigningKey::generate().unwrap();
+
+        let mut options = PackageOptions {
+            runtime_binary: runtime,
+            output_dir: dir.path().join("out"),
+            output_name: None,
+            placement: PayloadPlacement::Appended,
+        };
+        let output = package_data_only(&project(), &options, &key).await.unwrap();
+        assert_eq!(output.executable, dir.path().join("out/data-only.exe"));
+        assert!(output.payload.is_none());
+        let payload = find_payload(&output.executable).unwrap().unwrap();
+        let config: serde_json::Value =
+            serde_json::from_str(payload.verify(&[&key.public_key_hex()]).unwrap()).unwrap();
+        assert_eq!(config["name"], "Data Only");
+
+        options.placement = PayloadPlacement::Sidecar;
+        options.output_name = Some("trainer".to_string());
+        let output = package_data_only(&project(), &options, &key).await.unwrap();
+        assert_eq!(std::fs::read(&output.executable).unwrap(), b"MZ runtime");
+        assert_eq!(output.payload, Some(dir.path().join("out/trainer.fvcfg")));
+        assert!(find_payload(&output.executable).unwrap().is_some());
+
+        let manifest = crate::manifest::BuildManifest::load(&output.manifest).unwrap();
+        assert_eq!(output.manifest, dir.path().join("out/trainer.manifest.json"));
+        assert_eq!(manifest.outputs.len(), 2);
+        assert_eq!(manifest.options.target, "data-only");
+        assert!(manifest.trusted_keys.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_package_records_project_trusted_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = dir.path().join("forvanced-runtime");
+        std::fs::write(&runtime, b"ELF runtime").unwrap();
+        let key = SigningKey::generate().unwrap();
+        let options = PackageOptions {
+            runtime_binary: runtime,
+            output_dir: dir.path().join("out"),
+            output_name: None,
+            placement: PayloadPlacement::Appended,
+        };
+
+        let mut project = project();
+        project.config.build.trusted_keys = vec![SigningKey::generate().unwrap().public_key_hex()];
+        let err = package_data_only(&project, &options, &key).await.unwrap_err();
+        assert!(err.to_string().contains("not in the project's trusted keys"));
+
+        project.config.build.trusted_keys.push(key.public_key_hex().to_uppercase());
+        let output = package_data_only(&project, &options, &key).await.unwrap();
+        let manifest = crate::manifest::BuildManifest::load(&output.manifest).unwrap();
+        assert_eq!(manifest.trusted_keys, project.config.build.trusted_keys);
+    }
+}
@@ -0,0 +1,386 @@
+//! Cross-compilation preflight
+//!
+//! Checks what building for a [`BuildTarget`] needs beyond the base tools:
+//! the Rust standard library for the target triple, a linker or SDK that can
+//! produce binaries for it from this host, and a Frida devkit for the triple.
+//! Problems are reported as [`MissingTool`]s before the build starts, rather
+//! than as a cargo failure halfway through the compile phase.
+
+use crate::builder::BuildTarget;
+use crate::error::{InstallInstruction, MissingTool};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+/// Where `auto-download` fetches Frida devkits from
+const FRIDA_RELEASES_HOST: (&str, u16) = ("github.com", 443);
+
+/// Everything missing to build `target` on this machine
+pub fn check_target(
+    target: BuildTarget,
+    bundle_frida: bool,
+    release: bool,
+    project_dir: &Path,
+) -> Vec<MissingTool> {
+    let Some(host) = host_triple() else {
+        // No rustc; the base tool check already reports Rust as missing
+        return Vec::new();
+    };
+    let triple = match target.tauri_target() {
+        "" => host.clone(),
+        triple => triple.to_string(),
+    };
+
+    let mut missing = Vec::new();
+    if triple != host && !rust_target_installed(&triple) {
+        missing.push(rust_target_tool(&triple));
+    }
+    missing.extend(toolchain_missing(
+        &triple,
+        &host,
+        &|program| which::which(program).is_ok(),
+        &|var| std::env::var_os(var).is_some(),
+    ));
+    if bundle_frida {
+        let build_dir = cargo_build_dir(project_dir, target.tauri_target(), release);
+        missing.extend(frida_devkit_missing(&triple, &build_dir));
+    }
+    missing
+}
+
+/// Whether compiling for `triple` from `host` goes through cargo-xwin
+pub(crate) fn needs_xwin(triple: &str, host: &str) -> bool {
+    triple.ends_with("-windows-msvc") && !host.ends_with("-windows-msvc")
+}
+
+pub(crate) fn host_triple() -> Option<String> {
+    let output = Command::new("rustc").arg("-vV").output().ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .map(|host| host.trim().to_string())
+}
+
+fn rust_target_installed(triple: &str) -> bool {
+    let Ok(output) = Command::new("rustc").args(["--print", "sysroot"]).output() else {
+        return false;
+    };
+    let sysroot = String::from_utf8_lossy(&output.stdout);
+    target_in_sysroot(Path::new(sysroot.trim()), triple)
+}
+
+fn target_in_sysroot(sysroot: &Path, triple: &str) -> bool {
+    sysroot
+        .join("lib/rustlib")
+        .join(triple)
+        .join("lib")
+        .is_dir()
+}
+
+fn tool(name: &str, description: String, instructions: Vec<InstallInstruction>) -> MissingTool {
+    MissingTool {
+        name: name.to_string(),
+        description,
+        install_instructions: instructions,
+    }
+}
+
+fn instruction(platform: &str, command: String, url: Option<&str>) -> InstallInstruction {
+    InstallInstruction {
+        platform: platform.to_string(),
+        command,
+        url: url.map(String::from),
+    }
+}
+
+fn rust_target_tool(triple: &str) -> MissingTool {
+    tool(
+        "Rust target",
+        format!("{} 용 Rust 표준 라이브러리", triple),
+        vec![instruction(
+            "모든 플랫폼",
+            format!("rustup target add {}", triple),
+            Some("https://rust-lang.github.io/rustup/cross-compilation.html"),
+        )],
+    )
+}
+
+/// Linkers and SDKs needed to build and bundle for `triple` on `host`
+fn toolchain_missing(
+    triple: &str,
+    host: &str,
+    has_program: &dyn Fn(&str) -> bool,
+    has_env: &dyn Fn(&str) -> bool,
+) -> Vec<MissingTool> {
+    let mut missing = Vec::new();
+    let host_is_mac = host.contains("-apple-darwin");
+
+    if needs_xwin(triple, host) {
+        if !has_program("cargo-xwin") {
+            missing.push(tool(
+                "cargo-xwin",
+                format!("{} 링커 및 Windows SDK (MSVC 크로스 컴파일)", triple),
+                vec![instruction(
+                    "macOS/Linux",
+                    "cargo install --locked cargo-xwin".to_string(),
+                    Some("https://v2.tauri.app/distribute/windows-installer/#experimental-build-windows-apps-on-linux-and-macos"),
+                )],
+            ));
+        }
+        if !has_program("makensis") {
+            missing.push(tool(
+                "NSIS",
+                "Windows 설치 프로그램 번들러".to_string(),
+                vec![
+                    instruction("macOS", "brew install nsis".to_string(), None),
+                    instruction("Linux", "sudo apt install nsis".to_string(), None),
+                ],
+            ));
+        }
+        if !has_program("lld-link") && !has_program("clang-cl") {
+            missing.push(tool(
+                "LLVM",
+                "cargo-xwin 이 사용하는 lld-link / clang-cl".to_string(),
+                vec![
+                    instruction("macOS", "brew install llvm".to_string(), None),
+                    instruction("Linux", "sudo apt install lld llvm clang".to_string(), None),
+                ],
+            ));
+        }
+    } else if (triple.contains("-apple-darwin") || triple.contains("-apple-ios")) && triple != host
+    {
+        if !host_is_mac {
+            missing.push(tool(
+                "macOS SDK",
+                format!("{} 빌드에는 macOS 호스트의 Apple SDK 가 필요합니다", triple),
+                vec![instruction(
+                    "macOS",
+                    "이 타겟은 macOS 에서 빌드하세요".to_string(),
+                    None,
+                )],
+            ));
+        } else if !has_program("xcrun") {
+            missing.push(tool(
+                "Xcode Command Line Tools",
+                format!("{} 용 Apple SDK 및 링커", triple),
+                vec![instruction(
+                    "macOS",
+                    "xcode-select --install".to_string(),
+                    None,
+                )],
+            ));
+        }
+    } else if triple.contains("-linux-android") {
+        if !has_env("NDK_HOME") && !has_env("ANDROID_NDK_HOME") {
+            missing.push(tool(
+                "Android NDK",
+                format!("{} 링커 (NDK_HOME 또는 ANDROID_NDK_HOME 설정 필요)", triple),
+                vec![instruction(
+                    "모든 플랫폼",
+                    "sdkmanager \"ndk;27.0.12077973\" 설치 후 NDK_HOME 설정".to_string(),
+                    Some("https://v2.tauri.app/start/prerequisites/#android"),
+                )],
+            ));
+        }
+    } else if triple.contains("-linux-gnu") && triple != host {
+        let arch = triple.split('-').next().unwrap_or(triple);
+        let linker = format!("{}-linux-gnu-gcc", arch);
+        if !has_program(&linker) {
+            missing.push(tool(
+                "Linux cross linker",
+                format!("{} 링커 ({})", triple, linker),
+                vec![instruction(
+                    "Linux",
+                    format!("sudo apt install gcc-{}-linux-gnu", arch.replace('_', "-")),
+                    None,
+                )],
+            ));
+        }
+    }
+    missing
+}
+
+/// Platform name Frida publishes devkits under (`frida-core-devkit-<ver>-<platform>`)
+fn frida_devkit_platform(triple: &str) -> Option<&'static str> {
+    match triple {
+        "x86_64-pc-windows-msvc" => Some("windows-x86_64"),
+        "i686-pc-windows-msvc" => Some("windows-x86"),
+        "x86_64-apple-darwin" => Some("macos-x86_64"),
+        "aarch64-apple-darwin" => Some("macos-arm64"),
+        "x86_64-unknown-linux-gnu" => Some("linux-x86_64"),
+        "aarch64-unknown-linux-gnu" => Some("linux-arm64"),
+        "aarch64-linux-android" => Some("android-arm64"),
+        "aarch64-apple-ios" => Some("ios-arm64"),
+        _ => None,
+    }
+}
+
+fn frida_devkit_missing(triple: &str, build_dir: &Path) -> Option<MissingTool> {
+    let Some(platform) = frida_devkit_platform(triple) else {
+        return Some(tool(
+            "Frida devkit",
+            format!("{} 용 Frida devkit 이 배포되지 않습니다", triple),
+            vec![instruction(
+                "모든 플랫폼",
+                "Frida 번들 없이 빌드 (bundle_frida = false)".to_string(),
+                Some("https://github.com/frida/frida/releases"),
+            )],
+        ));
+    };
+    if devkit_cached(build_dir) || releases_reachable() {
+        return None;
+    }
+    Some(tool(
+        "Frida devkit",
+        format!(
+            "frida-core-devkit ({}) 를 다운로드할 수 없습니다 ({} 연결 실패)",
+            platform, FRIDA_RELEASES_HOST.0
+        ),
+        vec![instruction(
+            "모든 플랫폼",
+            "네트워크 연결을 확인한 뒤 다시 빌드하세요".to_string(),
+            Some("https://github.com/frida/frida/releases"),
+        )],
+    ))
+}
+
+/// Build script directory cargo uses for the generated project: per target
+/// triple (none for the host) and per profile, so a devkit unpacked by a
+/// build for another target or profile is not reused
+fn cargo_build_dir(project_dir: &Path, triple: &str, release: bool) -> PathBuf {
+    let crate_dir = project_dir.join("src-tauri");
+    let mut dir = match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(target_dir) => crate_dir.join(target_dir),
+        None => crate_dir.join("target"),
+    };
+    if !triple.is_empty() {
+        dir.push(triple);
+    }
+    dir.push(if release { "release" } else { "debug" });
+    dir.push("build");
+    dir
+}
+
+/// A previous build for the same target and profile already unpacked a devkit
+fn devkit_cached(build_dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(build_dir) else {
+        return false;
+    };
+    entries.filter_map(Result::ok).any(|entry| {
+        entry.file_name().to_string_lossy().starts_with("frida-sys-")
+            && entry.path().join("out").is_dir()
+    })
+}
+
+fn releases_reachable() -> bool {
+    let Ok(addrs) = FRIDA_RELEASES_HOST.to_socket_addrs() else {
+        return false;
+    };
+    addrs
+        .into_iter()
+        .any(|addr| TcpStream::connect_timeout(&addr, Duration::from_secs(3)).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINUX: &str = "x86_64-unknown-linux-gnu";
+    const MAC: &str = "aarch64-apple-darwin";
+
+    fn names(missing: &[MissingTool]) -> Vec<&str> {
+        missing.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_toolchain_requirements_per_target() {
+        let none = |_: &str| false;
+        let all = |_: &str| true;
+
+        let windows = toolchain_missing("x86_64-pc-windows-msvc", LINUX, &none, &none);
+        assert_eq!(names(&windows), ["cargo-xwin", "NSIS", "LLVM"]);
+        assert!(toolchain_missing("x86_64-pc-windows-msvc", LINUX, &all, &none).is_empty());
+        assert!(toolchain_missing(
+            "x86_64-pc-windows-msvc",
+            "x86_64-pc-windows-msvc",
+            &none,
+            &none
+        )
+        .is_empty());
+
+        assert_eq!(
+            names(&toolchain_missing(MAC, LINUX, &all, &all)),
+            ["macOS SDK"]
+        );
+        assert_eq!(
+            names(&toolchain_missing("x86_64-apple-darwin", MAC, &none, &none)),
+            ["Xcode Command Line Tools"]
+        );
+        assert!(toolchain_missing("x86_64-apple-darwin", MAC, &all, &none).is_empty());
+
+        let android = toolchain_missing("aarch64-linux-android", LINUX, &all, &none);
+        assert_eq!(names(&android), ["Android NDK"]);
+        assert!(toolchain_missing("aarch64-linux-android", LINUX, &none, &all).is_empty());
+
+        let linux = toolchain_missing(LINUX, "aarch64-unknown-linux-gnu", &none, &none);
+        assert!(linux[0].install_instructions[0]
+            .command
+            .contains("gcc-x86-64-linux-gnu"));
+        assert!(toolchain_missing(LINUX, LINUX, &none, &none).is_empty());
+    }
+
+    #[test]
+    fn test_every_target_has_a_frida_devkit() {
+        for target in [
+            BuildTarget::WindowsX64,
+            BuildTarget::MacOsX64,
+            BuildTarget::MacOsArm64,
+            BuildTarget::LinuxX64,
+            BuildTarget::Android,
+            BuildTarget::Ios,
+        ] {
+            assert!(
+                frida_devkit_platform(target.tauri_target()).is_some(),
+                "{:?}",
+                target
+            );
+        }
+        let missing = frida_devkit_missing("riscv64gc-unknown-linux-gnu", Path::new(".")).unwrap();
+        assert!(missing.description.contains("riscv64gc"));
+    }
+
+    #[test]
+    fn test_rust_target_and_devkit_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!target_in_sysroot(dir.path(), MAC));
+        std::fs::create_dir_all(dir.path().join("lib/rustlib").join(MAC).join("lib")).unwrap();
+        assert!(target_in_sysroot(dir.path(), MAC));
+        assert_eq!(
+            rust_target_tool(MAC).install_instructions[0].command,
+            "rustup target add aarch64-apple-darwin"
+        );
+
+        let release = cargo_build_dir(dir.path(), MAC, true);
+        let debug = cargo_build_dir(dir.path(), MAC, false);
+        let host = cargo_build_dir(dir.path(), "", true);
+        assert!(!devkit_cached(&release));
+        std::fs::create_dir_all(release.join("frida-sys-0123abcd/out")).unwrap();
+        assert!(devkit_cached(&release));
+
+        // Another profile or the host build unpack their own devkit
+        assert!(!devkit_cached(&debug));
+        assert!(!devkit_cached(&host));
+        if std::env::var_os("CARGO_TARGET_DIR").is_none() {
+            assert!(release.starts_with(dir.path().join("src-tauri/target").join(MAC)));
+            assert_eq!(host, dir.path().join("src-tauri/target/release/build"));
+        }
+    }
+
+    #[test]
+    fn test_current_target_needs_no_cross_tools() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = check_target(BuildTarget::Current, false, true, dir.path());
+        assert!(missing.is_empty(), "{:?}", names(&missing));
+    }
+}
@@ -0,0 +1,502 @@
+//! Build progress and cancellation
+//!
+//! [`Builder::build_with`](crate::Builder::build_with) runs a build as a
+//! fixed sequence of [`BuildPhase`]s and reports each one through
+//! [`BuildEvent`]s. Child process output is streamed line by line, and
+//! cancelling a [`BuildProgress`] kills the running child together with
+//! everything it started.
+
+use crate::error::BuildError;
+use serde::Serialize;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::sync::Notify;
+use tracing::warn;
+
+/// Steps of a build, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildPhase {
+    /// Sync the runtime template and embed the project
+    Generate,
+    /// Install frontend dependencies
+    Install,
+    /// Build the frontend and compile the runtime
+    Compile,
+    /// Package the compiled runtime into installers/bundles
+    Bundle,
+    /// Locate the outputs and write the build manifest
+    Collect,
+}
+
+impl BuildPhase {
+    pub const ALL: [BuildPhase; 5] = [
+        BuildPhase::Generate,
+        BuildPhase::Install,
+        BuildPhase::Compile,
+        BuildPhase::Bundle,
+        BuildPhase::Collect,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildPhase::Generate => "generate",
+            BuildPhase::Install => "install",
+            BuildPhase::Compile => "compile",
+            BuildPhase::Bundle => "bundle",
+            BuildPhase::Collect => "collect",
+        }
+    }
+
+    /// Rough overall progress (0-100) when this phase starts
+    pub fn progress(&self) -> u8 {
+        match self {
+            BuildPhase::Generate => 0,
+            BuildPhase::Install => 10,
+            BuildPhase::Compile => 25,
+            BuildPhase::Bundle => 80,
+            BuildPhase::Collect => 95,
+        }
+    }
+}
+
+impl std::fmt::Display for BuildPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Level for a line a child process wrote to stderr
+    ///
+    /// Only the diagnostic prefix counts (rustc's `error:`/`error[E..]:` and
+    /// `warning:`, npm's `ERR!`/`WARN`, the Tauri CLI's `Error`/`Warn`); crate
+    /// names such as `thiserror` or `error-chain` elsewhere on the line don't.
+    fn classify(line: &str) -> Self {
+        let line = line.trim_start();
+        let has_prefix = |prefixes: &[&str]| prefixes.iter().any(|p| line.starts_with(p));
+        if has_prefix(&["error:", "error[", "npm ERR!", "npm error ", "Error "]) {
+            LogLevel::Error
+        } else if has_prefix(&["warning:", "warning[", "npm WARN ", "npm warn ", "Warn "]) {
+            LogLevel::Warn
+        } else {
+            LogLevel::Info
+        }
+    }
+}
+
+/// Progress reported while a build runs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BuildEvent {
+    PhaseStarted {
+        phase: BuildPhase,
+        /// Overall progress (0-100)
+        progress: u8,
+    },
+    PhaseFinished {
+        phase: BuildPhase,
+        duration_ms: u64,
+    },
+    /// The phase had nothing to do (e.g. dependencies unchanged)
+    PhaseSkipped {
+        phase: BuildPhase,
+        reason: String,
+    },
+    /// A build message or a line of child process output
+    Log {
+        phase: BuildPhase,
+        level: LogLevel,
+        message: String,
+    },
+}
+
+/// How long a phase took
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTiming {
+    pub phase: BuildPhase,
+    #[serde(serialize_with = "serialize_millis")]
+    pub duration: Duration,
+}
+
+fn serialize_millis<S: serde::Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u64(d.as_millis() as u64)
+}
+
+type EventHandler = Arc<dyn Fn(&BuildEvent) + Send + Sync>;
+
+#[derive(Default)]
+struct CancelState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// Event sink and cancellation handle for one build
+///
+/// Clones share the cancellation state, so a clone can be kept elsewhere to
+/// cancel the build from another task. Without an event handler, child
+/// process output goes to stderr.
+#[derive(Clone, Default)]
+pub struct BuildProgress {
+    handler: Option<EventHandler>,
+    cancel: Arc<CancelState>,
+}
+
+impl std::fmt::Debug for BuildProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuildProgress")
+            .field("cancelled", &self.is_cancelled())
+            .finish_non_exhaustive()
+    }
+}
+
+impl BuildProgress {
+    pub fn new(handler: impl Fn(&BuildEvent) + Send + Sync + 'static) -> Self {
+        Self {
+            handler: Some(Arc::new(handler)),
+            cancel: Arc::default(),
+        }
+    }
+
+    /// Stop the build; a running child process is killed
+    pub fn cancel(&self) {
+        self.cancel.cancelled.store(true, Ordering::SeqCst);
+        self.cancel.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once [`BuildProgress::cancel`] has been called
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.cancel.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    pub(crate) fn check_cancelled(&self) -> Result<(), BuildError> {
+        if self.is_cancelled() {
+            Err(BuildError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn emit(&self, event: BuildEvent) {
+        if let Some(handler) = &self.handler {
+            handler(&event);
+        }
+    }
+
+    pub(crate) fn log(&self, phase: BuildPhase, level: LogLevel, message: impl Into<String>) {
+        self.emit(BuildEvent::Log {
+            phase,
+            level,
+            message: message.into(),
+        });
+    }
+
+    /// Output of a child process: to the handler, or to stderr without one
+    fn output(&self, phase: BuildPhase, level: LogLevel, line: String) {
+        if self.handler.is_some() {
+            self.log(phase, level, line);
+        } else {
+            eprintln!("{}", line);
+        }
+    }
+
+    /// Run one phase, emitting its start/finish events and recording its time
+    pub(crate) async fn phase<T, F>(
+        &self,
+        phase: BuildPhase,
+        timings: &mut Vec<PhaseTiming>,
+        run: F,
+    ) -> Result<T, BuildError>
+    where
+        F: std::future::Future<Output = Result<T, BuildError>>,
+    {
+        self.check_cancelled()?;
+        self.emit(BuildEvent::PhaseStarted {
+            phase,
+            progress: phase.progress(),
+        });
+        let started = Instant::now();
+        let result = run.await;
+        let duration = started.elapsed();
+        if let Err(e) = &result {
+            self.log(phase, LogLevel::Error, e.to_string());
+            return result;
+        }
+        timings.push(PhaseTiming { phase, duration });
+        self.emit(BuildEvent::PhaseFinished {
+            phase,
+            duration_ms: duration.as_millis() as u64,
+        });
+        result
+    }
+
+    /// Run a child process to completion, streaming its output. Cancellation
+    /// kills the process and its descendants.
+    pub(crate) async fn run_command(
+        &self,
+        phase: BuildPhase,
+        program: &str,
+        args: &[&str],
+        cwd: &Path,
+    ) -> Result<(), BuildError> {
+        self.check_cancelled()?;
+        let command_line = format!("{} {}", program, args.join(" "));
+        self.log(phase, LogLevel::Debug, format!("Running: {}", command_line));
+
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .current_dir(cwd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        // Own process group, so the whole tree can be killed on cancel
+        #[cfg(unix)]
+        command.process_group(0);
+
+        let mut child = command.spawn().map_err(|e| {
+            BuildError::CommandFailed(format!("{} failed to start: {}", command_line, e))
+        })?;
+        let pid = child.id();
+
+        let stdout = child
+            .stdout
+            .take()
+            .map(|out| tokio::spawn(self.clone().forward(phase, out, false)));
+        let stderr = child
+            .stderr
+            .take()
+            .map(|err| tokio::spawn(self.clone().forward(phase, err, true)));
+
+        let status = tokio::select! {
+            status = child.wait() => status?,
+            _ = self.cancelled() => {
+                warn!("Build cancelled, killing {}", command_line);
+                if let Some(pid) = pid {
+                    kill_process_tree(pid);
+                }
+                let _ = child.kill().await;
+                self.log(phase, LogLevel::Warn, "Build cancelled");
+                return Err(BuildError::Cancelled);
+            }
+        };
+        for task in stdout.into_iter().chain(stderr) {
+            let _ = task.await;
+        }
+
+        if !status.success() {
+            return Err(BuildError::CommandFailed(format!(
+                "{} exited with {}",
+                command_line, status
+            )));
+        }
+        Ok(())
+    }
+
+    async fn forward(self, phase: BuildPhase, stream: impl AsyncRead + Unpin, stderr: bool) {
+        let mut lines = BufReader::new(stream).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let level = if stderr {
+                LogLevel::classify(&line)
+            } else {
+                LogLevel::Info
+            };
+            self.output(phase, level, line);
+        }
+    }
+}
+
+/// Kill a process and everything it started
+pub fn kill_process_tree(pid: u32) {
+    #[cfg(unix)]
+    {
+        // Children run in their own process group (see `run_command`)
+        let _ = std::process::Command::new("kill")
+            .args(["-TERM", "--", &format!("-{}", pid)])
+            .status();
+        let _ = std::process::Command::new("kill")
+            .args(["-TERM", &pid.to_string()])
+            .status();
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = std::process::Command::new("taskkill")
+            .args(["/F", "/T", "/PID", &pid.to_string()])
+            .status();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_classify_uses_the_diagnostic_prefix() {
+        for line in [
+            "error[E0425]: cannot find value `x` in this scope",
+            "error: could not compile `trainer` (lib) due to 1 previous error",
+            "npm ERR! code E404",
+            "npm error code ERESOLVE",
+            "       Error failed to bundle project",
+        ] {
+            assert_eq!(LogLevel::classify(line), LogLevel::Error, "{}", line);
+        }
+        for line in [
+            "warning: unused variable: `x`",
+            "warning: `trainer` (lib) generated 1 warning",
+            "npm WARN deprecated inflight@1.0.6",
+            "        Warn Waiting for your frontend dev server to start",
+        ] {
+            assert_eq!(LogLevel::classify(line), LogLevel::Warn, "{}", line);
+        }
+        for line in [
+            "   Compiling thiserror v1.0.69",
+            "   Compiling anyhow v1.0.86 (depends on error-chain)",
+            "  Downloaded error-chain v0.12.4",
+            "   Compiling warnings v0.2.1",
+            "    Finished `release` profile [optimized] target(s) in 1m 02s",
+            "src/error.rs:12: handled",
+        ] {
+            assert_eq!(LogLevel::classify(line), LogLevel::Info, "{}", line);
+        }
+    }
+
+    fn recording() -> (BuildProgress, Arc<Mutex<Vec<BuildEvent>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let progress = BuildProgress::new(move |e| sink.lock().unwrap().push(e.clone()));
+        (progress, events)
+    }
+
+    #[tokio::test]
+    async fn test_run_command_streams_output() {
+        let (progress, events) = recording();
+        let dir = tempfile::tempdir().unwrap();
+        progress
+            .run_command(
+                BuildPhase::Install,
+                "sh",
+                &["-c", "echo installed; echo 'warning: slow' >&2"],
+                dir.path(),
+            )
+            .await
+            .unwrap();
+
+        let log = |level, message: &str| BuildEvent::Log {
+            phase: BuildPhase::Install,
+            level,
+            message: message.to_string(),
+        };
+        let logged = events.lock().unwrap().clone();
+        assert!(logged.contains(&log(LogLevel::Info, "installed")));
+        assert!(logged.contains(&log(LogLevel::Warn, "warning: slow")));
+
+        let err = progress
+            .run_command(BuildPhase::Install, "sh", &["-c", "exit 3"], dir.path())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BuildError::CommandFailed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_kills_child_tree() {
+        let (progress, events) = recording();
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("grandchild-ran");
+        // The grandchild would create the marker if it outlived the cancel
+        let script = format!("(sleep 1; touch {}) & wait", marker.display());
+
+        let canceller = progress.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            canceller.cancel();
+        });
+        let started = Instant::now();
+        let mut timings = Vec::new();
+        let result = progress
+            .phase(BuildPhase::Compile, &mut timings, async {
+                progress
+                    .run_command(BuildPhase::Compile, "sh", &["-c", &script], dir.path())
+                    .await
+            })
+            .await;
+        assert!(matches!(result, Err(BuildError::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(timings.is_empty());
+
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+        assert!(!marker.exists());
+
+        // Later phases refuse to start
+        let next = progress
+            .phase(BuildPhase::Bundle, &mut timings, async { Ok(()) })
+            .await;
+        assert!(matches!(next, Err(BuildError::Cancelled)));
+        let events = events.lock().unwrap();
+        assert_eq!(
+            events.first(),
+            Some(&BuildEvent::PhaseStarted {
+                phase: BuildPhase::Compile,
+                progress: 25
+            })
+        );
+        assert!(!events.iter().any(|e| matches!(
+            e,
+            BuildEvent::PhaseStarted {
+                phase: BuildPhase::Bundle,
+                ..
+            }
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_phase_records_timing() {
+        let (progress, events) = recording();
+        let mut timings = Vec::new();
+        let value = progress
+            .phase(BuildPhase::Collect, &mut timings, async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(7)
+            })
+            .await
+            .unwrap();
+        assert_eq!(value, 7);
+        assert_eq!(timings[0].phase, BuildPhase::Collect);
+        assert!(timings[0].duration >= Duration::from_millis(20));
+        assert!(matches!(
+            events.lock().unwrap().last(),
+            Some(BuildEvent::PhaseFinished {
+                phase: BuildPhase::Collect,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,159 @@
+//! Protected builds
+//!
+//! With `BuildOptions::protect` the runtime config and the target script are
+//! embedded sealed (see [`forvanced_core::protect`]) instead of as plain text,
+//! and the generated `lib.rs` refuses to start when either fails to open. No
+//! plaintext copy of either is left in the generated project.
+
+use crate::cache::BuildCache;
+use crate::error::BuildError;
+use forvanced_core::protect::{seal, SealKey};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Sealed runtime config, relative to the generated project
+pub(crate) const SEALED_CONFIG: &str = "src-tauri/project_config.sealed";
+
+/// Sealed target script, relative to the generated project
+pub(crate) const SEALED_SCRIPT: &str = "src-tauri/target_script.sealed";
+
+/// Key shares for the generated `lib.rs` (see [`SealKey::split`])
+pub(crate) type KeyShares = ([u8; 32], [u8; 32]);
+
+/// Freshly sealed files, whose key shares still have to go into `lib.rs`
+pub(crate) struct Sealed {
+    pub shares: KeyShares,
+    digest: String,
+}
+
+impl Sealed {
+    /// Record the sealed files as current. Call this once `lib.rs` holds the
+    /// shares; until then the next generation seals again rather than keeping
+    /// a `lib.rs` with another key.
+    pub(crate) fn commit(self, project_dir: &Path) -> Result<(), BuildError> {
+        let mut cache = BuildCache::load(project_dir);
+        cache.sealed_digest = Some(self.digest);
+        cache.save(project_dir)
+    }
+}
+
+/// Seal the config and target script into the generated project. Returns the
+/// sealed files for `lib.rs`, or `None` when the sealed files from the last
+/// committed generation still match, so an unchanged project does not trigger
+/// a rebuild.
+pub(crate) fn seal_embedded(
+    project_dir: &Path,
+    config_json: &str,
+    target_script: &str,
+) -> Result<Option<Sealed>, BuildError> {
+    let mut hasher = Sha256::new();
+    hasher.update(config_json.as_bytes());
+    hasher.update([0]);
+    hasher.update(target_script.as_bytes());
+    let digest = hex::encode(hasher.finalize());
+
+    let mut cache = BuildCache::load(project_dir);
+    let sealed_present = [SEALED_CONFIG, SEALED_SCRIPT]
+        .iter()
+        .all(|p| project_dir.join(p).exists());
+    if sealed_present && cache.sealed_digest.as_deref() == Some(digest.as_str()) {
+        return Ok(None);
+    }
+
+    // The files are about to change under the current lib.rs
+    if cache.sealed_digest.take().is_some() {
+        cache.save(project_dir)?;
+    }
+
+    let payload_error = |e: forvanced_core::CoreError| BuildError::Payload(e.to_string());
+    let key = SealKey::generate().map_err(payload_error)?;
+    std::fs::write(
+        project_dir.join(SEALED_CONFIG),
+        seal(&key, "config", config_json.as_bytes()).map_err(payload_error)?,
+    )?;
+    std::fs::write(
+        project_dir.join(SEALED_SCRIPT),
+        seal(&key, "script", target_script.as_bytes()).map_err(payload_error)?,
+    )?;
+
+    Ok(Some(Sealed {
+        shares: key.split().map_err(payload_error)?,
+        digest,
+    }))
+}
+
+/// Drop sealed files left over from an earlier protected generation
+pub(crate) fn remove_sealed(project_dir: &Path) -> Result<(), BuildError> {
+    for path in [SEALED_CONFIG, SEALED_SCRIPT] {
+        let path = project_dir.join(path);
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    let mut cache = BuildCache::load(project_dir);
+    if cache.sealed_digest.take().is_some() {
+        cache.save(project_dir)?;
+    }
+    Ok(())
+}
+
+/// Rust array literal for a key share
+pub(crate) fn byte_array(bytes: &[u8; 32]) -> String {
+    let items: Vec<String> = bytes.iter().map(|b| format!("0x{:02x}", b)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forvanced_core::protect::{open_str, SealKey};
+
+    #[test]
+    fn test_seal_embedded_is_incremental() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src-tauri")).unwrap();
+
+        let sealed = seal_embedded(dir.path(), "{}", "rpc.exports = {};")
+            .unwrap()
+            .unwrap();
+        let (masked, mask) = sealed.shares;
+        let key = SealKey::from_shares(&masked, &mask);
+        let script = std::fs::read(dir.path().join(SEALED_SCRIPT)).unwrap();
+        assert_eq!(
+            open_str(&key, "script", &script).unwrap(),
+            "rpc.exports = {};"
+        );
+        sealed.commit(dir.path()).unwrap();
+
+        assert!(seal_embedded(dir.path(), "{}", "rpc.exports = {};")
+            .unwrap()
+            .is_none());
+        let changed = seal_embedded(dir.path(), "{\"a\":1}", "rpc.exports = {};").unwrap();
+        assert!(changed.is_some());
+
+        remove_sealed(dir.path()).unwrap();
+        assert!(!dir.path().join(SEALED_CONFIG).exists());
+        assert!(seal_embedded(dir.path(), "{\"a\":1}", "rpc.exports = {};")
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_uncommitted_seal_is_redone() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src-tauri")).unwrap();
+
+        seal_embedded(dir.path(), "{}", "")
+            .unwrap()
+            .unwrap()
+            .commit(dir.path())
+            .unwrap();
+        // lib.rs never got the new shares, e.g. its generation failed
+        assert!(seal_embedded(dir.path(), "{\"a\":1}", "")
+            .unwrap()
+            .is_some());
+        // Going back to the committed input must not keep lib.rs either: the
+        // files on disk were sealed with another key since
+        assert!(seal_embedded(dir.path(), "{}", "").unwrap().is_some());
+    }
+}
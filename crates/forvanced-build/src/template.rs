@@ -236,6 +236,10 @@ class CheatEngine {
     }
   }
 
+  async resolveAddress(spec: unknown): Promise<string> {
+    return invoke("resolve_address", { spec });
+  }
+
   async memoryRead(address: string, valueType: string): Promise<unknown> {
     return invoke("memory_read", { address, valueType });
   }
@@ -516,6 +520,7 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             engine::attach_process,
             engine::detach_process,
+            engine::resolve_address,
             engine::memory_read,
             engine::memory_write,
             engine::memory_freeze,
@@ -0,0 +1,186 @@
+//! Pre-build project validation
+//!
+//! Collects every problem in a project instead of stopping at the first one,
+//! so the CLI and the Builder app can show a complete report.
+
+use forvanced_core::structs::validate_structs;
+use forvanced_core::{Address, Project};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// How serious a validation issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The project cannot be built
+    Error,
+    /// The project builds but probably won't behave as intended
+    Warning,
+}
+
+/// A single validation finding
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    /// Location in the project, e.g. `scripts[0].nodes[3]`
+    pub path: String,
+    pub message: String,
+}
+
+/// Result of validating a project
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        !self.issues.iter().any(|i| i.severity == Severity::Error)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|i| i.severity == Severity::Error)
+    }
+
+    fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ValidationIssue {
+            severity: Severity::Error,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ValidationIssue {
+            severity: Severity::Warning,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Validate a project before generating or building it
+pub fn validate_project(project: &Project) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    if project.name.trim().is_empty() {
+        report.error("name", "Project name is empty");
+    }
+
+    if project.ui.components.is_empty() {
+        report.error("ui.components", "Project has no components to build");
+    }
+
+    let mut component_ids = HashSet::new();
+    for (i, component) in project.ui.components.iter().enumerate() {
+        if !component_ids.insert(component.id.as_str()) {
+            report.error(
+                format!("ui.components[{}]", i),
+                format!("Duplicate component id '{}'", component.id),
+            );
+        }
+    }
+
+    if let Err(e) = validate_structs(&project.structs) {
+        report.error("structs", e.to_string());
+    }
+    let struct_names: HashSet<&str> = project.structs.iter().map(|s| s.name.as_str()).collect();
+
+    for (si, script) in project.scripts.iter().enumerate() {
+        let mut node_ids = HashSet::new();
+        for (ni, node) in script.nodes.iter().enumerate() {
+            let path = format!("scripts[{}].nodes[{}]", si, ni);
+            if !node_ids.insert(node.id.as_str()) {
+                report.error(&path, format!("Duplicate node id '{}'", node.id));
+            }
+
+            if matches!(node.node_type.as_str(), "struct_read" | "struct_write") {
+                match node.config.get("structName").and_then(|v| v.as_str()) {
+                    Some(name) if struct_names.contains(name) => {}
+                    Some(name) => report.error(&path, format!("Unknown struct '{}'", name)),
+                    None => report.error(&path, format!("{} requires structName", node.node_type)),
+                }
+            }
+
+            if let Some(text) = node.config.get("address").and_then(|v| v.as_str()) {
+                if !text.is_empty() {
+                    if let Err(e) = text.parse::<Address>() {
+                        report.error(&path, e.to_string());
+                    }
+                }
+            }
+        }
+
+        for (ci, connection) in script.connections.iter().enumerate() {
+            for node_id in [&connection.from_node_id, &connection.to_node_id] {
+                if !node_ids.contains(node_id.as_str()) {
+                    report.error(
+                        format!("scripts[{}].connections[{}]", si, ci),
+                        format!("Connection references missing node '{}'", node_id),
+                    );
+                }
+            }
+        }
+    }
+
+    let target = &project.config.target;
+    if target.auto_attach && target.process_name.is_none() && target.process_patterns.is_empty() {
+        report.warning(
+            "config.target",
+            "auto_attach is enabled but no process name or pattern is set",
+        );
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forvanced_core::project::{ScriptNode, VisualScript};
+    use forvanced_core::{ComponentType, UIComponent};
+
+    fn node(id: &str, node_type: &str, config: serde_json::Value) -> ScriptNode {
+        ScriptNode {
+            id: id.to_string(),
+            node_type: node_type.to_string(),
+            label: node_type.to_string(),
+            x: 0.0,
+            y: 0.0,
+            config: config.as_object().cloned().unwrap_or_default(),
+            inputs: vec![],
+            outputs: vec![],
+        }
+    }
+
+    #[test]
+    fn test_validate_project_reports_all_errors() {
+        let mut project = Project::new("Test");
+        let mut script = VisualScript::new("Main");
+        script.nodes.push(node("a", "struct_read", serde_json::json!({ "structName": "Missing" })));
+        script.nodes.push(node("b", "resolve_address", serde_json::json!({ "address": "[bad" })));
+        project.scripts.push(script);
+
+        let report = validate_project(&project);
+        assert!(!report.is_ok());
+        let messages: Vec<_> = report.errors().map(|i| i.message.as_str()).collect();
+        assert!(messages.iter().any(|m| m.contains("no components")));
+        assert!(messages.iter().any(|m| m.contains("Unknown struct 'Missing'")));
+        assert!(messages.iter().any(|m| m.contains("Invalid address")));
+    }
+
+    #[test]
+    fn test_validate_project_warnings_do_not_fail() {
+        let mut project = Project::new("Test");
+        project
+            .ui
+            .components
+            .push(UIComponent::new(ComponentType::Button, "Go", 0.0, 0.0));
+        project.config.target.auto_attach = true;
+
+        let report = validate_project(&project);
+        assert!(report.is_ok());
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].severity, Severity::Warning);
+    }
+}
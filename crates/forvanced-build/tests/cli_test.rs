@@ -0,0 +1,104 @@
+//! Exit codes and output of the `forvanced` binary
+
+#![cfg(feature = "cli")]
+
+use forvanced_core::project::{ComponentType, UIComponent};
+use forvanced_core::Project;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn forvanced(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_forvanced"))
+        .args(args)
+        // Android builds need an NDK, so without one the preflight always fails
+        .env_remove("NDK_HOME")
+        .env_remove("ANDROID_NDK_HOME")
+        .output()
+        .expect("failed to run forvanced")
+}
+
+fn stdout_json(output: &Output) -> serde_json::Value {
+    serde_json::from_slice(&output.stdout).expect("stdout is not a JSON object")
+}
+
+fn runtime_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../apps/runtime")
+}
+
+fn write_project(dir: &Path) -> PathBuf {
+    let path = dir.join("game.json");
+    let mut project = Project::new("Cli Test");
+    project
+        .ui
+        .components
+        .push(UIComponent::new(ComponentType::Button, "Go", 0.0, 0.0));
+    std::fs::write(&path, serde_json::to_string(&project).unwrap()).unwrap();
+    path
+}
+
+#[test]
+fn test_validate_succeeds() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = write_project(dir.path());
+    let output = forvanced(&["validate", project.to_str().unwrap()]);
+    assert!(output.status.success());
+    let json = stdout_json(&output);
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["project"], "Cli Test");
+}
+
+#[test]
+fn test_preflight_failure_exits_non_zero() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = write_project(dir.path());
+    let out = dir.path().join("dist");
+    let runtime = runtime_path();
+    let output = forvanced(&[
+        "build",
+        project.to_str().unwrap(),
+        "--target",
+        "android",
+        "--no-bundle-frida",
+        "--out",
+        out.to_str().unwrap(),
+        "--runtime",
+        runtime.to_str().unwrap(),
+    ]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let json = stdout_json(&output);
+    assert_eq!(json["ok"], false);
+    assert_eq!(json["command"], "build");
+    let missing: Vec<&str> = json["missingTools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert!(missing.contains(&"Android NDK"), "{:?}", missing);
+    // Nothing is generated when the preflight fails
+    assert!(!out.exists());
+}
+
+#[test]
+fn test_usage_errors_exit_non_zero() {
+    let output = forvanced(&["build"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let dir = tempfile::tempdir().unwrap();
+    let empty = dir.path().join("empty.json");
+    std::fs::write(
+        &empty,
+        serde_json::to_string(&Project::new("Empty")).unwrap(),
+    )
+    .unwrap();
+    let output = forvanced(&["validate", empty.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout_json(&output)["ok"], false);
+
+    let missing = dir.path().join("missing.json");
+    let output = forvanced(&["validate", missing.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout_json(&output)["command"], "load");
+}
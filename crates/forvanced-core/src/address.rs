@@ -0,0 +1,359 @@
+//! Target addresses that survive ASLR
+//!
+//! An [`Address`] is stored in project files as text and parsed on load:
+//!
+//! - `0x7ff6a1b2c3d0` – absolute address
+//! - `game.exe+0x1a2b` – module base plus offset
+//! - `libc.so!malloc` / `!malloc` – export (in a module, or any module)
+//! - `sym:Player::update` – debug symbol
+//! - `[[game.exe+0x10]+0x20]+0x8` – pointer chain: each `[...]` dereferences
+//!
+//! Everything except absolute addresses is resolved in the target when the
+//! action runs, using the JSON spec produced by [`Address::to_spec`].
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::CoreError;
+
+/// Where address resolution starts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressBase {
+    Absolute(u64),
+    Module(String),
+    Export {
+        module: Option<String>,
+        name: String,
+    },
+    Symbol(String),
+}
+
+/// A parsed address: `base + offset`, then one dereference per `chain` entry
+/// (`addr = read_pointer(addr) + chain[i]`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Address {
+    pub base: AddressBase,
+    pub offset: i64,
+    pub chain: Vec<i64>,
+}
+
+impl Address {
+    pub fn absolute(address: u64) -> Self {
+        Self {
+            base: AddressBase::Absolute(address),
+            offset: 0,
+            chain: Vec::new(),
+        }
+    }
+
+    pub fn module(module: impl Into<String>, offset: i64) -> Self {
+        Self {
+            base: AddressBase::Module(module.into()),
+            offset,
+            chain: Vec::new(),
+        }
+    }
+
+    pub fn export(module: Option<String>, name: impl Into<String>) -> Self {
+        Self {
+            base: AddressBase::Export {
+                module,
+                name: name.into(),
+            },
+            offset: 0,
+            chain: Vec::new(),
+        }
+    }
+
+    pub fn symbol(name: impl Into<String>) -> Self {
+        Self {
+            base: AddressBase::Symbol(name.into()),
+            offset: 0,
+            chain: Vec::new(),
+        }
+    }
+
+    /// Dereference the current address and add `offset`
+    pub fn deref(mut self, offset: i64) -> Self {
+        self.chain.push(offset);
+        self
+    }
+
+    /// True if the address needs no resolution in the target
+    pub fn is_absolute(&self) -> bool {
+        matches!(self.base, AddressBase::Absolute(_)) && self.chain.is_empty()
+    }
+
+    /// JSON form understood by the target-side `resolveAddress` helper
+    pub fn to_spec(&self) -> serde_json::Value {
+        let base = match &self.base {
+            AddressBase::Absolute(address) => serde_json::json!({
+                "kind": "absolute",
+                "address": format!("{:#x}", address),
+            }),
+            AddressBase::Module(module) => serde_json::json!({
+                "kind": "module",
+                "module": module,
+            }),
+            AddressBase::Export { module, name } => serde_json::json!({
+                "kind": "export",
+                "module": module,
+                "name": name,
+            }),
+            AddressBase::Symbol(name) => serde_json::json!({
+                "kind": "symbol",
+                "name": name,
+            }),
+        };
+        serde_json::json!({
+            "base": base,
+            "offset": format_offset(self.offset),
+            "chain": self.chain.iter().map(|o| format_offset(*o)).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn format_offset(offset: i64) -> String {
+    if offset < 0 {
+        format!("-{:#x}", offset.unsigned_abs())
+    } else {
+        format!("{:#x}", offset)
+    }
+}
+
+fn offset_suffix(offset: i64) -> String {
+    match offset {
+        0 => String::new(),
+        o if o < 0 => format!("-{:#x}", o.unsigned_abs()),
+        o => format!("+{:#x}", o),
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut text = match &self.base {
+            AddressBase::Absolute(address) => format!("{:#x}", address),
+            AddressBase::Module(module) => module.clone(),
+            AddressBase::Export { module, name } => {
+                format!("{}!{}", module.as_deref().unwrap_or(""), name)
+            }
+            AddressBase::Symbol(name) => format!("sym:{}", name),
+        };
+        text.push_str(&offset_suffix(self.offset));
+        for offset in &self.chain {
+            text = format!("[{}]{}", text, offset_suffix(*offset));
+        }
+        f.write_str(&text)
+    }
+}
+
+impl FromStr for Address {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_address(s.trim())
+            .map_err(|reason| CoreError::InvalidAddress(format!("'{}': {}", s, reason)))
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+fn parse_address(s: &str) -> Result<Address, String> {
+    if s.is_empty() {
+        return Err("address is empty".to_string());
+    }
+
+    if let Some(inner) = s.strip_prefix('[') {
+        let close = matching_bracket(inner).ok_or("unbalanced '['")?;
+        let mut address = parse_address(inner[..close].trim())?;
+        let rest = inner[close + 1..].trim();
+        let offset = if rest.is_empty() {
+            0
+        } else {
+            parse_signed_offset(rest)?
+        };
+        address.chain.push(offset);
+        return Ok(address);
+    }
+
+    if s.contains(['[', ']']) {
+        return Err("pointer chains must start with '['".to_string());
+    }
+
+    let (atom, offset) = split_trailing_offset(s);
+    if atom.is_empty() || atom.starts_with(['+', '-']) {
+        return Err("missing base before offset".to_string());
+    }
+
+    if let Some(value) = parse_number(atom) {
+        let address = if offset < 0 {
+            value.checked_sub(offset.unsigned_abs())
+        } else {
+            value.checked_add(offset as u64)
+        };
+        return address
+            .map(Address::absolute)
+            .ok_or_else(|| "address overflows".to_string());
+    }
+
+    let base = if let Some(name) = atom.strip_prefix("sym:") {
+        if name.is_empty() {
+            return Err("symbol name is empty".to_string());
+        }
+        AddressBase::Symbol(name.to_string())
+    } else if let Some((module, name)) = atom.rsplit_once('!') {
+        if name.is_empty() {
+            return Err("export name is empty".to_string());
+        }
+        AddressBase::Export {
+            module: (!module.is_empty()).then(|| module.to_string()),
+            name: name.to_string(),
+        }
+    } else {
+        AddressBase::Module(atom.to_string())
+    };
+
+    Ok(Address {
+        base,
+        offset,
+        chain: Vec::new(),
+    })
+}
+
+/// Index of the `]` closing an already-consumed `[`
+fn matching_bracket(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => return Some(i),
+            ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split `base+0x10` into (`base`, 0x10); names like `libc++.so` keep their `+`
+fn split_trailing_offset(s: &str) -> (&str, i64) {
+    for (i, c) in s.char_indices().rev() {
+        if (c == '+' || c == '-') && i > 0 {
+            if let Ok(offset) = parse_signed_offset(&s[i..]) {
+                return (s[..i].trim(), offset);
+            }
+        }
+    }
+    (s, 0)
+}
+
+fn parse_signed_offset(s: &str) -> Result<i64, String> {
+    let (negative, digits) = match s.chars().next() {
+        Some('+') => (false, &s[1..]),
+        Some('-') => (true, &s[1..]),
+        _ => return Err(format!("expected '+' or '-' before offset, found '{}'", s)),
+    };
+    let value = parse_number(digits.trim()).ok_or_else(|| format!("invalid offset '{}'", s))?;
+    let value = i64::try_from(value).map_err(|_| format!("offset '{}' is too large", s))?;
+    Ok(if negative { -value } else { value })
+}
+
+fn parse_number(s: &str) -> Option<u64> {
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        u64::from_str_radix(hex, 16).ok()
+    } else if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_address_kinds() {
+        assert_eq!("0x1000".parse::<Address>().unwrap(), Address::absolute(0x1000));
+        assert_eq!(
+            "game.exe+0x1a2b".parse::<Address>().unwrap(),
+            Address::module("game.exe", 0x1a2b)
+        );
+        assert_eq!(
+            "libc++_shared.so-0x10".parse::<Address>().unwrap(),
+            Address::module("libc++_shared.so", -0x10)
+        );
+        assert_eq!(
+            "libc.so!malloc".parse::<Address>().unwrap(),
+            Address::export(Some("libc.so".to_string()), "malloc")
+        );
+        assert_eq!(
+            "!malloc".parse::<Address>().unwrap(),
+            Address::export(None, "malloc")
+        );
+        assert_eq!(
+            "sym:Player::update".parse::<Address>().unwrap(),
+            Address::symbol("Player::update")
+        );
+    }
+
+    #[test]
+    fn test_parse_pointer_chain() {
+        let address: Address = "[[game.exe+0x10]+0x20] + 0x8".parse().unwrap();
+        assert_eq!(address, Address::module("game.exe", 0x10).deref(0x20).deref(0x8));
+        assert!(!address.is_absolute());
+
+        let address: Address = "[0x1000]".parse().unwrap();
+        assert_eq!(address, Address::absolute(0x1000).deref(0));
+    }
+
+    #[test]
+    fn test_address_display_round_trip() {
+        for text in [
+            "0x1000",
+            "game.exe+0x10",
+            "game.exe-0x10",
+            "libc.so!malloc",
+            "!malloc",
+            "sym:update",
+            "[[game.exe+0x10]+0x20]-0x8",
+            "[libc.so!environ]",
+        ] {
+            let address: Address = text.parse().unwrap();
+            assert_eq!(address.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_parse_address_errors() {
+        for text in ["", "[game.exe+0x10", "game.exe]", "+0x10", "libc.so!", "sym:", "[x]y"] {
+            assert!(text.parse::<Address>().is_err(), "{} should not parse", text);
+        }
+    }
+
+    #[test]
+    fn test_address_serde_and_spec() {
+        let address: Address = serde_json::from_str("\"[game.exe+0x10]+0x4\"").unwrap();
+        assert_eq!(serde_json::to_string(&address).unwrap(), "\"[game.exe+0x10]+0x4\"");
+
+        let spec = address.to_spec();
+        assert_eq!(spec["base"]["kind"], "module");
+        assert_eq!(spec["base"]["module"], "game.exe");
+        assert_eq!(spec["offset"], "0x10");
+        assert_eq!(spec["chain"], serde_json::json!(["0x4"]));
+
+        let err = serde_json::from_str::<Address>("\"[bad\"").unwrap_err();
+        assert!(err.to_string().contains("Invalid address"));
+    }
+}
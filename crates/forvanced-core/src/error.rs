@@ -8,9 +8,24 @@ pub enum CoreError {
     #[error("Invalid project format: {0}")]
     InvalidProjectFormat(String),
 
+    #[error("Project schema version {found} is newer than supported version {supported}; update Forvanced to open this file")]
+    UnsupportedSchemaVersion { found: u32, supported: u32 },
+
     #[error("Script not found: {0}")]
     ScriptNotFound(String),
 
+    #[error("Invalid struct layout: {0}")]
+    InvalidStructLayout(String),
+
+    #[error("Invalid address {0}")]
+    InvalidAddress(String),
+
+    #[error("Invalid config payload: {0}")]
+    InvalidPayload(String),
+
+    #[error("Integrity check failed: {0}")]
+    IntegrityCheckFailed(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
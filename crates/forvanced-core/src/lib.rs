@@ -1,10 +1,18 @@
+pub mod address;
 pub mod error;
+pub mod migration;
+pub mod payload;
 pub mod project;
+pub mod protect;
 pub mod script;
+pub mod structs;
 
+pub use address::{Address, AddressBase};
 pub use error::CoreError;
+pub use migration::{MigrationReport, SCHEMA_VERSION};
 pub use project::{
     ActionBinding, BuildConfig, ComponentEvent, ComponentType, FridaAction, HotkeyBinding,
     HotkeyConfig, Project, ProjectConfig, TargetConfig, UIComponent, UILayout, ValueType,
 };
 pub use script::{Script, ScriptConfig};
+pub use structs::{DataModel, FieldType, StructDefinition, StructField};
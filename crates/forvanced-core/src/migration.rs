@@ -0,0 +1,226 @@
+//! Project schema versioning
+//!
+//! Project files carry a `schema_version`. Older files are upgraded one
+//! version at a time by the migrations below before they are deserialized,
+//! and every change is recorded in a [`MigrationReport`]. Files written by a
+//! newer Forvanced are refused instead of being half-read.
+//!
+//! Adding a model change that is not covered by serde defaults means bumping
+//! [`SCHEMA_VERSION`] and appending a migration to [`MIGRATIONS`].
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::{CoreError, Result};
+use crate::project::{BuildConfig, HotkeyConfig, Project};
+
+/// Schema version written by this build
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Files without a `schema_version` field predate versioning
+const UNVERSIONED: u32 = 1;
+
+/// One upgrade step from `to - 1` to `to`
+struct Migration {
+    to: u32,
+    apply: fn(&mut Map<String, Value>, &mut Vec<String>),
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    to: 2,
+    apply: migrate_v1_to_v2,
+}];
+
+/// What [`migrate`] did to a project file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Human-readable description of each change, in order
+    pub changes: Vec<String>,
+}
+
+impl MigrationReport {
+    /// True if the file was written by an older schema
+    pub fn is_migrated(&self) -> bool {
+        self.from_version != self.to_version
+    }
+}
+
+/// Upgrade a raw project document to [`SCHEMA_VERSION`] in place
+pub fn migrate(value: &mut Value) -> Result<MigrationReport> {
+    let project = value.as_object_mut().ok_or_else(|| {
+        CoreError::InvalidProjectFormat("project file is not a JSON object".into())
+    })?;
+
+    let from_version = match project.get("schema_version") {
+        None => UNVERSIONED,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v >= UNVERSIONED)
+            .ok_or_else(|| {
+                CoreError::InvalidProjectFormat(format!("invalid schema_version {}", v))
+            })?,
+    };
+    if from_version > SCHEMA_VERSION {
+        return Err(CoreError::UnsupportedSchemaVersion {
+            found: from_version,
+            supported: SCHEMA_VERSION,
+        });
+    }
+
+    let mut report = MigrationReport {
+        from_version,
+        to_version: SCHEMA_VERSION,
+        changes: Vec::new(),
+    };
+    for migration in MIGRATIONS.iter().filter(|m| m.to > from_version) {
+        (migration.apply)(project, &mut report.changes);
+        project.insert("schema_version".into(), migration.to.into());
+    }
+    Ok(report)
+}
+
+/// Parse a project file, migrating it from older schema versions first
+pub fn load_project(json: &str) -> Result<(Project, MigrationReport)> {
+    let mut value: Value = serde_json::from_str(json)?;
+    let report = migrate(&mut value)?;
+    let project = serde_json::from_value(value)
+        .map_err(|e| CoreError::InvalidProjectFormat(e.to_string()))?;
+    Ok((project, report))
+}
+
+/// v1 → v2: fill in the config sections that are now required and turn
+/// numeric action addresses into hex strings.
+///
+/// Missing `config` and `config.target` objects are created; `config.target`
+/// gets `process_patterns: []`, `adapter_type: "local_pc"` and
+/// `auto_attach: false` where absent, and missing `config.build` and
+/// `config.hotkeys` get the current defaults. A numeric `action.address` on
+/// any UI component binding becomes the string `0x<hex>`. Present values are
+/// never overwritten.
+fn migrate_v1_to_v2(project: &mut Map<String, Value>, changes: &mut Vec<String>) {
+    let config = object_entry(project, "config", changes, "config");
+    let target = object_entry(config, "target", changes, "config.target");
+    for (key, default) in [
+        ("process_patterns", Value::Array(Vec::new())),
+        ("adapter_type", Value::from("local_pc")),
+        ("auto_attach", Value::Bool(false)),
+    ] {
+        if !target.contains_key(key) {
+            target.insert(key.into(), default);
+            changes.push(format!("Added default config.target.{}", key));
+        }
+    }
+    for (key, default) in [
+        ("build", serde_json::to_value(BuildConfig::default())),
+        ("hotkeys", serde_json::to_value(HotkeyConfig::default())),
+    ] {
+        if !config.contains_key(key) {
+            config.insert(key.into(), default.unwrap_or_default());
+            changes.push(format!("Added default config.{}", key));
+        }
+    }
+
+    let components = project
+        .get_mut("ui")
+        .and_then(|ui| ui.get_mut("components"))
+        .and_then(Value::as_array_mut);
+    for (ci, component) in components.into_iter().flatten().enumerate() {
+        let bindings = component.get_mut("bindings").and_then(Value::as_array_mut);
+        for (bi, binding) in bindings.into_iter().flatten().enumerate() {
+            let Some(address) = binding.get_mut("action").and_then(|a| a.get_mut("address")) else {
+                continue;
+            };
+            if let Some(n) = address.as_u64() {
+                *address = Value::String(format!("0x{:x}", n));
+                changes.push(format!(
+                    "Converted numeric address in ui.components[{}].bindings[{}] to 0x{:x}",
+                    ci, bi, n
+                ));
+            }
+        }
+    }
+}
+
+/// Get `key` as an object, inserting an empty one if it is missing
+fn object_entry<'a>(
+    parent: &'a mut Map<String, Value>,
+    key: &str,
+    changes: &mut Vec<String>,
+    path: &str,
+) -> &'a mut Map<String, Value> {
+    if !parent.get(key).is_some_and(Value::is_object) {
+        parent.insert(key.into(), Value::Object(Map::new()));
+        changes.push(format!("Added missing {}", path));
+    }
+    parent
+        .get_mut(key)
+        .and_then(Value::as_object_mut)
+        .expect("inserted above")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn legacy_project() -> Value {
+        json!({
+            "id": "p1",
+            "name": "Legacy",
+            "description": null,
+            "version": "0.1.0",
+            "author": null,
+            "config": { "target": { "process_name": "game.exe" } },
+            "ui": {
+                "components": [{
+                    "id": "c1", "type": "button", "label": "Write",
+                    "x": 0.0, "y": 0.0, "width": 100.0, "height": 30.0, "props": {},
+                    "bindings": [{
+                        "id": "b1", "event": "onClick",
+                        "action": { "type": "memory_write", "address": 4660, "value": "1", "value_type": "int32" }
+                    }]
+                }],
+                "width": 400, "height": 500, "theme": "dark"
+            }
+        })
+    }
+
+    #[test]
+    fn test_migrates_unversioned_file() {
+        let (project, report) = load_project(&legacy_project().to_string()).unwrap();
+        assert!(report.is_migrated());
+        assert_eq!((report.from_version, report.to_version), (1, SCHEMA_VERSION));
+        assert!(report.changes.iter().any(|c| c.contains("config.target.adapter_type")));
+        assert!(report.changes.iter().any(|c| c.contains("to 0x1234")));
+
+        assert_eq!(project.schema_version, SCHEMA_VERSION);
+        assert_eq!(project.config.target.adapter_type, "local_pc");
+        match &project.ui.components[0].bindings[0].action {
+            crate::FridaAction::MemoryWrite { address, .. } => {
+                assert_eq!(address.to_string(), "0x1234")
+            }
+            other => panic!("unexpected action {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_current_file_is_unchanged() {
+        let json = serde_json::to_string(&Project::new("Current")).unwrap();
+        let (_, report) = load_project(&json).unwrap();
+        assert!(!report.is_migrated());
+        assert!(report.changes.is_empty());
+    }
+
+    #[test]
+    fn test_newer_file_is_refused() {
+        let mut value = serde_json::to_value(Project::new("Future")).unwrap();
+        value["schema_version"] = json!(SCHEMA_VERSION + 1);
+        let err = load_project(&value.to_string()).unwrap_err();
+        assert!(matches!(err, CoreError::UnsupportedSchemaVersion { .. }));
+        assert!(err.to_string().contains("newer"));
+    }
+}
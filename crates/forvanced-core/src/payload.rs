@@ -0,0 +1,252 @@
+//! Signed config payloads for data-only packaging
+//!
+//! A prebuilt runtime binary reads its trainer config from a payload instead
+//! of having it compiled in. The payload is either appended to the executable
+//! (followed by a fixed footer) or stored next to it as `<exe stem>.fvcfg`.
+//!
+//! Appended layout: `[executable][payload JSON][payload length: u64 LE][FOOTER_MAGIC]`
+
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::error::{CoreError, Result};
+
+/// Current payload format version
+pub const PAYLOAD_FORMAT: u32 = 1;
+
+/// File extension of sidecar payloads
+pub const SIDECAR_EXTENSION: &str = "fvcfg";
+
+/// Marks the end of an executable with an appended payload
+const FOOTER_MAGIC: &[u8; 8] = b"FVCFG\0v1";
+const FOOTER_LEN: u64 = 16;
+
+/// Ed25519 key used to sign payloads, kept as a PKCS#8 document
+pub struct SigningKey {
+    key_pair: Ed25519KeyPair,
+    pkcs8: Vec<u8>,
+}
+
+impl SigningKey {
+    /// Generate a new random key
+    pub fn generate() -> Result<Self> {
+        let document = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|_| CoreError::InvalidPayload("failed to generate signing key".into()))?;
+        Self::from_pkcs8(document.as_ref())
+    }
+
+    pub fn from_pkcs8(pkcs8: &[u8]) -> Result<Self> {
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8)
+            .map_err(|e| CoreError::InvalidPayload(format!("invalid signing key: {}", e)))?;
+        Ok(Self {
+            key_pair,
+            pkcs8: pkcs8.to_vec(),
+        })
+    }
+
+    /// PKCS#8 bytes for storing the key on disk
+    pub fn pkcs8(&self) -> &[u8] {
+        &self.pkcs8
+    }
+
+    /// Hex-encoded public key, as listed in a runtime's trusted keys
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.key_pair.public_key().as_ref())
+    }
+}
+
+/// Trainer config plus the signature of its publisher
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigPayload {
+    pub format: u32,
+    /// Runtime config JSON, byte-for-byte as signed
+    pub config: String,
+    /// Hex-encoded Ed25519 public key of the signer
+    pub public_key: String,
+    /// Hex-encoded Ed25519 signature over `config`
+    pub signature: String,
+}
+
+impl ConfigPayload {
+    pub fn sign(config: impl Into<String>, key: &SigningKey) -> Self {
+        let config = config.into();
+        let signature = key.key_pair.sign(config.as_bytes());
+        Self {
+            format: PAYLOAD_FORMAT,
+            config,
+            public_key: key.public_key_hex(),
+            signature: hex::encode(signature.as_ref()),
+        }
+    }
+
+    /// Check the signature and return the config JSON. The signer must be one
+    /// of `trusted_keys`; with none, every payload is refused, since anyone can
+    /// re-sign a config with a key of their own.
+    pub fn verify(&self, trusted_keys: &[&str]) -> Result<&str> {
+        if self.format > PAYLOAD_FORMAT {
+            return Err(CoreError::InvalidPayload(format!(
+                "payload format {} is newer than supported format {}",
+                self.format, PAYLOAD_FORMAT
+            )));
+        }
+        if trusted_keys.is_empty() {
+            return Err(CoreError::InvalidPayload(
+                "no trusted keys configured, refusing payload".into(),
+            ));
+        }
+        if !trusted_keys
+            .iter()
+            .any(|k| k.eq_ignore_ascii_case(&self.public_key))
+        {
+            return Err(CoreError::InvalidPayload(format!(
+                "payload signed by untrusted key {}",
+                self.public_key
+            )));
+        }
+        let public_key = hex::decode(&self.public_key)
+            .map_err(|_| CoreError::InvalidPayload("malformed public key".into()))?;
+        let signature = hex::decode(&self.signature)
+            .map_err(|_| CoreError::InvalidPayload("malformed signature".into()))?;
+        UnparsedPublicKey::new(&ED25519, public_key)
+            .verify(self.config.as_bytes(), &signature)
+            .map_err(|_| CoreError::InvalidPayload("signature does not match config".into()))?;
+        Ok(&self.config)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|e| CoreError::InvalidPayload(format!("malformed payload: {}", e)))
+    }
+
+    /// Append this payload to an executable image
+    pub fn append_to(&self, executable: &[u8]) -> Result<Vec<u8>> {
+        let payload = self.to_bytes()?;
+        let mut out = Vec::with_capacity(executable.len() + payload.len() + FOOTER_LEN as usize);
+        out.extend_from_slice(strip_appended(executable));
+        out.extend_from_slice(&payload);
+        out.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        out.extend_from_slice(FOOTER_MAGIC);
+        Ok(out)
+    }
+
+    /// Read a payload appended to the file at `path`, if there is one
+    pub fn read_appended(path: &Path) -> Result<Option<Self>> {
+        let mut file = std::fs::File::open(path)?;
+        let file_len = file.metadata()?.len();
+        if file_len < FOOTER_LEN {
+            return Ok(None);
+        }
+        let mut footer = [0u8; FOOTER_LEN as usize];
+        file.seek(SeekFrom::End(-(FOOTER_LEN as i64)))?;
+        file.read_exact(&mut footer)?;
+        if &footer[8..] != FOOTER_MAGIC {
+            return Ok(None);
+        }
+        let payload_len = u64::from_le_bytes(footer[..8].try_into().expect("8-byte slice"));
+        if payload_len > file_len - FOOTER_LEN {
+            return Err(CoreError::InvalidPayload("appended payload length is corrupt".into()));
+        }
+        let mut payload = vec![0u8; payload_len as usize];
+        file.seek(SeekFrom::Start(file_len - FOOTER_LEN - payload_len))?;
+        file.read_exact(&mut payload)?;
+        Self::from_bytes(&payload).map(Some)
+    }
+}
+
+/// The executable without any previously appended payload, so repackaging
+/// replaces the payload instead of stacking another one
+pub fn strip_appended(executable: &[u8]) -> &[u8] {
+    let len = executable.len();
+    if len < FOOTER_LEN as usize || &executable[len - 8..] != FOOTER_MAGIC {
+        return executable;
+    }
+    let payload_len = u64::from_le_bytes(executable[len - 16..len - 8].try_into().expect("8-byte slice"));
+    match (len as u64).checked_sub(FOOTER_LEN + payload_len) {
+        Some(end) => &executable[..end as usize],
+        None => executable,
+    }
+}
+
+/// Sidecar payload location for an executable: `<dir>/<stem>.fvcfg`
+pub fn sidecar_path(executable: &Path) -> PathBuf {
+    executable.with_extension(SIDECAR_EXTENSION)
+}
+
+/// Find the payload for an executable: appended first, then the sidecar file
+pub fn find_payload(executable: &Path) -> Result<Option<ConfigPayload>> {
+    if let Some(payload) = ConfigPayload::read_appended(executable)? {
+        return Ok(Some(payload));
+    }
+    let sidecar = sidecar_path(executable);
+    if sidecar.exists() {
+        return ConfigPayload::from_bytes(&std::fs::read(sidecar)?).map(Some);
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::generate().unwrap();
+        let payload = ConfigPayload::sign(r#"{"name":"Demo"}"#, &key);
+        let trusted = key.public_key_hex();
+        assert_eq!(
+            payload.verify(&[trusted.as_str()]).unwrap(),
+            r#"{"name":"Demo"}"#
+        );
+        assert!(payload.verify(&["00"]).is_err());
+        // Without trusted keys any self-signed payload would pass
+        assert!(payload.verify(&[]).is_err());
+
+        let mut tampered = payload.clone();
+        tampered.config = r#"{"name":"Evil"}"#.to_string();
+        assert!(tampered.verify(&[trusted.as_str()]).is_err());
+
+        let reloaded = SigningKey::from_pkcs8(key.pkcs8()).unwrap();
+        assert_eq!(reloaded.public_key_hex(), trusted);
+    }
+
+    #[test]
+    fn test_appended_payload_round_trip() {
+        let key = SigningKey::generate().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("runtime.exe");
+
+        std::fs::write(&exe, b"MZ binary").unwrap();
+        assert!(find_payload(&exe).unwrap().is_none());
+
+        let first = ConfigPayload::sign("{\"v\":1}", &key);
+        std::fs::write(&exe, first.append_to(b"MZ binary").unwrap()).unwrap();
+        let second = ConfigPayload::sign("{\"v\":2}", &key);
+        let repackaged = second.append_to(&std::fs::read(&exe).unwrap()).unwrap();
+        assert!(repackaged.starts_with(b"MZ binary{"));
+        std::fs::write(&exe, repackaged).unwrap();
+
+        let found = find_payload(&exe).unwrap().unwrap();
+        assert_eq!(found.verify(&[&key.public_key_hex()]).unwrap(), "{\"v\":2}");
+    }
+
+    #[test]
+    fn test_sidecar_payload() {
+        let key = SigningKey::generate().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("runtime");
+        std::fs::write(&exe, b"ELF").unwrap();
+
+        let payload = ConfigPayload::sign("{}", &key);
+        std::fs::write(sidecar_path(&exe), payload.to_bytes().unwrap()).unwrap();
+        assert_eq!(sidecar_path(&exe), dir.path().join("runtime.fvcfg"));
+        assert!(find_payload(&exe).unwrap().is_some());
+    }
+}
@@ -1,8 +1,14 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use crate::address::Address;
+use crate::migration::SCHEMA_VERSION;
+use crate::structs::StructDefinition;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
+    /// File format version, see [`crate::migration`]
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
     pub id: String,
     pub name: String,
     pub description: Option<String>,
@@ -13,6 +19,9 @@ pub struct Project {
     /// Visual scripts associated with this project
     #[serde(default)]
     pub scripts: Vec<VisualScript>,
+    /// Struct layouts used by struct_read/struct_write nodes
+    #[serde(default)]
+    pub structs: Vec<StructDefinition>,
     #[serde(default)]
     pub created_at: i64,
     #[serde(default)]
@@ -23,6 +32,7 @@ impl Project {
     pub fn new(name: impl Into<String>) -> Self {
         let now = chrono::Utc::now().timestamp_millis();
         Self {
+            schema_version: SCHEMA_VERSION,
             id: Uuid::new_v4().to_string(),
             name: name.into(),
             description: None,
@@ -31,6 +41,7 @@ impl Project {
             config: ProjectConfig::default(),
             ui: UILayout::default(),
             scripts: Vec::new(),
+            structs: Vec::new(),
             created_at: now,
             updated_at: now,
         }
@@ -41,6 +52,12 @@ impl Project {
     }
 }
 
+/// Projects handed over in memory (e.g. from the Builder UI) are always current;
+/// files on disk go through [`crate::migration::load_project`] instead
+fn current_schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConfig {
     pub target: TargetConfig,
@@ -86,6 +103,10 @@ pub struct BuildConfig {
     pub targets: Vec<String>,
     pub icon: Option<String>,
     pub bundle_frida: bool,
+    /// Hex Ed25519 public keys data-only packages of this project may be
+    /// signed with; recorded in the package manifest for the runtime
+    #[serde(default)]
+    pub trusted_keys: Vec<String>,
 }
 
 impl Default for BuildConfig {
@@ -95,6 +116,7 @@ impl Default for BuildConfig {
             targets: vec!["current".to_string()],
             icon: None,
             bundle_frida: true,
+            trusted_keys: Vec::new(),
         }
     }
 }
@@ -296,22 +318,22 @@ pub enum ComponentEvent {
 pub enum FridaAction {
     // Memory
     MemoryRead {
-        address: String,
+        address: Address,
         value_type: ValueType,
     },
     MemoryWrite {
-        address: String,
+        address: Address,
         value: String,
         value_type: ValueType,
     },
     MemoryFreeze {
-        address: String,
+        address: Address,
         value: String,
         value_type: ValueType,
         interval_ms: u32,
     },
     MemoryUnfreeze {
-        address: String,
+        address: Address,
     },
 
     // Scanner
@@ -326,16 +348,16 @@ pub enum FridaAction {
 
     // Hook
     HookFunction {
-        address: String,
+        address: Address,
         log_enter: bool,
         log_leave: bool,
     },
     ReplaceReturn {
-        address: String,
+        address: Address,
         return_value: String,
     },
     NopFunction {
-        address: String,
+        address: Address,
     },
 
     // Java (Android)
@@ -536,7 +558,7 @@ mod tests {
         component.bindings.push(ActionBinding::new(
             ComponentEvent::OnClick,
             FridaAction::MemoryFreeze {
-                address: "0x12345678".to_string(),
+                address: "game.exe+0x1234".parse().unwrap(),
                 value: "999".to_string(),
                 value_type: ValueType::Int32,
                 interval_ms: 100,
@@ -547,5 +569,28 @@ mod tests {
         let json = serde_json::to_string_pretty(&project).unwrap();
         assert!(json.contains("Freeze HP"));
         assert!(json.contains("memory_freeze"));
+        assert!(json.contains("game.exe+0x1234"));
+
+        let loaded: Project = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.ui.components[0].bindings.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_address_rejected_on_load() {
+        let mut project = Project::new("Test");
+        let mut component = UIComponent::new(ComponentType::Button, "Nop", 0.0, 0.0);
+        component.bindings.push(ActionBinding::new(
+            ComponentEvent::OnClick,
+            FridaAction::NopFunction {
+                address: Address::module("game.exe", 0x10),
+            },
+        ));
+        project.ui.components.push(component);
+
+        let json = serde_json::to_string(&project)
+            .unwrap()
+            .replace("game.exe+0x10", "[game.exe+0x10");
+        let err = serde_json::from_str::<Project>(&json).unwrap_err();
+        assert!(err.to_string().contains("Invalid address"));
     }
 }
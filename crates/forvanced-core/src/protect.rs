@@ -0,0 +1,144 @@
+//! Sealed blobs for protected builds
+//!
+//! A protected build embeds its runtime config and target script encrypted
+//! with ChaCha20-Poly1305 instead of as plain text. The authentication tag
+//! doubles as the tamper check: a blob that was edited, truncated or swapped
+//! for another one fails to open.
+//!
+//! The key ships inside the trainer (split into two masked halves), so this
+//! keeps casual readers and editors out rather than a determined reverse
+//! engineer.
+//!
+//! Layout: `[SEALED_MAGIC][nonce: 12 bytes][ciphertext + tag]`
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::error::{CoreError, Result};
+
+/// Marks the start of a sealed blob
+pub const SEALED_MAGIC: &[u8; 8] = b"FVSEAL\0\x01";
+
+/// Symmetric key for sealing a build's embedded data
+#[derive(Clone)]
+pub struct SealKey([u8; 32]);
+
+impl SealKey {
+    /// Generate a new random key (one per build)
+    pub fn generate() -> Result<Self> {
+        let mut key = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut key)
+            .map_err(|_| CoreError::IntegrityCheckFailed("failed to generate seal key".into()))?;
+        Ok(Self(key))
+    }
+
+    /// Split into two shares whose XOR is the key, so the key never appears
+    /// verbatim in the binary
+    pub fn split(&self) -> Result<([u8; 32], [u8; 32])> {
+        let mut mask = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut mask)
+            .map_err(|_| CoreError::IntegrityCheckFailed("failed to generate key mask".into()))?;
+        let mut masked = self.0;
+        masked.iter_mut().zip(mask).for_each(|(b, m)| *b ^= m);
+        Ok((masked, mask))
+    }
+
+    /// Rebuild a key from the shares produced by [`SealKey::split`]
+    pub fn from_shares(masked: &[u8; 32], mask: &[u8; 32]) -> Self {
+        let mut key = *masked;
+        key.iter_mut().zip(mask).for_each(|(b, m)| *b ^= m);
+        Self(key)
+    }
+
+    fn aead_key(&self) -> LessSafeKey {
+        LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, &self.0).expect("32-byte key"))
+    }
+}
+
+/// True if `bytes` starts like a sealed blob
+pub fn is_sealed(bytes: &[u8]) -> bool {
+    bytes.starts_with(SEALED_MAGIC)
+}
+
+/// Encrypt and authenticate `plaintext`. The `label` is bound into the tag,
+/// so a blob sealed as "config" cannot be passed off as "script".
+pub fn seal(key: &SealKey, label: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| CoreError::IntegrityCheckFailed("failed to generate nonce".into()))?;
+
+    let mut in_out = plaintext.to_vec();
+    key.aead_key()
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(label.as_bytes()),
+            &mut in_out,
+        )
+        .map_err(|_| CoreError::IntegrityCheckFailed(format!("failed to seal {}", label)))?;
+
+    let mut out = Vec::with_capacity(SEALED_MAGIC.len() + NONCE_LEN + in_out.len());
+    out.extend_from_slice(SEALED_MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&in_out);
+    Ok(out)
+}
+
+/// Decrypt a sealed blob, failing if it was modified
+pub fn open(key: &SealKey, label: &str, sealed: &[u8]) -> Result<Vec<u8>> {
+    let tampered = || CoreError::IntegrityCheckFailed(format!("embedded {} was modified", label));
+
+    let body = sealed.strip_prefix(SEALED_MAGIC.as_slice()).ok_or_else(tampered)?;
+    if body.len() < NONCE_LEN {
+        return Err(tampered());
+    }
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| tampered())?;
+
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key
+        .aead_key()
+        .open_in_place(nonce, Aad::from(label.as_bytes()), &mut in_out)
+        .map_err(|_| tampered())?;
+    Ok(plaintext.to_vec())
+}
+
+/// [`open`] for text blobs
+pub fn open_str(key: &SealKey, label: &str, sealed: &[u8]) -> Result<String> {
+    String::from_utf8(open(key, label, sealed)?)
+        .map_err(|_| CoreError::IntegrityCheckFailed(format!("embedded {} is not UTF-8", label)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = SealKey::generate().unwrap();
+        let sealed = seal(&key, "config", b"{\"name\":\"Demo\"}").unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.windows(4).any(|w| w == b"Demo"));
+        assert_eq!(open_str(&key, "config", &sealed).unwrap(), "{\"name\":\"Demo\"}");
+
+        let (masked, mask) = key.split().unwrap();
+        let rebuilt = SealKey::from_shares(&masked, &mask);
+        assert_eq!(open(&rebuilt, "config", &sealed).unwrap(), b"{\"name\":\"Demo\"}");
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let key = SealKey::generate().unwrap();
+        let sealed = seal(&key, "config", b"{}").unwrap();
+
+        let mut flipped = sealed.clone();
+        *flipped.last_mut().unwrap() ^= 1;
+        assert!(open(&key, "config", &flipped).is_err());
+        assert!(open(&key, "script", &sealed).is_err());
+        assert!(open(&key, "config", &sealed[..sealed.len() - 1]).is_err());
+        assert!(open(&key, "config", b"{}").is_err());
+        assert!(open(&SealKey::generate().unwrap(), "config", &sealed).is_err());
+    }
+}
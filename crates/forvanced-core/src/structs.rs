@@ -0,0 +1,877 @@
+//! Struct layouts for typed memory access
+//!
+//! Layouts are defined in the project (by hand or imported from a C header)
+//! and resolved into self-contained JSON for the `struct_read`/`struct_write`
+//! target nodes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{CoreError, Result};
+use crate::project::ValueType;
+
+/// A named struct layout in target memory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StructDefinition {
+    pub name: String,
+    pub fields: Vec<StructField>,
+    /// Total size in bytes (computed in the target from the fields when absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+/// A field at a fixed offset inside a struct
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StructField {
+    pub name: String,
+    pub offset: u64,
+    #[serde(flatten)]
+    pub field_type: FieldType,
+    /// Element count for arrays; for `String` fields, the inline buffer length
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub array_length: Option<u32>,
+}
+
+/// Type of a struct field: a primitive value or another struct by name
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldType {
+    Value {
+        #[serde(rename = "valueType")]
+        value_type: ValueType,
+    },
+    Struct {
+        #[serde(rename = "structName")]
+        struct_name: String,
+    },
+}
+
+impl StructField {
+    pub fn value(name: impl Into<String>, offset: u64, value_type: ValueType) -> Self {
+        Self {
+            name: name.into(),
+            offset,
+            field_type: FieldType::Value { value_type },
+            array_length: None,
+        }
+    }
+
+    pub fn nested(name: impl Into<String>, offset: u64, struct_name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            offset,
+            field_type: FieldType::Struct {
+                struct_name: struct_name.into(),
+            },
+            array_length: None,
+        }
+    }
+
+    pub fn with_array_length(mut self, length: u32) -> Self {
+        self.array_length = Some(length);
+        self
+    }
+}
+
+/// Check that every nested struct exists and no struct contains itself
+pub fn validate_structs(defs: &[StructDefinition]) -> Result<()> {
+    let mut names = HashSet::new();
+    for def in defs {
+        if !names.insert(def.name.as_str()) {
+            return Err(CoreError::InvalidStructLayout(format!(
+                "duplicate struct '{}'",
+                def.name
+            )));
+        }
+    }
+    for def in defs {
+        resolve_layout(&def.name, defs)?;
+    }
+    Ok(())
+}
+
+/// Resolve a struct into the JSON layout understood by the target script,
+/// with nested structs inlined
+pub fn resolve_layout(name: &str, defs: &[StructDefinition]) -> Result<serde_json::Value> {
+    let by_name: HashMap<&str, &StructDefinition> =
+        defs.iter().map(|d| (d.name.as_str(), d)).collect();
+    resolve_inner(name, &by_name, &mut Vec::new())
+}
+
+fn resolve_inner<'a>(
+    name: &'a str,
+    defs: &HashMap<&str, &'a StructDefinition>,
+    stack: &mut Vec<&'a str>,
+) -> Result<serde_json::Value> {
+    let def = defs
+        .get(name)
+        .copied()
+        .ok_or_else(|| CoreError::InvalidStructLayout(format!("unknown struct '{}'", name)))?;
+    if stack.contains(&name) {
+        return Err(CoreError::InvalidStructLayout(format!(
+            "struct '{}' contains itself",
+            name
+        )));
+    }
+    stack.push(&def.name);
+
+    let mut fields = Vec::with_capacity(def.fields.len());
+    for field in &def.fields {
+        let mut entry = serde_json::json!({
+            "name": field.name,
+            "offset": field.offset,
+        });
+        match &field.field_type {
+            FieldType::Value { value_type } => {
+                if *value_type == ValueType::Any {
+                    return Err(CoreError::InvalidStructLayout(format!(
+                        "field '{}.{}' has no concrete type",
+                        def.name, field.name
+                    )));
+                }
+                entry["type"] = serde_json::to_value(value_type)?;
+            }
+            FieldType::Struct { struct_name } => {
+                entry["type"] = "struct".into();
+                entry["layout"] = resolve_inner(struct_name, defs, stack)?;
+            }
+        }
+        if let Some(length) = field.array_length {
+            entry["count"] = length.into();
+        }
+        fields.push(entry);
+    }
+
+    stack.pop();
+
+    let mut layout = serde_json::json!({ "name": def.name, "fields": fields });
+    if let Some(size) = def.size {
+        layout["size"] = size.into();
+    }
+    Ok(layout)
+}
+
+// ============================================
+// C Header Import
+// ============================================
+
+/// C data model of the target, which fixes the size of `long` and pointers
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DataModel {
+    /// 32-bit targets: `int`, `long` and pointers are 4 bytes
+    Ilp32,
+    /// 64-bit Linux, macOS, Android and iOS: `long` and pointers are 8 bytes
+    Lp64,
+    /// 64-bit Windows: `long` stays 4 bytes, pointers are 8 bytes
+    Llp64,
+}
+
+impl DataModel {
+    /// Data model for a target with the given pointer width in bytes
+    pub fn new(pointer_size: u64, windows: bool) -> Self {
+        match (pointer_size, windows) {
+            (8, true) => Self::Llp64,
+            (8, false) => Self::Lp64,
+            _ => Self::Ilp32,
+        }
+    }
+
+    /// Data model of the machine running the builder
+    pub fn host() -> Self {
+        Self::new(
+            std::mem::size_of::<usize>() as u64,
+            cfg!(target_os = "windows"),
+        )
+    }
+
+    pub fn pointer_size(self) -> u64 {
+        match self {
+            Self::Ilp32 => 4,
+            Self::Lp64 | Self::Llp64 => 8,
+        }
+    }
+
+    pub fn long_size(self) -> u64 {
+        match self {
+            Self::Lp64 => 8,
+            Self::Ilp32 | Self::Llp64 => 4,
+        }
+    }
+}
+
+/// Parse `struct` definitions from a C header, computing offsets with natural
+/// alignment for the target's data model.
+///
+/// Supports `struct X {..};`, `typedef struct [X] {..} Y;`, enums (named,
+/// typedef'd, inline and with a fixed underlying type), primitive typedefs,
+/// pointers and (multi-dimensional) arrays.
+pub fn parse_c_header(source: &str, model: DataModel) -> Result<Vec<StructDefinition>> {
+    let tokens = tokenize(&strip_comments(source));
+    let mut parser = HeaderParser {
+        tokens,
+        pos: 0,
+        model,
+        aliases: HashMap::new(),
+        layouts: HashMap::new(),
+        structs: Vec::new(),
+        pending: None,
+    };
+    parser.parse()?;
+    Ok(parser.structs)
+}
+
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+    // Drop preprocessor directives
+    out.lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn tokenize(source: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in source.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            current.push(c);
+            continue;
+        }
+        if !current.is_empty() {
+            tokens.push(std::mem::take(&mut current));
+        }
+        if !c.is_whitespace() {
+            tokens.push(c.to_string());
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Resolved C type: field type plus (size, alignment) in bytes
+#[derive(Clone)]
+struct CType {
+    field_type: FieldType,
+    size: u64,
+    align: u64,
+}
+
+struct HeaderParser {
+    tokens: Vec<String>,
+    pos: usize,
+    model: DataModel,
+    /// typedef name or enum tag -> type
+    aliases: HashMap<String, CType>,
+    /// struct tag or typedef name -> (struct name, size, alignment)
+    layouts: HashMap<String, (String, u64, u64)>,
+    structs: Vec<StructDefinition>,
+    /// Fields, size and alignment of the struct body parsed last
+    pending: Option<(Vec<StructField>, u64, u64)>,
+}
+
+impl HeaderParser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|s| s.as_str())
+    }
+
+    fn next(&mut self) -> Result<String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| CoreError::InvalidStructLayout("unexpected end of header".into()))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<()> {
+        let token = self.next()?;
+        if token != expected {
+            return Err(CoreError::InvalidStructLayout(format!(
+                "expected '{}' but found '{}'",
+                expected, token
+            )));
+        }
+        Ok(())
+    }
+
+    fn parse(&mut self) -> Result<()> {
+        while let Some(token) = self.peek() {
+            match token {
+                "typedef" => {
+                    self.pos += 1;
+                    if self.peek() == Some("struct") && self.is_struct_body_ahead() {
+                        self.pos += 1;
+                        let tag = self.parse_struct_body()?;
+                        let alias = self.next()?;
+                        self.expect(";")?;
+                        self.register_struct(tag.as_deref(), Some(&alias))?;
+                    } else if self.peek() == Some("enum") && self.is_enum_body_ahead() {
+                        let key = self.parse_enum()?;
+                        let alias = self.next()?;
+                        self.expect(";")?;
+                        let ty = self.aliases[&key].clone();
+                        self.aliases.insert(alias, ty);
+                    } else {
+                        self.parse_typedef()?;
+                    }
+                }
+                "struct" if self.is_struct_body_ahead() => {
+                    self.pos += 1;
+                    let tag = self.parse_struct_body()?;
+                    self.expect(";")?;
+                    self.register_struct(tag.as_deref(), None)?;
+                }
+                "enum" if self.is_enum_body_ahead() => {
+                    self.parse_enum()?;
+                    self.skip_statement();
+                }
+                _ => self.skip_statement(),
+            }
+        }
+        Ok(())
+    }
+
+    /// `struct [Tag] {` starting at the current `struct` token
+    fn is_struct_body_ahead(&self) -> bool {
+        let at = |i: usize| self.tokens.get(self.pos + i).map(|s| s.as_str());
+        at(1) == Some("{") || at(2) == Some("{")
+    }
+
+    /// `enum [Tag] [: type] {` starting at the current `enum` token
+    fn is_enum_body_ahead(&self) -> bool {
+        for token in self.tokens[self.pos + 1..].iter() {
+            match token.as_str() {
+                "{" => return true,
+                ":" => {}
+                t if is_identifier(t) => {}
+                _ => return false,
+            }
+        }
+        false
+    }
+
+    /// Parse `enum [Tag] [: type] { .. }` and register its type, returning the
+    /// alias it was registered under (the tag, or a generated key if anonymous).
+    ///
+    /// Without a fixed underlying type the enum is an `int`, widened as
+    /// GCC/Clang do when an enumerator does not fit.
+    fn parse_enum(&mut self) -> Result<String> {
+        self.expect("enum")?;
+        let tag = match self.peek() {
+            Some(t) if is_identifier(t) => Some(self.next()?),
+            _ => None,
+        };
+        let underlying = if self.peek() == Some(":") {
+            self.pos += 1;
+            let mut base = Vec::new();
+            while self.peek() != Some("{") {
+                base.push(self.next()?);
+            }
+            Some(self.resolve_type(&base, false)?)
+        } else {
+            None
+        };
+        self.expect("{")?;
+
+        let mut known: HashMap<String, i128> = HashMap::new();
+        let mut values = Vec::new();
+        let mut next = Some(0i128);
+        while self.peek() != Some("}") {
+            let name = self.next()?;
+            let mut expr = Vec::new();
+            if self.peek() == Some("=") {
+                self.pos += 1;
+                while !matches!(self.peek(), Some(",") | Some("}")) {
+                    expr.push(self.next()?);
+                }
+            }
+            let value = if expr.is_empty() {
+                next
+            } else {
+                enumerator_value(&expr, &known)
+            };
+            if let Some(value) = value {
+                known.insert(name, value);
+                values.push(value);
+            }
+            next = value.map(|v| v + 1);
+            if self.peek() == Some(",") {
+                self.pos += 1;
+            }
+        }
+        self.expect("}")?;
+
+        let ty = underlying.unwrap_or_else(|| enum_type(&values));
+        let key = tag.unwrap_or_else(|| format!("enum@{}", self.pos));
+        self.aliases.insert(key.clone(), ty);
+        Ok(key)
+    }
+
+    fn skip_statement(&mut self) {
+        let mut depth = 0;
+        while let Some(token) = self.tokens.get(self.pos) {
+            self.pos += 1;
+            match token.as_str() {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                ";" if depth <= 0 => return,
+                _ => {}
+            }
+        }
+    }
+
+    /// `typedef <type> <name>;` for primitive aliases
+    fn parse_typedef(&mut self) -> Result<()> {
+        let mut decl = Vec::new();
+        while self.peek() != Some(";") {
+            decl.push(self.next()?);
+        }
+        self.expect(";")?;
+        let name = decl
+            .pop()
+            .ok_or_else(|| CoreError::InvalidStructLayout("empty typedef".into()))?;
+        let pointer = decl.iter().any(|t| t == "*");
+        decl.retain(|t| t != "*");
+        if let Ok(ty) = self.resolve_type(&decl, pointer) {
+            self.aliases.insert(name, ty);
+        }
+        Ok(())
+    }
+
+    /// Parse `[Tag] { fields }` and stash the fields until the name is known
+    fn parse_struct_body(&mut self) -> Result<Option<String>> {
+        let tag = if self.peek() == Some("{") {
+            None
+        } else {
+            Some(self.next()?)
+        };
+        self.expect("{")?;
+
+        let mut fields = Vec::new();
+        let mut offset = 0u64;
+        let mut align = 1u64;
+        while self.peek() != Some("}") {
+            let mut decl = Vec::new();
+            if self.peek() == Some("enum") && self.is_enum_body_ahead() {
+                decl.push(self.parse_enum()?);
+            }
+            while self.peek() != Some(";") {
+                let token = self.next()?;
+                if token == "{" {
+                    return Err(CoreError::InvalidStructLayout(
+                        "nested anonymous structs/unions are not supported".into(),
+                    ));
+                }
+                decl.push(token);
+            }
+            self.expect(";")?;
+            for (field, size, field_align) in self.parse_field_decl(&decl)? {
+                offset = align_up(offset, field_align);
+                fields.push(StructField { offset, ..field });
+                offset += size;
+                align = align.max(field_align);
+            }
+        }
+        self.expect("}")?;
+
+        self.pending = Some((fields, align_up(offset, align), align));
+        Ok(tag)
+    }
+
+    fn register_struct(&mut self, tag: Option<&str>, alias: Option<&str>) -> Result<()> {
+        let name = alias
+            .or(tag)
+            .ok_or_else(|| CoreError::InvalidStructLayout("anonymous struct without typedef".into()))?
+            .to_string();
+        let (fields, size, align) = self.pending.take().unwrap_or_default();
+        for key in [tag, alias].into_iter().flatten() {
+            self.layouts.insert(key.to_string(), (name.clone(), size, align));
+        }
+        self.structs.push(StructDefinition {
+            name,
+            fields,
+            size: Some(size),
+        });
+        Ok(())
+    }
+
+    /// One declaration, possibly with several declarators: `int a, *b, c[4];`
+    fn parse_field_decl(&self, decl: &[String]) -> Result<Vec<(StructField, u64, u64)>> {
+        // The type ends where the first declarator starts: at its leading `*`,
+        // or at the identifier just before a `,`/`[` (or the end)
+        let first = decl.iter().position(|t| t == "*" || t == "," || t == "[");
+        let type_end = match first {
+            Some(i) if decl[i] == "*" => i,
+            Some(i) => i.saturating_sub(1),
+            None => decl.len().saturating_sub(1),
+        };
+        let (base, rest) = decl.split_at(type_end);
+        if base.is_empty() {
+            return Err(CoreError::InvalidStructLayout(format!(
+                "missing type in '{}'",
+                decl.join(" ")
+            )));
+        }
+
+        let mut out = Vec::new();
+        for declarator in rest.split(|t| t == ",") {
+            let pointer = declarator.iter().any(|t| t == "*");
+            let name = declarator
+                .iter()
+                .find(|t| is_identifier(t) && !t.chars().next().unwrap().is_ascii_digit())
+                .ok_or_else(|| {
+                    CoreError::InvalidStructLayout(format!("missing field name in '{}'", decl.join(" ")))
+                })?;
+
+            let mut count = 1u64;
+            let mut dims = declarator.iter().skip_while(|t| *t != "[");
+            while dims.next().is_some() {
+                let dim = dims.next().ok_or_else(|| {
+                    CoreError::InvalidStructLayout(format!("bad array size for '{}'", name))
+                })?;
+                count *= dim.parse::<u64>().map_err(|_| {
+                    CoreError::InvalidStructLayout(format!("bad array size '{}' for '{}'", dim, name))
+                })?;
+                dims.next(); // ']'
+            }
+            let is_array = declarator.iter().any(|t| t == "[");
+
+            let ty = self.resolve_type(base, pointer)?;
+            let field = StructField {
+                name: name.clone(),
+                offset: 0,
+                field_type: ty.field_type.clone(),
+                array_length: None,
+            };
+            let field = match (&ty.field_type, is_array) {
+                // char buffers become inline strings
+                (FieldType::Value { value_type: ValueType::Int8 }, true)
+                    if base.iter().any(|t| t == "char") && !base.iter().any(|t| t == "signed") =>
+                {
+                    StructField {
+                        field_type: FieldType::Value { value_type: ValueType::String },
+                        array_length: Some(count as u32),
+                        ..field
+                    }
+                }
+                (_, true) => field.with_array_length(count as u32),
+                (_, false) => field,
+            };
+            out.push((field, ty.size * count, ty.align));
+        }
+        Ok(out)
+    }
+
+    fn resolve_type(&self, base: &[String], pointer: bool) -> Result<CType> {
+        let value = |value_type: ValueType, size: u64| CType {
+            field_type: FieldType::Value { value_type },
+            size,
+            align: size,
+        };
+        if pointer {
+            return Ok(value(ValueType::Pointer, self.model.pointer_size()));
+        }
+
+        let words: Vec<&str> = base
+            .iter()
+            .map(|s| s.as_str())
+            .filter(|t| !matches!(*t, "const" | "volatile" | "struct" | "enum"))
+            .collect();
+
+        if let [single] = words.as_slice() {
+            if let Some(alias) = self.aliases.get(*single) {
+                return Ok(alias.clone());
+            }
+            if let Some((name, size, align)) = self.layouts.get(*single) {
+                return Ok(CType {
+                    field_type: FieldType::Struct {
+                        struct_name: name.clone(),
+                    },
+                    size: *size,
+                    align: *align,
+                });
+            }
+        }
+
+        let unsigned = words.contains(&"unsigned");
+        let core: Vec<&str> = words
+            .iter()
+            .copied()
+            .filter(|t| !matches!(*t, "signed" | "unsigned"))
+            .collect();
+        let (signed_type, unsigned_type, size) = match core.as_slice() {
+            ["char"] | ["int8_t"] => (ValueType::Int8, ValueType::UInt8, 1),
+            ["uint8_t"] | ["BYTE"] => (ValueType::UInt8, ValueType::UInt8, 1),
+            ["bool"] | ["_Bool"] => return Ok(value(ValueType::Boolean, 1)),
+            ["short"] | ["short", "int"] | ["int16_t"] => (ValueType::Int16, ValueType::UInt16, 2),
+            ["uint16_t"] | ["WORD"] => (ValueType::UInt16, ValueType::UInt16, 2),
+            ["long"] | ["long", "int"] if self.model.long_size() == 8 => {
+                (ValueType::Int64, ValueType::UInt64, 8)
+            }
+            [] | ["int"] | ["long"] | ["long", "int"] | ["int32_t"] => {
+                (ValueType::Int32, ValueType::UInt32, 4)
+            }
+            ["uint32_t"] | ["DWORD"] => (ValueType::UInt32, ValueType::UInt32, 4),
+            ["long", "long"] | ["long", "long", "int"] | ["int64_t"] => {
+                (ValueType::Int64, ValueType::UInt64, 8)
+            }
+            ["uint64_t"] | ["QWORD"] => (ValueType::UInt64, ValueType::UInt64, 8),
+            ["size_t"] | ["uintptr_t"] => {
+                let size = self.model.pointer_size();
+                let t = if size == 8 { ValueType::UInt64 } else { ValueType::UInt32 };
+                return Ok(value(t, size));
+            }
+            ["float"] => return Ok(value(ValueType::Float, 4)),
+            ["double"] => return Ok(value(ValueType::Double, 8)),
+            _ => {
+                return Err(CoreError::InvalidStructLayout(format!(
+                    "unknown type '{}'",
+                    base.join(" ")
+                )))
+            }
+        };
+        Ok(value(if unsigned { unsigned_type } else { signed_type }, size))
+    }
+}
+
+/// Value of an enumerator initializer: an integer literal or an earlier
+/// enumerator, optionally negated. Other expressions are left unevaluated.
+fn enumerator_value(expr: &[String], known: &HashMap<String, i128>) -> Option<i128> {
+    let (negate, operand) = match expr {
+        [sign, operand] if sign == "-" => (true, operand),
+        [sign, operand] if sign == "+" => (false, operand),
+        [operand] => (false, operand),
+        _ => return None,
+    };
+    let digits = operand.trim_end_matches(['u', 'U', 'l', 'L']);
+    let value = if let Some(value) = known.get(operand) {
+        *value
+    } else if let Some(hex) = digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        i128::from_str_radix(hex, 16).ok()?
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        i128::from_str_radix(&digits[1..], 8).ok()?
+    } else {
+        digits.parse().ok()?
+    };
+    Some(if negate { -value } else { value })
+}
+
+/// Smallest of `int`, `unsigned int`, `long long` and `unsigned long long`
+/// that holds every enumerator
+fn enum_type(values: &[i128]) -> CType {
+    let min = values.iter().copied().min().unwrap_or(0);
+    let max = values.iter().copied().max().unwrap_or(0);
+    let (value_type, size) = if min >= i32::MIN.into() && max <= i32::MAX.into() {
+        (ValueType::Int32, 4)
+    } else if min >= 0 && max <= u32::MAX.into() {
+        (ValueType::UInt32, 4)
+    } else if min >= i64::MIN.into() && max <= i64::MAX.into() {
+        (ValueType::Int64, 8)
+    } else {
+        (ValueType::UInt64, 8)
+    };
+    CType {
+        field_type: FieldType::Value { value_type },
+        size,
+        align: size,
+    }
+}
+
+fn is_identifier(token: &str) -> bool {
+    token.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn align_up(value: u64, align: u64) -> u64 {
+    if align <= 1 {
+        value
+    } else {
+        value.div_ceil(align) * align
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_c_header_offsets() {
+        let header = r#"
+            #pragma once
+            typedef unsigned int u32;
+
+            struct Vec3 { float x, y, z; };
+
+            /* Player entity */
+            typedef struct _Player {
+                int health;          // 0x0
+                char name[16];       // 0x4
+                struct Vec3 pos;     // 0x14
+                double speed;        // 0x20
+                u32 flags[2];        // 0x28
+                struct _Player *target;
+            } Player;
+        "#;
+
+        let structs = parse_c_header(header, DataModel::Lp64).unwrap();
+        assert_eq!(structs.len(), 2);
+
+        let vec3 = &structs[0];
+        assert_eq!(vec3.name, "Vec3");
+        assert_eq!(vec3.size, Some(12));
+        assert_eq!(vec3.fields[2], StructField::value("z", 8, ValueType::Float));
+
+        let player = &structs[1];
+        assert_eq!(player.name, "Player");
+        let offsets: Vec<u64> = player.fields.iter().map(|f| f.offset).collect();
+        assert_eq!(offsets, vec![0x0, 0x4, 0x14, 0x20, 0x28, 0x30]);
+        assert_eq!(player.size, Some(0x38));
+        assert_eq!(
+            player.fields[1],
+            StructField::value("name", 4, ValueType::String).with_array_length(16)
+        );
+        assert_eq!(player.fields[2], StructField::nested("pos", 0x14, "Vec3"));
+        assert_eq!(
+            player.fields[4],
+            StructField::value("flags", 0x28, ValueType::UInt32).with_array_length(2)
+        );
+        assert_eq!(player.fields[5], StructField::value("target", 0x30, ValueType::Pointer));
+    }
+
+    #[test]
+    fn test_parse_c_header_long_follows_data_model() {
+        let header = "struct S { long a; unsigned long b; void *p; size_t n; };";
+
+        let sizes = |model| {
+            let s = parse_c_header(header, model).unwrap().remove(0);
+            (s.fields.iter().map(|f| f.offset).collect::<Vec<_>>(), s.size)
+        };
+        assert_eq!(sizes(DataModel::Lp64), (vec![0, 8, 16, 24], Some(32)));
+        assert_eq!(sizes(DataModel::Llp64), (vec![0, 4, 8, 16], Some(24)));
+        assert_eq!(sizes(DataModel::Ilp32), (vec![0, 4, 8, 12], Some(16)));
+
+        let lp64 = parse_c_header(header, DataModel::Lp64).unwrap().remove(0);
+        assert_eq!(lp64.fields[1], StructField::value("b", 8, ValueType::UInt64));
+        assert_eq!(DataModel::new(8, true), DataModel::Llp64);
+        assert_eq!(DataModel::new(4, false), DataModel::Ilp32);
+    }
+
+    #[test]
+    fn test_parse_c_header_enum_fields() {
+        let header = r#"
+            enum State { IDLE, RUNNING = 0x10, DEAD };
+            typedef enum : unsigned char { LOW, HIGH } Level;
+            typedef enum { SMALL = -1, HUGE = 0x100000000 } Wide;
+
+            struct Unit {
+                enum State state;    // 0x0
+                Level level;         // 0x4
+                enum { A, B } kind;  // 0x8
+                Wide wide;           // 0x10
+            };
+        "#;
+
+        let unit = parse_c_header(header, DataModel::Lp64).unwrap().remove(0);
+        assert_eq!(unit.name, "Unit");
+        assert_eq!(
+            unit.fields,
+            vec![
+                StructField::value("state", 0x0, ValueType::Int32),
+                StructField::value("level", 0x4, ValueType::UInt8),
+                StructField::value("kind", 0x8, ValueType::Int32),
+                StructField::value("wide", 0x10, ValueType::Int64),
+            ]
+        );
+        assert_eq!(unit.size, Some(0x18));
+    }
+
+    #[test]
+    fn test_parse_c_header_unknown_type() {
+        let err = parse_c_header("struct A { Foo bar; };", DataModel::Lp64).unwrap_err();
+        assert!(err.to_string().contains("unknown type"));
+    }
+
+    #[test]
+    fn test_resolve_layout_inlines_nested() {
+        let defs = vec![
+            StructDefinition {
+                name: "Vec2".into(),
+                fields: vec![
+                    StructField::value("x", 0, ValueType::Float),
+                    StructField::value("y", 4, ValueType::Float),
+                ],
+                size: Some(8),
+            },
+            StructDefinition {
+                name: "Entity".into(),
+                fields: vec![
+                    StructField::value("hp", 0, ValueType::Int32),
+                    StructField::nested("path", 8, "Vec2").with_array_length(4),
+                ],
+                size: None,
+            },
+        ];
+
+        let layout = resolve_layout("Entity", &defs).unwrap();
+        assert_eq!(layout["fields"][0]["type"], "int32");
+        assert_eq!(layout["fields"][1]["type"], "struct");
+        assert_eq!(layout["fields"][1]["count"], 4);
+        assert_eq!(layout["fields"][1]["layout"]["size"], 8);
+        assert!(layout.get("size").is_none());
+        assert!(validate_structs(&defs).is_ok());
+    }
+
+    #[test]
+    fn test_validate_structs_rejects_cycles() {
+        let defs = vec![StructDefinition {
+            name: "Node".into(),
+            fields: vec![StructField::nested("next", 0, "Node")],
+            size: None,
+        }];
+        assert!(validate_structs(&defs).is_err());
+    }
+
+    #[test]
+    fn test_struct_definition_serde() {
+        let def = StructDefinition {
+            name: "Ammo".into(),
+            fields: vec![StructField::value("count", 0x10, ValueType::UInt16)],
+            size: None,
+        };
+        let json = serde_json::to_value(&def).unwrap();
+        assert_eq!(json["fields"][0]["kind"], "value");
+        assert_eq!(json["fields"][0]["valueType"], "uint16");
+        let back: StructDefinition = serde_json::from_value(json).unwrap();
+        assert_eq!(back, def);
+    }
+}
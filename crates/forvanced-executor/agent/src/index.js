@@ -0,0 +1,1302 @@
+// Forvanced target agent
+//
+// The one script injected into target processes. The host executor drives it
+// through `executeTargetNode`; generated trainers call the direct helpers
+// further down. Every name in `rpc.exports` is listed in
+// forvanced_frida::protocol, and the executor's tests fail when the two
+// disagree.
+//
+// Compiled with frida-compile by the executor's build script when it is
+// installed (`npm install` here), and embedded as-is otherwise. Keep it free
+// of imports so both forms load the same way.
+
+'use strict';
+
+// Target node implementations
+const targetNodes = {
+    // Memory Operations
+    memory_read: function(config, inputs) {
+        const address = resolveAddress(inputs.address);
+        const valueType = config.valueType || 'int32';
+
+        let value;
+        switch (valueType) {
+            case 'int8': value = address.readS8(); break;
+            case 'uint8': value = address.readU8(); break;
+            case 'int16': value = address.readS16(); break;
+            case 'uint16': value = address.readU16(); break;
+            case 'int32': value = address.readS32(); break;
+            case 'uint32': value = address.readU32(); break;
+            case 'int64': value = address.readS64().toString(); break;
+            case 'uint64': value = address.readU64().toString(); break;
+            case 'float': value = address.readFloat(); break;
+            case 'double': value = address.readDouble(); break;
+            case 'pointer': value = address.readPointer().toString(); break;
+            case 'string': value = address.readUtf8String(); break;
+            default: value = address.readS32();
+        }
+
+        return { value: value };
+    },
+
+    memory_write: function(config, inputs) {
+        const address = resolveAddress(inputs.address);
+        const value = inputs.value;
+        const valueType = config.valueType || 'int32';
+
+        switch (valueType) {
+            case 'int8': address.writeS8(value); break;
+            case 'uint8': address.writeU8(value); break;
+            case 'int16': address.writeS16(value); break;
+            case 'uint16': address.writeU16(value); break;
+            case 'int32': address.writeS32(value); break;
+            case 'uint32': address.writeU32(value); break;
+            case 'int64': address.writeS64(int64(value)); break;
+            case 'uint64': address.writeU64(uint64(value)); break;
+            case 'float': address.writeFloat(value); break;
+            case 'double': address.writeDouble(value); break;
+            case 'pointer': address.writePointer(ptr(value)); break;
+            case 'string': address.writeUtf8String(value); break;
+            default: address.writeS32(value);
+        }
+
+        return {};
+    },
+
+    // Keep writing `value` to `address` until the node runs with enabled=false
+    memory_freeze: function(config, inputs) {
+        const enabled = inputs.enabled !== undefined ? !!inputs.enabled : config.enabled !== false;
+        if (!enabled) {
+            return { frozen: false, released: stopFreeze(inputs.address) };
+        }
+        startFreeze(inputs.address, inputs.value, config.valueType || 'int32', config.intervalMs || 100);
+        return { frozen: true };
+    },
+
+    memory_scan: function(config, inputs) {
+        const pattern = inputs.value.toString();
+        const protection = config.protection || 'r--';
+        const ranges = Process.enumerateRanges(protection);
+
+        const results = [];
+        for (const range of ranges) {
+            const matches = Memory.scanSync(range.base, range.size, pattern);
+            for (const match of matches) {
+                results.push(match.address.toString());
+            }
+        }
+
+        return { results: results, count: results.length };
+    },
+
+    memory_alloc: function(config, inputs) {
+        const size = inputs.size || config.size || 256;
+        const near = inputs.near || config.near;
+        const address = near
+            ? allocNear(ptr(near), size, config.maxDistance)
+            : Memory.alloc(size);
+        return { address: address.toString() };
+    },
+
+    memory_protect: function(config, inputs) {
+        const address = resolveAddress(inputs.address);
+        const size = inputs.size;
+        const protection = config.protection || 'rwx';
+        const success = Memory.protect(address, size, protection);
+        return { success: success };
+    },
+
+    // Code cave: redirect `address` into freshly assembled code
+    code_cave: function(config, inputs, request) {
+        const address = resolveAddress(inputs.address);
+        const id = config.caveId || address.toString();
+        const enabled = inputs.enabled !== undefined ? !!inputs.enabled : config.enabled !== false;
+
+        if (!enabled) {
+            return { cave: '0x0', enabled: false, reverted: revertCodeCave(id) };
+        }
+
+        const existing = activeCodeCaves.get(id);
+        if (existing) {
+            return { cave: existing.cave.toString(), enabled: true };
+        }
+
+        const entry = installCodeCave(address, config.instructions || [], {
+            keepOriginal: config.keepOriginal !== false,
+            maxDistance: config.maxDistance
+        });
+        entry.request = request;
+        activeCodeCaves.set(id, entry);
+        return { cave: entry.cave.toString(), enabled: true };
+    },
+
+    // Struct Operations (config.layout is resolved on the host)
+    struct_read: function(config, inputs) {
+        return { value: readStruct(resolveAddress(inputs.address), config.layout) };
+    },
+
+    struct_write: function(config, inputs) {
+        writeStruct(resolveAddress(inputs.address), config.layout, inputs.value || {});
+        return {};
+    },
+
+    // Pointer Operations
+    pointer_add: function(config, inputs) {
+        const pointer = resolveAddress(inputs.pointer);
+        const offset = parseInt(inputs.offset);
+        const result = pointer.add(offset);
+        return { result: result.toString() };
+    },
+
+    pointer_read: function(config, inputs) {
+        const pointer = resolveAddress(inputs.pointer);
+        const readType = config.readType || 'uint32';
+
+        let value;
+        switch (readType) {
+            case 'pointer': value = pointer.readPointer().toString(); break;
+            case 'int8': value = pointer.readS8(); break;
+            case 'uint8': value = pointer.readU8(); break;
+            case 'int16': value = pointer.readS16(); break;
+            case 'uint16': value = pointer.readU16(); break;
+            case 'int32': value = pointer.readS32(); break;
+            case 'uint32': value = pointer.readU32(); break;
+            case 'int64': value = pointer.readS64().toString(); break;
+            case 'uint64': value = pointer.readU64().toString(); break;
+            case 'float': value = pointer.readFloat(); break;
+            case 'double': value = pointer.readDouble(); break;
+            case 'utf8': value = pointer.readUtf8String(); break;
+            case 'utf16': value = pointer.readUtf16String(); break;
+            default: value = pointer.readU32();
+        }
+
+        return { value: value };
+    },
+
+    pointer_write: function(config, inputs) {
+        const pointer = resolveAddress(inputs.pointer);
+        const value = inputs.value;
+        const writeType = config.writeType || 'uint32';
+
+        switch (writeType) {
+            case 'pointer': pointer.writePointer(ptr(value)); break;
+            case 'int8': pointer.writeS8(value); break;
+            case 'uint8': pointer.writeU8(value); break;
+            case 'int16': pointer.writeS16(value); break;
+            case 'uint16': pointer.writeU16(value); break;
+            case 'int32': pointer.writeS32(value); break;
+            case 'uint32': pointer.writeU32(value); break;
+            case 'int64': pointer.writeS64(int64(value)); break;
+            case 'uint64': pointer.writeU64(uint64(value)); break;
+            case 'float': pointer.writeFloat(value); break;
+            case 'double': pointer.writeDouble(value); break;
+            case 'utf8': pointer.writeUtf8String(value); break;
+            case 'utf16': pointer.writeUtf16String(value); break;
+            default: pointer.writeU32(value);
+        }
+
+        return {};
+    },
+
+    // Module Operations
+    get_module: function(config, inputs) {
+        const name = inputs.name;
+        const module = Process.findModuleByName(name);
+        if (!module) {
+            return { module: null, base: '0x0', size: 0 };
+        }
+        return {
+            module: module.name,
+            base: module.base.toString(),
+            size: module.size
+        };
+    },
+
+    find_symbol: function(config, inputs) {
+        const moduleName = inputs.module;
+        const symbolName = inputs.symbol;
+        const address = findExport(moduleName, symbolName);
+        return { address: address ? address.toString() : '0x0' };
+    },
+
+    resolve_address: function(config, inputs) {
+        return { address: resolveAddress(inputs.address).toString() };
+    },
+
+    get_base_address: function(config, inputs) {
+        const moduleName = inputs.moduleName;
+        const module = Process.findModuleByName(moduleName);
+        return { address: module ? module.base.toString() : '0x0' };
+    },
+
+    enumerate_modules: function(config, inputs) {
+        const modules = Process.enumerateModules();
+        return {
+            modules: modules.map(m => ({
+                name: m.name,
+                base: m.base.toString(),
+                size: m.size,
+                path: m.path
+            })),
+            count: modules.length
+        };
+    },
+
+    enumerate_exports: function(config, inputs) {
+        const moduleName = inputs.moduleName;
+        const module = Process.findModuleByName(moduleName);
+        if (!module) {
+            return { exports: [], count: 0 };
+        }
+        const exports = module.enumerateExports();
+        return {
+            exports: exports.map(e => ({
+                name: e.name,
+                address: e.address.toString(),
+                type: e.type
+            })),
+            count: exports.length
+        };
+    },
+
+    // Native Call
+    call_native: function(config, inputs) {
+        const address = resolveAddress(inputs.address);
+        const returnType = config.returnType || 'void';
+        const argTypes = config.argTypes || [];
+        const abi = config.abi || 'default';
+
+        const args = [];
+        for (let i = 0; i < (config.argCount || 0); i++) {
+            args.push(inputs['arg' + i]);
+        }
+
+        const func = new NativeFunction(address, returnType, argTypes, { abi: abi });
+        const result = func(...args);
+
+        return { return: result };
+    },
+
+    // Interceptor
+    interceptor_attach: function(config, inputs, request) {
+        const address = resolveAddress(inputs.address);
+        const hookId = requestHookId(config, inputs) || address.toString();
+        if (activeHooks.has(hookId)) {
+            return { hookId: hookId, success: true };
+        }
+
+        const hook = createHook(hookId, 'attach', address, request);
+        const captureArgs = config.captureArgs !== undefined ? config.captureArgs : 4;
+        // Args are always captured: retval rule conditions may test them
+        const callbacks = {
+            onEnter: function(args) {
+                applyArgRules(hook, args);
+                const captured = [];
+                for (let i = 0; i < captureArgs; i++) {
+                    captured.push(args[i].toString());
+                }
+                // Per call on `this`; other threads may be inside the same function
+                this.capturedArgs = captured;
+                hook.lastArgs = captured;
+                if (config.onEnter !== false) {
+                    send({
+                        type: 'hook', hookId: hookId, phase: 'enter', args: captured,
+                        address: address.toString(), threadId: this.threadId
+                    });
+                }
+            }
+        };
+        if (config.onLeave !== false) {
+            callbacks.onLeave = function(retval) {
+                const replaced = retvalRuleValue(hook, this.capturedArgs, retval);
+                if (replaced !== undefined) {
+                    retval.replace(ptr(replaced));
+                }
+                let captured = null;
+                if (config.captureRetval !== false) {
+                    captured = retval.toString();
+                    hook.lastRetval = captured;
+                }
+                send({
+                    type: 'hook', hookId: hookId, phase: 'leave', retval: captured,
+                    address: address.toString(), threadId: this.threadId
+                });
+            };
+        }
+        hook.listener = Interceptor.attach(address, callbacks);
+        activeHooks.set(hookId, hook);
+        return { hookId: hookId, success: true };
+    },
+
+    interceptor_replace: function(config, inputs, request) {
+        const address = resolveAddress(inputs.target);
+        const hookId = requestHookId(config, inputs) || address.toString();
+        if (activeHooks.has(hookId)) {
+            return { hookId: hookId, success: true };
+        }
+
+        const hook = createHook(hookId, 'replace', address, request);
+        if (inputs.replacement) {
+            Interceptor.replace(address, ptr(inputs.replacement));
+        } else {
+            // Call through to the original, applying the hook's rules in-target
+            const returnType = config.returnType || 'void';
+            const argTypes = config.argTypes || [];
+            const abi = config.abi || 'default';
+            const original = new NativeFunction(address, returnType, argTypes, { abi: abi });
+            hook.callback = new NativeCallback(function(...args) {
+                applyArgRules(hook, args, argTypes);
+                hook.lastArgs = args.map(a => a.toString());
+                let retval = original(...args);
+                const replaced = retvalRuleValue(hook, args, retval);
+                if (replaced !== undefined) {
+                    retval = toNativeValue(replaced, returnType);
+                }
+                hook.lastRetval = retval === undefined ? null : retval.toString();
+                return retval;
+            }, returnType, argTypes, abi);
+            Interceptor.replace(address, hook.callback);
+        }
+        activeHooks.set(hookId, hook);
+        return { hookId: hookId, success: true };
+    },
+
+    interceptor_detach: function(config, inputs) {
+        const hookId = requestHookId(config, inputs);
+        if (!hookId) {
+            Interceptor.detachAll();
+            for (const hook of activeHooks.values()) {
+                if (hook.kind === 'replace') {
+                    Interceptor.revert(hook.address);
+                }
+            }
+            activeHooks.clear();
+            return { success: true };
+        }
+        return { success: removeHook(hookId) };
+    },
+
+    // Reads use the event_hook context when given, otherwise the last captured call
+    read_arg: function(config, inputs) {
+        const ctx = hookContext(inputs.context);
+        const index = config.index || 0;
+        let args = ctx.args;
+        if (!args) {
+            const hook = activeHooks.get(ctx.hookId || requestHookId(config, inputs));
+            args = hook ? hook.lastArgs : [];
+        }
+        const raw = args[index];
+        return { value: raw === undefined || raw === null ? null : castHookValue(raw, config.asType) };
+    },
+
+    read_retval: function(config, inputs) {
+        const ctx = hookContext(inputs.context);
+        let raw = ctx.retval;
+        if (raw === undefined || raw === null) {
+            const hook = activeHooks.get(ctx.hookId || requestHookId(config, inputs));
+            raw = hook ? hook.lastRetval : null;
+        }
+        return { value: raw === undefined || raw === null ? null : castHookValue(raw, config.asType) };
+    },
+
+    // Writes install a rule on the hook, so toggling never re-hooks
+    write_arg: function(config, inputs, request) {
+        const hook = hookForRule(config, inputs);
+        const index = config.index || 0;
+        setHookRule(hook, config.ruleId || 'arg' + index, {
+            target: 'arg',
+            index: index,
+            value: inputs.value !== undefined ? inputs.value : config.value,
+            when: config.when || null
+        }, request);
+        return {};
+    },
+
+    replace_retval: function(config, inputs, request) {
+        const hook = hookForRule(config, inputs);
+        setHookRule(hook, config.ruleId || 'retval', {
+            target: 'retval',
+            value: inputs.value !== undefined ? inputs.value : config.value,
+            when: config.when || null
+        }, request);
+        return {};
+    },
+
+    // NativeCallback whose body runs on the host; `target` (optional) is replaced by it
+    native_callback: function(config, inputs, request) {
+        const callbackId = config.callbackId || request.node_id;
+        const existing = nativeCallbacks.get(callbackId);
+        if (existing) {
+            return { callback: existing.callback.toString(), callbackId: callbackId };
+        }
+
+        const returnType = config.returnType || 'void';
+        const argTypes = config.argTypes || [];
+        const abi = config.abi || 'default';
+        const timeoutMs = config.timeoutMs || 1000;
+        const target = inputs.target ? resolveAddress(inputs.target) : null;
+        const original = target ? new NativeFunction(target, returnType, argTypes, { abi: abi }) : null;
+
+        const callback = new NativeCallback(function(...args) {
+            const reply = forwardToHost(callbackId, args, timeoutMs);
+            if (reply.received && reply.value !== null && reply.value !== undefined) {
+                return returnType === 'void' ? undefined : toNativeValue(reply.value, returnType);
+            }
+            // Timed out or no value returned: behave like the original function
+            if (original) {
+                return original(...args);
+            }
+            return returnType === 'void' ? undefined : toNativeValue(0, returnType);
+        }, returnType, argTypes, abi);
+
+        if (target) {
+            Interceptor.replace(target, callback);
+        }
+        nativeCallbacks.set(callbackId, { target: target, callback: callback, request: request });
+        return { callback: callback.toString(), callbackId: callbackId };
+    }
+};
+
+// Interceptor state (for attached hooks)
+const activeInterceptors = new Map();
+
+// Export address or null. Frida 17 dropped the static Module.findExportByName;
+// exports are looked up on a module, or across all modules without one
+function findExport(moduleName, name) {
+    if (moduleName === null || moduleName === undefined || moduleName === '') {
+        return Module.findGlobalExportByName(name);
+    }
+    const module = Process.findModuleByName(moduleName);
+    return module === null ? null : module.findExportByName(name);
+}
+
+// Resolve an address input: plain pointers/numbers, or a spec built by the host
+// from module+offset, export, symbol and pointer-chain syntax
+function resolveAddress(spec) {
+    if (typeof spec !== 'object' || spec === null || spec instanceof NativePointer) {
+        return ptr(spec);
+    }
+    const offsetBy = (p, off) => off.startsWith('-') ? p.sub(ptr(off.slice(1))) : p.add(ptr(off));
+    const base = spec.base;
+    let address;
+    switch (base.kind) {
+        case 'absolute':
+            address = ptr(base.address);
+            break;
+        case 'module':
+            address = Process.getModuleByName(base.module).base;
+            break;
+        case 'export':
+            address = findExport(base.module, base.name);
+            if (address === null) throw new Error('Export not found: ' + base.name);
+            break;
+        case 'symbol':
+            address = DebugSymbol.getFunctionByName(base.name);
+            break;
+        default:
+            throw new Error('Unknown address kind: ' + base.kind);
+    }
+    address = offsetBy(address, spec.offset);
+    for (const off of spec.chain) {
+        address = offsetBy(address.readPointer(), off);
+    }
+    return address;
+}
+
+// Size in bytes of one element of a struct field
+function fieldSize(field) {
+    switch (field.type) {
+        case 'int8': case 'uint8': case 'boolean': case 'string': return 1;
+        case 'int16': case 'uint16': return 2;
+        case 'int32': case 'uint32': case 'float': return 4;
+        case 'int64': case 'uint64': case 'double': return 8;
+        case 'pointer': return Process.pointerSize;
+        case 'struct': return structSize(field.layout);
+        default: throw new Error('Unsupported struct field type: ' + field.type);
+    }
+}
+
+function structSize(layout) {
+    if (layout.size !== undefined) {
+        return layout.size;
+    }
+    let size = 0;
+    for (const field of layout.fields) {
+        const count = field.type === 'string' ? 1 : (field.count || 1);
+        const length = field.type === 'string' ? (field.count || 1) : fieldSize(field);
+        size = Math.max(size, field.offset + length * count);
+    }
+    return size;
+}
+
+function readField(address, field) {
+    switch (field.type) {
+        case 'int8': return address.readS8();
+        case 'uint8': return address.readU8();
+        case 'int16': return address.readS16();
+        case 'uint16': return address.readU16();
+        case 'int32': return address.readS32();
+        case 'uint32': return address.readU32();
+        case 'int64': return address.readS64().toString();
+        case 'uint64': return address.readU64().toString();
+        case 'float': return address.readFloat();
+        case 'double': return address.readDouble();
+        case 'boolean': return address.readU8() !== 0;
+        case 'pointer': return address.readPointer().toString();
+        case 'string': return address.readUtf8String(field.count || -1);
+        case 'struct': return readStruct(address, field.layout);
+        default: throw new Error('Unsupported struct field type: ' + field.type);
+    }
+}
+
+function writeField(address, field, value) {
+    switch (field.type) {
+        case 'int8': address.writeS8(value); break;
+        case 'uint8': address.writeU8(value); break;
+        case 'int16': address.writeS16(value); break;
+        case 'uint16': address.writeU16(value); break;
+        case 'int32': address.writeS32(value); break;
+        case 'uint32': address.writeU32(value); break;
+        case 'int64': address.writeS64(int64(value)); break;
+        case 'uint64': address.writeU64(uint64(value)); break;
+        case 'float': address.writeFloat(value); break;
+        case 'double': address.writeDouble(value); break;
+        case 'boolean': address.writeU8(value ? 1 : 0); break;
+        case 'pointer': address.writePointer(ptr(value)); break;
+        case 'string': {
+            // Keep within the inline buffer, always NUL-terminated
+            const utf8 = unescape(encodeURIComponent(String(value)));
+            const bytes = [];
+            for (let i = 0; i < Math.min(utf8.length, (field.count || 1) - 1); i++) {
+                bytes.push(utf8.charCodeAt(i));
+            }
+            bytes.push(0);
+            address.writeByteArray(bytes);
+            break;
+        }
+        case 'struct': writeStruct(address, field.layout, value || {}); break;
+        default: throw new Error('Unsupported struct field type: ' + field.type);
+    }
+}
+
+function readStruct(base, layout) {
+    const result = {};
+    for (const field of layout.fields) {
+        const address = base.add(field.offset);
+        if (field.count !== undefined && field.type !== 'string') {
+            const stride = fieldSize(field);
+            const items = [];
+            for (let i = 0; i < field.count; i++) {
+                items.push(readField(address.add(i * stride), field));
+            }
+            result[field.name] = items;
+        } else {
+            result[field.name] = readField(address, field);
+        }
+    }
+    return result;
+}
+
+// Only fields present in `value` are written
+function writeStruct(base, layout, value) {
+    for (const field of layout.fields) {
+        if (!(field.name in value)) {
+            continue;
+        }
+        const address = base.add(field.offset);
+        if (field.count !== undefined && field.type !== 'string') {
+            const stride = fieldSize(field);
+            const items = value[field.name] || [];
+            for (let i = 0; i < Math.min(field.count, items.length); i++) {
+                writeField(address.add(i * stride), field, items[i]);
+            }
+        } else {
+            writeField(address, field, value[field.name]);
+        }
+    }
+}
+
+// Hooks created by interceptor nodes: hookId -> { kind, address, listener, rules, lastArgs, lastRetval }
+// lastArgs/lastRetval record the most recent call for reads; rules never use them
+// `request` is the node request that created it, replayed by importState
+const activeHooks = new Map();
+
+function createHook(hookId, kind, address, request) {
+    return {
+        id: hookId,
+        kind: kind,
+        address: address,
+        request: request,
+        listener: null,
+        callback: null,
+        rules: new Map(),
+        lastArgs: [],
+        lastRetval: null
+    };
+}
+
+function removeHook(hookId) {
+    const hook = activeHooks.get(hookId);
+    if (!hook) {
+        return false;
+    }
+    if (hook.listener) {
+        hook.listener.detach();
+    } else {
+        Interceptor.revert(hook.address);
+    }
+    activeHooks.delete(hookId);
+    return true;
+}
+
+// `context` may be the whole event_hook payload or just its args/retval output
+function hookContext(context) {
+    if (Array.isArray(context)) {
+        return { args: context };
+    }
+    if (context !== null && typeof context === 'object') {
+        return context;
+    }
+    return context === undefined || context === null ? {} : { retval: context };
+}
+
+// The hookId input wins over config, the same precedence the host's replay
+// journal keys hooks and rules by
+function requestHookId(config, inputs) {
+    return inputs.hookId || config.hookId;
+}
+
+function hookForRule(config, inputs) {
+    const ctx = hookContext(inputs.context);
+    const hookId = ctx.hookId || requestHookId(config, inputs);
+    const hook = activeHooks.get(hookId);
+    if (!hook) {
+        throw new Error('Hook not found: ' + hookId);
+    }
+    return hook;
+}
+
+// `enabled` (input or config) toggles a rule in place; disabled rules stay registered
+function setHookRule(hook, ruleId, rule, request) {
+    const config = request.config || {};
+    const inputs = request.inputs || {};
+    rule.enabled = inputs.enabled !== undefined ? !!inputs.enabled : config.enabled !== false;
+    rule.request = request;
+    hook.rules.set(ruleId, rule);
+}
+
+function castHookValue(raw, asType) {
+    const p = typeof raw === 'number' ? ptr(raw) : ptr(raw.toString());
+    switch (asType) {
+        case 'int32': return p.toInt32();
+        case 'uint32': return p.toUInt32();
+        case 'string': return p.readUtf8String();
+        default: return p.toString();
+    }
+}
+
+function toNativeValue(value, type) {
+    switch (type) {
+        case 'pointer': return ptr(value);
+        case 'int64': return int64(value);
+        case 'uint64': return uint64(value);
+        case 'bool': return value ? 1 : 0;
+        default: return Number(value);
+    }
+}
+
+// Optional rule condition: { source: 'arg' | 'retval', index, op, value, valueType }
+function ruleMatches(rule, args, retval) {
+    const when = rule.when;
+    if (!when) {
+        return true;
+    }
+    const raw = when.source === 'retval' ? retval : args[when.index || 0];
+    if (raw === undefined || raw === null) {
+        return false;
+    }
+    let cmp;
+    if (when.valueType === 'int32' || when.valueType === 'uint32' || typeof raw === 'number') {
+        const a = typeof raw === 'number' ? raw : castHookValue(raw, when.valueType || 'int32');
+        cmp = Math.sign(a - Number(when.value));
+    } else {
+        cmp = ptr(raw.toString()).compare(ptr(when.value));
+    }
+    switch (when.op || '==') {
+        case '==': return cmp === 0;
+        case '!=': return cmp !== 0;
+        case '<': return cmp < 0;
+        case '<=': return cmp <= 0;
+        case '>': return cmp > 0;
+        case '>=': return cmp >= 0;
+        default: return false;
+    }
+}
+
+function applyArgRules(hook, args, argTypes) {
+    for (const rule of hook.rules.values()) {
+        if (rule.enabled && rule.target === 'arg' && ruleMatches(rule, args, null)) {
+            args[rule.index] = argTypes
+                ? toNativeValue(rule.value, argTypes[rule.index])
+                : ptr(rule.value);
+        }
+    }
+}
+
+function retvalRuleValue(hook, args, retval) {
+    let value;
+    for (const rule of hook.rules.values()) {
+        if (rule.enabled && rule.target === 'retval' && ruleMatches(rule, args, retval)) {
+            value = rule.value;
+        }
+    }
+    return value;
+}
+
+// Native callbacks: callbackId -> { target, callback }
+const nativeCallbacks = new Map();
+
+// Calls waiting for a host reply: callId -> { received, value }
+const pendingNativeCalls = new Map();
+let nativeCallSeq = 0;
+
+// Send the call to the host and block this thread until it replies or times out
+function forwardToHost(callbackId, args, timeoutMs) {
+    const callId = ++nativeCallSeq;
+    const pending = { received: false, value: null };
+    pendingNativeCalls.set(callId, pending);
+    send({
+        type: 'native_callback',
+        callbackId: callbackId,
+        callId: callId,
+        args: args.map(a => a === undefined || a === null ? null : a.toString())
+    });
+
+    // Thread.sleep releases the JS lock, letting the reply handler run
+    const deadline = Date.now() + timeoutMs;
+    while (!pending.received && Date.now() < deadline) {
+        Thread.sleep(0.001);
+    }
+    pendingNativeCalls.delete(callId);
+    return pending;
+}
+
+function resolveNativeCall(callId, value) {
+    const pending = pendingNativeCalls.get(callId);
+    if (!pending) {
+        return false;
+    }
+    pending.value = value;
+    pending.received = true;
+    return true;
+}
+
+// Host replies via post({ type: 'native_callback_reply', callId, value })
+function listenNativeReplies() {
+    recv('native_callback_reply', function(message) {
+        resolveNativeCall(message.callId, message.value);
+        listenNativeReplies();
+    });
+}
+listenNativeReplies();
+
+// Code cave state: id -> { address, size, original, cave }
+const activeCodeCaves = new Map();
+
+// Architecture-specific writer/relocator and the size of a near jump
+function caveArch() {
+    switch (Process.arch) {
+        case 'ia32':
+        case 'x64':
+            return {
+                Writer: X86Writer,
+                Relocator: X86Relocator,
+                jumpSize: 5,
+                maxDistance: 0x7fff0000,
+                putJump: (w, target) => w.putJmpAddress(target),
+                pad: (w, n) => w.putNopPadding(n)
+            };
+        case 'arm64':
+            return {
+                Writer: Arm64Writer,
+                Relocator: Arm64Relocator,
+                jumpSize: 4,
+                maxDistance: 0x7fff000,
+                putJump: (w, target) => w.putBranchAddress(target),
+                pad: (w, n) => { for (let i = 0; i < n; i += 4) w.putNop(); }
+            };
+        default:
+            throw new Error('code_cave is not supported on ' + Process.arch);
+    }
+}
+
+// Allocate memory within branch range of `address`
+function allocNear(address, size, maxDistance) {
+    return Memory.alloc(size, {
+        near: address,
+        maxDistance: maxDistance || caveArch().maxDistance
+    });
+}
+
+// Writer argument: numbers and register names pass through, { address: "0x..." }
+// becomes a pointer. A numeric string could be meant either way, so it is refused
+function caveOperand(insn, arg) {
+    if (typeof arg === 'string' && /^[-+]?(0x[0-9a-f]+|\d+)$/i.test(arg)) {
+        throw new Error('Ambiguous code cave operand ' + JSON.stringify(arg) + ' in ' +
+            JSON.stringify(insn) + ': pass a number, or { address: "0x..." } for a pointer');
+    }
+    if (arg !== null && typeof arg === 'object' && typeof arg.address === 'string') {
+        return ptr(arg.address);
+    }
+    return arg;
+}
+
+// Emit one user instruction: { bytes: "90 90" } or { op: "putMovRegU32", args: ["eax", 0] }
+function emitInstruction(writer, insn) {
+    if (typeof insn === 'string') {
+        insn = { bytes: insn };
+    }
+    if (insn.bytes !== undefined) {
+        const hex = insn.bytes.replace(/[^0-9a-fA-F]/g, '');
+        const bytes = [];
+        for (let i = 0; i < hex.length; i += 2) {
+            bytes.push(parseInt(hex.substr(i, 2), 16));
+        }
+        writer.putBytes(bytes);
+        return;
+    }
+    const op = insn.op;
+    if (typeof op !== 'string' || !op.startsWith('put') || typeof writer[op] !== 'function') {
+        throw new Error('Invalid code cave instruction: ' + JSON.stringify(insn));
+    }
+    const args = (insn.args || []).map(a => caveOperand(insn, a));
+    writer[op].apply(writer, args);
+}
+
+// Size of the jump from `from` to `to`, assembled off to the side
+function jumpSize(arch, from, to) {
+    const scratch = Memory.alloc(Process.pageSize);
+    const writer = new arch.Writer(scratch, { pc: from });
+    arch.putJump(writer, to);
+    writer.flush();
+    const size = writer.offset;
+    writer.dispose();
+    return size;
+}
+
+function installCodeCave(address, instructions, options) {
+    const arch = caveArch();
+    const cave = allocNear(address, Process.pageSize, options.maxDistance || arch.maxDistance);
+
+    // A far jump would overwrite more than the relocated instructions; refuse
+    // before anything is written
+    if (jumpSize(arch, address, cave) > arch.jumpSize) {
+        throw new Error('Code cave is out of branch range of ' + address);
+    }
+
+    // Assemble user code, relocate the instructions we overwrite, then jump back
+    let overwritten = 0;
+    Memory.patchCode(cave, Process.pageSize, code => {
+        const writer = new arch.Writer(code, { pc: cave });
+        for (const insn of instructions) {
+            emitInstruction(writer, insn);
+        }
+
+        const relocator = new arch.Relocator(address, writer);
+        do {
+            overwritten = relocator.readOne();
+            if (overwritten === 0) {
+                throw new Error('Unable to relocate instructions at ' + address);
+            }
+        } while (overwritten < arch.jumpSize);
+
+        if (options.keepOriginal) {
+            relocator.writeAll();
+        }
+        arch.putJump(writer, address.add(overwritten));
+        writer.flush();
+        writer.dispose();
+        relocator.dispose();
+    });
+
+    const original = address.readByteArray(overwritten);
+
+    Memory.patchCode(address, overwritten, code => {
+        const writer = new arch.Writer(code, { pc: address });
+        arch.putJump(writer, cave);
+        arch.pad(writer, overwritten - writer.offset);
+        writer.flush();
+        writer.dispose();
+    });
+
+    return { address: address, size: overwritten, original: original, cave: cave };
+}
+
+// Restore the original bytes; the cave itself is released with its last reference
+function revertCodeCave(id) {
+    const entry = activeCodeCaves.get(id);
+    if (!entry) {
+        return false;
+    }
+    Memory.patchCode(entry.address, entry.size, code => {
+        code.writeByteArray(entry.original);
+    });
+    activeCodeCaves.delete(id);
+    return true;
+}
+
+// Memory freezes from the trainer API: resolved address -> { address, value, type, intervalMs, timer }
+const activeFreezes = new Map();
+
+function startFreeze(address, value, type, intervalMs) {
+    const target = resolveAddress(address);
+    stopFreeze(target);
+    const timer = setInterval(() => {
+        targetNodes.memory_write({ valueType: type }, { address: target, value: value });
+    }, intervalMs);
+    activeFreezes.set(target.toString(), {
+        address: address, value: value, type: type, intervalMs: intervalMs, timer: timer
+    });
+}
+
+function stopFreeze(address) {
+    const key = resolveAddress(address).toString();
+    const freeze = activeFreezes.get(key);
+    if (!freeze) {
+        return false;
+    }
+    clearInterval(freeze.timer);
+    activeFreezes.delete(key);
+    return true;
+}
+
+// Byte pattern of `value` as stored in memory, for Memory.scan
+function valuePattern(value, type) {
+    const buffer = Memory.alloc(8);
+    targetNodes.memory_write({ valueType: type }, { address: buffer, value: value });
+    const size = { int8: 1, uint8: 1, int16: 2, uint16: 2, int64: 8, uint64: 8, double: 8 }[type] || 4;
+    return Array.from(new Uint8Array(buffer.readByteArray(size)))
+        .map(b => ('0' + b.toString(16)).slice(-2))
+        .join(' ');
+}
+
+function requireRuntime(name, available) {
+    if (!available) {
+        throw new Error(name + ' runtime not available');
+    }
+}
+
+// Node requests that rebuild the live hooks, rules, caves and callbacks, in
+// dependency order. Rules carry their current enabled flag, which may have
+// been toggled from the host since they were installed.
+function collectState() {
+    const requests = [];
+    for (const entry of nativeCallbacks.values()) {
+        requests.push(entry.request);
+    }
+    for (const hook of activeHooks.values()) {
+        if (!hook.request) {
+            continue;
+        }
+        requests.push(hook.request);
+        for (const rule of hook.rules.values()) {
+            const inputs = Object.assign({}, rule.request.inputs, { enabled: rule.enabled });
+            requests.push(Object.assign({}, rule.request, { inputs: inputs }));
+        }
+    }
+    for (const entry of activeCodeCaves.values()) {
+        requests.push(entry.request);
+    }
+    return requests;
+}
+
+// Undo everything collectState describes; a function can only be replaced by
+// one script at a time, so the old script lets go before the new one hooks
+function releaseState() {
+    for (const id of Array.from(activeHooks.keys())) {
+        removeHook(id);
+    }
+    for (const id of Array.from(activeCodeCaves.keys())) {
+        revertCodeCave(id);
+    }
+    for (const entry of nativeCallbacks.values()) {
+        if (entry.target) {
+            Interceptor.revert(entry.target);
+        }
+    }
+    nativeCallbacks.clear();
+    for (const address of Array.from(activeFreezes.keys())) {
+        stopFreeze(address);
+    }
+}
+
+// RPC exports for host communication
+rpc.exports = {
+    // Execute a single target node
+    executeTargetNode: function(request) {
+        try {
+            const handler = targetNodes[request.node_type];
+            if (!handler) {
+                return {
+                    id: request.id,
+                    success: false,
+                    error: 'Unknown node type: ' + request.node_type
+                };
+            }
+
+            const outputs = handler(request.config || {}, request.inputs || {}, request);
+            return {
+                id: request.id,
+                success: true,
+                outputs: outputs
+            };
+        } catch (e) {
+            return {
+                id: request.id,
+                success: false,
+                error: e.toString()
+            };
+        }
+    },
+
+    // Attach interceptor
+    attachInterceptor: function(id, address, options) {
+        const addr = ptr(address);
+        const listener = Interceptor.attach(addr, {
+            onEnter: function(args) {
+                send({ type: 'interceptor', event: 'enter', id: id, args: args });
+            },
+            onLeave: function(retval) {
+                send({ type: 'interceptor', event: 'leave', id: id, retval: retval });
+            }
+        });
+        activeInterceptors.set(id, listener);
+        return { success: true };
+    },
+
+    // Detach interceptor
+    detachInterceptor: function(id) {
+        const listener = activeInterceptors.get(id);
+        if (listener) {
+            listener.detach();
+            activeInterceptors.delete(id);
+            return { success: true };
+        }
+        if (removeHook(id)) {
+            return { success: true };
+        }
+        return { success: false, error: 'Interceptor not found' };
+    },
+
+    // Detach all interceptors
+    detachAll: function() {
+        Interceptor.detachAll();
+        for (const hook of activeHooks.values()) {
+            if (hook.kind === 'replace') {
+                Interceptor.revert(hook.address);
+            }
+        }
+        activeInterceptors.clear();
+        activeHooks.clear();
+        return { success: true };
+    },
+
+    // Toggle a hook rule from the host without re-hooking
+    setHookRuleEnabled: function(hookId, ruleId, enabled) {
+        const hook = activeHooks.get(hookId);
+        const rule = hook ? hook.rules.get(ruleId) : undefined;
+        if (!rule) {
+            return { success: false, error: 'Hook rule not found' };
+        }
+        rule.enabled = !!enabled;
+        return { success: true };
+    },
+
+    // Reply to a pending native_callback call (RPC alternative to post)
+    nativeCallbackReply: function(callId, value) {
+        return { success: resolveNativeCall(callId, value) };
+    },
+
+    // Revert all installed code caves
+    revertCodeCaves: function() {
+        for (const id of Array.from(activeCodeCaves.keys())) {
+            revertCodeCave(id);
+        }
+        return { success: true };
+    },
+
+    // Hot reload: the outgoing script hands over its state and lets go of it
+    exportState: function() {
+        const requests = collectState();
+        const freezes = Array.from(activeFreezes.values()).map(f => ({
+            address: f.address, value: f.value, type: f.type, intervalMs: f.intervalMs
+        }));
+        releaseState();
+        return { requests: requests, freezes: freezes };
+    },
+
+    // Hot reload: the incoming script re-establishes the old script's state
+    importState: function(state) {
+        const requests = (state && state.requests) || [];
+        const failed = [];
+        for (const f of (state && state.freezes) || []) {
+            startFreeze(f.address, f.value, f.type, f.intervalMs);
+        }
+        for (const request of requests) {
+            const result = rpc.exports.executeTargetNode(request);
+            if (!result.success) {
+                failed.push({ node_type: request.node_type, error: result.error });
+            }
+        }
+        return { restored: requests.length - failed.length, failed: failed };
+    },
+
+    // Ping for health check
+    ping: function() {
+        return { alive: true, timestamp: Date.now() };
+    },
+
+    // Trainer API: direct calls made by generated trainers
+
+    memoryRead: function(address, type) {
+        return targetNodes.memory_read({ valueType: type }, { address: address }).value;
+    },
+
+    memoryWrite: function(address, value, type) {
+        targetNodes.memory_write({ valueType: type }, { address: address, value: value });
+        return { success: true };
+    },
+
+    // Rewrite `value` every `intervalMs` until unfrozen
+    memoryFreeze: function(address, value, type, intervalMs) {
+        startFreeze(address, value, type, intervalMs || 100);
+        return { success: true };
+    },
+
+    memoryUnfreeze: function(address) {
+        return { success: stopFreeze(address) };
+    },
+
+    patternScan: function(pattern, protection) {
+        return targetNodes.memory_scan({ protection: protection }, { value: pattern }).results;
+    },
+
+    valueScan: function(value, type) {
+        return targetNodes.memory_scan({ protection: 'rw-' }, { value: valuePattern(value, type) }).results;
+    },
+
+    hookFunction: function(address, options) {
+        const target = resolveAddress(address);
+        const id = target.toString();
+        if (!activeInterceptors.has(id)) {
+            activeInterceptors.set(id, Interceptor.attach(target, {
+                onEnter: options.logEnter ? function(args) {
+                    console.log('[ENTER]', id);
+                } : undefined,
+                onLeave: options.logLeave ? function(retval) {
+                    console.log('[LEAVE]', id, '=', retval);
+                } : undefined
+            }));
+        }
+        return { success: true, id: id };
+    },
+
+    // Built from node requests so the hook survives a hot reload
+    replaceReturn: function(address, value) {
+        const hookId = 'return:' + resolveAddress(address).toString();
+        for (const request of [
+            { node_type: 'interceptor_attach', config: { hookId: hookId, onEnter: false }, inputs: { address: address } },
+            { node_type: 'replace_retval', config: { hookId: hookId }, inputs: { value: value } }
+        ]) {
+            const result = rpc.exports.executeTargetNode(request);
+            if (!result.success) {
+                throw new Error(result.error);
+            }
+        }
+        return { success: true, hookId: hookId };
+    },
+
+    nopFunction: function(address) {
+        const target = resolveAddress(address);
+        const nop = Process.arch === 'arm64' ? [0x1f, 0x20, 0x03, 0xd5] : [0x90];
+        Memory.patchCode(target, nop.length, code => {
+            code.writeByteArray(nop);
+        });
+        return { success: true };
+    },
+
+    javaHookMethod: function(className, methodName, overload) {
+        requireRuntime('Java', typeof Java !== 'undefined' && Java.available);
+        Java.perform(() => {
+            const clazz = Java.use(className);
+            const method = overload ? clazz[methodName].overload(overload) : clazz[methodName];
+            method.implementation = function(...args) {
+                send({ type: 'java', className: className, methodName: methodName, args: args.map(String) });
+                return method.apply(this, args);
+            };
+        });
+        return { success: true };
+    },
+
+    javaModifyReturn: function(className, methodName, value) {
+        requireRuntime('Java', typeof Java !== 'undefined' && Java.available);
+        Java.perform(() => {
+            Java.use(className)[methodName].implementation = function() {
+                return value;
+            };
+        });
+        return { success: true };
+    },
+
+    javaCallMethod: function(className, methodName, isStatic) {
+        requireRuntime('Java', typeof Java !== 'undefined' && Java.available);
+        if (!isStatic) {
+            throw new Error('Instance method calls need an instance');
+        }
+        let result = null;
+        Java.perform(() => {
+            result = Java.use(className)[methodName]();
+        });
+        return result === null || result === undefined ? null : result.toString();
+    },
+
+    objcHookMethod: function(className, selector, isClassMethod) {
+        requireRuntime('ObjC', typeof ObjC !== 'undefined' && ObjC.available);
+        const clazz = ObjC.classes[className];
+        const method = clazz[(isClassMethod ? '+ ' : '- ') + selector];
+        Interceptor.attach(method.implementation, {
+            onEnter(args) {
+                send({ type: 'objc', className: className, selector: selector });
+            }
+        });
+        return { success: true };
+    },
+
+    objcModifyReturn: function(className, selector, value) {
+        requireRuntime('ObjC', typeof ObjC !== 'undefined' && ObjC.available);
+        const method = ObjC.classes[className]['- ' + selector];
+        Interceptor.attach(method.implementation, {
+            onLeave(retval) {
+                retval.replace(ptr(value));
+            }
+        });
+        return { success: true };
+    },
+
+    listModules: function() {
+        return targetNodes.enumerate_modules({}, {}).modules;
+    },
+
+    findExport: function(moduleName, exportName) {
+        const address = findExport(moduleName, exportName);
+        return address ? address.toString() : null;
+    },
+
+    resolveAddress: (spec) => resolveAddress(spec).toString()
+};
+
+// Compiled trainer agents are appended after this script and call node
+// handlers directly; publish them in case a bundler scoped this file
+globalThis.targetNodes = targetNodes;
+
+console.log('[Forvanced] Target RPC handler loaded');
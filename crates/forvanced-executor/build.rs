@@ -0,0 +1,74 @@
+//! Embed the target agent (`agent/src/index.js`)
+//!
+//! The agent is compiled with frida-compile when it is installed, either in
+//! `agent/node_modules` (`bun install` there) or wherever `FRIDA_COMPILE`
+//! points. Without it the source is embedded unchanged, with a cargo warning;
+//! it has no imports, so Frida loads it the same way.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const AGENT_SOURCE: &str = "agent/src/index.js";
+
+/// Files that change when the agent's toolchain is installed or updated
+const AGENT_MANIFESTS: &[&str] = &[
+    "agent/package.json",
+    "agent/package-lock.json",
+    "agent/bun.lock",
+    "agent/bun.lockb",
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", AGENT_SOURCE);
+    println!("cargo:rerun-if-env-changed=FRIDA_COMPILE");
+    // A path that doesn't exist would rerun the script on every build
+    for manifest in AGENT_MANIFESTS.iter().filter(|m| Path::new(m).exists()) {
+        println!("cargo:rerun-if-changed={}", manifest);
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("agent.js");
+    match frida_compile() {
+        Some(compiler) => {
+            println!("cargo:rerun-if-changed={}", compiler.display());
+            compile(&compiler, &out);
+        }
+        None => {
+            // Picks up a later install, which adds agent/node_modules
+            println!("cargo:rerun-if-changed=agent");
+            println!(
+                "cargo:warning=frida-compile not found, embedding {} uncompiled \
+                 (install it in agent/ or set FRIDA_COMPILE)",
+                AGENT_SOURCE
+            );
+            fs::copy(AGENT_SOURCE, &out).expect("copy target agent");
+        }
+    }
+}
+
+fn frida_compile() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("FRIDA_COMPILE") {
+        return Some(PathBuf::from(path));
+    }
+    let bin = if cfg!(windows) {
+        "frida-compile.cmd"
+    } else {
+        "frida-compile"
+    };
+    let local = Path::new("agent/node_modules/.bin").join(bin);
+    local.exists().then_some(local)
+}
+
+// An IIFE bundle keeps the agent a plain script that compiled trainer agents
+// can be appended to
+fn compile(compiler: &Path, out: &Path) {
+    let status = Command::new(compiler)
+        .arg(AGENT_SOURCE)
+        .arg("-o")
+        .arg(out)
+        .args(["-B", "iife", "-S"])
+        .status()
+        .unwrap_or_else(|e| panic!("failed to run {}: {}", compiler.display(), e));
+    assert!(status.success(), "frida-compile failed on {}", AGENT_SOURCE);
+}
@@ -1,6 +1,7 @@
 //! Execution context - holds runtime state during script execution
 
 use crate::error::{ExecutorError, ExecutorResult};
+use crate::host::HostHandle;
 use crate::script::{Script, ScriptNode};
 use crate::value::Value;
 use std::collections::{HashMap, HashSet};
@@ -66,6 +67,9 @@ pub struct ExecutionContext {
 
     /// Notifications to send to frontend
     notifications: Vec<Notification>,
+
+    /// Device and session management for process nodes
+    host: Option<HostHandle>,
 }
 
 /// Notification to display in the frontend
@@ -104,6 +108,7 @@ impl ExecutionContext {
             event_component_id: None,
             logs: Vec::new(),
             notifications: Vec::new(),
+            host: None,
         }
     }
 
@@ -127,6 +132,7 @@ impl ExecutionContext {
             event_component_id: None,
             logs: Vec::new(),
             notifications: Vec::new(),
+            host: None,
         }
     }
 
@@ -173,6 +179,17 @@ impl ExecutionContext {
         self
     }
 
+    /// Give process nodes access to the app's sessions
+    pub fn with_host(mut self, host: HostHandle) -> Self {
+        self.host = Some(host);
+        self
+    }
+
+    /// Session management for process nodes
+    pub fn host(&self) -> Option<&HostHandle> {
+        self.host.as_ref()
+    }
+
     /// Get the event value
     pub fn event_value(&self) -> &Value {
         &self.event_value
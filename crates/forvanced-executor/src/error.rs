@@ -62,6 +62,9 @@ pub enum ExecutorError {
     #[error("Not attached to any process")]
     NotAttached,
 
+    #[error("No session named {0}")]
+    SessionNotFound(String),
+
     #[error("Already attached to process: {0}")]
     AlreadyAttached(String),
 
@@ -8,6 +8,7 @@
 
 use crate::context::{ExecutionContext, UIState};
 use crate::error::{ExecutorError, ExecutorResult};
+use crate::host::{DeviceProvider, HostHandle, SessionHost, DEFAULT_DEVICE};
 use crate::nodes::{self, flow, NodeOutput};
 use crate::rpc::RpcBridge;
 use crate::script::{PortType, Script, ScriptNode};
@@ -38,6 +39,9 @@ pub struct ExecutionResult {
 pub type VariableState = Arc<RwLock<HashMap<String, Value>>>;
 
 /// Main script executor
+///
+/// Clones share the RPC bridge, UI state and script variables.
+#[derive(Clone)]
 pub struct ScriptExecutor {
     /// RPC bridge for target node execution
     rpc_bridge: Arc<RwLock<RpcBridge>>,
@@ -46,6 +50,12 @@ pub struct ScriptExecutor {
     /// Script variable state (persists across executions within same script)
     /// Key is script ID, value is variable name -> value
     script_variables: Arc<RwLock<HashMap<String, HashMap<String, Value>>>>,
+    /// Lists devices, processes and applications for device nodes
+    device_provider: Option<Arc<dyn DeviceProvider>>,
+    /// Attaches and detaches processes for process nodes
+    session_host: Option<Arc<dyn SessionHost>>,
+    /// Device process nodes act on
+    device_id: Arc<RwLock<String>>,
 }
 
 impl ScriptExecutor {
@@ -55,9 +65,32 @@ impl ScriptExecutor {
             rpc_bridge: Arc::new(RwLock::new(RpcBridge::new())),
             ui_state,
             script_variables: Arc::new(RwLock::new(HashMap::new())),
+            device_provider: None,
+            session_host: None,
+            device_id: Arc::new(RwLock::new(DEFAULT_DEVICE.to_string())),
         }
     }
 
+    /// Back the device nodes with `provider`
+    pub fn set_device_provider(&mut self, provider: Arc<dyn DeviceProvider>) {
+        self.device_provider = Some(provider);
+    }
+
+    /// Let process nodes attach, spawn and detach through `host`
+    pub fn set_session_host(&mut self, host: Arc<dyn SessionHost>) {
+        self.session_host = Some(host);
+    }
+
+    /// What host nodes get to see of the app's sessions
+    fn host_handle(&self) -> HostHandle {
+        HostHandle::new(
+            self.device_provider.clone(),
+            self.session_host.clone(),
+            Arc::clone(&self.rpc_bridge),
+            Arc::clone(&self.device_id),
+        )
+    }
+
     /// Get or create variable state for a script
     async fn get_script_variables(&self, script: &Script) -> HashMap<String, Value> {
         let mut states = self.script_variables.write().await;
@@ -125,13 +158,81 @@ impl ScriptExecutor {
     pub async fn clear_session(&self) {
         let mut bridge = self.rpc_bridge.write().await;
         bridge.clear_session();
-        bridge.clear_rpc_caller().await;
+        bridge.clear_rpc_caller();
+    }
+
+    /// Attach a named session for Target nodes that select it
+    pub async fn add_session(
+        &self,
+        name: &str,
+        session_id: String,
+        caller: Arc<dyn crate::rpc::RpcCaller>,
+    ) {
+        let mut bridge = self.rpc_bridge.write().await;
+        bridge.add_session(name, session_id, caller);
+    }
+
+    /// Drop a named session; returns whether it existed
+    pub async fn remove_session(&self, name: &str) -> bool {
+        let mut bridge = self.rpc_bridge.write().await;
+        bridge.remove_session(name)
+    }
+
+    /// Names of the attached sessions
+    pub async fn session_names(&self) -> Vec<String> {
+        self.rpc_bridge.read().await.session_names()
+    }
+
+    /// Frida session ID behind a named session
+    pub async fn session_id(&self, name: &str) -> Option<String> {
+        self.rpc_bridge
+            .read()
+            .await
+            .session_id(name)
+            .map(str::to_string)
+    }
+
+    /// Re-apply journaled hooks and code caves after re-attaching
+    pub async fn replay_target_state(&self) -> ExecutorResult<Vec<String>> {
+        self.rpc_bridge.read().await.replay_journal().await
+    }
+
+    /// Re-apply a named session's hooks and code caves after re-attaching
+    pub async fn replay_session_state(&self, name: &str) -> ExecutorResult<Vec<String>> {
+        self.rpc_bridge
+            .read()
+            .await
+            .replay_session_journal(name)
+            .await
+    }
+
+    /// Forget hooks and code caves that would otherwise be re-applied
+    pub async fn clear_target_journal(&self) {
+        self.rpc_bridge.read().await.clear_journal().await;
+    }
+
+    /// Forget a named session's hooks and code caves
+    pub async fn clear_session_journal(&self, name: &str) {
+        self.rpc_bridge
+            .read()
+            .await
+            .clear_session_journal(name)
+            .await;
     }
 
     /// Set the RPC caller for target node execution
     pub async fn set_rpc_caller(&self, caller: Arc<dyn crate::rpc::RpcCaller>) {
-        let bridge = self.rpc_bridge.read().await;
-        bridge.set_rpc_caller(caller).await;
+        let mut bridge = self.rpc_bridge.write().await;
+        bridge.set_rpc_caller(caller);
+    }
+
+    /// Set the struct layouts available to struct_read/struct_write nodes
+    pub async fn set_struct_definitions(
+        &self,
+        definitions: Vec<forvanced_core::structs::StructDefinition>,
+    ) {
+        let mut bridge = self.rpc_bridge.write().await;
+        bridge.set_struct_definitions(definitions);
     }
 
     /// Execute a script from an event trigger
@@ -151,7 +252,8 @@ impl ScriptExecutor {
             Arc::clone(&self.ui_state),
             persisted_vars,
         )
-        .with_event(event_value, component_id);
+        .with_event(event_value, component_id)
+        .with_host(self.host_handle());
 
         // Find the event node
         let event_node = ctx.find_node(event_node_id)?.clone();
@@ -184,6 +286,16 @@ impl ScriptExecutor {
                 // session output contains session id
                 event_outputs.insert("session".to_string(), ctx.event_value().clone());
             }
+            "event_hook" => {
+                // hook payload sent by the target: hookId, args/retval, address, threadId
+                if let Value::Object(fields) = ctx.event_value() {
+                    for key in ["hookId", "args", "retval", "address", "threadId"] {
+                        if let Some(v) = fields.get(key) {
+                            event_outputs.insert(key.to_string(), v.clone());
+                        }
+                    }
+                }
+            }
             _ => {}
         }
         ctx.set_node_outputs(event_node_id, event_outputs);
@@ -224,6 +336,60 @@ impl ScriptExecutor {
         })
     }
 
+    /// Run the `onCall` flow of a native_callback node for one target call
+    ///
+    /// `args` is the argument array sent by the target. The value passed to a
+    /// `function_return` node becomes the callback's return value; `Null` means
+    /// no value was returned and the target falls back to the original function.
+    pub async fn execute_native_callback(
+        &self,
+        script: Script,
+        callback_node_id: &str,
+        args: Value,
+    ) -> ExecutorResult<Value> {
+        let persisted_vars = self.get_script_variables(&script).await;
+        let mut ctx = ExecutionContext::new_with_variables(
+            script,
+            Arc::clone(&self.ui_state),
+            persisted_vars,
+        )
+        .with_event(args.clone(), None)
+        .with_host(self.host_handle());
+
+        let callback_node = ctx.find_node(callback_node_id)?.clone();
+        if callback_node.node_type != "native_callback" {
+            return Err(ExecutorError::InvalidOperation(format!(
+                "Node {} is not a native_callback node",
+                callback_node_id
+            )));
+        }
+
+        let mut outputs = HashMap::new();
+        outputs.insert("args".to_string(), args);
+        ctx.set_node_outputs(callback_node_id, outputs);
+
+        let mut return_value = Value::Null;
+        let script = ctx.script().clone();
+        if let Some(call_port) = callback_node.output_by_name("onCall") {
+            for conn in script.connections_from_port(callback_node_id, &call_port.id) {
+                let next_node_id = conn.to_node_id.clone();
+                match self.execute_flow(&mut ctx, &next_node_id).await {
+                    Ok(()) => {}
+                    Err(ExecutorError::ReturnSignal(value)) => {
+                        return_value = *value;
+                        break;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        let final_variables = ctx.variables().clone();
+        self.save_script_variables(&script.id, final_variables).await;
+
+        Ok(return_value)
+    }
+
     /// Execute a flow of nodes starting from a given node
     /// Uses Box::pin to handle recursive async calls
     fn execute_flow<'a>(
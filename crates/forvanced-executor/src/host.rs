@@ -0,0 +1,340 @@
+//! Device and session management for host nodes
+//!
+//! Host nodes like process_attach don't talk to Frida themselves. The app
+//! gives the executor a [`DeviceProvider`] and a [`SessionHost`], and the
+//! nodes reach them through the [`HostHandle`] in their execution context. A
+//! session attached this way is registered with the RPC bridge under the name
+//! the node asked for, so Target nodes further down the flow run in it.
+
+use crate::rpc::{generate_target_script, RpcBridge, RpcCaller};
+use async_trait::async_trait;
+use forvanced_frida::{
+    ApplicationInfo, AttachTarget, DeviceInfo, FridaManager, ProcessInfo, SessionState,
+    SpawnOptions,
+};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Device used by process nodes until a script selects another
+pub const DEFAULT_DEVICE: &str = "local";
+
+/// Lists devices and what runs on them, for the device nodes
+#[async_trait]
+pub trait DeviceProvider: Send + Sync {
+    async fn devices(&self) -> Result<Vec<DeviceInfo>, String>;
+
+    async fn processes(&self, device_id: &str) -> Result<Vec<ProcessInfo>, String>;
+
+    /// Installed applications, with a pid for the running ones
+    async fn applications(&self, device_id: &str) -> Result<Vec<ApplicationInfo>, String>;
+}
+
+#[async_trait]
+impl DeviceProvider for FridaManager {
+    async fn devices(&self) -> Result<Vec<DeviceInfo>, String> {
+        self.enumerate_devices().await.map_err(|e| e.to_string())
+    }
+
+    async fn processes(&self, device_id: &str) -> Result<Vec<ProcessInfo>, String> {
+        self.enumerate_processes_on_device(device_id)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn applications(&self, device_id: &str) -> Result<Vec<ApplicationInfo>, String> {
+        self.enumerate_applications_on_device(device_id)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// A session attached by a [`SessionHost`], with the target agent loaded
+pub struct HostSession {
+    pub session_id: String,
+    pub process: ProcessInfo,
+    /// Calls into the agent running in the session
+    pub caller: Arc<dyn RpcCaller>,
+}
+
+/// Attaches, spawns and detaches processes on behalf of host nodes
+#[async_trait]
+pub trait SessionHost: Send + Sync {
+    /// Attach to a process and load the target agent
+    ///
+    /// Gated targets (`AttachTarget::Spawn`, `AttachTarget::Child`) wait for
+    /// the process to start.
+    async fn attach(&self, device_id: &str, target: AttachTarget) -> Result<HostSession, String>;
+
+    /// Spawn an application and load the target agent before it runs
+    async fn spawn(
+        &self,
+        device_id: &str,
+        identifier: &str,
+        resume: bool,
+    ) -> Result<HostSession, String>;
+
+    /// End a session
+    async fn detach(&self, session_id: &str) -> Result<(), String>;
+
+    /// Process behind a session; `None` once the session is gone
+    async fn session_process(&self, session_id: &str) -> Option<ProcessInfo>;
+}
+
+/// What host nodes can reach of the app: the device provider, the session
+/// host and the bridge its sessions are registered with
+#[derive(Clone)]
+pub struct HostHandle {
+    devices: Option<Arc<dyn DeviceProvider>>,
+    sessions: Option<Arc<dyn SessionHost>>,
+    bridge: Arc<RwLock<RpcBridge>>,
+    device_id: Arc<RwLock<String>>,
+}
+
+impl std::fmt::Debug for HostHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HostHandle")
+            .field("devices", &self.devices.is_some())
+            .field("sessions", &self.sessions.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl HostHandle {
+    pub(crate) fn new(
+        devices: Option<Arc<dyn DeviceProvider>>,
+        sessions: Option<Arc<dyn SessionHost>>,
+        bridge: Arc<RwLock<RpcBridge>>,
+        device_id: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            devices,
+            sessions,
+            bridge,
+            device_id,
+        }
+    }
+
+    fn provider(&self) -> Result<&Arc<dyn DeviceProvider>, String> {
+        self.devices
+            .as_ref()
+            .ok_or_else(|| "Device enumeration is not available here".to_string())
+    }
+
+    fn host(&self) -> Result<&Arc<dyn SessionHost>, String> {
+        self.sessions
+            .as_ref()
+            .ok_or_else(|| "Process control is not available here".to_string())
+    }
+
+    /// Device process nodes act on
+    pub async fn device_id(&self) -> String {
+        self.device_id.read().await.clone()
+    }
+
+    /// All devices the provider knows about
+    pub async fn devices(&self) -> Result<Vec<DeviceInfo>, String> {
+        self.provider()?.devices().await
+    }
+
+    /// Make `device` the one later process nodes act on
+    pub async fn select_device(&self, device: &DeviceInfo) {
+        *self.device_id.write().await = device.id.clone();
+    }
+
+    /// The selected device, if the provider still lists it
+    pub async fn current_device(&self) -> Result<Option<DeviceInfo>, String> {
+        let device_id = self.device_id().await;
+        Ok(self
+            .devices()
+            .await?
+            .into_iter()
+            .find(|device| device.id == device_id))
+    }
+
+    /// Processes running on the selected device
+    pub async fn processes(&self) -> Result<Vec<ProcessInfo>, String> {
+        let device_id = self.device_id().await;
+        self.provider()?.processes(&device_id).await
+    }
+
+    /// Applications on the selected device
+    pub async fn applications(&self) -> Result<Vec<ApplicationInfo>, String> {
+        let device_id = self.device_id().await;
+        self.provider()?.applications(&device_id).await
+    }
+
+    /// Attach and register the session as `name`
+    ///
+    /// A session already registered under `name` is detached first.
+    pub async fn attach(&self, name: &str, target: AttachTarget) -> Result<HostSession, String> {
+        let host = self.host()?;
+        let device_id = self.device_id().await;
+        let session = host.attach(&device_id, target).await?;
+        self.register(host, name, &session).await;
+        Ok(session)
+    }
+
+    /// Spawn, attach and register the session as `name`
+    pub async fn spawn(
+        &self,
+        name: &str,
+        identifier: &str,
+        resume: bool,
+    ) -> Result<HostSession, String> {
+        let host = self.host()?;
+        let device_id = self.device_id().await;
+        let session = host.spawn(&device_id, identifier, resume).await?;
+        self.register(host, name, &session).await;
+        Ok(session)
+    }
+
+    async fn register(&self, host: &Arc<dyn SessionHost>, name: &str, session: &HostSession) {
+        let mut bridge = self.bridge.write().await;
+        if let Some(old) = bridge.session_id(name).map(str::to_string) {
+            if old != session.session_id {
+                let _ = host.detach(&old).await;
+            }
+        }
+        // Hooks journaled for the old process don't carry over to this one
+        bridge.remove_session(name);
+        bridge.add_session(name, session.session_id.clone(), session.caller.clone());
+    }
+
+    /// Detach the session registered as `name` and forget it
+    pub async fn detach(&self, name: &str) -> Result<(), String> {
+        let host = self.host()?;
+        let session_id = self
+            .bridge
+            .read()
+            .await
+            .session_id(name)
+            .map(str::to_string)
+            .ok_or_else(|| format!("No session named {}", name))?;
+        self.bridge.write().await.remove_session(name);
+        host.detach(&session_id).await
+    }
+
+    /// Session ID and process of a live session registered as `name`
+    pub async fn process(&self, name: &str) -> Option<(String, ProcessInfo)> {
+        let session_id = self.bridge.read().await.session_id(name)?.to_string();
+        let process = self.sessions.as_ref()?.session_process(&session_id).await?;
+        Some((session_id, process))
+    }
+}
+
+/// Calls the agent of one session through the manager
+struct ManagerRpcCaller {
+    manager: Arc<FridaManager>,
+    session_id: String,
+    script_id: String,
+}
+
+#[async_trait]
+impl RpcCaller for ManagerRpcCaller {
+    async fn call(
+        &self,
+        method: &str,
+        args: Vec<serde_json::Value>,
+    ) -> Result<serde_json::Value, String> {
+        self.manager
+            .call_rpc(&self.session_id, &self.script_id, method, args)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// [`SessionHost`] backed by a [`FridaManager`]
+pub struct FridaSessionHost {
+    manager: Arc<FridaManager>,
+    agent: String,
+}
+
+impl FridaSessionHost {
+    /// Inject the built-in target agent into attached sessions
+    pub fn new(manager: Arc<FridaManager>) -> Self {
+        Self {
+            manager,
+            agent: generate_target_script(),
+        }
+    }
+
+    /// Inject `agent` instead of the built-in target agent
+    pub fn with_agent(mut self, agent: String) -> Self {
+        self.agent = agent;
+        self
+    }
+
+    /// Load the agent, then let a suspended process run if asked to
+    async fn load_agent(&self, session_id: String, resume: bool) -> Result<HostSession, String> {
+        let loaded = async {
+            let script_id = self.manager.inject_script(&session_id, &self.agent).await?;
+            let session =
+                self.manager.get_session(&session_id).await.ok_or_else(|| {
+                    forvanced_frida::FridaError::SessionNotFound(session_id.clone())
+                })?;
+            if resume && session.state().await == SessionState::Suspended {
+                self.manager.resume(&session_id).await?;
+            }
+            Ok::<_, forvanced_frida::FridaError>((script_id, session.process.clone()))
+        }
+        .await;
+
+        match loaded {
+            Ok((script_id, process)) => Ok(HostSession {
+                caller: Arc::new(ManagerRpcCaller {
+                    manager: Arc::clone(&self.manager),
+                    session_id: session_id.clone(),
+                    script_id,
+                }),
+                session_id,
+                process,
+            }),
+            Err(e) => {
+                let _ = self.manager.detach(&session_id).await;
+                Err(e.to_string())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SessionHost for FridaSessionHost {
+    async fn attach(&self, device_id: &str, target: AttachTarget) -> Result<HostSession, String> {
+        let session_id = self
+            .manager
+            .attach_target(device_id, target)
+            .await
+            .map_err(|e| e.to_string())?;
+        // Let a process that is still suspended run once the agent is in
+        self.load_agent(session_id, true).await
+    }
+
+    async fn spawn(
+        &self,
+        device_id: &str,
+        identifier: &str,
+        resume: bool,
+    ) -> Result<HostSession, String> {
+        let session_id = self
+            .manager
+            .spawn_suspended(device_id, identifier, SpawnOptions::default())
+            .await
+            .map_err(|e| e.to_string())?;
+        self.load_agent(session_id, resume).await
+    }
+
+    async fn detach(&self, session_id: &str) -> Result<(), String> {
+        self.manager
+            .detach(session_id)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn session_process(&self, session_id: &str) -> Option<ProcessInfo> {
+        let session = self.manager.get_session(session_id).await?;
+        if session.is_detached().await {
+            return None;
+        }
+        Some(session.process.clone())
+    }
+}
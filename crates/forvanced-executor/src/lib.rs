@@ -26,7 +26,9 @@
 pub mod context;
 pub mod error;
 pub mod executor;
+pub mod host;
 pub mod nodes;
+pub mod replay;
 pub mod rpc;
 pub mod script;
 pub mod value;
@@ -34,7 +36,9 @@ pub mod value;
 pub use context::{ExecutionContext, Notification};
 pub use error::{ExecutorError, ExecutorResult};
 pub use executor::ScriptExecutor;
-pub use rpc::{RpcBridge, RpcCaller, RpcRequest, RpcResponse};
+pub use host::{DeviceProvider, FridaSessionHost, HostHandle, HostSession, SessionHost};
+pub use replay::TargetJournal;
+pub use rpc::{RpcBridge, RpcCaller, RpcRequest, RpcResponse, DEFAULT_SESSION};
 pub use value::Value;
 
 /// Node execution context classification
@@ -98,14 +102,17 @@ pub fn classify_node(node_type: &str) -> NodeContext {
 
         // Memory Operations - Target
         "memory_scan" | "memory_read" | "memory_write" | "memory_freeze" | "memory_alloc"
-        | "memory_protect" => NodeContext::Target,
+        | "memory_protect" | "code_cave" => NodeContext::Target,
 
         // Pointer Operations - Target
         "pointer_add" | "pointer_read" | "pointer_write" => NodeContext::Target,
 
+        // Struct Operations - Target
+        "struct_read" | "struct_write" => NodeContext::Target,
+
         // Module/Symbol - Target
-        "get_module" | "find_symbol" | "get_base_address" | "enumerate_modules"
-        | "enumerate_exports" => NodeContext::Target,
+        "get_module" | "find_symbol" | "get_base_address" | "resolve_address"
+        | "enumerate_modules" | "enumerate_exports" => NodeContext::Target,
 
         // Native Calls - Target
         "call_native" | "native_callback" => NodeContext::Target,
@@ -135,5 +142,7 @@ mod tests {
         assert_eq!(classify_node("memory_read"), NodeContext::Target);
         assert_eq!(classify_node("call_native"), NodeContext::Target);
         assert_eq!(classify_node("interceptor_attach"), NodeContext::Target);
+        assert_eq!(classify_node("code_cave"), NodeContext::Target);
+        assert_eq!(classify_node("resolve_address"), NodeContext::Target);
     }
 }
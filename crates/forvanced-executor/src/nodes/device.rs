@@ -1,18 +1,70 @@
 //! Device management node executors
 //!
-//! These nodes run on the Host and provide device/process enumeration.
-//! In the builder context, they return mock data since the real Frida
-//! operations are handled by the frontend's Tauri commands.
+//! These nodes run on the Host and list devices, processes and applications
+//! through the device provider the app gave the executor. The selected device
+//! is shared with the process nodes, so attach and spawn act on it.
 
 use crate::context::ExecutionContext;
-use crate::error::ExecutorResult;
+use crate::error::{ExecutorError, ExecutorResult};
+use crate::host::HostHandle;
 use crate::script::ScriptNode;
 use crate::value::Value;
 use async_trait::async_trait;
+use forvanced_frida::{ApplicationInfo, DeviceInfo, ProcessInfo};
 use std::collections::HashMap;
 
 use super::{NodeExecutor, NodeOutput};
 
+fn host(ctx: &ExecutionContext) -> ExecutorResult<HostHandle> {
+    ctx.host().cloned().ok_or_else(|| {
+        ExecutorError::InvalidOperation("Device enumeration is not available here".to_string())
+    })
+}
+
+fn device_value(device: &DeviceInfo) -> Value {
+    let mut m = HashMap::new();
+    m.insert("id".to_string(), Value::String(device.id.clone()));
+    m.insert("name".to_string(), Value::String(device.name.clone()));
+    m.insert(
+        "type".to_string(),
+        Value::String(device.device_type.to_string()),
+    );
+    Value::Object(m)
+}
+
+fn process_value(process: &ProcessInfo, full: bool) -> Value {
+    let mut m = HashMap::new();
+    m.insert("pid".to_string(), Value::Integer(process.pid as i64));
+    m.insert("name".to_string(), Value::String(process.name.clone()));
+    if full {
+        if let Some(path) = &process.path {
+            m.insert("path".to_string(), Value::String(path.clone()));
+        }
+    }
+    Value::Object(m)
+}
+
+fn application_value(app: &ApplicationInfo) -> Value {
+    let mut m = HashMap::new();
+    m.insert(
+        "identifier".to_string(),
+        Value::String(app.identifier.clone()),
+    );
+    m.insert("name".to_string(), Value::String(app.name.clone()));
+    if let Some(pid) = app.pid {
+        m.insert("pid".to_string(), Value::Integer(pid as i64));
+    }
+    Value::Object(m)
+}
+
+/// Outputs of the enumeration nodes: the list and its length
+fn list_output(name: &str, items: Vec<Value>) -> NodeOutput {
+    let mut values = HashMap::new();
+    values.insert("count".to_string(), Value::Integer(items.len() as i64));
+    values.insert(name.to_string(), Value::Array(items));
+    NodeOutput::values(values).with_flow("exec")
+}
+
 /// Enumerate available Frida devices
 pub struct DeviceEnumerateExecutor;
 
@@ -26,36 +78,17 @@ impl NodeExecutor for DeviceEnumerateExecutor {
     ) -> ExecutorResult<NodeOutput> {
         ctx.add_log("Enumerating devices...".to_string());
 
-        // Return mock devices - real enumeration is done via Tauri commands
-        let devices = Value::Array(vec![
-            Value::Object({
-                let mut m = HashMap::new();
-                m.insert("id".to_string(), Value::String("local".to_string()));
-                m.insert("name".to_string(), Value::String("Local System".to_string()));
-                m.insert("type".to_string(), Value::String("local".to_string()));
-                m
-            }),
-            Value::Object({
-                let mut m = HashMap::new();
-                m.insert("id".to_string(), Value::String("usb-device".to_string()));
-                m.insert("name".to_string(), Value::String("USB Device".to_string()));
-                m.insert("type".to_string(), Value::String("usb".to_string()));
-                m
-            }),
-        ]);
-
-        let count = match &devices {
-            Value::Array(arr) => Value::Integer(arr.len() as i64),
-            _ => Value::Integer(0),
-        };
+        let devices = host(ctx)?
+            .devices()
+            .await
+            .map_err(ExecutorError::FridaError)?;
 
-        ctx.add_log(format!("Found {} devices", count));
-
-        let mut values = HashMap::new();
-        values.insert("devices".to_string(), devices);
-        values.insert("count".to_string(), count);
+        ctx.add_log(format!("Found {} devices", devices.len()));
 
-        Ok(NodeOutput::values(values).with_flow("exec"))
+        Ok(list_output(
+            "devices",
+            devices.iter().map(device_value).collect(),
+        ))
     }
 }
 
@@ -70,31 +103,57 @@ impl NodeExecutor for DeviceSelectExecutor {
         inputs: &HashMap<String, Value>,
         ctx: &mut ExecutionContext,
     ) -> ExecutorResult<NodeOutput> {
-        let selection_mode = node.config.get("selectionMode")
+        let selection_mode = node
+            .config
+            .get("selectionMode")
             .and_then(|v| v.as_str())
             .unwrap_or("type");
 
-        let device_type = node.config.get("deviceType")
+        let device_type = node
+            .config
+            .get("deviceType")
             .and_then(|v| v.as_str())
             .unwrap_or("local");
 
-        let device_id = inputs.get("deviceId")
-            .map(|v| v.to_string())
-            .unwrap_or_default();
-
-        ctx.add_log(format!("Selecting device: mode={}, type={}, id={}", selection_mode, device_type, device_id));
-
-        // Mock success - return local device
-        let mock_device = Value::Object({
-            let mut m = HashMap::new();
-            m.insert("id".to_string(), Value::String("local".to_string()));
-            m.insert("name".to_string(), Value::String("Local System".to_string()));
-            m.insert("type".to_string(), Value::String("local".to_string()));
-            m
+        let device_id = inputs
+            .get("deviceId")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        ctx.add_log(format!(
+            "Selecting device: mode={}, type={}, id={}",
+            selection_mode, device_type, device_id
+        ));
+
+        let host = host(ctx)?;
+        let found = host.devices().await.map(|devices| {
+            devices.into_iter().find(|d| match selection_mode {
+                "id" => d.id == device_id,
+                _ => d.device_type.to_string() == device_type,
+            })
         });
 
+        let device = match found {
+            Ok(Some(device)) => device,
+            Ok(None) => {
+                let wanted = if selection_mode == "id" {
+                    &device_id
+                } else {
+                    device_type
+                };
+                let error = format!("No device matching {}", wanted);
+                ctx.add_log(error.clone());
+                return Ok(NodeOutput::single("error", Value::String(error)).with_flow("failure"));
+            }
+            Err(e) => {
+                return Ok(NodeOutput::single("error", Value::String(e)).with_flow("failure"));
+            }
+        };
+        host.select_device(&device).await;
+
         let mut values = HashMap::new();
-        values.insert("device".to_string(), mock_device);
+        values.insert("device".to_string(), device_value(&device));
         values.insert("error".to_string(), Value::Null);
 
         Ok(NodeOutput::values(values).with_flow("success"))
@@ -110,20 +169,22 @@ impl NodeExecutor for DeviceGetCurrentExecutor {
         &self,
         _node: &ScriptNode,
         _inputs: &HashMap<String, Value>,
-        _ctx: &mut ExecutionContext,
+        ctx: &mut ExecutionContext,
     ) -> ExecutorResult<NodeOutput> {
-        // Mock: return local device
-        let mock_device = Value::Object({
-            let mut m = HashMap::new();
-            m.insert("id".to_string(), Value::String("local".to_string()));
-            m.insert("name".to_string(), Value::String("Local System".to_string()));
-            m.insert("type".to_string(), Value::String("local".to_string()));
-            m
-        });
+        let device = match ctx.host() {
+            Some(host) => host
+                .current_device()
+                .await
+                .map_err(ExecutorError::FridaError)?,
+            None => None,
+        };
 
         let mut values = HashMap::new();
-        values.insert("device".to_string(), mock_device);
-        values.insert("hasDevice".to_string(), Value::Boolean(true));
+        values.insert("hasDevice".to_string(), Value::Boolean(device.is_some()));
+        values.insert(
+            "device".to_string(),
+            device.as_ref().map(device_value).unwrap_or(Value::Null),
+        );
 
         Ok(NodeOutput::values(values))
     }
@@ -140,46 +201,26 @@ impl NodeExecutor for ProcessEnumerateExecutor {
         _inputs: &HashMap<String, Value>,
         ctx: &mut ExecutionContext,
     ) -> ExecutorResult<NodeOutput> {
-        let scope = node.config.get("scope")
+        let scope = node
+            .config
+            .get("scope")
             .and_then(|v| v.as_str())
             .unwrap_or("minimal");
 
         ctx.add_log(format!("Enumerating processes (scope: {})", scope));
 
-        // Mock processes
-        let processes = Value::Array(vec![
-            Value::Object({
-                let mut m = HashMap::new();
-                m.insert("pid".to_string(), Value::Integer(1));
-                m.insert("name".to_string(), Value::String("init".to_string()));
-                m
-            }),
-            Value::Object({
-                let mut m = HashMap::new();
-                m.insert("pid".to_string(), Value::Integer(100));
-                m.insert("name".to_string(), Value::String("ExampleApp".to_string()));
-                m
-            }),
-            Value::Object({
-                let mut m = HashMap::new();
-                m.insert("pid".to_string(), Value::Integer(200));
-                m.insert("name".to_string(), Value::String("TargetGame".to_string()));
-                m
-            }),
-        ]);
-
-        let count = match &processes {
-            Value::Array(arr) => Value::Integer(arr.len() as i64),
-            _ => Value::Integer(0),
-        };
+        let processes = host(ctx)?
+            .processes()
+            .await
+            .map_err(ExecutorError::FridaError)?;
 
-        ctx.add_log(format!("Found {} processes", count));
+        ctx.add_log(format!("Found {} processes", processes.len()));
 
-        let mut values = HashMap::new();
-        values.insert("processes".to_string(), processes);
-        values.insert("count".to_string(), count);
-
-        Ok(NodeOutput::values(values).with_flow("exec"))
+        let full = scope == "full";
+        Ok(list_output(
+            "processes",
+            processes.iter().map(|p| process_value(p, full)).collect(),
+        ))
     }
 }
 
@@ -194,39 +235,193 @@ impl NodeExecutor for ApplicationEnumerateExecutor {
         _inputs: &HashMap<String, Value>,
         ctx: &mut ExecutionContext,
     ) -> ExecutorResult<NodeOutput> {
-        let scope = node.config.get("scope")
+        let scope = node
+            .config
+            .get("scope")
             .and_then(|v| v.as_str())
             .unwrap_or("minimal");
+        let include_running = node.config_bool("includeRunning").unwrap_or(true);
+        let include_installed = node.config_bool("includeInstalled").unwrap_or(true);
 
         ctx.add_log(format!("Enumerating applications (scope: {})", scope));
 
-        // Mock applications
-        let applications = Value::Array(vec![
-            Value::Object({
-                let mut m = HashMap::new();
-                m.insert("identifier".to_string(), Value::String("com.example.app".to_string()));
-                m.insert("name".to_string(), Value::String("Example App".to_string()));
-                m
-            }),
-            Value::Object({
-                let mut m = HashMap::new();
-                m.insert("identifier".to_string(), Value::String("com.game.target".to_string()));
-                m.insert("name".to_string(), Value::String("Target Game".to_string()));
-                m
-            }),
-        ]);
-
-        let count = match &applications {
-            Value::Array(arr) => Value::Integer(arr.len() as i64),
-            _ => Value::Integer(0),
+        let applications: Vec<Value> = host(ctx)?
+            .applications()
+            .await
+            .map_err(ExecutorError::FridaError)?
+            .iter()
+            .filter(|app| match app.pid {
+                Some(_) => include_running,
+                None => include_installed,
+            })
+            .map(application_value)
+            .collect();
+
+        ctx.add_log(format!("Found {} applications", applications.len()));
+
+        Ok(list_output("applications", applications))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::DeviceProvider;
+    use crate::rpc::RpcBridge;
+    use crate::script::Script;
+    use forvanced_frida::FridaManager;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn make_node(node_type: &str, config: serde_json::Value) -> ScriptNode {
+        ScriptNode {
+            id: "test".to_string(),
+            node_type: node_type.to_string(),
+            label: "Test".to_string(),
+            x: 0.0,
+            y: 0.0,
+            config: config
+                .as_object()
+                .unwrap()
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            inputs: vec![],
+            outputs: vec![],
+        }
+    }
+
+    fn context(device_id: &str) -> ExecutionContext {
+        let script = Script {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: None,
+            variables: vec![],
+            nodes: vec![],
+            connections: vec![],
         };
+        let provider: Arc<dyn DeviceProvider> = Arc::new(FridaManager::new().unwrap());
+        let host = HostHandle::new(
+            Some(provider),
+            None,
+            Arc::new(RwLock::new(RpcBridge::new())),
+            Arc::new(RwLock::new(device_id.to_string())),
+        );
+        ExecutionContext::new(script, Arc::new(RwLock::new(HashMap::new()))).with_host(host)
+    }
 
-        ctx.add_log(format!("Found {} applications", count));
+    #[tokio::test]
+    async fn test_device_enumerate_lists_manager_devices() {
+        let mut ctx = context("local");
+        let node = make_node("device_enumerate", serde_json::json!({}));
+        let output = DeviceEnumerateExecutor
+            .execute(&node, &HashMap::new(), &mut ctx)
+            .await
+            .unwrap();
+
+        let devices = output.values["devices"].as_array().unwrap();
+        assert_eq!(output.values["count"], Value::Integer(devices.len() as i64));
+        let first = devices[0].as_object().unwrap();
+        assert_eq!(first["id"], Value::String("local".to_string()));
+        assert_eq!(first["type"], Value::String("local".to_string()));
+        assert!(devices
+            .iter()
+            .any(|d| d.as_object().unwrap()["type"] == Value::String("usb".to_string())));
+    }
 
-        let mut values = HashMap::new();
-        values.insert("applications".to_string(), applications);
-        values.insert("count".to_string(), count);
+    #[tokio::test]
+    async fn test_device_select_switches_enumeration() {
+        let mut ctx = context("local");
+        let node = make_node(
+            "device_select",
+            serde_json::json!({ "selectionMode": "id" }),
+        );
+        let inputs = HashMap::from([(
+            "deviceId".to_string(),
+            Value::String("usb-android".to_string()),
+        )]);
+        let output = DeviceSelectExecutor
+            .execute(&node, &inputs, &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.flow_output.as_deref(), Some("success"));
+        assert_eq!(ctx.host().unwrap().device_id().await, "usb-android");
+
+        let current = make_node("device_get_current", serde_json::json!({}));
+        let output = DeviceGetCurrentExecutor
+            .execute(&current, &HashMap::new(), &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.values["hasDevice"], Value::Boolean(true));
+        assert_eq!(
+            output.values["device"].as_object().unwrap()["name"],
+            Value::String("Pixel 8 (USB)".to_string())
+        );
+
+        let missing = HashMap::from([("deviceId".to_string(), Value::String("nope".to_string()))]);
+        let output = DeviceSelectExecutor
+            .execute(&node, &missing, &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.flow_output.as_deref(), Some("failure"));
+        assert_eq!(ctx.host().unwrap().device_id().await, "usb-android");
+    }
+
+    #[tokio::test]
+    async fn test_process_and_application_enumeration() {
+        let mut ctx = context("local");
+        let node = make_node("process_enumerate", serde_json::json!({ "scope": "full" }));
+        let output = ProcessEnumerateExecutor
+            .execute(&node, &HashMap::new(), &mut ctx)
+            .await
+            .unwrap();
+        let processes = output.values["processes"].as_array().unwrap();
+        assert!(!processes.is_empty());
+        assert!(processes
+            .iter()
+            .all(|p| p.as_object().unwrap().contains_key("pid")));
+
+        // The mock only has applications on mobile devices
+        let mut ctx = context("usb-iphone");
+        let all = make_node("application_enumerate", serde_json::json!({}));
+        let output = ApplicationEnumerateExecutor
+            .execute(&all, &HashMap::new(), &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.values["count"], Value::Integer(5));
+
+        let running = make_node(
+            "application_enumerate",
+            serde_json::json!({ "includeInstalled": false }),
+        );
+        let output = ApplicationEnumerateExecutor
+            .execute(&running, &HashMap::new(), &mut ctx)
+            .await
+            .unwrap();
+        let apps = output.values["applications"].as_array().unwrap();
+        assert_eq!(apps.len(), 3);
+        assert!(apps
+            .iter()
+            .all(|a| a.as_object().unwrap().contains_key("pid")));
+    }
 
-        Ok(NodeOutput::values(values).with_flow("exec"))
+    #[tokio::test]
+    async fn test_enumeration_without_provider_fails() {
+        let mut ctx = ExecutionContext::new(
+            Script {
+                id: "test".to_string(),
+                name: "Test".to_string(),
+                description: None,
+                variables: vec![],
+                nodes: vec![],
+                connections: vec![],
+            },
+            Arc::new(RwLock::new(HashMap::new())),
+        );
+        let node = make_node("device_enumerate", serde_json::json!({}));
+        assert!(DeviceEnumerateExecutor
+            .execute(&node, &HashMap::new(), &mut ctx)
+            .await
+            .is_err());
     }
 }
@@ -125,6 +125,22 @@ impl NodeExecutor for ContinueExecutor {
     }
 }
 
+/// Return from the current function or callback
+pub struct FunctionReturnExecutor;
+
+#[async_trait]
+impl NodeExecutor for FunctionReturnExecutor {
+    async fn execute(
+        &self,
+        _node: &ScriptNode,
+        inputs: &HashMap<String, Value>,
+        _ctx: &mut ExecutionContext,
+    ) -> ExecutorResult<NodeOutput> {
+        let value = inputs.get("value").cloned().unwrap_or(Value::Null);
+        Err(ExecutorError::ReturnSignal(Box::new(value)))
+    }
+}
+
 /// ForEach loop state (used by executor, not a standalone NodeExecutor)
 #[derive(Debug)]
 pub struct ForEachState {
@@ -68,18 +68,18 @@ impl NodeExecutor for MathExecutor {
                     if b_i == 0 {
                         return Err(ExecutorError::DivisionByZero);
                     }
-                    Value::Integer(a_i / b_i)
+                    Value::Integer(a_i.wrapping_div(b_i))
                 }
                 "modulo" | "mod" => {
                     if b_i == 0 {
                         return Err(ExecutorError::DivisionByZero);
                     }
-                    Value::Integer(a_i % b_i)
+                    Value::Integer(a_i.wrapping_rem(b_i))
                 }
-                "power" | "pow" => Value::Integer(a_i.pow(b_i as u32)),
+                "power" | "pow" => Value::Integer(a_i.wrapping_pow(b_i as u32)),
                 "min" => Value::Integer(a_i.min(b_i)),
                 "max" => Value::Integer(a_i.max(b_i)),
-                "abs" => Value::Integer(a_i.abs()),
+                "abs" => Value::Integer(a_i.wrapping_abs()),
                 "floor" | "ceil" | "round" => Value::Integer(a_i), // No-op for integers
                 "sqrt" => Value::Integer((a_i as f64).sqrt() as i64),
                 // Bitwise operations
@@ -87,8 +87,8 @@ impl NodeExecutor for MathExecutor {
                 "bit_or" => Value::Integer(a_i | b_i),
                 "bit_xor" => Value::Integer(a_i ^ b_i),
                 "bit_not" => Value::Integer(!a_i),
-                "shift_left" | "shl" => Value::Integer(a_i << b_i),
-                "shift_right" | "shr" => Value::Integer(a_i >> b_i),
+                "shift_left" | "shl" => Value::Integer(a_i.wrapping_shl(b_i as u32)),
+                "shift_right" | "shr" => Value::Integer(a_i.wrapping_shr(b_i as u32)),
                 _ => return Err(ExecutorError::InvalidOperation(format!("Unknown math operation: {}", operation))),
             }
         };
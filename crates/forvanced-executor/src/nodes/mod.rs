@@ -12,6 +12,7 @@ pub mod arrays;
 pub mod objects;
 pub mod strings;
 pub mod output;
+pub mod process;
 pub mod ui;
 
 use crate::context::ExecutionContext;
@@ -104,6 +105,7 @@ pub fn get_executor(node_type: &str) -> Option<Box<dyn NodeExecutor>> {
         "delay" => Some(Box::new(flow::DelayExecutor)),
         "break" => Some(Box::new(flow::BreakExecutor)),
         "continue" => Some(Box::new(flow::ContinueExecutor)),
+        "function_return" => Some(Box::new(flow::FunctionReturnExecutor)),
         // Note: for_each, for_range, loop are handled specially in the executor
 
         // Math/Logic
@@ -148,6 +150,12 @@ pub fn get_executor(node_type: &str) -> Option<Box<dyn NodeExecutor>> {
         "process_enumerate" => Some(Box::new(device::ProcessEnumerateExecutor)),
         "application_enumerate" => Some(Box::new(device::ApplicationEnumerateExecutor)),
 
+        // Process Control
+        "process_attach" => Some(Box::new(process::ProcessAttachExecutor)),
+        "process_spawn" => Some(Box::new(process::ProcessSpawnExecutor)),
+        "process_detach" => Some(Box::new(process::ProcessDetachExecutor)),
+        "process_is_attached" => Some(Box::new(process::ProcessIsAttachedExecutor)),
+
         // Event nodes don't have executors - they're entry points
         "event_ui" | "event_attach" | "event_detach" | "event_hotkey" | "event_interval"
         | "event_hook" | "event_memory_watch" => None,
@@ -0,0 +1,435 @@
+//! Process control node executors
+//!
+//! Attach, spawn and detach go through the session host the app gave the
+//! executor. Each attached process is registered under a session name; the
+//! `session` output is that name, ready to be wired into Target nodes and
+//! process_detach.
+
+use crate::context::ExecutionContext;
+use crate::error::{ExecutorError, ExecutorResult};
+use crate::host::{HostHandle, HostSession};
+use crate::rpc::DEFAULT_SESSION;
+use crate::script::ScriptNode;
+use crate::value::Value;
+use async_trait::async_trait;
+use forvanced_frida::AttachTarget;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::{NodeExecutor, NodeOutput};
+
+/// How long process_attach waits for a launch when `timeoutMs` is unset or 0
+const DEFAULT_LAUNCH_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn host(ctx: &ExecutionContext) -> Result<HostHandle, String> {
+    ctx.host()
+        .cloned()
+        .ok_or_else(|| "Process control is not available here".to_string())
+}
+
+/// Session name from the `session` input, then the node config
+fn session_name(node: &ScriptNode, inputs: &HashMap<String, Value>) -> String {
+    inputs
+        .get("session")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| node.config_str("session"))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| DEFAULT_SESSION.to_string())
+}
+
+fn failure(ctx: &mut ExecutionContext, node: &ScriptNode, error: String) -> NodeOutput {
+    ctx.add_log(format!("{} failed: {}", node.label, error));
+    NodeOutput::single("error", Value::String(error)).with_flow("failure")
+}
+
+fn attached(name: String, session: &HostSession) -> NodeOutput {
+    let mut values = HashMap::new();
+    values.insert("session".to_string(), Value::String(name));
+    values.insert(
+        "sessionId".to_string(),
+        Value::String(session.session_id.clone()),
+    );
+    values.insert(
+        "pid".to_string(),
+        Value::Integer(session.process.pid as i64),
+    );
+    values.insert(
+        "processName".to_string(),
+        Value::String(session.process.name.clone()),
+    );
+    values.insert("error".to_string(), Value::Null);
+    NodeOutput::values(values).with_flow("success")
+}
+
+/// What process_attach should attach to, from its attach mode and target
+fn attach_target(node: &ScriptNode, target: Option<&Value>) -> ExecutorResult<AttachTarget> {
+    let mode = node
+        .config_str("attachMode")
+        .unwrap_or_else(|| "pid".to_string());
+    let text = || {
+        target
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .or_else(|| node.config_str("target"))
+            .ok_or_else(|| {
+                ExecutorError::InvalidConfig(format!("{} needs a target", node.node_type))
+            })
+    };
+    match mode.as_str() {
+        "pid" => target
+            .and_then(|v| v.as_i64())
+            .and_then(|pid| u32::try_from(pid).ok())
+            .map(AttachTarget::Pid)
+            .ok_or_else(|| ExecutorError::InvalidConfig("Attach by PID needs a PID".to_string())),
+        "name" => Ok(AttachTarget::Name(text()?)),
+        "identifier" => Ok(AttachTarget::Identifier(text()?)),
+        "launch" => Ok(AttachTarget::Spawn { pattern: text()? }),
+        other => Err(ExecutorError::InvalidConfig(format!(
+            "Unknown attach mode: {}",
+            other
+        ))),
+    }
+}
+
+/// `timeoutMs`, with unset or 0 meaning [`DEFAULT_LAUNCH_TIMEOUT`]
+fn launch_timeout(node: &ScriptNode) -> Duration {
+    node.config_i64("timeoutMs")
+        .filter(|ms| *ms > 0)
+        .map_or(DEFAULT_LAUNCH_TIMEOUT, |ms| {
+            Duration::from_millis(ms as u64)
+        })
+}
+
+/// Attach to a process, or wait for it to launch, under a session name
+pub struct ProcessAttachExecutor;
+
+#[async_trait]
+impl NodeExecutor for ProcessAttachExecutor {
+    async fn execute(
+        &self,
+        node: &ScriptNode,
+        inputs: &HashMap<String, Value>,
+        ctx: &mut ExecutionContext,
+    ) -> ExecutorResult<NodeOutput> {
+        let host = match host(ctx) {
+            Ok(host) => host,
+            Err(e) => return Ok(failure(ctx, node, e)),
+        };
+        let target = attach_target(node, inputs.get("target"))?;
+        let name = session_name(node, inputs);
+        ctx.add_log(format!("Attaching to {} as session '{}'", target, name));
+
+        // Waiting for a launch always gives up, so a process that never
+        // appears can't stall the script for good
+        let result = if target.is_gated() {
+            let timeout = launch_timeout(node);
+            tokio::time::timeout(timeout, host.attach(&name, target))
+                .await
+                .unwrap_or_else(|_| Err(format!("Timed out after {}ms", timeout.as_millis())))
+        } else {
+            host.attach(&name, target).await
+        };
+
+        match result {
+            Ok(session) => Ok(attached(name, &session)),
+            Err(e) => Ok(failure(ctx, node, e)),
+        }
+    }
+}
+
+/// Spawn an application and attach to it under a session name
+pub struct ProcessSpawnExecutor;
+
+#[async_trait]
+impl NodeExecutor for ProcessSpawnExecutor {
+    async fn execute(
+        &self,
+        node: &ScriptNode,
+        inputs: &HashMap<String, Value>,
+        ctx: &mut ExecutionContext,
+    ) -> ExecutorResult<NodeOutput> {
+        let host = match host(ctx) {
+            Ok(host) => host,
+            Err(e) => return Ok(failure(ctx, node, e)),
+        };
+        let identifier = inputs
+            .get("identifier")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .or_else(|| node.config_str("identifier"))
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| ExecutorError::InvalidConfig("Spawn needs an identifier".to_string()))?;
+        let resume = node.config_bool("resumeAfterSpawn").unwrap_or(true);
+        let name = session_name(node, inputs);
+        ctx.add_log(format!("Spawning {} as session '{}'", identifier, name));
+
+        match host.spawn(&name, &identifier, resume).await {
+            Ok(session) => Ok(attached(name, &session)),
+            Err(e) => Ok(failure(ctx, node, e)),
+        }
+    }
+}
+
+/// Detach a session
+pub struct ProcessDetachExecutor;
+
+#[async_trait]
+impl NodeExecutor for ProcessDetachExecutor {
+    async fn execute(
+        &self,
+        node: &ScriptNode,
+        inputs: &HashMap<String, Value>,
+        ctx: &mut ExecutionContext,
+    ) -> ExecutorResult<NodeOutput> {
+        let name = session_name(node, inputs);
+        let result = match host(ctx) {
+            Ok(host) => host.detach(&name).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => {
+                ctx.add_log(format!("Detached session '{}'", name));
+                Ok(NodeOutput::single("error", Value::Null).with_flow("success"))
+            }
+            Err(e) => Ok(failure(ctx, node, e)),
+        }
+    }
+}
+
+/// Check whether a session is still attached
+pub struct ProcessIsAttachedExecutor;
+
+#[async_trait]
+impl NodeExecutor for ProcessIsAttachedExecutor {
+    async fn execute(
+        &self,
+        node: &ScriptNode,
+        inputs: &HashMap<String, Value>,
+        ctx: &mut ExecutionContext,
+    ) -> ExecutorResult<NodeOutput> {
+        let name = session_name(node, inputs);
+        let process = match ctx.host() {
+            Some(host) => host.process(&name).await,
+            None => None,
+        };
+
+        let mut values = HashMap::new();
+        values.insert("attached".to_string(), Value::Boolean(process.is_some()));
+        match process {
+            Some((session_id, process)) => {
+                values.insert("sessionId".to_string(), Value::String(session_id));
+                values.insert("pid".to_string(), Value::Integer(process.pid as i64));
+            }
+            None => {
+                values.insert("sessionId".to_string(), Value::Null);
+                values.insert("pid".to_string(), Value::Null);
+            }
+        }
+        Ok(NodeOutput::values(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::FridaSessionHost;
+    use crate::rpc::RpcBridge;
+    use crate::script::Script;
+    use forvanced_frida::{FridaManager, ProcessInfo};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn make_node(node_type: &str, config: serde_json::Value) -> ScriptNode {
+        ScriptNode {
+            id: "test".to_string(),
+            node_type: node_type.to_string(),
+            label: "Test".to_string(),
+            x: 0.0,
+            y: 0.0,
+            config: config
+                .as_object()
+                .unwrap()
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            inputs: vec![],
+            outputs: vec![],
+        }
+    }
+
+    fn empty_script() -> Script {
+        Script {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: None,
+            variables: vec![],
+            nodes: vec![],
+            connections: vec![],
+        }
+    }
+
+    fn context(manager: &Arc<FridaManager>, bridge: &Arc<RwLock<RpcBridge>>) -> ExecutionContext {
+        let host = HostHandle::new(
+            None,
+            Some(Arc::new(FridaSessionHost::new(Arc::clone(manager)))),
+            Arc::clone(bridge),
+            Arc::new(RwLock::new("local".to_string())),
+        );
+        ExecutionContext::new(empty_script(), Arc::new(RwLock::new(HashMap::new()))).with_host(host)
+    }
+
+    #[tokio::test]
+    async fn test_attach_registers_named_session() {
+        let manager = Arc::new(FridaManager::new().unwrap());
+        let bridge = Arc::new(RwLock::new(RpcBridge::new()));
+        let mut ctx = context(&manager, &bridge);
+
+        let node = make_node(
+            "process_attach",
+            serde_json::json!({ "attachMode": "name", "session": "server" }),
+        );
+        let inputs = HashMap::from([(
+            "target".to_string(),
+            Value::String("server.exe".to_string()),
+        )]);
+        let output = ProcessAttachExecutor
+            .execute(&node, &inputs, &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.flow_output.as_deref(), Some("success"));
+        assert_eq!(
+            output.values["session"],
+            Value::String("server".to_string())
+        );
+
+        let session_id = output.values["sessionId"].as_str().unwrap().to_string();
+        assert_eq!(
+            bridge.read().await.session_id("server"),
+            Some(session_id.as_str())
+        );
+        assert!(!bridge.read().await.is_connected());
+        let session = manager.get_session(&session_id).await.unwrap();
+        assert_eq!(session.get_script_ids().await.len(), 1);
+
+        let check = make_node("process_is_attached", serde_json::json!({}));
+        let by_handle = HashMap::from([("session".to_string(), output.values["session"].clone())]);
+        let status = ProcessIsAttachedExecutor
+            .execute(&check, &by_handle, &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(status.values["attached"], Value::Boolean(true));
+        let status = ProcessIsAttachedExecutor
+            .execute(&check, &HashMap::new(), &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(status.values["attached"], Value::Boolean(false));
+
+        let detach = make_node("process_detach", serde_json::json!({}));
+        let output = ProcessDetachExecutor
+            .execute(&detach, &by_handle, &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.flow_output.as_deref(), Some("success"));
+        assert!(bridge.read().await.session_names().is_empty());
+        assert!(manager.get_session(&session_id).await.is_none());
+
+        // Detaching again reports failure instead of stopping the flow
+        let output = ProcessDetachExecutor
+            .execute(&detach, &by_handle, &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.flow_output.as_deref(), Some("failure"));
+    }
+
+    #[tokio::test]
+    async fn test_attach_waits_for_launch() {
+        let manager = Arc::new(FridaManager::new().unwrap());
+        let bridge = Arc::new(RwLock::new(RpcBridge::new()));
+        let mut ctx = context(&manager, &bridge);
+        let node = make_node(
+            "process_attach",
+            serde_json::json!({ "attachMode": "launch", "timeoutMs": 2000 }),
+        );
+        let inputs = HashMap::from([("target".to_string(), Value::String("game*".to_string()))]);
+
+        let launcher = Arc::clone(&manager);
+        tokio::spawn(async move {
+            // Keep starting the game until the gate is armed and catches it
+            loop {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                let game = ProcessInfo::new(4242, "Game.exe");
+                if launcher
+                    .dispatch_spawn("local", game, None)
+                    .await
+                    .unwrap()
+                    .is_some()
+                {
+                    break;
+                }
+            }
+        });
+        let output = ProcessAttachExecutor
+            .execute(&node, &inputs, &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.flow_output.as_deref(), Some("success"));
+        assert_eq!(output.values["pid"], Value::Integer(4242));
+        assert!(bridge.read().await.is_connected());
+
+        // Attached to the game while it runs
+        let session_id = output.values["sessionId"].as_str().unwrap();
+        let session = manager.get_session(session_id).await.unwrap();
+        assert_eq!(session.state().await, forvanced_frida::SessionState::Active);
+
+        let short = make_node(
+            "process_attach",
+            serde_json::json!({ "attachMode": "launch", "timeoutMs": 10 }),
+        );
+        let output = ProcessAttachExecutor
+            .execute(&short, &inputs, &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.flow_output.as_deref(), Some("failure"));
+    }
+
+    #[test]
+    fn test_launch_wait_is_always_bounded() {
+        let unset = make_node(
+            "process_attach",
+            serde_json::json!({ "attachMode": "launch" }),
+        );
+        assert_eq!(launch_timeout(&unset), DEFAULT_LAUNCH_TIMEOUT);
+        // Projects saved while 0 meant "wait forever" get the default too
+        let zero = make_node(
+            "process_attach",
+            serde_json::json!({ "attachMode": "launch", "timeoutMs": 0 }),
+        );
+        assert_eq!(launch_timeout(&zero), DEFAULT_LAUNCH_TIMEOUT);
+        let set = make_node(
+            "process_attach",
+            serde_json::json!({ "attachMode": "launch", "timeoutMs": 250 }),
+        );
+        assert_eq!(launch_timeout(&set), Duration::from_millis(250));
+    }
+
+    #[tokio::test]
+    async fn test_process_nodes_without_host() {
+        let mut ctx = ExecutionContext::new(empty_script(), Arc::new(RwLock::new(HashMap::new())));
+        let node = make_node("process_attach", serde_json::json!({ "attachMode": "pid" }));
+        let inputs = HashMap::from([("target".to_string(), Value::Integer(100))]);
+        let output = ProcessAttachExecutor
+            .execute(&node, &inputs, &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.flow_output.as_deref(), Some("failure"));
+
+        let check = make_node("process_is_attached", serde_json::json!({}));
+        let status = ProcessIsAttachedExecutor
+            .execute(&check, &HashMap::new(), &mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(status.values["attached"], Value::Boolean(false));
+    }
+}
@@ -0,0 +1,320 @@
+//! Journal of persistent target-side state
+//!
+//! Hooks, hook rules, code caves and memory freezes live inside the target
+//! process and vanish when it restarts. The journal keeps the requests that
+//! created them so they can be sent again to a re-attached session. Requests
+//! whose addresses were already resolved to absolute values are left out:
+//! after a restart those point at the wrong place, so event_attach flows have
+//! to re-create them.
+
+use crate::rpc::RpcRequest;
+use serde_json::Value as JsonValue;
+
+/// Inputs that hold addresses the target resolves itself
+const ADDRESS_INPUTS: &[&str] = &["address", "target"];
+
+/// Target requests to re-send after re-attaching, in the order they were made
+#[derive(Debug, Default, Clone)]
+pub struct TargetJournal {
+    entries: Vec<(String, RpcRequest)>,
+}
+
+impl TargetJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track a request the target accepted
+    pub fn record(&mut self, request: &RpcRequest) {
+        match request.node_type.as_str() {
+            "interceptor_attach" | "interceptor_replace" => {
+                if request.inputs.contains_key("replacement") || !relocatable(request) {
+                    return;
+                }
+                let hook_id = str_field(request, "hookId").or_else(|| address_key(request));
+                if let Some(hook_id) = hook_id {
+                    self.upsert(format!("hook:{}", hook_id), request);
+                }
+            }
+            "write_arg" | "replace_retval" => {
+                let hook_id = request
+                    .inputs
+                    .get("context")
+                    .and_then(|c| c.get("hookId"))
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+                    .or_else(|| str_field(request, "hookId"));
+                let Some(hook_id) = hook_id else {
+                    return;
+                };
+                let rule_id = request
+                    .config
+                    .get("ruleId")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| match request.node_type.as_str() {
+                        "write_arg" => format!(
+                            "arg{}",
+                            request
+                                .config
+                                .get("index")
+                                .and_then(|v| v.as_u64())
+                                .unwrap_or(0)
+                        ),
+                        _ => "retval".to_string(),
+                    });
+                self.upsert(format!("rule:{}:{}", hook_id, rule_id), request);
+            }
+            "code_cave" => {
+                let Some(id) = request
+                    .config
+                    .get("caveId")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+                    .or_else(|| address_key(request))
+                else {
+                    return;
+                };
+                self.toggle(format!("cave:{}", id), request);
+            }
+            "memory_freeze" => {
+                if let Some(address) = address_key(request) {
+                    self.toggle(format!("freeze:{}", address), request);
+                }
+            }
+            "interceptor_detach" => match str_field(request, "hookId") {
+                Some(hook_id) => {
+                    let hook = format!("hook:{}", hook_id);
+                    let rules = format!("rule:{}:", hook_id);
+                    self.entries
+                        .retain(|(k, _)| *k != hook && !k.starts_with(&rules));
+                }
+                None => self
+                    .entries
+                    .retain(|(k, _)| !k.starts_with("hook:") && !k.starts_with("rule:")),
+            },
+            _ => {}
+        }
+    }
+
+    /// Requests to re-send, oldest first
+    pub fn requests(&self) -> impl Iterator<Item = &RpcRequest> {
+        self.entries.iter().map(|(_, request)| request)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Track an entry that stays in place until a request disables it
+    fn toggle(&mut self, key: String, request: &RpcRequest) {
+        let enabled = request
+            .inputs
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| request.config.get("enabled") != Some(&JsonValue::Bool(false)));
+        if !enabled {
+            self.entries.retain(|(k, _)| *k != key);
+        } else if relocatable(request) {
+            self.upsert(key, request);
+        }
+    }
+
+    /// Replace an entry in place so rules keep following their hook
+    fn upsert(&mut self, key: String, request: &RpcRequest) {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = request.clone(),
+            None => self.entries.push((key, request.clone())),
+        }
+    }
+}
+
+/// `inputs.<key>` first, then `config.<key>`, as the target script reads them
+fn str_field(request: &RpcRequest, key: &str) -> Option<String> {
+    request
+        .inputs
+        .get(key)
+        .or_else(|| request.config.get(key))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Stable id for an unnamed hook or cave: its unresolved address spec
+fn address_key(request: &RpcRequest) -> Option<String> {
+    ADDRESS_INPUTS
+        .iter()
+        .find_map(|key| request.inputs.get(*key))
+        .map(|spec| spec.to_string())
+}
+
+/// Absolute addresses (strings or numbers) don't survive a restart
+fn relocatable(request: &RpcRequest) -> bool {
+    ADDRESS_INPUTS
+        .iter()
+        .filter_map(|key| request.inputs.get(*key))
+        .all(|value| value.is_object())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn request(node_type: &str, config: JsonValue, inputs: JsonValue) -> RpcRequest {
+        RpcRequest {
+            id: 1,
+            node_id: node_type.to_string(),
+            node_type: node_type.to_string(),
+            config,
+            inputs: serde_json::from_value::<HashMap<String, JsonValue>>(inputs).unwrap(),
+        }
+    }
+
+    fn spec() -> JsonValue {
+        json!({ "base": { "kind": "module", "module": "game.exe" }, "offset": "0x1234", "chain": [] })
+    }
+
+    #[test]
+    fn test_hooks_and_rules_are_kept_in_order() {
+        let mut journal = TargetJournal::new();
+        journal.record(&request(
+            "interceptor_attach",
+            json!({ "hookId": "hp" }),
+            json!({ "address": spec() }),
+        ));
+        journal.record(&request(
+            "replace_retval",
+            json!({ "hookId": "hp", "value": 1 }),
+            json!({}),
+        ));
+        journal.record(&request(
+            "write_arg",
+            json!({ "hookId": "hp", "index": 2 }),
+            json!({}),
+        ));
+        // Toggling a rule replaces it where it was
+        journal.record(&request(
+            "replace_retval",
+            json!({ "hookId": "hp", "value": 1 }),
+            json!({ "enabled": false }),
+        ));
+        journal.record(&request(
+            "memory_write",
+            json!({}),
+            json!({ "address": spec() }),
+        ));
+
+        let types: Vec<_> = journal.requests().map(|r| r.node_type.as_str()).collect();
+        assert_eq!(
+            types,
+            vec!["interceptor_attach", "replace_retval", "write_arg"]
+        );
+        assert_eq!(journal.requests().nth(1).unwrap().inputs["enabled"], false);
+    }
+
+    #[test]
+    fn test_absolute_addresses_are_not_replayed() {
+        let mut journal = TargetJournal::new();
+        journal.record(&request(
+            "interceptor_attach",
+            json!({}),
+            json!({ "address": "0x7ff612340000" }),
+        ));
+        journal.record(&request(
+            "code_cave",
+            json!({}),
+            json!({ "address": "0x401000" }),
+        ));
+        journal.record(&request(
+            "interceptor_replace",
+            json!({ "hookId": "cb" }),
+            json!({ "target": spec(), "replacement": "0x1000" }),
+        ));
+        assert!(journal.is_empty());
+    }
+
+    #[test]
+    fn test_detach_forgets_hook_and_its_rules() {
+        let mut journal = TargetJournal::new();
+        for id in ["a", "b"] {
+            journal.record(&request(
+                "interceptor_attach",
+                json!({ "hookId": id }),
+                json!({ "address": spec() }),
+            ));
+            journal.record(&request(
+                "replace_retval",
+                json!({ "hookId": id }),
+                json!({}),
+            ));
+        }
+
+        journal.record(&request(
+            "interceptor_detach",
+            json!({}),
+            json!({ "hookId": "a" }),
+        ));
+        assert_eq!(journal.len(), 2);
+        assert!(journal.requests().all(|r| r.config["hookId"] == "b"));
+
+        journal.record(&request("interceptor_detach", json!({}), json!({})));
+        assert!(journal.is_empty());
+    }
+
+    #[test]
+    fn test_code_cave_disable_forgets_it() {
+        let mut journal = TargetJournal::new();
+        journal.record(&request(
+            "code_cave",
+            json!({ "caveId": "god" }),
+            json!({ "address": spec() }),
+        ));
+        assert_eq!(journal.len(), 1);
+        journal.record(&request(
+            "code_cave",
+            json!({ "caveId": "god" }),
+            json!({ "enabled": false }),
+        ));
+        assert!(journal.is_empty());
+    }
+
+    #[test]
+    fn test_freezes_are_kept_until_released() {
+        let mut journal = TargetJournal::new();
+        journal.record(&request(
+            "memory_freeze",
+            json!({ "valueType": "int32" }),
+            json!({ "address": spec(), "value": 100 }),
+        ));
+        // Freezing again with a new value replaces the first freeze
+        journal.record(&request(
+            "memory_freeze",
+            json!({ "valueType": "int32" }),
+            json!({ "address": spec(), "value": 999 }),
+        ));
+        journal.record(&request(
+            "memory_freeze",
+            json!({}),
+            json!({ "address": "0x401000", "value": 1 }),
+        ));
+        assert_eq!(journal.len(), 1);
+        assert_eq!(journal.requests().next().unwrap().inputs["value"], 999);
+
+        journal.record(&request(
+            "memory_freeze",
+            json!({}),
+            json!({ "address": spec(), "enabled": false }),
+        ));
+        assert!(journal.is_empty());
+    }
+}
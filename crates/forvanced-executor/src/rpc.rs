@@ -4,14 +4,21 @@
 //! Target nodes (memory operations, hooks, native calls) are executed via RPC.
 
 use crate::error::{ExecutorError, ExecutorResult};
+use crate::replay::TargetJournal;
 use crate::script::ScriptNode;
 use crate::value::Value;
 use async_trait::async_trait;
+use forvanced_core::structs::{resolve_layout, StructDefinition};
+use forvanced_core::{Address, CoreError};
+use forvanced_frida::protocol;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Inputs that carry target addresses and accept the textual address syntax
+const ADDRESS_INPUTS: &[&str] = &["address", "target", "pointer"];
+
 /// Trait for making RPC calls to Frida.
 /// This allows the executor to work without direct Frida dependency.
 #[async_trait]
@@ -45,6 +52,7 @@ impl RpcCaller for NoOpRpcCaller {
 #[derive(Debug, Clone, Serialize)]
 pub struct RpcRequest {
     pub id: u64,
+    pub node_id: String,
     pub node_type: String,
     pub config: serde_json::Value,
     pub inputs: HashMap<String, serde_json::Value>,
@@ -59,16 +67,36 @@ pub struct RpcResponse {
     pub error: Option<String>,
 }
 
-/// Bridge for RPC communication with Frida target
-pub struct RpcBridge {
-    /// Current session ID
-    session_id: Option<String>,
+/// Session used by Target nodes that don't name one
+pub const DEFAULT_SESSION: &str = "default";
 
-    /// Current script ID
+/// Config key and input port through which a Target node picks its session
+const SESSION_KEY: &str = "session";
+
+/// One attached process, addressed by name from Target nodes
+#[derive(Default)]
+struct SessionSlot {
+    session_id: Option<String>,
     script_id: Option<String>,
+    caller: Option<Arc<dyn RpcCaller>>,
+    /// Hooks and code caves to restore after re-attaching
+    journal: RwLock<TargetJournal>,
+}
 
-    /// RPC caller implementation
-    rpc_caller: Arc<RwLock<Option<Arc<dyn RpcCaller>>>>,
+impl SessionSlot {
+    fn is_connected(&self) -> bool {
+        self.session_id.is_some()
+    }
+}
+
+/// Bridge for RPC communication with Frida target
+///
+/// A trainer can drive several processes at once (a game and its server,
+/// say). Each one lives in a named session; the unnamed setters below work
+/// on [`DEFAULT_SESSION`].
+pub struct RpcBridge {
+    /// Sessions by name
+    sessions: HashMap<String, SessionSlot>,
 
     /// Request counter for unique IDs
     request_counter: Arc<RwLock<u64>>,
@@ -76,44 +104,87 @@ pub struct RpcBridge {
     /// Timeout for RPC calls (milliseconds)
     #[allow(dead_code)]
     timeout_ms: u64,
+
+    /// Struct layouts available to struct_read/struct_write nodes
+    struct_definitions: Vec<StructDefinition>,
 }
 
 impl RpcBridge {
     /// Create a new RPC bridge
     pub fn new() -> Self {
         Self {
-            session_id: None,
-            script_id: None,
-            rpc_caller: Arc::new(RwLock::new(None)),
+            sessions: HashMap::new(),
             request_counter: Arc::new(RwLock::new(0)),
             timeout_ms: 5000, // 5 second default timeout
+            struct_definitions: Vec::new(),
         }
     }
 
+    fn slot_mut(&mut self, name: &str) -> &mut SessionSlot {
+        self.sessions.entry(name.to_string()).or_default()
+    }
+
     /// Set the session ID
     pub fn set_session(&mut self, session_id: String) {
-        self.session_id = Some(session_id);
+        self.slot_mut(DEFAULT_SESSION).session_id = Some(session_id);
     }
 
     /// Set the script ID
     pub fn set_script(&mut self, script_id: String) {
-        self.script_id = Some(script_id);
+        self.slot_mut(DEFAULT_SESSION).script_id = Some(script_id);
     }
 
     /// Set the RPC caller
-    pub async fn set_rpc_caller(&self, caller: Arc<dyn RpcCaller>) {
-        *self.rpc_caller.write().await = Some(caller);
+    pub fn set_rpc_caller(&mut self, caller: Arc<dyn RpcCaller>) {
+        self.slot_mut(DEFAULT_SESSION).caller = Some(caller);
     }
 
     /// Clear the session
     pub fn clear_session(&mut self) {
-        self.session_id = None;
-        self.script_id = None;
+        if let Some(slot) = self.sessions.get_mut(DEFAULT_SESSION) {
+            slot.session_id = None;
+            slot.script_id = None;
+        }
     }
 
     /// Clear the RPC caller
-    pub async fn clear_rpc_caller(&self) {
-        *self.rpc_caller.write().await = None;
+    pub fn clear_rpc_caller(&mut self) {
+        if let Some(slot) = self.sessions.get_mut(DEFAULT_SESSION) {
+            slot.caller = None;
+        }
+    }
+
+    /// Attach a named session, replacing whatever it pointed at before
+    ///
+    /// The session's journal is kept, so a re-attached process can be
+    /// restored with [`RpcBridge::replay_session_journal`].
+    pub fn add_session(&mut self, name: &str, session_id: String, caller: Arc<dyn RpcCaller>) {
+        let slot = self.slot_mut(name);
+        slot.session_id = Some(session_id);
+        slot.script_id = None;
+        slot.caller = Some(caller);
+    }
+
+    /// Forget a named session and its journal; returns whether it existed
+    pub fn remove_session(&mut self, name: &str) -> bool {
+        self.sessions.remove(name).is_some()
+    }
+
+    /// Names of the sessions that are currently attached, sorted
+    pub fn session_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|(_, slot)| slot.is_connected())
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Frida session ID behind a named session
+    pub fn session_id(&self, name: &str) -> Option<&str> {
+        self.sessions.get(name)?.session_id.as_deref()
     }
 
     /// Set the timeout for RPC calls
@@ -121,9 +192,33 @@ impl RpcBridge {
         self.timeout_ms = timeout_ms;
     }
 
+    /// Set the struct layouts used to resolve `structName` in struct nodes
+    pub fn set_struct_definitions(&mut self, definitions: Vec<StructDefinition>) {
+        self.struct_definitions = definitions;
+    }
+
     /// Check if connected to a session
     pub fn is_connected(&self) -> bool {
-        self.session_id.is_some()
+        self.is_session_connected(DEFAULT_SESSION)
+    }
+
+    /// Check if a named session is attached
+    pub fn is_session_connected(&self, name: &str) -> bool {
+        self.sessions.get(name).is_some_and(SessionSlot::is_connected)
+    }
+
+    /// Look up an attached session and its caller
+    fn connected_slot(&self, name: &str) -> ExecutorResult<(&SessionSlot, Arc<dyn RpcCaller>)> {
+        let slot = match self.sessions.get(name) {
+            Some(slot) if slot.is_connected() => slot,
+            _ if name == DEFAULT_SESSION => return Err(ExecutorError::NotAttached),
+            _ => return Err(ExecutorError::SessionNotFound(name.to_string())),
+        };
+        let caller = slot
+            .caller
+            .clone()
+            .ok_or_else(|| ExecutorError::RpcError("No RPC caller configured".to_string()))?;
+        Ok((slot, caller))
     }
 
     /// Get the next request ID
@@ -139,43 +234,75 @@ impl RpcBridge {
         node: &ScriptNode,
         inputs: &HashMap<String, Value>,
     ) -> ExecutorResult<HashMap<String, Value>> {
-        let _session_id = self
-            .session_id
-            .as_ref()
-            .ok_or(ExecutorError::NotAttached)?;
+        // A `session` input (a handle from process_attach) beats the config
+        let session = match inputs.get(SESSION_KEY) {
+            Some(Value::String(name)) if !name.is_empty() => name.clone(),
+            _ => node
+                .config_str(SESSION_KEY)
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| DEFAULT_SESSION.to_string()),
+        };
+        let (slot, caller) = self.connected_slot(&session)?;
 
         let request_id = self.next_request_id().await;
 
         // Convert inputs to JSON
-        let json_inputs: HashMap<String, serde_json::Value> = inputs
+        let mut json_inputs: HashMap<String, serde_json::Value> = inputs
             .iter()
+            .filter(|(k, _)| k.as_str() != SESSION_KEY)
             .map(|(k, v)| (k.clone(), serde_json::Value::from(v.clone())))
             .collect();
 
+        let mut config: serde_json::Map<String, serde_json::Value> = node
+            .config
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        if node.node_type == "resolve_address" && !json_inputs.contains_key("address") {
+            if let Some(text) = node.config_str("address") {
+                json_inputs.insert("address".to_string(), serde_json::Value::String(text));
+            }
+        }
+
+        // Module-relative addresses and pointer chains are resolved in the target
+        for key in ADDRESS_INPUTS {
+            if let Some(serde_json::Value::String(text)) = json_inputs.get(*key) {
+                let address: Address = text
+                    .parse()
+                    .map_err(|e: CoreError| ExecutorError::InvalidConfig(e.to_string()))?;
+                if !address.is_absolute() {
+                    json_inputs.insert(key.to_string(), address.to_spec());
+                }
+            }
+        }
+
+        // Struct nodes carry the resolved layout so the target needs no registry
+        if matches!(node.node_type.as_str(), "struct_read" | "struct_write")
+            && !config.contains_key("layout")
+        {
+            let name = node.config_str("structName").ok_or_else(|| {
+                ExecutorError::InvalidConfig(format!("{} requires structName", node.node_type))
+            })?;
+            let layout = resolve_layout(&name, &self.struct_definitions)
+                .map_err(|e| ExecutorError::InvalidConfig(e.to_string()))?;
+            config.insert("layout".to_string(), layout);
+        }
+
         let request = RpcRequest {
             id: request_id,
+            node_id: node.id.clone(),
             node_type: node.node_type.clone(),
-            config: serde_json::Value::Object(
-                node.config
-                    .iter()
-                    .map(|(k, v)| (k.clone(), v.clone()))
-                    .collect(),
-            ),
+            config: serde_json::Value::Object(config),
             inputs: json_inputs,
         };
 
-        // Get the RPC caller
-        let caller_guard = self.rpc_caller.read().await;
-        let caller = caller_guard
-            .as_ref()
-            .ok_or_else(|| ExecutorError::RpcError("No RPC caller configured".to_string()))?;
-
         // Call the RPC method
         let request_json = serde_json::to_value(&request)
             .map_err(|e| ExecutorError::RpcError(format!("Failed to serialize request: {}", e)))?;
 
         let response_json = caller
-            .call("executeTargetNode", vec![request_json])
+            .call(protocol::EXECUTE_TARGET_NODE, vec![request_json])
             .await
             .map_err(|e| ExecutorError::RpcError(e))?;
 
@@ -188,6 +315,7 @@ impl RpcBridge {
                 response.error.unwrap_or_else(|| "Unknown RPC error".to_string()),
             ));
         }
+        slot.journal.write().await.record(&request);
 
         // Convert outputs back to Value
         let outputs = response
@@ -200,6 +328,69 @@ impl RpcBridge {
         Ok(outputs)
     }
 
+    /// Re-send journaled hooks and code caves to the current session
+    ///
+    /// Returns one message per request the target rejected; the rest are applied.
+    pub async fn replay_journal(&self) -> ExecutorResult<Vec<String>> {
+        self.replay_session_journal(DEFAULT_SESSION).await
+    }
+
+    /// Re-send a named session's journal after its process was re-attached
+    pub async fn replay_session_journal(&self, name: &str) -> ExecutorResult<Vec<String>> {
+        let (slot, caller) = self.connected_slot(name)?;
+        let requests: Vec<RpcRequest> = slot.journal.read().await.requests().cloned().collect();
+
+        let mut failures = Vec::new();
+        for mut request in requests {
+            request.id = self.next_request_id().await;
+            let request_json = serde_json::to_value(&request).map_err(|e| {
+                ExecutorError::RpcError(format!("Failed to serialize request: {}", e))
+            })?;
+            let outcome = caller
+                .call(protocol::EXECUTE_TARGET_NODE, vec![request_json])
+                .await
+                .and_then(|response| {
+                    serde_json::from_value::<RpcResponse>(response).map_err(|e| e.to_string())
+                });
+            match outcome {
+                Ok(response) if response.success => {}
+                Ok(response) => failures.push(format!(
+                    "{} ({}): {}",
+                    request.node_type,
+                    request.node_id,
+                    response.error.unwrap_or_default()
+                )),
+                Err(e) => failures.push(format!("{} ({}): {}", request.node_type, request.node_id, e)),
+            }
+        }
+        Ok(failures)
+    }
+
+    /// Forget journaled target state, e.g. when the user detaches on purpose
+    pub async fn clear_journal(&self) {
+        self.clear_session_journal(DEFAULT_SESSION).await;
+    }
+
+    /// Forget a named session's journaled target state
+    pub async fn clear_session_journal(&self, name: &str) {
+        if let Some(slot) = self.sessions.get(name) {
+            slot.journal.write().await.clear();
+        }
+    }
+
+    /// Number of journaled requests
+    pub async fn journal_len(&self) -> usize {
+        self.session_journal_len(DEFAULT_SESSION).await
+    }
+
+    /// Number of requests journaled for a named session
+    pub async fn session_journal_len(&self, name: &str) -> usize {
+        match self.sessions.get(name) {
+            Some(slot) => slot.journal.read().await.len(),
+            None => 0,
+        }
+    }
+
     /// Execute multiple target nodes in a batch (optimization)
     pub async fn execute_batch(
         &self,
@@ -222,331 +413,574 @@ impl Default for RpcBridge {
     }
 }
 
+/// The target agent, built from `agent/src/index.js` by the build script
+pub const TARGET_AGENT: &str = include_str!(concat!(env!("OUT_DIR"), "/agent.js"));
+
 /// Generate the JavaScript code for the target-side RPC handler
 ///
 /// This JavaScript code is injected into the target process and handles
 /// RPC calls from the host executor.
 pub fn generate_target_script() -> String {
-    r#"
-// Forvanced Target RPC Handler
-// This script handles RPC calls from the host executor
-
-'use strict';
-
-// Target node implementations
-const targetNodes = {
-    // Memory Operations
-    memory_read: function(config, inputs) {
-        const address = ptr(inputs.address);
-        const valueType = config.valueType || 'int32';
-
-        let value;
-        switch (valueType) {
-            case 'int8': value = address.readS8(); break;
-            case 'uint8': value = address.readU8(); break;
-            case 'int16': value = address.readS16(); break;
-            case 'uint16': value = address.readU16(); break;
-            case 'int32': value = address.readS32(); break;
-            case 'uint32': value = address.readU32(); break;
-            case 'int64': value = address.readS64().toString(); break;
-            case 'uint64': value = address.readU64().toString(); break;
-            case 'float': value = address.readFloat(); break;
-            case 'double': value = address.readDouble(); break;
-            case 'pointer': value = address.readPointer().toString(); break;
-            case 'string': value = address.readUtf8String(); break;
-            default: value = address.readS32();
-        }
-
-        return { value: value };
-    },
+    TARGET_AGENT.to_string()
+}
 
-    memory_write: function(config, inputs) {
-        const address = ptr(inputs.address);
-        const value = inputs.value;
-        const valueType = config.valueType || 'int32';
-
-        switch (valueType) {
-            case 'int8': address.writeS8(value); break;
-            case 'uint8': address.writeU8(value); break;
-            case 'int16': address.writeS16(value); break;
-            case 'uint16': address.writeU16(value); break;
-            case 'int32': address.writeS32(value); break;
-            case 'uint32': address.writeU32(value); break;
-            case 'int64': address.writeS64(int64(value)); break;
-            case 'uint64': address.writeU64(uint64(value)); break;
-            case 'float': address.writeFloat(value); break;
-            case 'double': address.writeDouble(value); break;
-            case 'pointer': address.writePointer(ptr(value)); break;
-            case 'string': address.writeUtf8String(value); break;
-            default: address.writeS32(value);
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        return {};
-    },
+    #[test]
+    fn test_rpc_bridge_creation() {
+        let bridge = RpcBridge::new();
+        assert!(!bridge.is_connected());
+    }
 
-    memory_scan: function(config, inputs) {
-        const pattern = inputs.value.toString();
-        const protection = config.protection || 'r--';
-        const ranges = Process.enumerateRanges(protection);
+    #[test]
+    fn test_rpc_bridge_session() {
+        let mut bridge = RpcBridge::new();
+        bridge.set_session("session-123".to_string());
+        assert!(bridge.is_connected());
+        bridge.clear_session();
+        assert!(!bridge.is_connected());
+    }
 
-        const results = [];
-        for (const range of ranges) {
-            const matches = Memory.scanSync(range.base, range.size, pattern);
-            for (const match of matches) {
-                results.push(match.address.toString());
-            }
-        }
+    #[test]
+    fn test_generate_target_script() {
+        let script = generate_target_script();
+        assert!(script.contains("rpc.exports"));
+        assert!(script.contains("executeTargetNode"));
+        assert!(script.contains("memory_read"));
+    }
 
-        return { results: results, count: results.length };
-    },
+    #[test]
+    fn test_target_script_code_cave() {
+        let script = generate_target_script();
+        assert!(script.contains("code_cave: function"));
+        assert!(script.contains("X86Relocator"));
+        assert!(script.contains("Arm64Relocator"));
+        assert!(script.contains("revertCodeCaves"));
+    }
 
-    memory_alloc: function(config, inputs) {
-        const size = inputs.size || config.size || 256;
-        const address = Memory.alloc(size);
-        return { address: address.toString() };
+    /// Just enough of Frida's x64 API for `installCodeCave`: byte-addressed
+    /// memory, an X86Writer that emits far jumps out of rel32 range, a
+    /// relocator over fixed-size instructions, and `Memory.patchCode` that
+    /// records writes outside the patched range
+    const CODE_CAVE_PRELUDE: &str = r#"
+const mem = new Map();
+const patches = [];
+const overflows = [];
+const calls = [];
+let patchRange = null;
+let caveDistance = 0x100000;
+let scratch = 0x9000000;
+class NativePointer {
+    constructor(v) { this.v = typeof v === 'number' ? v : Number(BigInt(v)); }
+    add(o) { return new NativePointer(this.v + num(o)); }
+    sub(o) { return new NativePointer(this.v - num(o)); }
+    toString() { return '0x' + this.v.toString(16); }
+    readByteArray(n) { return read(this.v, n); }
+    writeByteArray(bytes) { bytes.forEach((b, i) => writeByte(this.v + i, b)); }
+}
+function num(o) { return o instanceof NativePointer ? o.v : Number(o); }
+function ptr(v) { return v instanceof NativePointer ? v : new NativePointer(v); }
+function read(at, n) { return Array.from({ length: n }, (_, i) => mem.get(at + i) || 0); }
+function writeByte(at, b) {
+    if (patchRange && (at < patchRange[0] || at >= patchRange[1])) overflows.push(at);
+    mem.set(at, b);
+}
+const Process = { arch: 'x64', pageSize: 4096 };
+const Memory = {
+    alloc(size, options) {
+        if (options && options.near) return options.near.add(caveDistance);
+        scratch += size;
+        return ptr(scratch);
     },
+    patchCode(address, size, apply) {
+        patches.push({ address: address.toString(), size: size });
+        patchRange = [address.v, address.v + size];
+        try { apply(address); } finally { patchRange = null; }
+    }
+};
+class X86Writer {
+    constructor(code, options) { this.base = code.v; this.pc = options.pc.v; this.offset = 0; }
+    putBytes(bytes) { for (const b of bytes) writeByte(this.base + this.offset++, b); }
+    putJmpAddress(target) {
+        const rel = target.v - (this.pc + this.offset + 5);
+        if (rel >= -0x80000000 && rel <= 0x7fffffff) {
+            this.putBytes([0xe9, rel & 0xff, (rel >> 8) & 0xff, (rel >> 16) & 0xff, (rel >> 24) & 0xff]);
+        } else {
+            this.putBytes([0xff, 0x25, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        }
+    }
+    putNopPadding(n) { this.putBytes(new Array(n).fill(0x90)); }
+    putMovRegU32(reg, imm) {
+        calls.push({ op: 'putMovRegU32', args: [reg, typeof imm] });
+        this.putBytes([0xb8, imm & 0xff, 0, 0, 0]);
+    }
+    putMovRegAddress(reg, address) {
+        calls.push({ op: 'putMovRegAddress', args: [reg, address instanceof NativePointer ? address.toString() : typeof address] });
+        this.putBytes([0x48, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+    flush() {}
+    dispose() {}
+}
+class X86Relocator {
+    constructor(input, writer) { this.input = input.v; this.writer = writer; this.read = 0; this.sizes = [3, 4, 2, 6]; }
+    readOne() { this.read += this.sizes.shift() || 0; return this.read; }
+    writeAll() { this.writer.putBytes(read(this.input, this.read)); }
+    dispose() {}
+}
+const rpc = {};
+function recv() {}
+function send() {}
+console.log = () => {};
+"#;
+
+    const CODE_CAVE_DRIVER: &str = r#"
+const target = 0x10000;
+for (let i = 0; i < 32; i++) mem.set(target + i, 0x40 + i);
+function run(config, enabled) {
+    patches.length = 0;
+    const response = rpc.exports.executeTargetNode({
+        id: 'n', node_type: 'code_cave', config: config, inputs: { address: '0x10000', enabled: enabled }
+    });
+    return { response: response, patches: patches.slice(), bytes: read(target, 16) };
+}
+const results = {};
+caveDistance = 0x100000000;
+results.far = run({ caveId: 'far', instructions: ['90'] });
+caveDistance = 0x100000;
+results.ambiguous = run({ caveId: 'ambiguous', instructions: [{ op: 'putMovRegU32', args: ['eax', '10'] }] });
+results.installed = run({ caveId: 'cave', instructions: [
+    { bytes: '90 90' },
+    { op: 'putMovRegU32', args: ['eax', 10] },
+    { op: 'putMovRegAddress', args: ['rax', { address: '0x1234' }] }
+] });
+results.reverted = run({ caveId: 'cave' }, false);
+results.calls = calls;
+results.overflows = overflows;
+process.stdout.write(JSON.stringify(results));
+"#;
+
+    fn run_code_cave_harness() -> serde_json::Value {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let harness = format!(
+            "{}{}{}",
+            CODE_CAVE_PRELUDE,
+            generate_target_script().replace("'use strict';", ""),
+            CODE_CAVE_DRIVER
+        );
+        let mut node = Command::new("node")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("the code cave test needs node on PATH");
+        node.stdin
+            .take()
+            .unwrap()
+            .write_all(harness.as_bytes())
+            .unwrap();
+        let output = node.wait_with_output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice(&output.stdout).unwrap()
+    }
 
-    memory_protect: function(config, inputs) {
-        const address = ptr(inputs.address);
-        const size = inputs.size;
-        const protection = config.protection || 'rwx';
-        const success = Memory.protect(address, size, protection);
-        return { success: success };
-    },
+    #[test]
+    fn test_code_cave_behaviour() {
+        let results = run_code_cave_harness();
+        let original: Vec<u64> = (0x40..0x50).collect();
+        let bytes = |case: &str| -> Vec<u64> {
+            results[case]["bytes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|b| b.as_u64().unwrap())
+                .collect()
+        };
 
-    // Pointer Operations
-    pointer_add: function(config, inputs) {
-        const pointer = ptr(inputs.pointer);
-        const offset = parseInt(inputs.offset);
-        const result = pointer.add(offset);
-        return { result: result.toString() };
-    },
+        // A cave out of rel32 range is refused before anything is written
+        let far = &results["far"];
+        assert_eq!(far["response"]["success"], false);
+        let error = far["response"]["error"].as_str().unwrap();
+        assert!(error.contains("out of branch range"), "{}", error);
+        assert_eq!(far["patches"], serde_json::json!([]));
+        assert_eq!(bytes("far"), original);
+
+        // Numeric strings are neither numbers nor pointers
+        let ambiguous = &results["ambiguous"];
+        assert_eq!(ambiguous["response"]["success"], false);
+        let error = ambiguous["response"]["error"].as_str().unwrap();
+        assert!(
+            error.contains("Ambiguous code cave operand \"10\""),
+            "{}",
+            error
+        );
+        assert!(!ambiguous["patches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p["address"] == "0x10000"));
+        assert_eq!(bytes("ambiguous"), original);
+
+        // Whole instructions (3 + 4 bytes) are replaced by a jump and padding
+        let installed = &results["installed"];
+        assert_eq!(
+            installed["response"]["success"], true,
+            "{}",
+            installed["response"]
+        );
+        let target_patch = installed["patches"]
+            .as_array()
+            .unwrap()
+            .last()
+            .unwrap()
+            .clone();
+        assert_eq!(
+            target_patch,
+            serde_json::json!({ "address": "0x10000", "size": 7 })
+        );
+        let patched = bytes("installed");
+        assert_eq!(patched[0], 0xe9);
+        assert_eq!(&patched[5..7], &[0x90, 0x90]);
+        assert_eq!(&patched[7..], &original[7..]);
+        assert_eq!(
+            results["calls"],
+            serde_json::json!([
+                { "op": "putMovRegU32", "args": ["eax", "number"] },
+                { "op": "putMovRegAddress", "args": ["rax", "0x1234"] }
+            ])
+        );
+        assert_eq!(results["overflows"], serde_json::json!([]));
+
+        let reverted = &results["reverted"];
+        assert_eq!(reverted["response"]["outputs"]["reverted"], true);
+        assert_eq!(bytes("reverted"), original);
+    }
 
-    pointer_read: function(config, inputs) {
-        const pointer = ptr(inputs.pointer);
-        const readType = config.readType || 'uint32';
-
-        let value;
-        switch (readType) {
-            case 'pointer': value = pointer.readPointer().toString(); break;
-            case 'int8': value = pointer.readS8(); break;
-            case 'uint8': value = pointer.readU8(); break;
-            case 'int16': value = pointer.readS16(); break;
-            case 'uint16': value = pointer.readU16(); break;
-            case 'int32': value = pointer.readS32(); break;
-            case 'uint32': value = pointer.readU32(); break;
-            case 'int64': value = pointer.readS64().toString(); break;
-            case 'uint64': value = pointer.readU64().toString(); break;
-            case 'float': value = pointer.readFloat(); break;
-            case 'double': value = pointer.readDouble(); break;
-            case 'utf8': value = pointer.readUtf8String(); break;
-            case 'utf16': value = pointer.readUtf16String(); break;
-            default: value = pointer.readU32();
-        }
+    #[test]
+    fn test_target_script_memory_freeze() {
+        let script = generate_target_script();
+        assert!(script.contains("memory_freeze: function"));
+        assert!(script.contains("startFreeze(inputs.address"));
+    }
 
-        return { value: value };
-    },
+    /// Names defined in the agent's `rpc.exports` block
+    fn agent_exports() -> Vec<String> {
+        let source = include_str!("../agent/src/index.js");
+        let block = source
+            .split("rpc.exports = {")
+            .nth(1)
+            .and_then(|rest| rest.split("\n};").next())
+            .expect("rpc.exports block");
+        block
+            .lines()
+            .filter_map(|line| line.strip_prefix("    "))
+            .filter_map(|line| line.split_once(':').map(|(name, _)| name))
+            .filter(|name| name.chars().all(|c| c.is_ascii_alphanumeric()))
+            .map(str::to_string)
+            .collect()
+    }
 
-    pointer_write: function(config, inputs) {
-        const pointer = ptr(inputs.pointer);
-        const value = inputs.value;
-        const writeType = config.writeType || 'uint32';
-
-        switch (writeType) {
-            case 'pointer': pointer.writePointer(ptr(value)); break;
-            case 'int8': pointer.writeS8(value); break;
-            case 'uint8': pointer.writeU8(value); break;
-            case 'int16': pointer.writeS16(value); break;
-            case 'uint16': pointer.writeU16(value); break;
-            case 'int32': pointer.writeS32(value); break;
-            case 'uint32': pointer.writeU32(value); break;
-            case 'int64': pointer.writeS64(int64(value)); break;
-            case 'uint64': pointer.writeU64(uint64(value)); break;
-            case 'float': pointer.writeFloat(value); break;
-            case 'double': pointer.writeDouble(value); break;
-            case 'utf8': pointer.writeUtf8String(value); break;
-            case 'utf16': pointer.writeUtf16String(value); break;
-            default: pointer.writeU32(value);
+    #[test]
+    fn test_agent_exports_match_protocol() {
+        let exports = agent_exports();
+        for method in protocol::METHODS {
+            assert!(
+                exports.iter().any(|e| e == method),
+                "agent does not export {}",
+                method
+            );
         }
-
-        return {};
-    },
-
-    // Module Operations
-    get_module: function(config, inputs) {
-        const name = inputs.name;
-        const module = Process.findModuleByName(name);
-        if (!module) {
-            return { module: null, base: '0x0', size: 0 };
+        for export in &exports {
+            assert!(
+                protocol::METHODS.contains(&export.as_str()),
+                "{} is missing from forvanced_frida::protocol",
+                export
+            );
         }
-        return {
-            module: module.name,
-            base: module.base.toString(),
-            size: module.size
-        };
-    },
+    }
 
-    find_symbol: function(config, inputs) {
-        const moduleName = inputs.module;
-        const symbolName = inputs.symbol;
-        const address = Module.findExportByName(moduleName, symbolName);
-        return { address: address ? address.toString() : '0x0' };
-    },
+    #[test]
+    fn test_target_script_interceptor_nodes() {
+        let script = generate_target_script();
+        for node in [
+            "interceptor_attach",
+            "interceptor_replace",
+            "interceptor_detach",
+            "read_arg",
+            "write_arg",
+            "read_retval",
+            "replace_retval",
+        ] {
+            assert!(
+                script.contains(&format!("{}: function", node)),
+                "missing target node: {}",
+                node
+            );
+        }
+        assert!(script.contains("setHookRuleEnabled"));
+        // Retval rules see the args of their own call, not the last one
+        assert!(script.contains("retvalRuleValue(hook, this.capturedArgs, retval)"));
+    }
 
-    get_base_address: function(config, inputs) {
-        const moduleName = inputs.moduleName;
-        const module = Process.findModuleByName(moduleName);
-        return { address: module ? module.base.toString() : '0x0' };
-    },
+    #[test]
+    fn test_target_script_native_callback() {
+        let script = generate_target_script();
+        assert!(script.contains("native_callback: function"));
+        assert!(script.contains("recv('native_callback_reply'"));
+        assert!(script.contains("nativeCallbackReply"));
+    }
 
-    enumerate_modules: function(config, inputs) {
-        const modules = Process.enumerateModules();
-        return {
-            modules: modules.map(m => ({
-                name: m.name,
-                base: m.base.toString(),
-                size: m.size,
-                path: m.path
-            })),
-            count: modules.length
-        };
-    },
+    /// Records the last request and answers with a successful empty response
+    struct CapturingCaller {
+        last: std::sync::Mutex<Option<serde_json::Value>>,
+    }
 
-    enumerate_exports: function(config, inputs) {
-        const moduleName = inputs.moduleName;
-        const module = Process.findModuleByName(moduleName);
-        if (!module) {
-            return { exports: [], count: 0 };
+    #[async_trait]
+    impl RpcCaller for CapturingCaller {
+        async fn call(
+            &self,
+            _method: &str,
+            args: Vec<serde_json::Value>,
+        ) -> Result<serde_json::Value, String> {
+            let request = args.into_iter().next().unwrap_or_default();
+            let id = request["id"].clone();
+            *self.last.lock().unwrap() = Some(request);
+            Ok(serde_json::json!({ "id": id, "success": true, "outputs": {} }))
         }
-        const exports = module.enumerateExports();
-        return {
-            exports: exports.map(e => ({
-                name: e.name,
-                address: e.address.toString(),
-                type: e.type
-            })),
-            count: exports.length
-        };
-    },
-
-    // Native Call
-    call_native: function(config, inputs) {
-        const address = ptr(inputs.address);
-        const returnType = config.returnType || 'void';
-        const argTypes = config.argTypes || [];
-        const abi = config.abi || 'default';
+    }
 
-        const args = [];
-        for (let i = 0; i < (config.argCount || 0); i++) {
-            args.push(inputs['arg' + i]);
+    fn target_node(node_type: &str, config: serde_json::Value) -> ScriptNode {
+        ScriptNode {
+            id: "read".to_string(),
+            node_type: node_type.to_string(),
+            label: "Read".to_string(),
+            x: 0.0,
+            y: 0.0,
+            config: config
+                .as_object()
+                .unwrap()
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            inputs: vec![],
+            outputs: vec![],
         }
+    }
 
-        const func = new NativeFunction(address, returnType, argTypes, { abi: abi });
-        const result = func(...args);
+    #[tokio::test]
+    async fn test_journal_replays_hooks_on_new_session() {
+        let caller = Arc::new(CapturingCaller {
+            last: std::sync::Mutex::new(None),
+        });
+        let mut bridge = RpcBridge::new();
+        bridge.set_session("first".to_string());
+        bridge.set_rpc_caller(caller.clone());
+
+        let node = target_node("interceptor_attach", serde_json::json!({ "hookId": "hp" }));
+        let inputs = HashMap::from([("address".to_string(), Value::String("game.exe+0x10".to_string()))]);
+        bridge.execute_target_node(&node, &inputs).await.unwrap();
+        let read = target_node("memory_read", serde_json::json!({}));
+        bridge.execute_target_node(&read, &inputs).await.unwrap();
+        assert_eq!(bridge.journal_len().await, 1);
 
-        return { return: result };
+        // A detach keeps the journal; the next session gets the hook again
+        bridge.clear_session();
+        assert!(bridge.replay_journal().await.is_err());
+        bridge.set_session("second".to_string());
+        *caller.last.lock().unwrap() = None;
+        assert!(bridge.replay_journal().await.unwrap().is_empty());
+        let request = caller.last.lock().unwrap().clone().unwrap();
+        assert_eq!(request["node_type"], "interceptor_attach");
+        assert_eq!(request["inputs"]["address"]["base"]["module"], "game.exe");
+        assert_eq!(request["id"], 3);
+
+        bridge.clear_journal().await;
+        assert_eq!(bridge.journal_len().await, 0);
     }
-};
 
-// Interceptor state (for attached hooks)
-const activeInterceptors = new Map();
-
-// RPC exports for host communication
-rpc.exports = {
-    // Execute a single target node
-    executeTargetNode: function(request) {
-        try {
-            const handler = targetNodes[request.node_type];
-            if (!handler) {
-                return {
-                    id: request.id,
-                    success: false,
-                    error: 'Unknown node type: ' + request.node_type
-                };
-            }
+    #[tokio::test]
+    async fn test_struct_node_layout_resolution() {
+        use forvanced_core::structs::StructField;
+        use forvanced_core::ValueType;
 
-            const outputs = handler(request.config, request.inputs);
-            return {
-                id: request.id,
-                success: true,
-                outputs: outputs
-            };
-        } catch (e) {
-            return {
-                id: request.id,
-                success: false,
-                error: e.toString()
-            };
-        }
-    },
+        let caller = Arc::new(CapturingCaller {
+            last: std::sync::Mutex::new(None),
+        });
+        let mut bridge = RpcBridge::new();
+        bridge.set_session("session".to_string());
+        bridge.set_struct_definitions(vec![StructDefinition {
+            name: "Player".to_string(),
+            fields: vec![StructField::value("hp", 0x10, ValueType::Int32)],
+            size: None,
+        }]);
+        bridge.set_rpc_caller(caller.clone());
+
+        let node = target_node("struct_read", serde_json::json!({ "structName": "Player" }));
+        bridge.execute_target_node(&node, &HashMap::new()).await.unwrap();
+        let request = caller.last.lock().unwrap().clone().unwrap();
+        assert_eq!(request["config"]["layout"]["fields"][0]["offset"], 0x10);
+        assert_eq!(request["config"]["layout"]["fields"][0]["type"], "int32");
+
+        let unknown = target_node("struct_read", serde_json::json!({ "structName": "Missing" }));
+        let err = bridge.execute_target_node(&unknown, &HashMap::new()).await.unwrap_err();
+        assert!(matches!(err, ExecutorError::InvalidConfig(_)));
+    }
 
-    // Attach interceptor
-    attachInterceptor: function(id, address, options) {
-        const addr = ptr(address);
-        const listener = Interceptor.attach(addr, {
-            onEnter: function(args) {
-                send({ type: 'interceptor', event: 'enter', id: id, args: args });
-            },
-            onLeave: function(retval) {
-                send({ type: 'interceptor', event: 'leave', id: id, retval: retval });
-            }
+    #[tokio::test]
+    async fn test_target_nodes_route_to_named_sessions() {
+        let game = Arc::new(CapturingCaller {
+            last: std::sync::Mutex::new(None),
         });
-        activeInterceptors.set(id, listener);
-        return { success: true };
-    },
+        let server = Arc::new(CapturingCaller {
+            last: std::sync::Mutex::new(None),
+        });
+        let mut bridge = RpcBridge::new();
+        bridge.set_session("game-session".to_string());
+        bridge.set_rpc_caller(game.clone());
+        bridge.add_session("server", "server-session".to_string(), server.clone());
+        assert_eq!(bridge.session_names(), vec!["default", "server"]);
+        assert_eq!(bridge.session_id("server"), Some("server-session"));
+
+        // Config picks the session; the default is used otherwise
+        let node = target_node("memory_read", serde_json::json!({ "session": "server" }));
+        bridge.execute_target_node(&node, &HashMap::new()).await.unwrap();
+        assert!(server.last.lock().unwrap().take().is_some());
+        assert!(game.last.lock().unwrap().is_none());
+
+        let plain = target_node("memory_read", serde_json::json!({}));
+        bridge.execute_target_node(&plain, &HashMap::new()).await.unwrap();
+        assert!(game.last.lock().unwrap().take().is_some());
+
+        // A session handle on the input wins over the config and isn't forwarded
+        let inputs = HashMap::from([("session".to_string(), Value::String("default".to_string()))]);
+        bridge.execute_target_node(&node, &inputs).await.unwrap();
+        let request = game.last.lock().unwrap().take().unwrap();
+        assert!(request["inputs"].get("session").is_none());
+        assert!(server.last.lock().unwrap().is_none());
+
+        assert!(bridge.remove_session("server"));
+        let err = bridge.execute_target_node(&node, &HashMap::new()).await.unwrap_err();
+        assert!(matches!(err, ExecutorError::SessionNotFound(name) if name == "server"));
+        bridge.clear_session();
+        let err = bridge.execute_target_node(&plain, &HashMap::new()).await.unwrap_err();
+        assert!(matches!(err, ExecutorError::NotAttached));
+    }
+
+    #[tokio::test]
+    async fn test_named_sessions_keep_their_own_journal() {
+        let caller = Arc::new(CapturingCaller {
+            last: std::sync::Mutex::new(None),
+        });
+        let mut bridge = RpcBridge::new();
+        bridge.add_session("server", "first".to_string(), caller.clone());
+
+        let hook = target_node(
+            "interceptor_attach",
+            serde_json::json!({ "hookId": "tick", "session": "server" }),
+        );
+        let inputs = HashMap::from([("address".to_string(), Value::String("server.exe+0x20".to_string()))]);
+        bridge.execute_target_node(&hook, &inputs).await.unwrap();
+        assert_eq!(bridge.journal_len().await, 0);
+
+        bridge.add_session("server", "second".to_string(), caller.clone());
+        *caller.last.lock().unwrap() = None;
+        assert!(bridge.replay_session_journal("server").await.unwrap().is_empty());
+        let request = caller.last.lock().unwrap().clone().unwrap();
+        assert_eq!(request["inputs"]["address"]["base"]["module"], "server.exe");
+    }
 
-    // Detach interceptor
-    detachInterceptor: function(id) {
-        const listener = activeInterceptors.get(id);
-        if (listener) {
-            listener.detach();
-            activeInterceptors.delete(id);
-            return { success: true };
+    #[tokio::test]
+    async fn test_journals_are_cleared_per_session() {
+        let caller = Arc::new(CapturingCaller {
+            last: std::sync::Mutex::new(None),
+        });
+        let mut bridge = RpcBridge::new();
+        bridge.set_session("game".to_string());
+        bridge.set_rpc_caller(caller.clone());
+        bridge.add_session("server", "server".to_string(), caller.clone());
+
+        let inputs = HashMap::from([("address".to_string(), Value::String("game.exe+0x10".to_string()))]);
+        for session in ["default", "server"] {
+            let hook = target_node(
+                "interceptor_attach",
+                serde_json::json!({ "hookId": "tick", "session": session }),
+            );
+            bridge.execute_target_node(&hook, &inputs).await.unwrap();
         }
-        return { success: false, error: 'Interceptor not found' };
-    },
+        assert_eq!(bridge.journal_len().await, 1);
+        assert_eq!(bridge.session_journal_len("server").await, 1);
+        assert_eq!(bridge.session_journal_len("missing").await, 0);
 
-    // Detach all interceptors
-    detachAll: function() {
-        Interceptor.detachAll();
-        activeInterceptors.clear();
-        return { success: true };
-    },
+        bridge.clear_session_journal("server").await;
+        assert_eq!(bridge.session_journal_len("server").await, 0);
+        assert_eq!(bridge.journal_len().await, 1);
 
-    // Ping for health check
-    ping: function() {
-        return { alive: true, timestamp: Date.now() };
+        bridge.clear_journal().await;
+        assert_eq!(bridge.journal_len().await, 0);
+        bridge.clear_session_journal("missing").await;
     }
-};
-
-console.log('[Forvanced] Target RPC handler loaded');
-"#.to_string()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn test_rpc_bridge_creation() {
-        let bridge = RpcBridge::new();
-        assert!(!bridge.is_connected());
+    fn test_target_script_struct_nodes() {
+        let script = generate_target_script();
+        assert!(script.contains("struct_read: function"));
+        assert!(script.contains("struct_write: function"));
     }
 
-    #[test]
-    fn test_rpc_bridge_session() {
+    #[tokio::test]
+    async fn test_address_inputs_become_specs() {
+        let caller = Arc::new(CapturingCaller {
+            last: std::sync::Mutex::new(None),
+        });
         let mut bridge = RpcBridge::new();
-        bridge.set_session("session-123".to_string());
-        assert!(bridge.is_connected());
-        bridge.clear_session();
-        assert!(!bridge.is_connected());
+        bridge.set_session("session".to_string());
+        bridge.set_rpc_caller(caller.clone());
+
+        let node = target_node("memory_read", serde_json::json!({}));
+        let mut inputs = HashMap::new();
+        inputs.insert("address".to_string(), Value::String("[game.exe+0x10]+0x4".to_string()));
+        bridge.execute_target_node(&node, &inputs).await.unwrap();
+        let request = caller.last.lock().unwrap().clone().unwrap();
+        assert_eq!(request["inputs"]["address"]["base"]["module"], "game.exe");
+        assert_eq!(request["inputs"]["address"]["chain"][0], "0x4");
+
+        // Absolute addresses pass through untouched
+        inputs.insert("address".to_string(), Value::String("0x1000".to_string()));
+        bridge.execute_target_node(&node, &inputs).await.unwrap();
+        let request = caller.last.lock().unwrap().clone().unwrap();
+        assert_eq!(request["inputs"]["address"], "0x1000");
+
+        let resolve = target_node("resolve_address", serde_json::json!({ "address": "libc.so!malloc" }));
+        bridge.execute_target_node(&resolve, &HashMap::new()).await.unwrap();
+        let request = caller.last.lock().unwrap().clone().unwrap();
+        assert_eq!(request["inputs"]["address"]["base"]["kind"], "export");
+
+        inputs.insert("address".to_string(), Value::String("[broken".to_string()));
+        let err = bridge.execute_target_node(&node, &inputs).await.unwrap_err();
+        assert!(matches!(err, ExecutorError::InvalidConfig(_)));
     }
 
     #[test]
-    fn test_generate_target_script() {
+    fn test_target_script_resolve_address() {
         let script = generate_target_script();
-        assert!(script.contains("rpc.exports"));
-        assert!(script.contains("executeTargetNode"));
-        assert!(script.contains("memory_read"));
+        assert!(script.contains("function resolveAddress(spec)"));
+        assert!(script.contains("resolve_address: function"));
+        assert!(!script.contains("ptr(inputs.address)"));
+    }
+
+    /// Frida 17 removed the static `Module.findExportByName`
+    #[test]
+    fn test_target_script_uses_frida_17_export_lookup() {
+        let script = generate_target_script();
+        assert!(!script.contains("Module.findExportByName("));
+        assert!(script.contains("Module.findGlobalExportByName(name)"));
+        assert!(script.contains("module.findExportByName(name)"));
     }
 }
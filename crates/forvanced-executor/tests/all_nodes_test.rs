@@ -722,6 +722,50 @@ async fn test_event_interval_tick_value() {
     assert_eq!(result.logs, vec!["5"]);
 }
 
+// ============================================
+// Event Hook Test
+// ============================================
+
+#[tokio::test]
+async fn test_event_hook_outputs() {
+    let event = {
+        let mut n = make_node("event", "event_hook", serde_json::json!({ "hookId": "damage", "phase": "leave" }));
+        n.outputs = vec![
+            make_flow_port("exec", "exec", PortDirection::Output),
+            make_value_port("retval", "retval", PortDirection::Output),
+        ];
+        n
+    };
+
+    let log = {
+        let mut n = make_node("log", "log", serde_json::json!({}));
+        n.inputs = vec![
+            make_flow_port("exec", "exec", PortDirection::Input),
+            make_value_port("message", "message", PortDirection::Input),
+        ];
+        n.outputs = vec![make_flow_port("out", "exec", PortDirection::Output)];
+        n
+    };
+
+    let script = make_script(
+        vec![event, log],
+        vec![
+            make_connection("c1", "event", "exec", "log", "exec"),
+            make_connection("c2", "event", "retval", "log", "message"),
+        ],
+    );
+
+    let payload = Value::from(serde_json::json!({
+        "type": "hook",
+        "hookId": "damage",
+        "phase": "leave",
+        "retval": "0x64",
+    }));
+    let result = execute_event(script, "event", payload).await;
+    assert!(result.success);
+    assert_eq!(result.logs, vec!["0x64"]);
+}
+
 // ============================================
 // Chained Value Nodes Test
 // ============================================
@@ -789,3 +833,69 @@ async fn test_chained_value_nodes() {
     assert!(result.success);
     assert_eq!(result.logs, vec!["Result: 50"]); // 10 * 5 = 50
 }
+
+// ============================================
+// Native Callback Test
+// ============================================
+
+#[tokio::test]
+async fn test_native_callback_returns_host_value() {
+    let callback = {
+        let mut n = make_node("cb", "native_callback", serde_json::json!({ "returnType": "int" }));
+        n.outputs = vec![
+            make_flow_port("exec", "exec", PortDirection::Output),
+            make_flow_port("onCall", "onCall", PortDirection::Output),
+            make_value_port("args", "args", PortDirection::Output),
+        ];
+        n
+    };
+
+    let first_arg = {
+        let mut n = make_node("get", "array_get", serde_json::json!({}));
+        n.inputs = vec![
+            make_value_port("array", "array", PortDirection::Input),
+            make_value_port("index", "index", PortDirection::Input),
+        ];
+        n.outputs = vec![make_value_port("element", "element", PortDirection::Output)];
+        n
+    };
+
+    let zero = {
+        let mut n = make_node("zero", "const_number", serde_json::json!({ "value": 0 }));
+        n.outputs = vec![make_value_port("value", "value", PortDirection::Output)];
+        n
+    };
+
+    let ret = {
+        let mut n = make_node("ret", "function_return", serde_json::json!({}));
+        n.inputs = vec![
+            make_flow_port("exec", "exec", PortDirection::Input),
+            make_value_port("value", "value", PortDirection::Input),
+        ];
+        n
+    };
+
+    let script = make_script(
+        vec![callback, first_arg, zero, ret],
+        vec![
+            make_connection("c1", "cb", "onCall", "ret", "exec"),
+            make_connection("c2", "cb", "args", "get", "array"),
+            make_connection("c3", "zero", "value", "get", "index"),
+            make_connection("c4", "get", "element", "ret", "value"),
+        ],
+    );
+
+    let ui_state = Arc::new(RwLock::new(HashMap::new()));
+    let executor = ScriptExecutor::new(ui_state);
+    let args = Value::Array(vec![Value::String("0x2a".to_string()), Value::String("0x1".to_string())]);
+    let result = executor.execute_native_callback(script.clone(), "cb", args).await.unwrap();
+    assert_eq!(result, Value::String("0x2a".to_string()));
+
+    // Without a function_return, the callback yields Null (target falls back to the original)
+    let no_return = make_script(script.nodes[..1].to_vec(), vec![]);
+    let result = executor
+        .execute_native_callback(no_return, "cb", Value::Array(vec![]))
+        .await
+        .unwrap();
+    assert_eq!(result, Value::Null);
+}
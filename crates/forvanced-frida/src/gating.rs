@@ -0,0 +1,232 @@
+//! Waiting for processes to start
+//!
+//! A gate watches a device for new processes: the ones matching its pattern
+//! are attached, everything else is reported and left alone. Backends notice
+//! new processes by polling, so a process is attached shortly after it
+//! starts rather than before it runs. Child gates only take processes whose
+//! parent the backend reports.
+
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+
+use crate::error::{FridaError, Result};
+use crate::process::{AttachTarget, ProcessInfo};
+use crate::watcher::ProcessMatcher;
+
+/// Process caught or released by a gate
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GateEvent {
+    /// Matched a gate and was attached while already running
+    Attached {
+        session_id: String,
+        process: ProcessInfo,
+        parent_pid: Option<u32>,
+    },
+    /// Started under a gate without matching it and was left alone
+    Released {
+        process: ProcessInfo,
+        parent_pid: Option<u32>,
+    },
+}
+
+/// Callback for gate events
+pub type GateCallback = Arc<dyn Fn(GateEvent) + Send + Sync>;
+
+/// Sessions for processes caught by one gate, in the order they started
+///
+/// The gate stays armed until this is dropped, so a launcher that restarts
+/// the game hands out a new session each time.
+pub struct SpawnGate {
+    rx: mpsc::UnboundedReceiver<String>,
+}
+
+impl SpawnGate {
+    /// Wait for the next matching process; its session id is returned
+    pub async fn next(&mut self) -> Result<String> {
+        self.rx
+            .recv()
+            .await
+            .ok_or_else(|| FridaError::AttachFailed("Gate closed".to_string()))
+    }
+}
+
+/// What a gate decided about a starting process
+pub(crate) enum Claim {
+    /// Attach it and hand the session to this gate
+    Matched(mpsc::UnboundedSender<String>),
+    /// A gate held it but it didn't match
+    Released,
+    /// No gate covers it
+    Ungated,
+}
+
+struct Gate {
+    device_id: String,
+    target: AttachTarget,
+    matcher: ProcessMatcher,
+    tx: mpsc::UnboundedSender<String>,
+}
+
+impl Gate {
+    /// `parent_pid` is `None` when the backend can't tell who started the
+    /// process; child gates never cover those.
+    fn covers(&self, device_id: &str, parent_pid: Option<u32>) -> bool {
+        self.device_id == device_id
+            && match &self.target {
+                AttachTarget::Child { parent, .. } => parent_pid == Some(*parent),
+                _ => true,
+            }
+    }
+}
+
+/// Armed gates and gate listeners, shared by both manager backends
+#[derive(Default)]
+pub(crate) struct GateRegistry {
+    gates: RwLock<Vec<Gate>>,
+    listeners: RwLock<Vec<GateCallback>>,
+}
+
+impl GateRegistry {
+    pub async fn register(&self, device_id: &str, target: AttachTarget) -> Result<SpawnGate> {
+        let pattern = match &target {
+            AttachTarget::Child { pattern, .. } | AttachTarget::Spawn { pattern } => {
+                pattern.clone()
+            }
+            other => {
+                return Err(FridaError::AttachFailed(format!(
+                    "{} is not a gated target",
+                    other
+                )))
+            }
+        };
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.gates.write().await.push(Gate {
+            device_id: device_id.to_string(),
+            target,
+            matcher: ProcessMatcher::new(None, vec![pattern]),
+            tx,
+        });
+        Ok(SpawnGate { rx })
+    }
+
+    /// Decide what happens to a process that just started on `device_id`
+    pub async fn claim(
+        &self,
+        device_id: &str,
+        process: &ProcessInfo,
+        parent_pid: Option<u32>,
+    ) -> Claim {
+        let mut gates = self.gates.write().await;
+        gates.retain(|gate| !gate.tx.is_closed());
+
+        let mut covered = false;
+        for gate in gates.iter().filter(|g| g.covers(device_id, parent_pid)) {
+            if gate.matcher.matches(process) {
+                return Claim::Matched(gate.tx.clone());
+            }
+            covered = true;
+        }
+        if covered {
+            Claim::Released
+        } else {
+            Claim::Ungated
+        }
+    }
+
+    /// Whether any live gate still watches `device_id`; the polling
+    /// backend stops looking at devices nobody gates anymore
+    #[cfg(any(feature = "real", test))]
+    pub async fn watches(&self, device_id: &str) -> bool {
+        self.gates
+            .read()
+            .await
+            .iter()
+            .any(|gate| gate.device_id == device_id && !gate.tx.is_closed())
+    }
+
+    pub async fn on_event(&self, callback: GateCallback) {
+        self.listeners.write().await.push(callback);
+    }
+
+    pub async fn notify(&self, event: GateEvent) {
+        for listener in self.listeners.read().await.iter() {
+            listener(event.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child(parent: u32) -> AttachTarget {
+        AttachTarget::Child {
+            parent,
+            pattern: "game*".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_child_gate_only_covers_its_parent() {
+        let registry = GateRegistry::default();
+        let _gate = registry.register("local", child(10)).await.unwrap();
+        let game = ProcessInfo::new(11, "Game.exe");
+        let helper = ProcessInfo::new(12, "CrashReporter");
+
+        assert!(matches!(
+            registry.claim("local", &game, Some(10)).await,
+            Claim::Matched(_)
+        ));
+        assert!(matches!(
+            registry.claim("local", &helper, Some(10)).await,
+            Claim::Released
+        ));
+        assert!(matches!(
+            registry.claim("local", &game, Some(99)).await,
+            Claim::Ungated
+        ));
+        assert!(matches!(
+            registry.claim("usb", &game, Some(10)).await,
+            Claim::Ungated
+        ));
+        // A process with an unknown parent is nobody's child
+        assert!(matches!(
+            registry.claim("local", &game, None).await,
+            Claim::Ungated
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_gate_is_disarmed() {
+        let registry = GateRegistry::default();
+        let gate = registry
+            .register(
+                "local",
+                AttachTarget::Spawn {
+                    pattern: "*".into(),
+                },
+            )
+            .await
+            .unwrap();
+        assert!(registry.watches("local").await);
+
+        drop(gate);
+        assert!(!registry.watches("local").await);
+        let process = ProcessInfo::new(1, "anything");
+        assert!(matches!(
+            registry.claim("local", &process, None).await,
+            Claim::Ungated
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_plain_targets_cannot_be_gated() {
+        let registry = GateRegistry::default();
+        assert!(registry
+            .register("local", AttachTarget::Pid(1))
+            .await
+            .is_err());
+    }
+}
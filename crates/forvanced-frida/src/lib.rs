@@ -1,6 +1,10 @@
 pub mod error;
+pub mod gating;
 pub mod process;
+pub mod protocol;
+mod reaper;
 pub mod session;
+pub mod watcher;
 
 // Mock and real implementations are mutually exclusive
 // If both are enabled, prefer real (allows: --features real with default mock)
@@ -11,8 +15,15 @@ pub mod mock;
 pub mod manager;
 
 pub use error::FridaError;
+pub use gating::{GateCallback, GateEvent, SpawnGate};
 pub use process::{ApplicationInfo, AttachTarget, DeviceInfo, FridaDeviceType, ProcessInfo, SpawnOptions};
-pub use session::{FridaSession, MessageCallback, RpcResult, ScriptHandle, ScriptMessage, SessionState};
+pub use session::{
+    DetachCallback, DetachReason, FridaSession, MessageCallback, RpcResult, ScriptHandle, ScriptMessage,
+    SessionState,
+};
+pub use watcher::{
+    glob_match, ProcessMatcher, ProcessWatcher, WatchCallback, WatchConfig, WatchEvent,
+};
 
 #[cfg(all(feature = "mock", not(feature = "real")))]
 pub use mock::FridaManager;
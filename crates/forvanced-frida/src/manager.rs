@@ -7,23 +7,41 @@
 //! Session and Script types don't implement Send. We use channels to communicate
 //! between the async world and the Frida thread.
 //!
-//! Note: frida-rust has complex lifetime requirements. We use Box::leak and unsafe
-//! transmute to work around these limitations. The leaked memory is acceptable
-//! because sessions are long-lived and cleaned up on detach.
-
-use std::collections::HashMap;
+//! The worker keeps every attached session and loaded script alive until it
+//! is detached or unloaded, so RPC calls and posted messages reach the script
+//! instance that holds the hooks. frida-rust ties a session to its device and
+//! a script to its session by lifetime; sessions are leaked with Box::leak
+//! (after an unsafe lifetime transmute) so scripts can borrow them for as long
+//! as the worker runs. The leaked memory is acceptable because sessions are
+//! long-lived and ended on the Frida side on detach.
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::CString;
 use std::mem::ManuallyDrop;
+use std::sync::mpsc::RecvTimeoutError;
 use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, oneshot, RwLock};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use frida::{DeviceManager, DeviceType, Frida, Message, ScriptHandler, ScriptOption};
+use frida::{
+    DeviceManager, DeviceType, Frida, Message, Script, ScriptHandler, ScriptOption, Session, SpawnStdio,
+};
 
 use crate::error::{FridaError, Result};
-use crate::process::{ApplicationInfo, AttachTarget, DeviceInfo, FridaDeviceType, ProcessInfo, SpawnOptions};
-use crate::session::{FridaSession, MessageCallback, ScriptMessage};
+use crate::gating::{Claim, GateCallback, GateEvent, GateRegistry, SpawnGate};
+use crate::process::{ApplicationInfo, AttachTarget, DeviceInfo, FridaDeviceType, ProcessInfo, SpawnOptions, StdioMode};
+use crate::reaper::{finish_session, Reaper, SessionMap};
+use crate::protocol;
+use crate::session::{DetachCallback, DetachReason, FridaSession, MessageCallback, ScriptMessage, SessionState};
+
+/// How often the worker checks that attached processes and devices are still there
+const LIVENESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often devices with an armed gate are checked for new processes
+const SPAWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Commands sent to the Frida worker thread
 enum FridaCommand {
@@ -50,8 +68,15 @@ enum FridaCommand {
     SpawnAndAttach {
         device_id: String,
         identifier: String,
+        options: SpawnOptions,
+        /// Leave the process suspended so scripts can load before it runs
+        suspended: bool,
         reply: oneshot::Sender<Result<(String, u32)>>, // (session_id, pid)
     },
+    Resume {
+        session_id: String,
+        reply: oneshot::Sender<Result<()>>,
+    },
     Detach {
         session_id: String,
         reply: oneshot::Sender<Result<()>>,
@@ -66,6 +91,12 @@ enum FridaCommand {
         script_id: String,
         reply: oneshot::Sender<Result<()>>,
     },
+    ReloadScript {
+        session_id: String,
+        script_id: String,
+        script_source: String,
+        reply: oneshot::Sender<Result<bool>>, // state migrated
+    },
     CallRpc {
         session_id: String,
         script_id: String,
@@ -73,16 +104,56 @@ enum FridaCommand {
         args: Vec<serde_json::Value>,
         reply: oneshot::Sender<Result<serde_json::Value>>,
     },
+    /// Deliver a message to a script's `recv` handlers
+    PostMessage {
+        session_id: String,
+        script_id: String,
+        message: serde_json::Value,
+        reply: oneshot::Sender<Result<()>>,
+    },
+    /// Start reporting processes that appear on a device
+    WatchSpawns {
+        device_id: String,
+        reply: oneshot::Sender<Result<()>>,
+    },
+    UnwatchSpawns {
+        device_id: String,
+    },
     Shutdown,
 }
 
-/// Session info stored in worker thread (no raw pointers - safer approach)
-/// We re-attach when needed since Frida supports multiple attach calls to same PID
+/// A loaded script, boxed so it stays put while the worker holds it
+type LiveScript = ManuallyDrop<Box<Script<'static>>>;
+
+/// Session state owned by the worker thread
 struct SessionInfo {
     device_id: String,
+    /// Remote devices vanish when the connection drops rather than the device
+    remote: bool,
     pid: u32,
-    /// Script sources keyed by script_id (we reload scripts when needed)
-    script_sources: HashMap<String, String>,
+    /// The attached session, kept until `detach`
+    session: &'static Session<'static>,
+    /// Loaded scripts keyed by script_id; RPCs go to these instances
+    scripts: HashMap<String, LiveScript>,
+}
+
+/// Keep an attached session for as long as the worker runs
+///
+/// Scripts borrow the session they are created on, so it is leaked rather
+/// than stored by value.
+fn keep_session(session: Session<'_>) -> &'static Session<'static> {
+    // SAFETY: sessions borrow from device lists returned by
+    // `enumerate_all_devices`, which are wrapped in ManuallyDrop and never freed
+    Box::leak(Box::new(unsafe { std::mem::transmute::<Session<'_>, Session<'static>>(session) }))
+}
+
+/// Call an RPC export of a loaded script
+fn call_export(script: &mut LiveScript, method: &str, args: Vec<serde_json::Value>) -> Result<serde_json::Value> {
+    let result = script
+        .exports
+        .call(method, Some(serde_json::Value::Array(args)))
+        .map_err(|e| FridaError::RpcCallFailed(e.to_string()))?;
+    Ok(result.unwrap_or(serde_json::Value::Null))
 }
 
 /// Message handler that implements ScriptHandler trait
@@ -143,10 +214,18 @@ struct FridaWorker {
     device_manager: ManuallyDrop<DeviceManager<'static>>,
     sessions: HashMap<String, SessionInfo>,
     message_tx: mpsc::UnboundedSender<(String, String, ScriptMessage)>,
+    detach_tx: mpsc::UnboundedSender<(String, DetachReason)>,
+    /// Pids already seen on each device watched for new processes
+    spawn_watch: HashMap<String, HashSet<u32>>,
+    spawn_tx: mpsc::UnboundedSender<(String, ProcessInfo)>,
 }
 
 impl FridaWorker {
-    fn new(message_tx: mpsc::UnboundedSender<(String, String, ScriptMessage)>) -> Self {
+    fn new(
+        message_tx: mpsc::UnboundedSender<(String, String, ScriptMessage)>,
+        detach_tx: mpsc::UnboundedSender<(String, DetachReason)>,
+        spawn_tx: mpsc::UnboundedSender<(String, ProcessInfo)>,
+    ) -> Self {
         // Initialize Frida on this thread
         // Box::leak is used to give Frida a 'static lifetime - this is intentional
         // as Frida should live for the entire duration of the worker thread
@@ -157,13 +236,34 @@ impl FridaWorker {
             device_manager,
             sessions: HashMap::new(),
             message_tx,
+            detach_tx,
+            spawn_watch: HashMap::new(),
+            spawn_tx,
         }
     }
 
     fn run(&mut self, cmd_rx: std::sync::mpsc::Receiver<FridaCommand>) {
         info!("Frida worker thread started");
 
-        while let Ok(cmd) = cmd_rx.recv() {
+        let mut last_liveness_check = Instant::now();
+        loop {
+            let timeout = if self.spawn_watch.is_empty() {
+                LIVENESS_INTERVAL
+            } else {
+                SPAWN_POLL_INTERVAL
+            };
+            let cmd = match cmd_rx.recv_timeout(timeout) {
+                Ok(cmd) => cmd,
+                Err(RecvTimeoutError::Timeout) => {
+                    self.check_spawns();
+                    if last_liveness_check.elapsed() >= LIVENESS_INTERVAL {
+                        self.check_sessions();
+                        last_liveness_check = Instant::now();
+                    }
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
             match cmd {
                 FridaCommand::EnumerateDevices { reply } => {
                     let result = self.enumerate_devices();
@@ -185,8 +285,12 @@ impl FridaWorker {
                     let result = self.attach(&device_id, target);
                     let _ = reply.send(result);
                 }
-                FridaCommand::SpawnAndAttach { device_id, identifier, reply } => {
-                    let result = self.spawn_and_attach(&device_id, &identifier);
+                FridaCommand::SpawnAndAttach { device_id, identifier, options, suspended, reply } => {
+                    let result = self.spawn_and_attach(&device_id, &identifier, &options, suspended);
+                    let _ = reply.send(result);
+                }
+                FridaCommand::Resume { session_id, reply } => {
+                    let result = self.resume(&session_id);
                     let _ = reply.send(result);
                 }
                 FridaCommand::Detach { session_id, reply } => {
@@ -201,10 +305,25 @@ impl FridaWorker {
                     let result = self.unload_script(&session_id, &script_id);
                     let _ = reply.send(result);
                 }
+                FridaCommand::ReloadScript { session_id, script_id, script_source, reply } => {
+                    let result = self.reload_script(&session_id, &script_id, &script_source);
+                    let _ = reply.send(result);
+                }
                 FridaCommand::CallRpc { session_id, script_id, method, args, reply } => {
                     let result = self.call_rpc(&session_id, &script_id, &method, args);
                     let _ = reply.send(result);
                 }
+                FridaCommand::PostMessage { session_id, script_id, message, reply } => {
+                    let result = self.post_message(&session_id, &script_id, &message);
+                    let _ = reply.send(result);
+                }
+                FridaCommand::WatchSpawns { device_id, reply } => {
+                    let result = self.watch_spawns(&device_id);
+                    let _ = reply.send(result);
+                }
+                FridaCommand::UnwatchSpawns { device_id } => {
+                    self.spawn_watch.remove(&device_id);
+                }
                 FridaCommand::Shutdown => {
                     info!("Frida worker shutting down");
                     break;
@@ -328,22 +447,27 @@ impl FridaWorker {
                         identifier
                     )))?
             }
+            AttachTarget::Child { .. } | AttachTarget::Spawn { .. } => {
+                return Err(FridaError::AttachFailed(format!("{} is only reachable through a gate", target)));
+            }
         };
 
-        let _session = ManuallyDrop::new(
+        let session = keep_session(
             devices[device_idx].attach(pid)
                 .map_err(|e| FridaError::AttachFailed(e.to_string()))?
         );
 
+        let remote = matches!(devices[device_idx].get_type(), DeviceType::Remote);
         let session_id = Uuid::new_v4().to_string();
 
-        // Store session info (no raw pointers - we re-attach when needed)
         self.sessions.insert(
             session_id.clone(),
             SessionInfo {
                 device_id: device_id.to_string(),
+                remote,
                 pid,
-                script_sources: HashMap::new(),
+                session,
+                scripts: HashMap::new(),
             },
         );
 
@@ -351,37 +475,47 @@ impl FridaWorker {
         Ok((session_id, pid))
     }
 
-    fn spawn_and_attach(&mut self, device_id: &str, identifier: &str) -> Result<(String, u32)> {
-        info!("Spawning and attaching to {} on device {}", identifier, device_id);
+    fn spawn_and_attach(
+        &mut self,
+        device_id: &str,
+        identifier: &str,
+        options: &SpawnOptions,
+        suspended: bool,
+    ) -> Result<(String, u32)> {
+        info!("Spawning and attaching to {} on device {} (suspended: {})", identifier, device_id, suspended);
+
+        let spawn_options = to_frida_spawn_options(identifier, options)?;
 
         let mut devices = ManuallyDrop::new(self.device_manager.enumerate_all_devices());
 
         let device_idx = devices.iter().position(|d| d.get_id() == device_id)
             .ok_or_else(|| FridaError::DeviceNotFound(format!("Device '{}' not found", device_id)))?;
 
-        // Spawn the application
-        let pid = devices[device_idx].spawn(identifier, &frida::SpawnOptions::new())
+        // Spawn the application; Frida always starts it suspended
+        let pid = devices[device_idx].spawn(identifier, &spawn_options)
             .map_err(|e| FridaError::SpawnFailed(e.to_string()))?;
 
-        // Attach to verify it works
-        let _session = ManuallyDrop::new(
+        let session = keep_session(
             devices[device_idx].attach(pid)
                 .map_err(|e| FridaError::AttachFailed(e.to_string()))?
         );
 
-        // Resume the process
-        devices[device_idx].resume(pid)
-            .map_err(|e| FridaError::ResumeFailed(e.to_string()))?;
+        if !suspended {
+            devices[device_idx].resume(pid)
+                .map_err(|e| FridaError::ResumeFailed(e.to_string()))?;
+        }
 
+        let remote = matches!(devices[device_idx].get_type(), DeviceType::Remote);
         let session_id = Uuid::new_v4().to_string();
 
-        // Store session info
         self.sessions.insert(
             session_id.clone(),
             SessionInfo {
                 device_id: device_id.to_string(),
+                remote,
                 pid,
-                script_sources: HashMap::new(),
+                session,
+                scripts: HashMap::new(),
             },
         );
 
@@ -389,64 +523,209 @@ impl FridaWorker {
         Ok((session_id, pid))
     }
 
+    fn resume(&mut self, session_id: &str) -> Result<()> {
+        let session_info = self.sessions.get(session_id)
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
+        let pid = session_info.pid;
+
+        let mut devices = ManuallyDrop::new(self.device_manager.enumerate_all_devices());
+        let device_idx = devices.iter().position(|d| d.get_id() == session_info.device_id)
+            .ok_or_else(|| FridaError::DeviceNotFound(format!("Device '{}' not found", session_info.device_id)))?;
+
+        devices[device_idx].resume(pid)
+            .map_err(|e| FridaError::ResumeFailed(e.to_string()))?;
+
+        info!("Resumed process {} (session {})", pid, session_id);
+        Ok(())
+    }
+
+    /// Drop sessions Frida reports detached and report why.
+    ///
+    /// frida-rust does not surface the session `detached` signal, so each
+    /// session's `is_detached` flag is checked between commands. The reason
+    /// isn't exposed either: a session whose device is still there lost its
+    /// process.
+    fn check_sessions(&mut self) {
+        let detached: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|(_, info)| info.session.is_detached())
+            .map(|(session_id, _)| session_id.clone())
+            .collect();
+        if detached.is_empty() {
+            return;
+        }
+
+        let devices = ManuallyDrop::new(self.device_manager.enumerate_all_devices());
+        for session_id in detached {
+            let Some(info) = self.sessions.remove(&session_id) else {
+                continue;
+            };
+            let reason = if devices.iter().any(|d| d.get_id() == info.device_id) {
+                DetachReason::ProcessTerminated
+            } else if info.remote {
+                DetachReason::ConnectionLost
+            } else {
+                DetachReason::DeviceLost
+            };
+            warn!("Session {} detached: {}", session_id, reason);
+            let _ = self.detach_tx.send((session_id, reason));
+        }
+    }
+
+    /// Remember which processes already run on `device_id`; anything newer
+    /// is reported by `check_spawns`.
+    fn watch_spawns(&mut self, device_id: &str) -> Result<()> {
+        if self.spawn_watch.contains_key(device_id) {
+            return Ok(());
+        }
+        let pids = self.enumerate_processes(device_id)?.into_iter().map(|p| p.pid).collect();
+        self.spawn_watch.insert(device_id.to_string(), pids);
+        Ok(())
+    }
+
+    /// Report processes that appeared since the last poll.
+    ///
+    /// frida-rust exposes neither spawn gating nor child gating, so new
+    /// processes are noticed by polling: they are already running when we
+    /// attach and their parent is unknown.
+    fn check_spawns(&mut self) {
+        let device_ids: Vec<String> = self.spawn_watch.keys().cloned().collect();
+        for device_id in device_ids {
+            let Ok(processes) = self.enumerate_processes(&device_id) else {
+                continue;
+            };
+            let Some(known) = self.spawn_watch.get_mut(&device_id) else {
+                continue;
+            };
+            let mut live = HashSet::with_capacity(processes.len());
+            for process in processes {
+                live.insert(process.pid);
+                if !known.contains(&process.pid) {
+                    let _ = self.spawn_tx.send((device_id.clone(), process));
+                }
+            }
+            *known = live;
+        }
+    }
+
     fn detach(&mut self, session_id: &str) -> Result<()> {
         info!("Detaching session: {}", session_id);
 
-        let _session_info = self.sessions.remove(session_id)
+        let session_info = self.sessions.remove(session_id)
             .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
 
-        // Session info is just metadata, actual Frida session is not held
+        // Frida unloads the session's scripts with it; a session whose process
+        // already exited has nothing left to end
+        if let Err(e) = session_info.session.detach() {
+            warn!("Session {} did not detach cleanly: {}", session_id, e);
+        }
+
         info!("Session {} detached", session_id);
         Ok(())
     }
 
+    fn script_mut(&mut self, session_id: &str, script_id: &str) -> Result<&mut LiveScript> {
+        self.sessions.get_mut(session_id)
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?
+            .scripts
+            .get_mut(script_id)
+            .ok_or_else(|| FridaError::ScriptNotFound(script_id.to_string()))
+    }
+
     fn inject_script(&mut self, session_id: &str, script_source: &str) -> Result<String> {
         info!("Injecting script into session {}, source length: {}", session_id, script_source.len());
 
-        let session_info = self.sessions.get_mut(session_id)
-            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
-
         let script_id = Uuid::new_v4().to_string();
-        let device_id = session_info.device_id.clone();
-        let pid = session_info.pid;
+        let script = self.load_script(session_id, &script_id, script_source)?;
 
-        // Re-attach to get a fresh session
-        let devices = ManuallyDrop::new(self.device_manager.enumerate_all_devices());
-        let device_idx = devices.iter().position(|d| d.get_id() == device_id)
-            .ok_or_else(|| FridaError::DeviceNotFound(format!("Device '{}' not found", device_id)))?;
+        if let Some(session_info) = self.sessions.get_mut(session_id) {
+            session_info.scripts.insert(script_id.clone(), script);
+        }
 
-        let session = ManuallyDrop::new(
-            devices[device_idx].attach(pid)
-                .map_err(|e| FridaError::AttachFailed(format!("Failed to re-attach: {}", e)))?
-        );
+        info!("Script {} injected into session {}", script_id, session_id);
+        Ok(script_id)
+    }
 
-        let mut script_option = ScriptOption::new();
+    /// Create and load a script whose messages are reported under `script_id`
+    fn load_script(&self, session_id: &str, script_id: &str, script_source: &str) -> Result<LiveScript> {
+        let session_info = self.sessions.get(session_id)
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
 
-        // Create the script
-        let mut script = ManuallyDrop::new(
-            session.create_script(script_source, &mut script_option)
-                .map_err(|e| FridaError::ScriptCreationFailed(e.to_string()))?
-        );
+        let mut script_option = ScriptOption::new();
+        let script: Script<'static> = session_info.session
+            .create_script(script_source, &mut script_option)
+            .map_err(|e| FridaError::ScriptCreationFailed(e.to_string()))?;
+        let mut script = ManuallyDrop::new(Box::new(script));
 
         // Set up message handler
         let handler = MessageHandler {
             session_id: session_id.to_string(),
-            script_id: script_id.clone(),
+            script_id: script_id.to_string(),
             message_tx: self.message_tx.clone(),
         };
         script.handle_message(handler)
             .map_err(|e| FridaError::ScriptCreationFailed(format!("Failed to set message handler: {}", e)))?;
 
-        // Load the script
         script.load().map_err(|e| FridaError::ScriptLoadFailed(e.to_string()))?;
+        Ok(script)
+    }
 
-        // Store script source for potential reload
-        if let Some(session_info) = self.sessions.get_mut(session_id) {
-            session_info.script_sources.insert(script_id.clone(), script_source.to_string());
+    /// Replace a script in place and hand its state to the new copy.
+    ///
+    /// The new source is loaded first; if it fails to, the old script is left
+    /// untouched. Then the old script exports its state (releasing its hooks),
+    /// the new one imports it, and the old one is unloaded. If the import
+    /// fails, the new copy is dropped and the state goes back into the old
+    /// script, which keeps running. Returns whether the state was handed over.
+    fn reload_script(&mut self, session_id: &str, script_id: &str, script_source: &str) -> Result<bool> {
+        info!("Reloading script {} in session {}, source length: {}", script_id, session_id, script_source.len());
+
+        // The new copy has to load before the old one gives anything up
+        self.script_mut(session_id, script_id)?;
+        let mut script = self.load_script(session_id, script_id, script_source)?;
+
+        // Scripts without the export hook have nothing to hand over
+        let old = self.script_mut(session_id, script_id)?;
+        let state = match call_export(old, protocol::EXPORT_STATE, Vec::new()) {
+            Ok(state) => Some(state),
+            Err(e) => {
+                debug!("Script {} exported no state: {}", script_id, e);
+                None
+            }
+        };
+
+        let migrated = match state {
+            Some(state) => match call_export(&mut script, protocol::IMPORT_STATE, vec![state.clone()]) {
+                Ok(_) => true,
+                Err(e) => {
+                    // The old copy takes its state back and keeps running
+                    if let Err(e) = script.unload() {
+                        warn!("Rejected copy of script {} failed to unload: {}", script_id, e);
+                    }
+                    let old = self.script_mut(session_id, script_id)?;
+                    if let Err(e) = call_export(old, protocol::IMPORT_STATE, vec![state]) {
+                        warn!("Script {} lost its state after a failed reload: {}", script_id, e);
+                    }
+                    return Err(FridaError::ScriptLoadFailed(format!(
+                        "New copy of script {} did not take over its state: {}",
+                        script_id, e
+                    )));
+                }
+            },
+            None => false,
+        };
+
+        let session_info = self.sessions.get_mut(session_id)
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
+        if let Some(old) = session_info.scripts.insert(script_id.to_string(), script) {
+            if let Err(e) = old.unload() {
+                warn!("Old copy of script {} failed to unload: {}", script_id, e);
+            }
         }
 
-        info!("Script {} injected into session {}", script_id, session_id);
-        Ok(script_id)
+        info!("Script {} reloaded in session {} (state migrated: {})", script_id, session_id, migrated);
+        Ok(migrated)
     }
 
     fn unload_script(&mut self, session_id: &str, script_id: &str) -> Result<()> {
@@ -455,12 +734,10 @@ impl FridaWorker {
         let session_info = self.sessions.get_mut(session_id)
             .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
 
-        session_info.script_sources.remove(script_id)
+        let script = session_info.scripts.remove(script_id)
             .ok_or_else(|| FridaError::ScriptNotFound(script_id.to_string()))?;
-
-        // Note: The actual script in the target process may still be running
-        // To truly unload, we'd need to keep the Script object alive
-        // For now, we just remove from our tracking
+        script.unload()
+            .map_err(|e| FridaError::Frida(format!("Failed to unload script {}: {}", script_id, e)))?;
 
         info!("Script {} unloaded from session {}", script_id, session_id);
         Ok(())
@@ -469,42 +746,113 @@ impl FridaWorker {
     fn call_rpc(&mut self, session_id: &str, script_id: &str, method: &str, args: Vec<serde_json::Value>) -> Result<serde_json::Value> {
         info!("Calling RPC method '{}' on script {} in session {}", method, script_id, session_id);
 
-        let session_info = self.sessions.get(session_id)
-            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
+        let result = call_export(self.script_mut(session_id, script_id)?, method, args)?;
 
-        let script_source = session_info.script_sources.get(script_id)
-            .ok_or_else(|| FridaError::ScriptNotFound(script_id.to_string()))?
-            .clone();
+        info!("RPC call '{}' completed", method);
+        Ok(result)
+    }
 
-        let device_id = session_info.device_id.clone();
-        let pid = session_info.pid;
+    fn post_message(&mut self, session_id: &str, script_id: &str, message: &serde_json::Value) -> Result<()> {
+        debug!("Posting message to script {} in session {}", script_id, session_id);
 
-        // Re-attach and reload script for RPC call
-        let devices = ManuallyDrop::new(self.device_manager.enumerate_all_devices());
-        let device_idx = devices.iter().position(|d| d.get_id() == device_id)
-            .ok_or_else(|| FridaError::DeviceNotFound(format!("Device '{}' not found", device_id)))?;
+        self.script_mut(session_id, script_id)?
+            .post(message.to_string(), None)
+            .map_err(|e| FridaError::ScriptExecutionError(format!("Failed to post message: {}", e)))
+    }
+}
 
-        let session = ManuallyDrop::new(
-            devices[device_idx].attach(pid)
-                .map_err(|e| FridaError::AttachFailed(format!("Failed to re-attach: {}", e)))?
-        );
+/// Translate our spawn options into Frida's, keeping the program as argv[0]
+fn to_frida_spawn_options(program: &str, options: &SpawnOptions) -> Result<frida::SpawnOptions> {
+    let mut spawn_options = frida::SpawnOptions::new();
 
-        let mut script_option = ScriptOption::new();
-        let mut script = ManuallyDrop::new(
-            session.create_script(&script_source, &mut script_option)
-                .map_err(|e| FridaError::ScriptCreationFailed(e.to_string()))?
-        );
+    if !options.argv.is_empty() {
+        spawn_options = spawn_options.argv(options.full_argv(program));
+    }
 
-        script.load().map_err(|e| FridaError::ScriptLoadFailed(e.to_string()))?;
+    // Added on top of the inherited environment rather than replacing it
+    let env = options.env_pairs()?;
+    if !env.is_empty() {
+        spawn_options = spawn_options.env(env);
+    }
 
-        let args_value = serde_json::Value::Array(args);
+    if let Some(cwd) = &options.cwd {
+        let cwd = CString::new(cwd.as_str())
+            .map_err(|_| FridaError::SpawnFailed(format!("Invalid working directory '{}'", cwd)))?;
+        spawn_options = spawn_options.cwd(cwd);
+    }
 
-        let result = script.exports.call(method, Some(args_value))
-            .map_err(|e| FridaError::RpcCallFailed(e.to_string()))?;
+    let stdio = match options.stdio {
+        StdioMode::Inherit => SpawnStdio::Inherit,
+        StdioMode::Pipe => SpawnStdio::Pipe,
+    };
+    Ok(spawn_options.stdio(stdio))
+}
 
-        info!("RPC call '{}' completed", method);
-        Ok(result.unwrap_or(serde_json::Value::Null))
-    }
+/// Hand a newly started process to the gate that wants it
+///
+/// Returns the id of the session opened for it, if a gate matched.
+async fn adopt_spawn(
+    cmd_tx: &std::sync::mpsc::Sender<FridaCommand>,
+    sessions: &SessionMap,
+    gates: &GateRegistry,
+    device_id: &str,
+    process: ProcessInfo,
+    parent_pid: Option<u32>,
+) -> Result<Option<String>> {
+    let tx = match gates.claim(device_id, &process, parent_pid).await {
+        Claim::Matched(tx) => tx,
+        Claim::Released => {
+            gates.notify(GateEvent::Released { process, parent_pid }).await;
+            return Ok(None);
+        }
+        Claim::Ungated => {
+            if !gates.watches(device_id).await {
+                let _ = cmd_tx.send(FridaCommand::UnwatchSpawns {
+                    device_id: device_id.to_string(),
+                });
+            }
+            return Ok(None);
+        }
+    };
+
+    let (reply, reply_rx) = oneshot::channel();
+    cmd_tx
+        .send(FridaCommand::Attach {
+            device_id: device_id.to_string(),
+            target: AttachTarget::Pid(process.pid),
+            reply,
+        })
+        .map_err(|_| FridaError::NotConnected)?;
+    let (session_id, _) = reply_rx.await.map_err(|_| FridaError::NotConnected)??;
+
+    // Already running by the time polling found it, so never Suspended here
+    let frida_session = Arc::new(FridaSession::new(session_id.clone(), process.clone()));
+    frida_session.set_state(SessionState::Active).await;
+    sessions.write().await.insert(session_id.clone(), frida_session);
+
+    info!("Gated {} ({}), session: {}", process.name, process.pid, session_id);
+    gates
+        .notify(GateEvent::Attached {
+            session_id: session_id.clone(),
+            process,
+            parent_pid,
+        })
+        .await;
+    let _ = tx.send(session_id.clone());
+    Ok(Some(session_id))
+}
+
+/// Send a command to the worker and wait for its reply
+async fn send_to_worker<T, F>(cmd_tx: &std::sync::mpsc::Sender<FridaCommand>, make_cmd: F) -> Result<T>
+where
+    F: FnOnce(oneshot::Sender<Result<T>>) -> FridaCommand,
+{
+    let (reply_tx, reply_rx) = oneshot::channel();
+    cmd_tx.send(make_cmd(reply_tx))
+        .map_err(|_| FridaError::NotConnected)?;
+
+    reply_rx.await
+        .map_err(|_| FridaError::NotConnected)?
 }
 
 pub struct FridaManager {
@@ -514,7 +862,13 @@ pub struct FridaManager {
     #[allow(dead_code)]
     worker_handle: Option<thread::JoinHandle<()>>,
     /// Session metadata (for async access without going to worker)
-    sessions: Arc<RwLock<HashMap<String, Arc<FridaSession>>>>,
+    sessions: SessionMap,
+    /// Listeners notified when any session detaches
+    detach_listeners: Arc<RwLock<Vec<DetachCallback>>>,
+    /// Child and spawn gates waiting for processes
+    gates: Arc<GateRegistry>,
+    /// Detaches idle sessions when an idle timeout is set
+    reaper: Reaper,
     /// Message receiver handle (spawned task)
     #[allow(dead_code)]
     message_rx_handle: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
@@ -534,8 +888,15 @@ impl FridaManager {
         // Create message channel for async message dispatching
         let (message_tx, mut message_rx) = mpsc::unbounded_channel::<(String, String, ScriptMessage)>();
 
-        let sessions: Arc<RwLock<HashMap<String, Arc<FridaSession>>>> = Arc::new(RwLock::new(HashMap::new()));
+        // Detaches the worker notices on its own (process exit, lost device)
+        let (detach_tx, mut detach_rx) = mpsc::unbounded_channel::<(String, DetachReason)>();
+
+        // Processes that appeared on devices watched by a gate
+        let (spawn_tx, mut spawn_rx) = mpsc::unbounded_channel::<(String, ProcessInfo)>();
+
+        let sessions: SessionMap = Arc::new(RwLock::new(HashMap::new()));
         let sessions_clone = sessions.clone();
+        let detach_listeners: Arc<RwLock<Vec<DetachCallback>>> = Arc::new(RwLock::new(Vec::new()));
 
         // Spawn message dispatcher task (async)
         let handle = tokio::spawn(async move {
@@ -547,13 +908,36 @@ impl FridaManager {
             }
         });
 
+        let detach_sessions = sessions.clone();
+        let listeners = detach_listeners.clone();
+        tokio::spawn(async move {
+            while let Some((session_id, reason)) = detach_rx.recv().await {
+                finish_session(&detach_sessions, &listeners, &session_id, reason).await;
+            }
+        });
+
+        let gates = Arc::new(GateRegistry::default());
+        let spawn_cmd_tx = cmd_tx.clone();
+        let spawn_sessions = sessions.clone();
+        let spawn_gates = gates.clone();
+        tokio::spawn(async move {
+            while let Some((device_id, process)) = spawn_rx.recv().await {
+                let pid = process.pid;
+                if let Err(e) =
+                    adopt_spawn(&spawn_cmd_tx, &spawn_sessions, &spawn_gates, &device_id, process, None).await
+                {
+                    warn!("Failed to attach to gated process {}: {}", pid, e);
+                }
+            }
+        });
+
         // Spawn Frida worker thread (sync, owns all Frida objects)
         let worker_handle = thread::Builder::new()
             .name("frida-worker".to_string())
             .spawn(move || {
                 // Set up panic hook for this thread
                 let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                    let mut worker = FridaWorker::new(message_tx);
+                    let mut worker = FridaWorker::new(message_tx, detach_tx, spawn_tx);
                     worker.run(cmd_rx);
                 }));
                 
@@ -575,6 +959,9 @@ impl FridaManager {
             cmd_tx,
             worker_handle: Some(worker_handle),
             sessions,
+            detach_listeners,
+            gates,
+            reaper: Reaper::default(),
             message_rx_handle: Arc::new(RwLock::new(Some(handle))),
         })
     }
@@ -584,14 +971,34 @@ impl FridaManager {
     where
         F: FnOnce(oneshot::Sender<Result<T>>) -> FridaCommand,
     {
-        let (reply_tx, reply_rx) = oneshot::channel();
-        let cmd = make_cmd(reply_tx);
-
-        self.cmd_tx.send(cmd)
-            .map_err(|_| FridaError::NotConnected)?;
+        send_to_worker(&self.cmd_tx, make_cmd).await
+    }
 
-        reply_rx.await
-            .map_err(|_| FridaError::NotConnected)?
+    /// Detach sessions without RPC calls or script messages for `timeout`.
+    ///
+    /// `None` stops reaping. Reaped sessions have each script unloaded, then
+    /// the Frida session detached, and end with `DetachReason::IdleTimeout`.
+    /// Failures along the way are ignored: the session is dropped either way.
+    pub async fn set_idle_timeout(&self, timeout: Option<Duration>) {
+        let cmd_tx = self.cmd_tx.clone();
+        self.reaper.configure(
+            self.sessions.clone(),
+            self.detach_listeners.clone(),
+            timeout,
+            move |session_id, script_ids| {
+                let cmd_tx = cmd_tx.clone();
+                async move {
+                    for script_id in script_ids {
+                        let _ = send_to_worker(&cmd_tx, |reply| FridaCommand::UnloadScript {
+                            session_id: session_id.clone(),
+                            script_id,
+                            reply,
+                        }).await;
+                    }
+                    let _ = send_to_worker(&cmd_tx, |reply| FridaCommand::Detach { session_id, reply }).await;
+                }
+            },
+        ).await;
     }
 
     /// Enumerate all available Frida devices
@@ -629,7 +1036,13 @@ impl FridaManager {
     }
 
     /// Attach to a target on a specific device
+    ///
+    /// Gated targets wait for the next matching process to start.
     pub async fn attach_target(&self, device_id: &str, target: AttachTarget) -> Result<String> {
+        if target.is_gated() {
+            return self.gate(device_id, target).await?.next().await;
+        }
+
         let (session_id, pid) = self.send_command(|reply| FridaCommand::Attach {
             device_id: device_id.to_string(),
             target: target.clone(),
@@ -639,7 +1052,7 @@ impl FridaManager {
         // Create FridaSession metadata for async access
         let process_info = ProcessInfo::new(pid, format!("{}:{}", device_id, target));
         let frida_session = Arc::new(FridaSession::new(session_id.clone(), process_info));
-        frida_session.set_state(crate::session::SessionState::Active).await;
+        frida_session.set_state(SessionState::Active).await;
 
         self.sessions
             .write()
@@ -650,17 +1063,31 @@ impl FridaManager {
     }
 
     /// Spawn and attach to an application by identifier
-    pub async fn spawn_and_attach(&self, device_id: &str, identifier: &str, _options: SpawnOptions) -> Result<String> {
+    pub async fn spawn_and_attach(&self, device_id: &str, identifier: &str, options: SpawnOptions) -> Result<String> {
+        self.spawn(device_id, identifier, options, false).await
+    }
+
+    /// Spawn an application suspended and attach to it.
+    ///
+    /// Scripts injected before `resume` run ahead of the program's first instruction.
+    pub async fn spawn_suspended(&self, device_id: &str, identifier: &str, options: SpawnOptions) -> Result<String> {
+        self.spawn(device_id, identifier, options, true).await
+    }
+
+    async fn spawn(&self, device_id: &str, identifier: &str, options: SpawnOptions, suspended: bool) -> Result<String> {
         let (session_id, pid) = self.send_command(|reply| FridaCommand::SpawnAndAttach {
             device_id: device_id.to_string(),
             identifier: identifier.to_string(),
+            options,
+            suspended,
             reply,
         }).await?;
 
         // Create FridaSession metadata for async access
         let process_info = ProcessInfo::new(pid, format!("{}:spawn:{}", device_id, identifier));
         let frida_session = Arc::new(FridaSession::new(session_id.clone(), process_info));
-        frida_session.set_state(crate::session::SessionState::Active).await;
+        let state = if suspended { SessionState::Suspended } else { SessionState::Active };
+        frida_session.set_state(state).await;
 
         self.sessions
             .write()
@@ -670,6 +1097,64 @@ impl FridaManager {
         Ok(session_id)
     }
 
+    /// Arm a spawn gate on a device.
+    ///
+    /// Sessions for matching processes come out of the returned gate until it
+    /// is dropped. Child gates are refused: new processes are found by
+    /// polling, which can't tell who started them.
+    pub async fn gate(&self, device_id: &str, target: AttachTarget) -> Result<SpawnGate> {
+        if let AttachTarget::Child { .. } = target {
+            return Err(FridaError::AttachFailed(format!(
+                "{}: parent processes are unknown on this backend",
+                target
+            )));
+        }
+        let gate = self.gates.register(device_id, target).await?;
+        self.send_command(|reply| FridaCommand::WatchSpawns {
+            device_id: device_id.to_string(),
+            reply,
+        }).await?;
+        Ok(gate)
+    }
+
+    /// Register a callback fired for every process a gate catches or releases.
+    pub async fn on_gated(&self, callback: GateCallback) {
+        self.gates.on_event(callback).await;
+    }
+
+    /// Offer a newly started process to the armed gates (for testing or internal use).
+    pub async fn dispatch_spawn(
+        &self,
+        device_id: &str,
+        process: ProcessInfo,
+        parent_pid: Option<u32>,
+    ) -> Result<Option<String>> {
+        adopt_spawn(&self.cmd_tx, &self.sessions, &self.gates, device_id, process, parent_pid).await
+    }
+
+    /// Resume a process spawned with `spawn_suspended`
+    pub async fn resume(&self, session_id: &str) -> Result<()> {
+        let session = self
+            .get_session(session_id)
+            .await
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
+
+        if session.state().await != SessionState::Suspended {
+            return Err(FridaError::ResumeFailed(format!(
+                "Session {} is not suspended",
+                session_id
+            )));
+        }
+
+        self.send_command(|reply| FridaCommand::Resume {
+            session_id: session_id.to_string(),
+            reply,
+        }).await?;
+
+        session.set_state(SessionState::Active).await;
+        Ok(())
+    }
+
     pub async fn detach(&self, session_id: &str) -> Result<()> {
         // Send detach command to worker
         self.send_command(|reply| FridaCommand::Detach {
@@ -678,9 +1163,7 @@ impl FridaManager {
         }).await?;
 
         // Clean up session metadata
-        if let Some(session) = self.sessions.write().await.remove(session_id) {
-            session.mark_detached().await;
-        }
+        finish_session(&self.sessions, &self.detach_listeners, session_id, DetachReason::ApplicationRequested).await;
 
         Ok(())
     }
@@ -703,6 +1186,7 @@ impl FridaManager {
 
         // Update session metadata
         if let Some(session) = self.sessions.read().await.get(session_id) {
+            session.touch().await;
             session.add_script(script_id.clone(), "user_script".to_string(), script_source.to_string()).await;
             session.mark_script_loaded(&script_id).await;
         }
@@ -710,6 +1194,28 @@ impl FridaManager {
         Ok(script_id)
     }
 
+    /// Replace a loaded script with new source, keeping its id.
+    ///
+    /// The new script is loaded before the old one's state is handed over
+    /// through its `exportState` / `importState` exports, then the old script
+    /// is unloaded; returns whether the state was handed over. The old script
+    /// keeps running when the new one fails to load or to take the state.
+    pub async fn reload_script(&self, session_id: &str, script_id: &str, script_source: &str) -> Result<bool> {
+        let migrated = self.send_command(|reply| FridaCommand::ReloadScript {
+            session_id: session_id.to_string(),
+            script_id: script_id.to_string(),
+            script_source: script_source.to_string(),
+            reply,
+        }).await?;
+
+        if let Some(session) = self.sessions.read().await.get(session_id) {
+            session.touch().await;
+            session.replace_script_source(script_id, script_source.to_string()).await;
+        }
+
+        Ok(migrated)
+    }
+
     /// Unload a script from a session.
     pub async fn unload_script(&self, session_id: &str, script_id: &str) -> Result<()> {
         self.send_command(|reply| FridaCommand::UnloadScript {
@@ -734,6 +1240,10 @@ impl FridaManager {
         method: &str,
         args: Vec<serde_json::Value>,
     ) -> Result<serde_json::Value> {
+        // RPC traffic keeps the session from being reaped as idle
+        if let Some(session) = self.get_session(session_id).await {
+            session.touch().await;
+        }
         self.send_command(|reply| FridaCommand::CallRpc {
             session_id: session_id.to_string(),
             script_id: script_id.to_string(),
@@ -743,6 +1253,21 @@ impl FridaManager {
         }).await
     }
 
+    /// Post a message to a script, delivered to its `recv` handlers.
+    ///
+    /// Unlike `call_rpc` this doesn't wait for the script to handle it.
+    pub async fn post_message(&self, session_id: &str, script_id: &str, message: serde_json::Value) -> Result<()> {
+        if let Some(session) = self.get_session(session_id).await {
+            session.touch().await;
+        }
+        self.send_command(|reply| FridaCommand::PostMessage {
+            session_id: session_id.to_string(),
+            script_id: script_id.to_string(),
+            message,
+            reply,
+        }).await
+    }
+
     /// Register a message callback for a session.
     pub async fn on_session_message(
         &self,
@@ -762,6 +1287,36 @@ impl FridaManager {
         Ok(())
     }
 
+    /// Register a callback fired when a session detaches, for any reason.
+    pub async fn on_session_detached(&self, session_id: &str, callback: DetachCallback) -> Result<()> {
+        let session = self
+            .get_session(session_id)
+            .await
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
+
+        session.on_detached(callback).await;
+        Ok(())
+    }
+
+    /// Register a callback fired whenever any session detaches.
+    pub async fn on_detached(&self, callback: DetachCallback) {
+        self.detach_listeners.write().await.push(callback);
+    }
+
+    /// End a session as if Frida reported it detached (for testing or internal use).
+    pub async fn dispatch_detached(&self, session_id: &str, reason: DetachReason) -> Result<()> {
+        if self.get_session(session_id).await.is_none() {
+            return Err(FridaError::SessionNotFound(session_id.to_string()));
+        }
+        // Forget the worker side too so no RPC reaches the dead session
+        let _ = self.send_command(|reply| FridaCommand::Detach {
+            session_id: session_id.to_string(),
+            reply,
+        }).await;
+        finish_session(&self.sessions, &self.detach_listeners, session_id, reason).await;
+        Ok(())
+    }
+
     /// Dispatch a message to a session's callbacks (for testing or internal use).
     pub async fn dispatch_message(
         &self,
@@ -4,18 +4,31 @@
 //! and allows testing the UI without requiring the Frida native libraries.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use crate::error::{FridaError, Result};
+use crate::gating::{Claim, GateCallback, GateEvent, GateRegistry, SpawnGate};
 use crate::process::{ApplicationInfo, AttachTarget, DeviceInfo, FridaDeviceType, ProcessInfo, SpawnOptions};
-use crate::session::FridaSession;
+use crate::reaper::{self, Reaper, SessionMap};
+use crate::protocol;
+use crate::session::{DetachCallback, DetachReason, FridaSession, SessionState};
 
 /// Mock FridaManager that simulates Frida functionality for development.
 pub struct FridaManager {
-    sessions: Arc<RwLock<HashMap<String, Arc<FridaSession>>>>,
+    sessions: SessionMap,
+    /// Listeners notified when any session detaches
+    detach_listeners: Arc<RwLock<Vec<DetachCallback>>>,
+    /// Spawn gates waiting for processes
+    gates: GateRegistry,
+    /// Detaches idle sessions when an idle timeout is set
+    reaper: Reaper,
+    /// Attaches left to fail (see `fail_next_attaches`)
+    attach_failures: AtomicUsize,
 }
 
 impl FridaManager {
@@ -25,6 +38,10 @@ impl FridaManager {
 
         Ok(Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
+            detach_listeners: Arc::new(RwLock::new(Vec::new())),
+            gates: GateRegistry::default(),
+            reaper: Reaper::default(),
+            attach_failures: AtomicUsize::new(0),
         })
     }
 
@@ -135,8 +152,23 @@ impl FridaManager {
                 let mock_pid = identifier.len() as u32 * 50;
                 (mock_pid, format!("identifier:{}", identifier))
             }
+            AttachTarget::Child { .. } | AttachTarget::Spawn { .. } => {
+                // Resolved once a matching process starts (see dispatch_spawn)
+                return self.gate(device_id, target.clone()).await?.next().await;
+            }
         };
 
+        if self
+            .attach_failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            return Err(FridaError::AttachFailed(format!(
+                "Mock: process {} is not ready",
+                pid
+            )));
+        }
+
         let process_info = ProcessInfo::new(pid, format!("{}:{}", device_id, target_name));
         let session_id = Uuid::new_v4().to_string();
         let frida_session = Arc::new(FridaSession::new(session_id.clone(), process_info));
@@ -150,9 +182,24 @@ impl FridaManager {
         Ok(session_id)
     }
 
+    /// Simulates the next `count` attaches failing, like a target that is
+    /// still starting up.
+    pub fn fail_next_attaches(&self, count: usize) {
+        self.attach_failures.store(count, Ordering::SeqCst);
+    }
+
     /// Simulates spawning and attaching to an application.
-    pub async fn spawn_and_attach(&self, device_id: &str, identifier: &str, _options: SpawnOptions) -> Result<String> {
-        info!("Mock: spawning and attaching to {} on device {}", identifier, device_id);
+    pub async fn spawn_and_attach(&self, device_id: &str, identifier: &str, options: SpawnOptions) -> Result<String> {
+        let session_id = self.spawn_suspended(device_id, identifier, options).await?;
+        self.resume(&session_id).await?;
+        Ok(session_id)
+    }
+
+    /// Simulates spawning an application suspended and attaching to it.
+    ///
+    /// The session stays `Suspended` until `resume` is called.
+    pub async fn spawn_suspended(&self, device_id: &str, identifier: &str, options: SpawnOptions) -> Result<String> {
+        info!("Mock: spawning {} suspended on device {}", identifier, device_id);
 
         let valid_devices = ["local", "usb-iphone", "usb-android"];
         if !valid_devices.contains(&device_id) {
@@ -162,20 +209,124 @@ impl FridaManager {
             )));
         }
 
+        // Reject the same malformed options the real spawn would
+        let env = options.env_pairs()?;
+        debug!(
+            "Mock: argv={:?} env={:?} cwd={:?} stdio={:?}",
+            options.full_argv(identifier),
+            env,
+            options.cwd,
+            options.stdio
+        );
+
         let pid = identifier.len() as u32 * 100 + 5000;
-        let process_info = ProcessInfo::new(pid, format!("{}:spawn:{}", device_id, identifier));
+        let mut process_info = ProcessInfo::new(pid, format!("{}:spawn:{}", device_id, identifier));
+        if let Some(cwd) = &options.cwd {
+            process_info = process_info.with_path(cwd.clone());
+        }
         let session_id = Uuid::new_v4().to_string();
         let frida_session = Arc::new(FridaSession::new(session_id.clone(), process_info));
+        frida_session.set_state(SessionState::Suspended).await;
 
         self.sessions
             .write()
             .await
             .insert(session_id.clone(), frida_session);
 
-        info!("Mock: spawned and attached to {}, session: {}", identifier, session_id);
+        info!("Mock: spawned {} suspended, session: {}", identifier, session_id);
         Ok(session_id)
     }
 
+    /// Simulates resuming a process spawned with `spawn_suspended`.
+    pub async fn resume(&self, session_id: &str) -> Result<()> {
+        let session = self
+            .sessions
+            .read()
+            .await
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
+
+        if session.state().await != SessionState::Suspended {
+            return Err(FridaError::ResumeFailed(format!(
+                "Session {} is not suspended",
+                session_id
+            )));
+        }
+
+        session.set_state(SessionState::Active).await;
+        info!("Mock: resumed process {} (session {})", session.process.pid, session_id);
+        Ok(())
+    }
+
+    /// Arm a spawn gate; matching processes arrive through `dispatch_spawn`.
+    ///
+    /// Child gates are refused, as on the real backend.
+    pub async fn gate(&self, device_id: &str, target: AttachTarget) -> Result<SpawnGate> {
+        if let AttachTarget::Child { .. } = target {
+            return Err(FridaError::AttachFailed(format!(
+                "Mock: {}: parent processes are unknown on this backend",
+                target
+            )));
+        }
+        let valid_devices = ["local", "usb-iphone", "usb-android"];
+        if !valid_devices.contains(&device_id) {
+            return Err(FridaError::DeviceNotFound(format!(
+                "Mock: Device '{}' not found",
+                device_id
+            )));
+        }
+        info!("Mock: gating {} on device {}", target, device_id);
+        self.gates.register(device_id, target).await
+    }
+
+    /// Register a callback fired for every process a gate catches or releases.
+    pub async fn on_gated(&self, callback: GateCallback) {
+        self.gates.on_event(callback).await;
+    }
+
+    /// Simulates a process starting on a device, optionally as a child of `parent_pid`.
+    ///
+    /// A gate that matches it gets a session for the running process, whose
+    /// id is returned.
+    pub async fn dispatch_spawn(
+        &self,
+        device_id: &str,
+        process: ProcessInfo,
+        parent_pid: Option<u32>,
+    ) -> Result<Option<String>> {
+        let tx = match self.gates.claim(device_id, &process, parent_pid).await {
+            Claim::Matched(tx) => tx,
+            Claim::Released => {
+                debug!("Mock: released ungated process {} ({})", process.name, process.pid);
+                self.gates
+                    .notify(GateEvent::Released { process, parent_pid })
+                    .await;
+                return Ok(None);
+            }
+            Claim::Ungated => return Ok(None),
+        };
+
+        let session_id = Uuid::new_v4().to_string();
+        let frida_session = Arc::new(FridaSession::new(session_id.clone(), process.clone()));
+        frida_session.set_state(SessionState::Active).await;
+        self.sessions
+            .write()
+            .await
+            .insert(session_id.clone(), frida_session);
+
+        info!("Mock: gated {} ({}), session: {}", process.name, process.pid, session_id);
+        self.gates
+            .notify(GateEvent::Attached {
+                session_id: session_id.clone(),
+                process,
+                parent_pid,
+            })
+            .await;
+        let _ = tx.send(session_id.clone());
+        Ok(Some(session_id))
+    }
+
     /// Simulates adding a remote device.
     pub async fn add_remote_device(&self, address: &str) -> Result<DeviceInfo> {
         debug!("Mock: adding remote device at {}", address);
@@ -191,17 +342,38 @@ impl FridaManager {
     /// Simulates detaching from a session.
     pub async fn detach(&self, session_id: &str) -> Result<()> {
         info!("Mock: detaching session: {}", session_id);
+        self.finish_session(session_id, DetachReason::ApplicationRequested).await?;
+        info!("Mock: session {} detached", session_id);
+        Ok(())
+    }
 
-        let session = self
-            .sessions
-            .write()
+    /// Simulates the target side ending a session (process exit, lost device...).
+    pub async fn dispatch_detached(&self, session_id: &str, reason: DetachReason) -> Result<()> {
+        warn!("Mock: session {} detached: {}", session_id, reason);
+        self.finish_session(session_id, reason).await
+    }
+
+    /// Drop a session, record why it ended and notify listeners
+    async fn finish_session(&self, session_id: &str, reason: DetachReason) -> Result<()> {
+        reaper::finish_session(&self.sessions, &self.detach_listeners, session_id, reason)
             .await
-            .remove(session_id)
-            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
+            .map(|_| ())
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))
+    }
 
-        session.mark_detached().await;
-        info!("Mock: session {} detached", session_id);
-        Ok(())
+    /// Detach sessions without RPC calls or script messages for `timeout`.
+    ///
+    /// `None` stops reaping. Reaped sessions end with `DetachReason::IdleTimeout`.
+    pub async fn set_idle_timeout(&self, timeout: Option<Duration>) {
+        self.reaper.configure(
+            self.sessions.clone(),
+            self.detach_listeners.clone(),
+            timeout,
+            |session_id, _script_ids| async move {
+                debug!("Mock: releasing idle session {}", session_id);
+            },
+        )
+        .await;
     }
 
     pub async fn get_session(&self, session_id: &str) -> Option<Arc<FridaSession>> {
@@ -228,6 +400,7 @@ impl FridaManager {
             .cloned()
             .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
 
+        session.touch().await;
         let script_id = Uuid::new_v4().to_string();
         session.add_script(script_id.clone(), "mock_script".to_string(), script_source.to_string()).await;
         session.mark_script_loaded(&script_id).await;
@@ -236,6 +409,42 @@ impl FridaManager {
         Ok(script_id)
     }
 
+    /// Simulates replacing a loaded script in place.
+    ///
+    /// The script keeps its id. Returns whether state was migrated, which the
+    /// mock assumes when the old source exports its state and the new source
+    /// imports it.
+    pub async fn reload_script(&self, session_id: &str, script_id: &str, script_source: &str) -> Result<bool> {
+        info!(
+            "Mock: reloading script {} in session {}, source length: {}",
+            script_id,
+            session_id,
+            script_source.len()
+        );
+
+        let session = self
+            .sessions
+            .read()
+            .await
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
+
+        session.touch().await;
+        let old_source = session
+            .replace_script_source(script_id, script_source.to_string())
+            .await
+            .ok_or_else(|| FridaError::ScriptNotFound(script_id.to_string()))?;
+
+        let migrated =
+            old_source.contains(protocol::EXPORT_STATE) && script_source.contains(protocol::IMPORT_STATE);
+        info!(
+            "Mock: script {} reloaded in session {} (state migrated: {})",
+            script_id, session_id, migrated
+        );
+        Ok(migrated)
+    }
+
     /// Simulates unloading a script from a session.
     pub async fn unload_script(&self, session_id: &str, script_id: &str) -> Result<()> {
         info!(
@@ -281,6 +490,8 @@ impl FridaManager {
             .cloned()
             .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
 
+        session.touch().await;
+
         // Verify script exists
         let scripts = session.scripts.read().await;
         if !scripts.contains_key(script_id) {
@@ -288,11 +499,26 @@ impl FridaManager {
         }
         drop(scripts);
 
-        // Return mock responses based on method name (matching frida-agent.ts RPC exports)
+        // Simulated responses for the agent's exports (see `protocol`)
         let result = match method {
-            // Memory Operations
-            "memoryRead" => {
-                let value_type = args.get(2).and_then(|v| v.as_str()).unwrap_or("uint32");
+            // Node protocol
+            protocol::EXECUTE_TARGET_NODE => {
+                let request = args.first().cloned().unwrap_or_default();
+                serde_json::json!({ "id": request["id"], "success": true, "outputs": {} })
+            },
+            protocol::ATTACH_INTERCEPTOR
+            | protocol::DETACH_INTERCEPTOR
+            | protocol::DETACH_ALL
+            | protocol::SET_HOOK_RULE_ENABLED
+            | protocol::NATIVE_CALLBACK_REPLY
+            | protocol::REVERT_CODE_CAVES => serde_json::json!({ "success": true }),
+            protocol::PING => serde_json::json!({ "alive": true, "timestamp": 0 }),
+            protocol::EXPORT_STATE => serde_json::json!({ "requests": [], "freezes": [] }),
+            protocol::IMPORT_STATE => serde_json::json!({ "restored": 0, "failed": [] }),
+
+            // Trainer API
+            protocol::MEMORY_READ => {
+                let value_type = args.get(1).and_then(|v| v.as_str()).unwrap_or("int32");
                 match value_type {
                     "int8" | "uint8" => serde_json::json!(42),
                     "int16" | "uint16" => serde_json::json!(1234),
@@ -302,125 +528,79 @@ impl FridaManager {
                     "double" => serde_json::json!(3.14159265359),
                     "pointer" => serde_json::json!("0x7fff1234"),
                     "string" => serde_json::json!("mock_string"),
-                    _ => serde_json::json!([0x48, 0x65, 0x6c, 0x6c, 0x6f]), // "Hello" bytes
+                    _ => serde_json::json!(123456),
                 }
             },
-            "memoryWrite" => serde_json::json!({ "success": true }),
-            "memoryScan" => serde_json::json!([
+            protocol::MEMORY_WRITE
+            | protocol::MEMORY_FREEZE
+            | protocol::MEMORY_UNFREEZE
+            | protocol::HOOK_FUNCTION
+            | protocol::NOP_FUNCTION
+            | protocol::OBJC_HOOK_METHOD
+            | protocol::OBJC_MODIFY_RETURN => serde_json::json!({ "success": true }),
+            protocol::REPLACE_RETURN => {
+                let address = args.first().and_then(|v| v.as_str()).unwrap_or("0x0");
+                serde_json::json!({ "success": true, "hookId": format!("return:{}", address) })
+            },
+            protocol::PATTERN_SCAN | protocol::VALUE_SCAN => serde_json::json!([
                 "0x7fff1234",
                 "0x7fff5678",
                 "0x7fff9abc"
             ]),
-            "memoryProtect" => serde_json::json!({ "success": true }),
-            "memoryAlloc" => serde_json::json!("0x7fff0000"),
-
-            // Module Operations
-            "getModuleBase" => {
-                let name = args.first().and_then(|v| v.as_str()).unwrap_or("unknown");
-                serde_json::json!(format!("0x{:x}", name.len() * 0x100000))
-            },
-            "getModuleInfo" => {
-                let name = args.first().and_then(|v| v.as_str()).unwrap_or("unknown");
-                serde_json::json!({
-                    "name": name,
-                    "base": format!("0x{:x}", name.len() * 0x100000),
-                    "size": 0x100000,
-                    "path": format!("/usr/lib/{}", name)
-                })
+            // Mocked as non-Android
+            protocol::JAVA_HOOK_METHOD | protocol::JAVA_MODIFY_RETURN | protocol::JAVA_CALL_METHOD => {
+                return Err(FridaError::RpcCallFailed("Java runtime not available".to_string()));
             },
-            "enumerateModules" => serde_json::json!([
+            protocol::LIST_MODULES => serde_json::json!([
                 { "name": "libc.so", "base": "0x7fff100000", "size": 0x200000, "path": "/usr/lib/libc.so" },
                 { "name": "libm.so", "base": "0x7fff300000", "size": 0x50000, "path": "/usr/lib/libm.so" },
                 { "name": "app.so", "base": "0x400000", "size": 0x100000, "path": "/data/app/app.so" }
             ]),
-            "enumerateExports" => serde_json::json!([
-                { "type": "function", "name": "malloc", "address": "0x7fff100100" },
-                { "type": "function", "name": "free", "address": "0x7fff100200" },
-                { "type": "function", "name": "printf", "address": "0x7fff100300" }
-            ]),
-            "findExportByName" => {
+            protocol::FIND_EXPORT => {
                 let export_name = args.get(1).and_then(|v| v.as_str()).unwrap_or("unknown");
                 serde_json::json!(format!("0x7fff{:04x}", export_name.len() * 0x100))
             },
-
-            // Interceptor Operations
-            "interceptorAttach" => {
-                let address = args.first().and_then(|v| v.as_str()).unwrap_or("0x0");
-                let hook_id = args.get(1).and_then(|v| v.as_str()).unwrap_or("hook_1");
-                info!("Mock: interceptor attached at {} with hookId {}", address, hook_id);
-                serde_json::json!({ "success": true, "hookId": hook_id })
-            },
-            "interceptorDetach" => {
-                let hook_id = args.first().and_then(|v| v.as_str()).unwrap_or("unknown");
-                info!("Mock: interceptor detached: {}", hook_id);
-                serde_json::json!({ "success": true })
-            },
-            "interceptorDetachAll" => {
-                info!("Mock: all interceptors detached");
-                serde_json::json!({ "success": true })
-            },
-
-            // Memory Watch Operations
-            "memoryWatchAdd" => {
-                let address = args.first().and_then(|v| v.as_str()).unwrap_or("0x0");
-                let watch_id = args.get(2).and_then(|v| v.as_str()).unwrap_or("watch_1");
-                info!("Mock: memory watch added at {} with watchId {}", address, watch_id);
-                serde_json::json!({ "success": true, "watchId": watch_id })
-            },
-            "memoryWatchRemove" => {
-                let watch_id = args.first().and_then(|v| v.as_str()).unwrap_or("unknown");
-                info!("Mock: memory watch removed: {}", watch_id);
-                serde_json::json!({ "success": true })
+            // Module-relative specs resolve against a base derived from the module name
+            protocol::RESOLVE_ADDRESS => {
+                let spec = args.first().cloned().unwrap_or_default();
+                match spec["base"]["module"].as_str() {
+                    Some(module) => serde_json::json!(format!("0x{:x}", module.len() * 0x100000)),
+                    None => spec["base"]["address"].clone(),
+                }
             },
 
-            // Java Operations
-            "javaAvailable" => serde_json::json!(false), // Mock as non-Android
-            "javaHookMethod" => serde_json::json!({ "error": "Java runtime not available" }),
-
-            // Native Function Call
-            "callNative" => {
-                let address = args.first().and_then(|v| v.as_str()).unwrap_or("0x0");
-                info!("Mock: native function called at {}", address);
-                serde_json::json!(0) // Return 0 as mock result
+            // Like Frida, refuse anything the agent doesn't export
+            _ => {
+                return Err(FridaError::RpcCallFailed(format!(
+                    "Mock: agent has no RPC export '{}'",
+                    method
+                )));
             },
-
-            // Utility
-            "ping" => serde_json::json!("pong"),
-            "getHookInfo" => serde_json::json!({
-                "interceptors": [],
-                "memoryWatches": [],
-                "javaHooks": []
-            }),
-
-            // Legacy names (for backward compatibility)
-            "readMemory" => serde_json::json!({
-                "address": args.first().cloned().unwrap_or(serde_json::json!("0x0")),
-                "value": 42,
-                "size": args.get(1).cloned().unwrap_or(serde_json::json!(4))
-            }),
-            "writeMemory" => serde_json::json!({
-                "success": true,
-                "address": args.first().cloned().unwrap_or(serde_json::json!("0x0"))
-            }),
-            "scanMemory" => serde_json::json!({
-                "matches": [
-                    { "address": "0x7fff1234", "value": 100 },
-                    { "address": "0x7fff5678", "value": 100 }
-                ]
-            }),
-
-            _ => serde_json::json!({
-                "mock": true,
-                "method": method,
-                "args": args,
-                "result": "mock_response"
-            }),
         };
 
         info!("Mock: RPC call '{}' returned: {:?}", method, result);
         Ok(result)
     }
 
+    /// Simulates posting a message to a script's `recv` handlers.
+    pub async fn post_message(&self, session_id: &str, script_id: &str, message: serde_json::Value) -> Result<()> {
+        let session = self
+            .sessions
+            .read()
+            .await
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
+
+        session.touch().await;
+        if !session.scripts.read().await.contains_key(script_id) {
+            return Err(FridaError::ScriptNotFound(script_id.to_string()));
+        }
+
+        debug!("Mock: posted {} to script {} in session {}", message, script_id, session_id);
+        Ok(())
+    }
+
     /// Register a message callback for a session.
     pub async fn on_session_message(
         &self,
@@ -440,6 +620,25 @@ impl FridaManager {
         Ok(())
     }
 
+    /// Register a callback fired when a session detaches, for any reason.
+    pub async fn on_session_detached(&self, session_id: &str, callback: DetachCallback) -> Result<()> {
+        let session = self
+            .sessions
+            .read()
+            .await
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| FridaError::SessionNotFound(session_id.to_string()))?;
+
+        session.on_detached(callback).await;
+        Ok(())
+    }
+
+    /// Register a callback fired whenever any session detaches.
+    pub async fn on_detached(&self, callback: DetachCallback) {
+        self.detach_listeners.write().await.push(callback);
+    }
+
     /// Dispatch a message to a session's callbacks.
     pub async fn dispatch_message(
         &self,
@@ -620,6 +819,65 @@ mod tests {
         assert!(session.process.name.contains("spawn:com.example.app"));
     }
 
+    #[tokio::test]
+    async fn test_mock_spawn_and_attach_resumes() {
+        let manager = make_manager();
+        let session_id = manager
+            .spawn_and_attach("local", "/opt/game/bin", SpawnOptions::default())
+            .await
+            .unwrap();
+
+        let session = manager.get_session(&session_id).await.unwrap();
+        assert_eq!(session.state().await, SessionState::Active);
+        assert!(manager.resume(&session_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_spawn_suspended_then_resume() {
+        let manager = make_manager();
+        let options = SpawnOptions {
+            argv: vec!["--windowed".into()],
+            envp: vec!["LANG=C".into()],
+            cwd: Some("/opt/game".into()),
+            stdio: crate::process::StdioMode::Pipe,
+        };
+        let session_id = manager
+            .spawn_suspended("local", "/opt/game/bin", options)
+            .await
+            .unwrap();
+
+        let session = manager.get_session(&session_id).await.unwrap();
+        assert_eq!(session.state().await, SessionState::Suspended);
+        assert_eq!(session.process.path.as_deref(), Some("/opt/game"));
+
+        // Early instrumentation happens while the process is still suspended
+        let script_id = manager.inject_script(&session_id, "code").await.unwrap();
+        assert!(session.get_script_ids().await.contains(&script_id));
+        assert_eq!(session.state().await, SessionState::Suspended);
+
+        manager.resume(&session_id).await.unwrap();
+        assert_eq!(session.state().await, SessionState::Active);
+    }
+
+    #[tokio::test]
+    async fn test_mock_spawn_rejects_malformed_env() {
+        let manager = make_manager();
+        let options = SpawnOptions {
+            envp: vec!["LANG".into()],
+            ..Default::default()
+        };
+        let result = manager.spawn_suspended("local", "/opt/game/bin", options).await;
+        assert!(result.unwrap_err().to_string().contains("KEY=VALUE"));
+        assert!(manager.list_sessions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mock_resume_unknown_session() {
+        let manager = make_manager();
+        let result = manager.resume("nonexistent").await;
+        assert!(result.unwrap_err().to_string().contains("Session not found"));
+    }
+
     #[tokio::test]
     async fn test_mock_spawn_invalid_device() {
         let manager = make_manager();
@@ -698,6 +956,45 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Script not found"));
     }
 
+    // --- Script reload ---
+
+    #[tokio::test]
+    async fn test_mock_reload_script_keeps_id() {
+        let manager = make_manager();
+        let session_id = manager.attach_on_device("local", 100).await.unwrap();
+        let script_id = manager
+            .inject_script(&session_id, "rpc.exports = { exportState() {} };")
+            .await
+            .unwrap();
+
+        let migrated = manager
+            .reload_script(&session_id, &script_id, "rpc.exports = { importState(s) {} };")
+            .await
+            .unwrap();
+        assert!(migrated);
+
+        let session = manager.get_session(&session_id).await.unwrap();
+        assert_eq!(session.get_script_ids().await, vec![script_id.clone()]);
+        let scripts = session.scripts.read().await;
+        assert!(scripts[&script_id].source.contains("importState"));
+        drop(scripts);
+
+        // RPC keeps working against the same id
+        let result = manager.call_rpc(&session_id, &script_id, protocol::PING, vec![]).await.unwrap();
+        assert_eq!(result["alive"], true);
+    }
+
+    #[tokio::test]
+    async fn test_mock_reload_script_without_hooks() {
+        let manager = make_manager();
+        let session_id = manager.attach_on_device("local", 100).await.unwrap();
+        let script_id = manager.inject_script(&session_id, "code").await.unwrap();
+
+        assert!(!manager.reload_script(&session_id, &script_id, "new code").await.unwrap());
+        let result = manager.reload_script(&session_id, "nonexistent-script", "code").await;
+        assert!(result.unwrap_err().to_string().contains("Script not found"));
+    }
+
     // --- RPC calls ---
 
     #[tokio::test]
@@ -706,8 +1003,8 @@ mod tests {
         let session_id = manager.attach_on_device("local", 100).await.unwrap();
         let script_id = manager.inject_script(&session_id, "code").await.unwrap();
 
-        let result = manager.call_rpc(&session_id, &script_id, "ping", vec![]).await.unwrap();
-        assert_eq!(result, serde_json::json!("pong"));
+        let result = manager.call_rpc(&session_id, &script_id, protocol::PING, vec![]).await.unwrap();
+        assert_eq!(result["alive"], true);
     }
 
     #[tokio::test]
@@ -717,9 +1014,8 @@ mod tests {
         let script_id = manager.inject_script(&session_id, "code").await.unwrap();
 
         let result = manager
-            .call_rpc(&session_id, &script_id, "memoryRead", vec![
+            .call_rpc(&session_id, &script_id, protocol::MEMORY_READ, vec![
                 serde_json::json!("0x1000"),
-                serde_json::json!(4),
                 serde_json::json!("uint32"),
             ])
             .await
@@ -734,9 +1030,8 @@ mod tests {
         let script_id = manager.inject_script(&session_id, "code").await.unwrap();
 
         let result = manager
-            .call_rpc(&session_id, &script_id, "memoryRead", vec![
+            .call_rpc(&session_id, &script_id, protocol::MEMORY_READ, vec![
                 serde_json::json!("0x1000"),
-                serde_json::json!(32),
                 serde_json::json!("string"),
             ])
             .await
@@ -751,20 +1046,21 @@ mod tests {
         let script_id = manager.inject_script(&session_id, "code").await.unwrap();
 
         let result = manager
-            .call_rpc(&session_id, &script_id, "memoryWrite", vec![])
+            .call_rpc(&session_id, &script_id, protocol::MEMORY_WRITE, vec![])
             .await
             .unwrap();
         assert_eq!(result["success"], true);
     }
 
     #[tokio::test]
-    async fn test_mock_call_rpc_get_module_base() {
+    async fn test_mock_call_rpc_resolve_module_address() {
         let manager = make_manager();
         let session_id = manager.attach_on_device("local", 100).await.unwrap();
         let script_id = manager.inject_script(&session_id, "code").await.unwrap();
 
+        let spec = serde_json::json!({ "base": { "kind": "module", "module": "libc.so" } });
         let result = manager
-            .call_rpc(&session_id, &script_id, "getModuleBase", vec![serde_json::json!("libc.so")])
+            .call_rpc(&session_id, &script_id, protocol::RESOLVE_ADDRESS, vec![spec])
             .await
             .unwrap();
         // "libc.so" has 7 chars, 7 * 0x100000 = 0x700000
@@ -772,31 +1068,55 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_mock_call_rpc_enumerate_modules() {
+    async fn test_mock_call_rpc_list_modules() {
         let manager = make_manager();
         let session_id = manager.attach_on_device("local", 100).await.unwrap();
         let script_id = manager.inject_script(&session_id, "code").await.unwrap();
 
         let result = manager
-            .call_rpc(&session_id, &script_id, "enumerateModules", vec![])
+            .call_rpc(&session_id, &script_id, protocol::LIST_MODULES, vec![])
             .await
             .unwrap();
         assert!(result.is_array());
         assert_eq!(result.as_array().unwrap().len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_mock_simulates_every_agent_method() {
+        let manager = make_manager();
+        let session_id = manager.attach_on_device("local", 100).await.unwrap();
+        let script_id = manager.inject_script(&session_id, "code").await.unwrap();
+
+        for method in protocol::METHODS {
+            let result = manager.call_rpc(&session_id, &script_id, method, vec![]).await;
+            match result {
+                Ok(_) => {}
+                // Java methods fail like they would on a non-Android target
+                Err(FridaError::RpcCallFailed(e)) if e.contains("Java") => {}
+                Err(e) => panic!("mock doesn't simulate {}: {}", method, e),
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_mock_call_rpc_unknown_method() {
         let manager = make_manager();
         let session_id = manager.attach_on_device("local", 100).await.unwrap();
         let script_id = manager.inject_script(&session_id, "code").await.unwrap();
 
-        let result = manager
-            .call_rpc(&session_id, &script_id, "unknownMethod", vec![serde_json::json!("arg1")])
-            .await
-            .unwrap();
-        assert_eq!(result["mock"], true);
-        assert_eq!(result["method"], "unknownMethod");
+        // A misspelled or removed export must not pass as a mock success
+        for method in ["unknownMethod", "memoryread", "execute_target_node"] {
+            assert!(!protocol::METHODS.contains(&method));
+            let result = manager
+                .call_rpc(&session_id, &script_id, method, vec![serde_json::json!("arg1")])
+                .await;
+            assert!(
+                matches!(result, Err(FridaError::RpcCallFailed(ref e)) if e.contains(method)),
+                "{} was accepted: {:?}",
+                method,
+                result
+            );
+        }
     }
 
     #[tokio::test]
@@ -818,6 +1138,18 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Script not found"));
     }
 
+    #[tokio::test]
+    async fn test_mock_post_message() {
+        let manager = make_manager();
+        let session_id = manager.attach_on_device("local", 100).await.unwrap();
+        let script_id = manager.inject_script(&session_id, "recv(function() {});").await.unwrap();
+        let message = serde_json::json!({ "type": "native_callback_reply", "callId": 1, "value": 7 });
+
+        assert!(manager.post_message(&session_id, &script_id, message.clone()).await.is_ok());
+        let result = manager.post_message(&session_id, "nonexistent-script", message).await;
+        assert!(result.unwrap_err().to_string().contains("Script not found"));
+    }
+
     // --- Message callbacks ---
 
     #[tokio::test]
@@ -893,6 +1225,202 @@ mod tests {
         assert!(manager.get_session(&session_id).await.is_none());
     }
 
+    // --- Detach notifications ---
+
+    #[tokio::test]
+    async fn test_mock_dispatch_detached_records_reason() {
+        let manager = make_manager();
+        let session_id = manager.attach_on_device("local", 100).await.unwrap();
+        let script_id = manager.inject_script(&session_id, "code").await.unwrap();
+        let session = manager.get_session(&session_id).await.unwrap();
+
+        let per_session = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let global = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let ps = per_session.clone();
+        manager
+            .on_session_detached(&session_id, Arc::new(move |_, reason| ps.lock().unwrap().push(reason)))
+            .await
+            .unwrap();
+        let gl = global.clone();
+        manager
+            .on_detached(Arc::new(move |id, reason| gl.lock().unwrap().push((id, reason))))
+            .await;
+
+        manager
+            .dispatch_detached(&session_id, DetachReason::ProcessTerminated)
+            .await
+            .unwrap();
+
+        assert!(session.is_detached().await);
+        assert_eq!(session.detach_reason().await, Some(DetachReason::ProcessTerminated));
+        assert_eq!(*per_session.lock().unwrap(), vec![DetachReason::ProcessTerminated]);
+        assert_eq!(
+            *global.lock().unwrap(),
+            vec![(session_id.clone(), DetachReason::ProcessTerminated)]
+        );
+
+        // Nothing is sent into a dead session
+        assert!(manager.call_rpc(&session_id, &script_id, "ping", vec![]).await.is_err());
+        assert!(manager.detach(&session_id).await.is_err());
+        assert_eq!(global.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mock_detach_notifies_application_requested() {
+        let manager = make_manager();
+        let session_id = manager.attach_on_device("local", 100).await.unwrap();
+
+        let reasons = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let r = reasons.clone();
+        manager
+            .on_detached(Arc::new(move |_, reason| r.lock().unwrap().push(reason)))
+            .await;
+
+        manager.detach(&session_id).await.unwrap();
+        assert_eq!(*reasons.lock().unwrap(), vec![DetachReason::ApplicationRequested]);
+    }
+
+    #[tokio::test]
+    async fn test_mock_on_session_detached_invalid_session() {
+        let manager = make_manager();
+        let result = manager.on_session_detached("nonexistent", Arc::new(|_, _| {})).await;
+        assert!(result.is_err());
+    }
+
+    // --- Idle reaping ---
+
+    #[tokio::test]
+    async fn test_mock_reaper_detaches_idle_sessions() {
+        let manager = make_manager();
+        let session_id = manager.attach_on_device("local", 100).await.unwrap();
+        manager.inject_script(&session_id, "code").await.unwrap();
+        let session = manager.get_session(&session_id).await.unwrap();
+
+        let reasons = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let r = reasons.clone();
+        manager
+            .on_detached(Arc::new(move |id, reason| r.lock().unwrap().push((id, reason))))
+            .await;
+
+        manager.set_idle_timeout(Some(Duration::from_millis(20))).await;
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        assert!(manager.get_session(&session_id).await.is_none());
+        assert_eq!(session.detach_reason().await, Some(DetachReason::IdleTimeout));
+        assert!(session.get_script_ids().await.is_empty());
+        assert_eq!(
+            *reasons.lock().unwrap(),
+            vec![(session_id, DetachReason::IdleTimeout)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_rpc_traffic_keeps_session_alive() {
+        let manager = make_manager();
+        let session_id = manager.attach_on_device("local", 100).await.unwrap();
+        let script_id = manager.inject_script(&session_id, "code").await.unwrap();
+
+        manager.set_idle_timeout(Some(Duration::from_millis(60))).await;
+        for _ in 0..10 {
+            tokio::time::sleep(Duration::from_millis(15)).await;
+            manager.call_rpc(&session_id, &script_id, "ping", vec![]).await.unwrap();
+        }
+        assert!(manager.get_session(&session_id).await.is_some());
+
+        // Turning the reaper off leaves idle sessions alone
+        manager.set_idle_timeout(None).await;
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert!(manager.get_session(&session_id).await.is_some());
+    }
+
+    // --- Spawn gating ---
+
+    #[tokio::test]
+    async fn test_mock_spawn_gate_attaches_matching_process() {
+        let manager = make_manager();
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let ev = events.clone();
+        manager
+            .on_gated(Arc::new(move |event| ev.lock().unwrap().push(event)))
+            .await;
+
+        let mut gate = manager
+            .gate("local", AttachTarget::Spawn { pattern: "game*".into() })
+            .await
+            .unwrap();
+
+        // The launcher's updater is let go, the game is caught while running
+        let updater = manager
+            .dispatch_spawn("local", ProcessInfo::new(43, "Updater"), Some(42))
+            .await
+            .unwrap();
+        assert!(updater.is_none());
+        let caught = manager
+            .dispatch_spawn("local", ProcessInfo::new(44, "Game.exe"), Some(42))
+            .await
+            .unwrap();
+
+        let session_id = gate.next().await.unwrap();
+        assert_eq!(caught, Some(session_id.clone()));
+        let session = manager.get_session(&session_id).await.unwrap();
+        assert_eq!(session.process.pid, 44);
+        assert_eq!(session.state().await, SessionState::Active);
+
+        let events = events.lock().unwrap();
+        assert!(matches!(&events[0], GateEvent::Released { process, .. } if process.pid == 43));
+        assert!(matches!(&events[1], GateEvent::Attached { session_id: id, .. } if *id == session_id));
+    }
+
+    #[tokio::test]
+    async fn test_mock_child_gate_refused() {
+        let manager = make_manager();
+        let target = AttachTarget::Child { parent: 42, pattern: "game*".into() };
+        assert!(matches!(
+            manager.gate("local", target.clone()).await,
+            Err(FridaError::AttachFailed(_))
+        ));
+        assert!(manager.attach_target("local", target).await.is_err());
+
+        // Nothing was armed, so a matching child goes unnoticed
+        let game = ProcessInfo::new(44, "Game.exe");
+        assert!(manager.dispatch_spawn("local", game, Some(42)).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mock_attach_target_waits_for_spawn() {
+        let manager = Arc::new(make_manager());
+        let waiter = {
+            let manager = manager.clone();
+            tokio::spawn(async move {
+                manager
+                    .attach_target("local", AttachTarget::Spawn { pattern: "*/Games/*".into() })
+                    .await
+            })
+        };
+
+        // Keep spawning until the gate is armed by the waiting attach
+        let game = ProcessInfo::new(77, "Game").with_path("/Users/user/Games/Game.app");
+        let session_id = loop {
+            if let Some(id) = manager.dispatch_spawn("local", game.clone(), None).await.unwrap() {
+                break id;
+            }
+            tokio::task::yield_now().await;
+        };
+        assert_eq!(waiter.await.unwrap().unwrap(), session_id);
+
+        // Nothing gates processes once the attach returned
+        assert!(manager.dispatch_spawn("local", game, None).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mock_gate_invalid_device() {
+        let manager = make_manager();
+        let result = manager
+            .gate("nonexistent", AttachTarget::Spawn { pattern: "*".into() })
+            .await;
+        assert!(result.is_err());
+    }
+
     // --- Multi-session management ---
 
     #[tokio::test]
@@ -947,8 +1475,8 @@ mod tests {
         })).await.unwrap();
 
         // 6. Call RPC
-        let rpc_result = manager.call_rpc(&session_id, &script_id, "ping", vec![]).await.unwrap();
-        assert_eq!(rpc_result, serde_json::json!("pong"));
+        let rpc_result = manager.call_rpc(&session_id, &script_id, protocol::PING, vec![]).await.unwrap();
+        assert_eq!(rpc_result["alive"], true);
 
         // 7. Dispatch message
         manager.dispatch_message(&session_id, &script_id, ScriptMessage::Log {
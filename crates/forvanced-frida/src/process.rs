@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::error::{FridaError, Result};
+
 /// Process information from Frida
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessInfo {
@@ -67,6 +69,20 @@ pub enum AttachTarget {
     Name(String),
     /// Attach by bundle identifier (e.g., "com.apple.Safari", "com.example.app")
     Identifier(String),
+    /// Wait for a child of `parent` matching `pattern`, e.g. the game a
+    /// launcher starts; needs a backend that knows parent processes, which
+    /// neither the mock nor the frida-rust backend does
+    Child { parent: u32, pattern: String },
+    /// Wait for the next process started on the device matching `pattern`;
+    /// it is attached once seen running
+    Spawn { pattern: String },
+}
+
+impl AttachTarget {
+    /// Whether this target names a process that has yet to start
+    pub fn is_gated(&self) -> bool {
+        matches!(self, AttachTarget::Child { .. } | AttachTarget::Spawn { .. })
+    }
 }
 
 impl std::fmt::Display for AttachTarget {
@@ -75,6 +91,8 @@ impl std::fmt::Display for AttachTarget {
             AttachTarget::Pid(pid) => write!(f, "pid:{}", pid),
             AttachTarget::Name(name) => write!(f, "name:{}", name),
             AttachTarget::Identifier(id) => write!(f, "identifier:{}", id),
+            AttachTarget::Child { parent, pattern } => write!(f, "child:{}:{}", parent, pattern),
+            AttachTarget::Spawn { pattern } => write!(f, "spawn:{}", pattern),
         }
     }
 }
@@ -96,7 +114,30 @@ pub struct SpawnOptions {
     pub stdio: StdioMode,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+impl SpawnOptions {
+    /// Full argv for `program`: the program itself followed by `argv`
+    pub fn full_argv(&self, program: &str) -> Vec<String> {
+        std::iter::once(program.to_string())
+            .chain(self.argv.iter().cloned())
+            .collect()
+    }
+
+    /// Split `envp` entries (`KEY=VALUE`) into key/value pairs
+    pub fn env_pairs(&self) -> Result<Vec<(String, String)>> {
+        self.envp
+            .iter()
+            .map(|entry| match entry.split_once('=') {
+                Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+                _ => Err(FridaError::SpawnFailed(format!(
+                    "Invalid environment entry '{}', expected KEY=VALUE",
+                    entry
+                ))),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum StdioMode {
     #[default]
@@ -224,6 +265,21 @@ mod tests {
         assert_eq!(format!("{}", t), "identifier:com.apple.Safari");
     }
 
+    #[test]
+    fn test_attach_target_gated_variants() {
+        let child = AttachTarget::Child {
+            parent: 7,
+            pattern: "game*".into(),
+        };
+        assert_eq!(child.to_string(), "child:7:game*");
+        assert!(child.is_gated());
+        assert!(!AttachTarget::Pid(7).is_gated());
+
+        let json = serde_json::to_value(AttachTarget::Spawn { pattern: "game*".into() }).unwrap();
+        assert_eq!(json["type"], "Spawn");
+        assert_eq!(json["value"]["pattern"], "game*");
+    }
+
     #[test]
     fn test_attach_target_serde() {
         let t = AttachTarget::Pid(42);
@@ -249,6 +305,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_spawn_options_full_argv() {
+        let opts = SpawnOptions {
+            argv: vec!["--windowed".into(), "-nosplash".into()],
+            ..Default::default()
+        };
+        assert_eq!(opts.full_argv("/opt/game/bin"), vec!["/opt/game/bin", "--windowed", "-nosplash"]);
+    }
+
+    #[test]
+    fn test_spawn_options_env_pairs() {
+        let opts = SpawnOptions {
+            envp: vec!["LANG=C".into(), "EMPTY=".into(), "URL=a=b".into()],
+            ..Default::default()
+        };
+        let pairs = opts.env_pairs().unwrap();
+        assert_eq!(pairs[0], ("LANG".to_string(), "C".to_string()));
+        assert_eq!(pairs[1], ("EMPTY".to_string(), String::new()));
+        assert_eq!(pairs[2], ("URL".to_string(), "a=b".to_string()));
+
+        let bad = SpawnOptions {
+            envp: vec!["NOEQUALS".into()],
+            ..Default::default()
+        };
+        assert!(bad.env_pairs().unwrap_err().to_string().contains("NOEQUALS"));
+    }
+
+    #[test]
+    fn test_spawn_options_serde() {
+        let opts: SpawnOptions =
+            serde_json::from_str(r#"{"argv":["-v"],"cwd":"/tmp","stdio":"pipe"}"#).unwrap();
+        assert_eq!(opts.argv, vec!["-v"]);
+        assert!(opts.envp.is_empty());
+        assert_eq!(opts.cwd.as_deref(), Some("/tmp"));
+        assert_eq!(opts.stdio, StdioMode::Pipe);
+    }
+
     // --- FridaDeviceType ---
 
     #[test]
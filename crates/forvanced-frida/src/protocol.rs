@@ -0,0 +1,71 @@
+//! RPC methods exported by the target agent
+//!
+//! The agent source lives in forvanced-executor (`agent/src/index.js`). These
+//! names are what the executor, the trainer codegen and the mock backend call
+//! it by; [`METHODS`] must list exactly what its `rpc.exports` defines.
+
+// Node protocol, used by the host executor
+pub const EXECUTE_TARGET_NODE: &str = "executeTargetNode";
+pub const ATTACH_INTERCEPTOR: &str = "attachInterceptor";
+pub const DETACH_INTERCEPTOR: &str = "detachInterceptor";
+pub const DETACH_ALL: &str = "detachAll";
+pub const SET_HOOK_RULE_ENABLED: &str = "setHookRuleEnabled";
+pub const NATIVE_CALLBACK_REPLY: &str = "nativeCallbackReply";
+pub const REVERT_CODE_CAVES: &str = "revertCodeCaves";
+pub const PING: &str = "ping";
+
+/// Hands a script's live state to a reloaded copy and releases whatever the
+/// new copy is going to re-install
+pub const EXPORT_STATE: &str = "exportState";
+/// Takes the value returned by [`EXPORT_STATE`]
+pub const IMPORT_STATE: &str = "importState";
+
+// Trainer API, called directly by generated trainers
+pub const MEMORY_READ: &str = "memoryRead";
+pub const MEMORY_WRITE: &str = "memoryWrite";
+pub const MEMORY_FREEZE: &str = "memoryFreeze";
+pub const MEMORY_UNFREEZE: &str = "memoryUnfreeze";
+pub const PATTERN_SCAN: &str = "patternScan";
+pub const VALUE_SCAN: &str = "valueScan";
+pub const HOOK_FUNCTION: &str = "hookFunction";
+pub const REPLACE_RETURN: &str = "replaceReturn";
+pub const NOP_FUNCTION: &str = "nopFunction";
+pub const JAVA_HOOK_METHOD: &str = "javaHookMethod";
+pub const JAVA_MODIFY_RETURN: &str = "javaModifyReturn";
+pub const JAVA_CALL_METHOD: &str = "javaCallMethod";
+pub const OBJC_HOOK_METHOD: &str = "objcHookMethod";
+pub const OBJC_MODIFY_RETURN: &str = "objcModifyReturn";
+pub const LIST_MODULES: &str = "listModules";
+pub const FIND_EXPORT: &str = "findExport";
+pub const RESOLVE_ADDRESS: &str = "resolveAddress";
+
+/// Every method the agent exports
+pub const METHODS: &[&str] = &[
+    EXECUTE_TARGET_NODE,
+    ATTACH_INTERCEPTOR,
+    DETACH_INTERCEPTOR,
+    DETACH_ALL,
+    SET_HOOK_RULE_ENABLED,
+    NATIVE_CALLBACK_REPLY,
+    REVERT_CODE_CAVES,
+    PING,
+    EXPORT_STATE,
+    IMPORT_STATE,
+    MEMORY_READ,
+    MEMORY_WRITE,
+    MEMORY_FREEZE,
+    MEMORY_UNFREEZE,
+    PATTERN_SCAN,
+    VALUE_SCAN,
+    HOOK_FUNCTION,
+    REPLACE_RETURN,
+    NOP_FUNCTION,
+    JAVA_HOOK_METHOD,
+    JAVA_MODIFY_RETURN,
+    JAVA_CALL_METHOD,
+    OBJC_HOOK_METHOD,
+    OBJC_MODIFY_RETURN,
+    LIST_MODULES,
+    FIND_EXPORT,
+    RESOLVE_ADDRESS,
+];
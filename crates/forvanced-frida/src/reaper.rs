@@ -0,0 +1,124 @@
+//! Idle session reaper
+//!
+//! Sessions nobody talks to (no RPC calls, no script messages) pile up when a
+//! caller forgets to detach. The reaper ends them once they have been idle for
+//! the configured timeout, with `DetachReason::IdleTimeout`.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tracing::info;
+
+use crate::session::{DetachCallback, DetachReason, FridaSession};
+
+pub(crate) type SessionMap = Arc<RwLock<HashMap<String, Arc<FridaSession>>>>;
+
+/// Drop a session, record why it ended and notify listeners
+///
+/// Returns the session if it was still registered.
+pub(crate) async fn finish_session(
+    sessions: &SessionMap,
+    listeners: &RwLock<Vec<DetachCallback>>,
+    session_id: &str,
+    reason: DetachReason,
+) -> Option<Arc<FridaSession>> {
+    let session = sessions.write().await.remove(session_id)?;
+    if session.mark_detached_with(reason).await {
+        for listener in listeners.read().await.iter() {
+            listener(session_id.to_string(), reason);
+        }
+    }
+    Some(session)
+}
+
+/// Handle to the running reaper task, if any
+#[derive(Default)]
+pub(crate) struct Reaper {
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Reaper {
+    /// Replace the running reaper; `None` turns reaping off
+    ///
+    /// For each expired session the reaper awaits `release` with the session
+    /// id and its script ids, which should end the session on the backend.
+    /// It then drops the scripts and the session from `sessions` and tells
+    /// `listeners`. Async so the reaper task is always spawned from inside a
+    /// runtime.
+    pub async fn configure<F, Fut>(
+        &self,
+        sessions: SessionMap,
+        listeners: Arc<RwLock<Vec<DetachCallback>>>,
+        idle_timeout: Option<Duration>,
+        release: F,
+    ) where
+        F: Fn(String, Vec<String>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let task = idle_timeout.map(|timeout| {
+            info!("Detaching sessions idle for more than {:?}", timeout);
+            tokio::spawn(reap(sessions, listeners, timeout, release))
+        });
+        if let Some(old) = std::mem::replace(&mut *self.task.lock().unwrap(), task) {
+            old.abort();
+        }
+    }
+}
+
+impl Drop for Reaper {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.get_mut().unwrap().take() {
+            task.abort();
+        }
+    }
+}
+
+async fn reap<F, Fut>(
+    sessions: SessionMap,
+    listeners: Arc<RwLock<Vec<DetachCallback>>>,
+    timeout: Duration,
+    release: F,
+) where
+    F: Fn(String, Vec<String>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let interval = (timeout / 4).clamp(Duration::from_millis(10), Duration::from_secs(30));
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let mut expired = Vec::new();
+        for (session_id, session) in sessions.read().await.iter() {
+            if session.is_expired(timeout).await {
+                expired.push((session_id.clone(), session.clone()));
+            }
+        }
+
+        for (session_id, session) in expired {
+            // An RPC may have come in since the scan
+            if !session.is_expired(timeout).await {
+                continue;
+            }
+            let script_ids = session.get_script_ids().await;
+            info!(
+                "Session {} idle for {:?}, detaching ({} scripts)",
+                session_id,
+                timeout,
+                script_ids.len()
+            );
+            release(session_id.clone(), script_ids.clone()).await;
+            for script_id in &script_ids {
+                session.remove_script(script_id).await;
+            }
+            finish_session(
+                &sessions,
+                &listeners,
+                &session_id,
+                DetachReason::IdleTimeout,
+            )
+            .await;
+        }
+    }
+}
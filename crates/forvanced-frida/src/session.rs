@@ -18,10 +18,40 @@ pub enum SessionState {
     Created,
     /// Session is actively attached and usable
     Active,
+    /// Process was spawned suspended and waits for `resume`
+    Suspended,
     /// Session has been detached
     Detached,
 }
 
+/// Why a session stopped, mirroring Frida's `SessionDetachReason`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetachReason {
+    /// We detached on purpose
+    ApplicationRequested,
+    /// The target process exited or was killed
+    ProcessTerminated,
+    /// The connection to a remote frida-server dropped
+    ConnectionLost,
+    /// The device was unplugged or disappeared
+    DeviceLost,
+    /// Nothing used the session for longer than the idle timeout
+    IdleTimeout,
+}
+
+impl std::fmt::Display for DetachReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DetachReason::ApplicationRequested => write!(f, "application requested"),
+            DetachReason::ProcessTerminated => write!(f, "process terminated"),
+            DetachReason::ConnectionLost => write!(f, "connection lost"),
+            DetachReason::DeviceLost => write!(f, "device lost"),
+            DetachReason::IdleTimeout => write!(f, "idle timeout"),
+        }
+    }
+}
+
 /// Message from Frida script
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
@@ -62,6 +92,9 @@ pub enum RpcResult {
 /// Callback for receiving script messages
 pub type MessageCallback = Arc<dyn Fn(String, ScriptMessage) + Send + Sync>;
 
+/// Callback for session detach notifications, called with the session id
+pub type DetachCallback = Arc<dyn Fn(String, DetachReason) + Send + Sync>;
+
 /// Script handle with metadata
 pub struct ScriptHandle {
     pub id: String,
@@ -92,6 +125,8 @@ pub struct FridaSession {
     last_activity: Arc<RwLock<Instant>>,
     /// Message callbacks
     message_callbacks: Arc<RwLock<Vec<MessageCallback>>>,
+    detach_reason: Arc<RwLock<Option<DetachReason>>>,
+    detach_callbacks: Arc<RwLock<Vec<DetachCallback>>>,
 }
 
 impl FridaSession {
@@ -105,6 +140,8 @@ impl FridaSession {
             created_at: now,
             last_activity: Arc::new(RwLock::new(now)),
             message_callbacks: Arc::new(RwLock::new(Vec::new())),
+            detach_reason: Arc::new(RwLock::new(None)),
+            detach_callbacks: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
@@ -124,7 +161,37 @@ impl FridaSession {
     }
 
     pub async fn mark_detached(&self) {
+        self.mark_detached_with(DetachReason::ApplicationRequested).await;
+    }
+
+    /// Move to `Detached`, record why and notify detach callbacks.
+    ///
+    /// Only the first call has any effect; returns whether it was this one.
+    pub async fn mark_detached_with(&self, reason: DetachReason) -> bool {
+        {
+            let mut detach_reason = self.detach_reason.write().await;
+            if detach_reason.is_some() {
+                return false;
+            }
+            *detach_reason = Some(reason);
+        }
         *self.state.write().await = SessionState::Detached;
+
+        let callbacks = self.detach_callbacks.read().await;
+        for callback in callbacks.iter() {
+            callback(self.id.clone(), reason);
+        }
+        true
+    }
+
+    /// Why the session detached, if it has
+    pub async fn detach_reason(&self) -> Option<DetachReason> {
+        *self.detach_reason.read().await
+    }
+
+    /// Register a callback fired once when the session detaches
+    pub async fn on_detached(&self, callback: DetachCallback) {
+        self.detach_callbacks.write().await.push(callback);
     }
 
     /// Update last activity timestamp
@@ -132,11 +199,11 @@ impl FridaSession {
         *self.last_activity.write().await = Instant::now();
     }
 
-    /// Check if session has exceeded the given timeout since last activity
     pub fn created_at(&self) -> Instant {
         self.created_at
     }
 
+    /// Check if session has exceeded the given timeout since last activity
     pub async fn is_expired(&self, timeout: Duration) -> bool {
         self.last_activity.read().await.elapsed() > timeout
     }
@@ -154,6 +221,15 @@ impl FridaSession {
         }
     }
 
+    /// Swap in a reloaded script's source under the same id
+    ///
+    /// Returns the previous source, or `None` if the script isn't loaded.
+    pub async fn replace_script_source(&self, script_id: &str, source: String) -> Option<String> {
+        let mut scripts = self.scripts.write().await;
+        let handle = scripts.get_mut(script_id)?;
+        Some(std::mem::replace(&mut handle.source, source))
+    }
+
     /// Remove a script from this session
     pub async fn remove_script(&self, script_id: &str) -> Option<ScriptHandle> {
         self.scripts.write().await.remove(script_id)
@@ -171,6 +247,7 @@ impl FridaSession {
 
     /// Dispatch a message to all registered callbacks
     pub async fn dispatch_message(&self, script_id: &str, message: ScriptMessage) {
+        self.touch().await;
         let callbacks = self.message_callbacks.read().await;
         for callback in callbacks.iter() {
             callback(script_id.to_string(), message.clone());
@@ -254,6 +331,47 @@ mod tests {
         assert_eq!(session.state().await, SessionState::Detached);
     }
 
+    #[tokio::test]
+    async fn test_session_detach_reason_and_callbacks() {
+        let session = make_session();
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let seen_clone = seen.clone();
+        session.on_detached(Arc::new(move |id, reason| {
+            seen_clone.lock().unwrap().push((id, reason));
+        })).await;
+        assert_eq!(session.detach_reason().await, None);
+
+        assert!(session.mark_detached_with(DetachReason::ProcessTerminated).await);
+        assert!(session.is_detached().await);
+        assert_eq!(session.detach_reason().await, Some(DetachReason::ProcessTerminated));
+
+        // A later detach neither overwrites the reason nor notifies again
+        assert!(!session.mark_detached_with(DetachReason::DeviceLost).await);
+        session.mark_detached().await;
+        assert_eq!(session.detach_reason().await, Some(DetachReason::ProcessTerminated));
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![("test-session".to_string(), DetachReason::ProcessTerminated)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_mark_detached_is_application_requested() {
+        let session = make_session();
+        session.mark_detached().await;
+        assert_eq!(session.detach_reason().await, Some(DetachReason::ApplicationRequested));
+    }
+
+    #[test]
+    fn test_detach_reason_serde() {
+        let json = serde_json::to_string(&DetachReason::ConnectionLost).unwrap();
+        assert_eq!(json, "\"connection_lost\"");
+        assert_eq!(DetachReason::DeviceLost.to_string(), "device lost");
+        let json = serde_json::to_string(&DetachReason::IdleTimeout).unwrap();
+        assert_eq!(json, "\"idle_timeout\"");
+    }
+
     // --- Activity / expiry tests ---
 
     #[tokio::test]
@@ -0,0 +1,401 @@
+//! Process watching for auto-attach
+//!
+//! Polls a device for a process matching the target config, attaches when it
+//! shows up, injects the target scripts and starts over when the session ends,
+//! so a restarted game is picked up again without user action.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::error::Result;
+use crate::process::{AttachTarget, ProcessInfo};
+use crate::session::DetachReason;
+use crate::FridaManager;
+
+/// Case-insensitive match with `*` (any run) and `?` (any character)
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    fn matches(p: &[char], n: &[char]) -> bool {
+        match (p.first(), n.first()) {
+            (None, None) => true,
+            (Some('*'), _) => matches(&p[1..], n) || (!n.is_empty() && matches(p, &n[1..])),
+            (Some('?'), Some(_)) => matches(&p[1..], &n[1..]),
+            (Some(a), Some(b)) => a == b && matches(&p[1..], &n[1..]),
+            _ => false,
+        }
+    }
+    let p: Vec<char> = pattern.to_lowercase().chars().collect();
+    let n: Vec<char> = text.to_lowercase().chars().collect();
+    matches(&p, &n)
+}
+
+/// Which processes count as the target
+///
+/// `name` must equal the process name (ignoring case). A pattern containing a
+/// path separator is matched against the executable path, any other pattern
+/// against the process name.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessMatcher {
+    pub name: Option<String>,
+    pub patterns: Vec<String>,
+}
+
+impl ProcessMatcher {
+    pub fn new(name: Option<String>, patterns: Vec<String>) -> Self {
+        Self {
+            name: name.filter(|n| !n.is_empty()),
+            patterns,
+        }
+    }
+
+    /// True when nothing could ever match
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.patterns.is_empty()
+    }
+
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        if self
+            .name
+            .as_ref()
+            .is_some_and(|name| name.eq_ignore_ascii_case(&process.name))
+        {
+            return true;
+        }
+        self.patterns.iter().any(|pattern| {
+            if pattern.contains('/') || pattern.contains('\\') {
+                process.path.as_ref().is_some_and(|path| {
+                    glob_match(&pattern.replace('\\', "/"), &path.replace('\\', "/"))
+                })
+            } else {
+                glob_match(pattern, &process.name)
+            }
+        })
+    }
+}
+
+/// What to watch for and how often to look
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    pub device_id: String,
+    pub matcher: ProcessMatcher,
+    /// Delay between process list polls while no session is attached
+    pub interval: Duration,
+    /// Scripts injected into every session, in order
+    pub scripts: Vec<String>,
+}
+
+/// Progress reported by a [`ProcessWatcher`]
+#[derive(Debug, Clone)]
+pub enum WatchEvent {
+    /// Attached to a matching process and injected the scripts
+    Attached {
+        session_id: String,
+        process: ProcessInfo,
+        /// Ids of the injected scripts, in `WatchConfig::scripts` order
+        script_ids: Vec<String>,
+        /// False for the first attach, true after the target came back
+        reattached: bool,
+    },
+    /// The session ended; the watcher looks for the process again unless we
+    /// detached on purpose
+    Detached {
+        session_id: String,
+        reason: DetachReason,
+    },
+    /// Attaching or injecting failed; retried on the next poll
+    Failed { pid: u32, error: String },
+}
+
+/// Callback for watcher events
+pub type WatchCallback = Arc<dyn Fn(WatchEvent) + Send + Sync>;
+
+/// Background task that keeps a session attached to the configured target
+pub struct ProcessWatcher {
+    task: JoinHandle<()>,
+}
+
+impl ProcessWatcher {
+    /// Start watching; events go to `on_event` from the watcher task
+    pub fn start(manager: Arc<FridaManager>, config: WatchConfig, on_event: WatchCallback) -> Self {
+        info!(
+            "Watching device {} for {:?} / {:?}",
+            config.device_id, config.matcher.name, config.matcher.patterns
+        );
+        let task = tokio::spawn(watch(manager, config, on_event));
+        Self { task }
+    }
+
+    /// Stop watching; an attached session stays attached
+    pub fn stop(&self) {
+        self.task.abort();
+    }
+
+    /// Whether the watcher gave up (manual detach) or was stopped
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+impl Drop for ProcessWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn watch(manager: Arc<FridaManager>, config: WatchConfig, on_event: WatchCallback) {
+    let mut attached_once = false;
+    // The process we just lost can linger in the list while it exits
+    let mut lost_pid: Option<u32> = None;
+
+    loop {
+        let process = match find_target(&manager, &config, lost_pid).await {
+            Ok(Some(process)) => process,
+            Ok(None) => {
+                tokio::time::sleep(config.interval).await;
+                continue;
+            }
+            Err(e) => {
+                debug!("Process poll on {} failed: {}", config.device_id, e);
+                tokio::time::sleep(config.interval).await;
+                continue;
+            }
+        };
+
+        let (detach_tx, mut detach_rx) = mpsc::unbounded_channel();
+        let session_id = match attach(&manager, &config, &process, detach_tx).await {
+            Ok((session_id, script_ids)) => {
+                on_event(WatchEvent::Attached {
+                    session_id: session_id.clone(),
+                    process: process.clone(),
+                    script_ids,
+                    reattached: attached_once,
+                });
+                session_id
+            }
+            Err(e) => {
+                warn!(
+                    "Auto-attach to {} ({}) failed: {}",
+                    process.name, process.pid, e
+                );
+                on_event(WatchEvent::Failed {
+                    pid: process.pid,
+                    error: e.to_string(),
+                });
+                tokio::time::sleep(config.interval).await;
+                continue;
+            }
+        };
+        attached_once = true;
+
+        // Sender lives in the session's callback list, so this only ends on detach
+        let reason = detach_rx
+            .recv()
+            .await
+            .unwrap_or(DetachReason::ProcessTerminated);
+        on_event(WatchEvent::Detached { session_id, reason });
+        if reason == DetachReason::ApplicationRequested {
+            info!("Session detached on request, stopping process watch");
+            return;
+        }
+        lost_pid = Some(process.pid);
+    }
+}
+
+async fn find_target(
+    manager: &FridaManager,
+    config: &WatchConfig,
+    skip_pid: Option<u32>,
+) -> Result<Option<ProcessInfo>> {
+    let processes = manager
+        .enumerate_processes_on_device(&config.device_id)
+        .await?;
+    Ok(processes
+        .into_iter()
+        .find(|p| Some(p.pid) != skip_pid && config.matcher.matches(p)))
+}
+
+async fn attach(
+    manager: &FridaManager,
+    config: &WatchConfig,
+    process: &ProcessInfo,
+    detach_tx: mpsc::UnboundedSender<DetachReason>,
+) -> Result<(String, Vec<String>)> {
+    let session_id = manager
+        .attach_target(&config.device_id, AttachTarget::Pid(process.pid))
+        .await?;
+    manager
+        .on_session_detached(
+            &session_id,
+            Arc::new(move |_, reason| {
+                let _ = detach_tx.send(reason);
+            }),
+        )
+        .await?;
+
+    let mut script_ids = Vec::with_capacity(config.scripts.len());
+    for source in &config.scripts {
+        match manager.inject_script(&session_id, source).await {
+            Ok(script_id) => script_ids.push(script_id),
+            Err(e) => {
+                let _ = manager.detach(&session_id).await;
+                return Err(e);
+            }
+        }
+    }
+    Ok((session_id, script_ids))
+}
+
+#[cfg(all(test, feature = "mock", not(feature = "real")))]
+mod tests {
+    use super::*;
+    use tokio::time::timeout;
+
+    fn config(matcher: ProcessMatcher) -> WatchConfig {
+        WatchConfig {
+            device_id: "local".to_string(),
+            matcher,
+            interval: Duration::from_millis(5),
+            scripts: vec!["rpc.exports = {};".to_string()],
+        }
+    }
+
+    async fn next(rx: &mut mpsc::UnboundedReceiver<WatchEvent>) -> WatchEvent {
+        timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("watcher event")
+            .expect("watcher running")
+    }
+
+    fn channel_callback() -> (WatchCallback, mpsc::UnboundedReceiver<WatchEvent>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let callback: WatchCallback = Arc::new(move |event| {
+            let _ = tx.send(event);
+        });
+        (callback, rx)
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("game*.exe", "Game-Win64.exe"));
+        assert!(glob_match("proc?", "proc1"));
+        assert!(!glob_match("proc?", "proc12"));
+        assert!(!glob_match("game*.exe", "launcher.exe"));
+    }
+
+    #[test]
+    fn test_matcher_name_pattern_and_path() {
+        let game = ProcessInfo::new(1, "Game.exe").with_path("C:\\Games\\Example\\Game.exe");
+
+        assert!(ProcessMatcher::new(Some("game.EXE".into()), vec![]).matches(&game));
+        assert!(!ProcessMatcher::new(Some("Game".into()), vec![]).matches(&game));
+        assert!(ProcessMatcher::new(None, vec!["gam?.*".into()]).matches(&game));
+        assert!(ProcessMatcher::new(None, vec!["c:/games/*/game.exe".into()]).matches(&game));
+        assert!(!ProcessMatcher::new(None, vec!["/opt/*".into()]).matches(&game));
+        // Path patterns never fall back to the bare name
+        assert!(!ProcessMatcher::new(None, vec!["*/Game.exe".into()])
+            .matches(&ProcessInfo::new(2, "Game.exe")));
+        assert!(ProcessMatcher::new(Some(String::new()), vec![]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_attaches_and_reattaches() {
+        let manager = Arc::new(FridaManager::new().unwrap());
+        let (callback, mut rx) = channel_callback();
+        // Safari (300) and Slack (800) both match; the lost pid is skipped, so
+        // losing Safari stands in for the game coming back under a new pid
+        let matcher = ProcessMatcher::new(None, vec!["/applications/s*".into()]);
+        let _watcher = ProcessWatcher::start(manager.clone(), config(matcher), callback);
+
+        let WatchEvent::Attached {
+            session_id,
+            process,
+            script_ids,
+            reattached,
+        } = next(&mut rx).await
+        else {
+            panic!("expected attach");
+        };
+        assert_eq!(process.pid, 300);
+        assert!(!reattached);
+        let session = manager.get_session(&session_id).await.unwrap();
+        assert_eq!(session.get_script_ids().await, script_ids);
+
+        manager
+            .dispatch_detached(&session_id, DetachReason::ProcessTerminated)
+            .await
+            .unwrap();
+        assert!(matches!(
+            next(&mut rx).await,
+            WatchEvent::Detached {
+                reason: DetachReason::ProcessTerminated,
+                ..
+            }
+        ));
+
+        let WatchEvent::Attached {
+            session_id: new_session,
+            process,
+            script_ids,
+            reattached,
+        } = next(&mut rx).await
+        else {
+            panic!("expected re-attach");
+        };
+        assert_eq!(process.pid, 800);
+        assert!(reattached);
+        assert_ne!(new_session, session_id);
+        assert_eq!(script_ids.len(), 1);
+        assert!(manager.get_session(&session_id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_retries_failed_attach() {
+        let manager = Arc::new(FridaManager::new().unwrap());
+        manager.fail_next_attaches(1);
+        let (callback, mut rx) = channel_callback();
+        let matcher = ProcessMatcher::new(Some("Safari".into()), vec![]);
+        let _watcher = ProcessWatcher::start(manager.clone(), config(matcher), callback);
+
+        assert!(matches!(next(&mut rx).await, WatchEvent::Failed { pid: 300, .. }));
+        // The same process is tried again, not skipped like a lost one
+        let WatchEvent::Attached {
+            process,
+            reattached,
+            ..
+        } = next(&mut rx).await
+        else {
+            panic!("expected attach after the failed one");
+        };
+        assert_eq!(process.pid, 300);
+        assert!(!reattached);
+    }
+
+    #[tokio::test]
+    async fn test_watcher_stops_after_manual_detach() {
+        let manager = Arc::new(FridaManager::new().unwrap());
+        let (callback, mut rx) = channel_callback();
+        let matcher = ProcessMatcher::new(Some("Safari".into()), vec![]);
+        let watcher = ProcessWatcher::start(manager.clone(), config(matcher), callback);
+
+        let WatchEvent::Attached { session_id, .. } = next(&mut rx).await else {
+            panic!("expected attach");
+        };
+        manager.detach(&session_id).await.unwrap();
+        assert!(matches!(
+            next(&mut rx).await,
+            WatchEvent::Detached {
+                reason: DetachReason::ApplicationRequested,
+                ..
+            }
+        ));
+        timeout(Duration::from_secs(2), async {
+            while !watcher.is_finished() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+}